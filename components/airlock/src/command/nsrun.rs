@@ -59,7 +59,13 @@ const MASKED_PATHS: &'static [&'static str] = &[
     "/sys/firmware",
 ];
 
-pub fn run(rootfs: &Path, cmd: &OsStr, args: Vec<&OsStr>, mount_artifacts: bool) -> Result<()> {
+pub fn run(
+    rootfs: &Path,
+    cmd: &OsStr,
+    args: Vec<&OsStr>,
+    mount_artifacts: bool,
+    artifact_cache_dir: Option<&Path>,
+) -> Result<()> {
     let umask_val = 0o0022;
     debug!("setting umask, value={:#o}", umask_val);
     umask(umask_val);
@@ -186,9 +192,12 @@ pub fn run(rootfs: &Path, cmd: &OsStr, args: Vec<&OsStr>, mount_artifacts: bool)
 
     if mount_artifacts {
         // Bind mount outside artifact cache (and ensure outside directory exists)
-        let source = env::home_dir()
-            .ok_or(Error::HomeDirectoryNotFound)?
-            .join(".hab/cache/artifacts");
+        let source = match artifact_cache_dir {
+            Some(dir) => dir.to_path_buf(),
+            None => env::home_dir()
+                .ok_or(Error::HomeDirectoryNotFound)?
+                .join(".hab/cache/artifacts"),
+        };
         mkdir_p(&source)?;
         mount::bind(
             source,
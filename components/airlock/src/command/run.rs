@@ -31,6 +31,7 @@ pub fn run(
     args: Vec<&OsStr>,
     namespaces: Option<(&Path, &Path)>,
     mount_artifacts: bool,
+    artifact_cache_dir: Option<&Path>,
 ) -> Result<()> {
     check_required_packages()?;
     util::check_user_group_membership(&user::my_username()?)?;
@@ -38,7 +39,14 @@ pub fn run(
         join_network_namespaces(userns, netns)?;
     }
     let new_userns = namespaces == None;
-    let mut command = unshare_command(fs_root.as_ref(), cmd, args, new_userns, mount_artifacts)?;
+    let mut command = unshare_command(
+        fs_root.as_ref(),
+        cmd,
+        args,
+        new_userns,
+        mount_artifacts,
+        artifact_cache_dir,
+    )?;
     debug!("running, command={:?}", command);
     let exit_status = command.spawn()?.wait()?;
     fs_root.finish()?;
@@ -71,6 +79,7 @@ fn unshare_command(
     args: Vec<&OsStr>,
     new_userns: bool,
     mount_artifacts: bool,
+    artifact_cache_dir: Option<&Path>,
 ) -> Result<unshare::Command> {
     let program = util::proc_exe()?;
     let mut namespaces = vec![
@@ -86,7 +95,11 @@ fn unshare_command(
     let mut command = unshare::Command::new(program);
     command.arg("nsrun");
     if mount_artifacts {
-        command.arg("--mount-artifacts");
+        command.arg("--mount-artifact-cache");
+        if let Some(dir) = artifact_cache_dir {
+            command.arg("--artifact-cache-dir");
+            command.arg(dir);
+        }
     }
     command.arg(rootfs);
     command.arg(cmd);
@@ -97,11 +97,12 @@ fn sub_netns_destroy(m: &ArgMatches) -> Result<()> {
 fn sub_nsrun(m: &ArgMatches) -> Result<()> {
     let fs_root = Path::new(m.value_of("FS_ROOT").unwrap());
     let mount_artifacts = m.is_present("MOUNT_ARTIFACT_CACHE");
+    let artifact_cache_dir = m.value_of("ARTIFACT_CACHE_DIR").map(Path::new);
     let mut args: Vec<&OsStr> = m.values_of_os("CMD").unwrap().collect();
     // cmd arg is required and multiple so must contain a first element
     let cmd = args.remove(0);
 
-    command::nsrun::run(fs_root, cmd, args, mount_artifacts)
+    command::nsrun::run(fs_root, cmd, args, mount_artifacts, artifact_cache_dir)
 }
 
 fn sub_run(m: &ArgMatches) -> Result<()> {
@@ -126,8 +127,16 @@ fn sub_run(m: &ArgMatches) -> Result<()> {
         None => None,
     };
     let mount_artifacts = m.is_present("MOUNT_ARTIFACT_CACHE");
+    let artifact_cache_dir = m.value_of("ARTIFACT_CACHE_DIR").map(Path::new);
 
-    command::run::run(fs_root, cmd, args, namespaces, mount_artifacts)
+    command::run::run(
+        fs_root,
+        cmd,
+        args,
+        namespaces,
+        mount_artifacts,
+        artifact_cache_dir,
+    )
 }
 
 fn cli<'a, 'b>() -> App<'a, 'b> {
@@ -151,6 +160,9 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             (@setting TrailingVarArg)
             (@arg MOUNT_ARTIFACT_CACHE: --("mount-artifact-cache") -m
                 "Mount the user's Habitat artifact cache directory (default: no)")
+            (@arg ARTIFACT_CACHE_DIR: --("artifact-cache-dir") +takes_value {validate_dir_exists}
+                requires[MOUNT_ARTIFACT_CACHE]
+                "Artifact cache directory to mount instead of the user's (ex: /tmp/artifacts)")
             (@arg FS_ROOT: +required +takes_value {validate_dir_exists}
                 "Path to the rootfs (ex: /tmp/rootfs)")
             (@arg CMD: +required +takes_value +multiple
@@ -171,6 +183,9 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
                 "Use user namespace (ex: /tmp/airlock-ns/userns)")
             (@arg MOUNT_ARTIFACT_CACHE: --("mount-artifact-cache") -m
                 "Mount the user's Habitat artifact cache directory (default: no)")
+            (@arg ARTIFACT_CACHE_DIR: --("artifact-cache-dir") +takes_value {validate_dir_exists}
+                requires[MOUNT_ARTIFACT_CACHE]
+                "Artifact cache directory to mount instead of the user's (ex: /tmp/artifacts)")
             (@arg CMD: +required +takes_value +multiple
                 "The command and arguments to execute (ex: ls -l /tmp)")
         )
@@ -0,0 +1,192 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Read;
+use std::str::FromStr;
+
+use bldr_core::metrics::CounterMetric;
+use constant_time_eq::constant_time_eq;
+use hex;
+use http_gateway::http::controller::*;
+use iron::status;
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+use persistent;
+use protocol::jobsrv::JobGroupSpec;
+use protocol::sessionsrv::{Account, AccountGet};
+use serde_json;
+
+use config::Config;
+use error::Error;
+use github::schedule_plans;
+use headers::*;
+use metrics::Counter;
+use repos::{self, RepoClient, RepoHost};
+use types::*;
+
+pub enum BitbucketEvent {
+    Push,
+}
+
+impl FromStr for BitbucketEvent {
+    type Err = Error;
+
+    fn from_str(event: &str) -> Result<Self, Self::Err> {
+        match event {
+            "repo:push" => Ok(BitbucketEvent::Push),
+            _ => Err(Error::UnknownBitbucketEvent(event.to_string())),
+        }
+    }
+}
+
+pub fn handle_event(req: &mut Request) -> IronResult<Response> {
+    Counter::BitbucketEvent.increment();
+
+    let event = match req.headers.get::<XEventKey>() {
+        Some(&XEventKey(ref event)) => match BitbucketEvent::from_str(event) {
+            Ok(event) => event,
+            Err(err) => return Ok(Response::with((status::BadRequest, err.to_string()))),
+        },
+        _ => return Ok(Response::with(status::BadRequest)),
+    };
+
+    // The signature is verified against the hook secret of every project the hook would build
+    let config = req.get::<persistent::Read<Config>>().unwrap();
+    let signature = match req.headers.get::<XHubSignature>() {
+        Some(&XHubSignature(ref sig)) => sig.clone(),
+        None => {
+            warn!("Received a Bitbucket hook with no signature");
+            return Ok(Response::with(status::BadRequest));
+        }
+    };
+
+    let mut payload = String::new();
+    if let Err(err) = req.body.read_to_string(&mut payload) {
+        warn!("Unable to read Bitbucket Hook request body, {}", err);
+        return Ok(Response::with(status::BadRequest));
+    }
+    trace!("handle-notify, {}", payload);
+
+    match event {
+        BitbucketEvent::Push => handle_push(req, &config, &payload, &signature),
+    }
+}
+
+/// Signs a hook's payload with the webhook secret, in the form Bitbucket sends it.
+fn sign(secret: &str, payload: &str) -> String {
+    let key = PKey::hmac(secret.as_bytes()).unwrap();
+    let mut signer = Signer::new(MessageDigest::sha256(), &key).unwrap();
+    signer.update(payload.as_bytes()).unwrap();
+    let hmac = signer.sign_to_vec().unwrap();
+    format!("sha256={}", &hex::encode(hmac))
+}
+
+fn verify_signature(secret: &str, payload: &str, signature: &str) -> bool {
+    !secret.is_empty() && constant_time_eq(signature.as_bytes(), sign(secret, payload).as_bytes())
+}
+
+fn handle_push(
+    req: &mut Request,
+    config: &Config,
+    body: &str,
+    signature: &str,
+) -> IronResult<Response> {
+    let hook = match serde_json::from_str::<BitbucketWebhookPush>(&body) {
+        Ok(hook) => hook,
+        Err(err) => {
+            return Ok(Response::with((
+                status::UnprocessableEntity,
+                err.to_string(),
+            )));
+        }
+    };
+    debug!(
+        "BITBUCKET-WEBHOOK builder_api::bitbucket::handle_push: received hook; repository={}",
+        hook.repository.full_name
+    );
+
+    let repo_url = match repos::normalize_repo_url(&hook.repository.links.html.href) {
+        Ok(url) => url,
+        Err(err) => return Ok(Response::with((status::UnprocessableEntity, err.to_string()))),
+    };
+
+    let mut account_get = AccountGet::new();
+    account_get.set_name(hook.actor.nickname.clone());
+    let account_id = match route_message::<AccountGet, Account>(req, &account_get) {
+        Ok(account) => Some(account.get_id()),
+        Err(_) => None,
+    };
+
+    let client = RepoClient::new(RepoHost::Bitbucket, config);
+    let repo = &hook.repository.full_name;
+    let mut plans = vec![];
+    // A single push may update several branches, deleted branches have no new ref and tags
+    // never trigger builds
+    for change in hook.push.changes.iter() {
+        let new = match change.new {
+            Some(ref new) if new.ref_type == "branch" => new,
+            _ => continue,
+        };
+        let old = change.old.as_ref().map(|old| old.target.hash.as_str());
+        let changed = match client.changed(repo, &new.target.hash, old) {
+            Ok(changed) => changed,
+            Err(err) => {
+                warn!("unable to retrieve changed paths of {}, {}", repo, err);
+                continue;
+            }
+        };
+        let bldr_config = client.bldr_config(repo, &new.target.hash);
+        debug!("Config, {:?}", bldr_config);
+        let triggered = client.triggered_plans(
+            repo,
+            &new.target.hash,
+            &bldr_config,
+            &new.name,
+            changed.as_slice(),
+        );
+        debug!("Triggered Plans, {:?}", triggered);
+        // Build exactly the commit each branch was pushed to
+        let mut request = JobGroupSpec::new();
+        request.set_git_ref(new.target.hash.clone());
+        let built = schedule_plans(
+            req,
+            request,
+            &repo_url,
+            &hook.actor.nickname,
+            account_id,
+            triggered,
+            &|project| verify_signature(project.get_hook_secret(), body, signature),
+        );
+        plans.extend(built.into_iter().map(|triggered| triggered.plan));
+    }
+    Ok(render_json(status::Ok, &plans))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAYLOAD: &'static str = r#"{"push":{"changes":[]}}"#;
+
+    #[test]
+    fn verify_signature_of_payload() {
+        let signature = "sha256=a4d1216f1a18c0d07b07368700c7ff5b3457a0cacab862cfb38da934a0c5de39";
+        assert_eq!(sign("secret", PAYLOAD), signature);
+        assert!(verify_signature("secret", PAYLOAD, signature));
+        assert!(!verify_signature("other", PAYLOAD, signature));
+        assert!(!verify_signature("secret", r#"{"push":{}}"#, signature));
+        assert!(!verify_signature("", PAYLOAD, signature));
+    }
+}
@@ -34,10 +34,15 @@ pub struct Config {
     pub http: HttpCfg,
     /// List of net addresses for routing servers to connect to
     pub routers: Vec<RouterAddr>,
+    /// CurveZMQ keys used to connect to the routers
+    pub curve: CurveCfg,
     pub oauth: OAuth2Cfg,
     pub github: GitHubCfg,
+    pub gitlab: GitLabCfg,
+    pub bitbucket: BitbucketCfg,
     pub segment: SegmentCfg,
     pub s3: depot::config::S3Cfg,
+    pub storage: depot::config::StorageCfg,
     pub ui: UiCfg,
     /// Depot's configuration
     pub depot: depot::config::Config,
@@ -56,9 +61,13 @@ impl Default for Config {
         Config {
             http: HttpCfg::default(),
             routers: vec![RouterAddr::default()],
+            curve: CurveCfg::default(),
             oauth: OAuth2Cfg::default(),
             github: GitHubCfg::default(),
+            gitlab: GitLabCfg::default(),
+            bitbucket: BitbucketCfg::default(),
             s3: depot::config::S3Cfg::default(),
+            storage: depot::config::StorageCfg::default(),
             segment: SegmentCfg::default(),
             ui: UiCfg::default(),
             depot: depot::config::Config::default(),
@@ -90,6 +99,10 @@ impl GatewayCfg for Config {
     fn route_addrs(&self) -> &[RouterAddr] {
         self.routers.as_slice()
     }
+
+    fn curve(&self) -> &CurveCfg {
+        &self.curve
+    }
 }
 
 impl typemap::Key for Config {
@@ -103,6 +116,9 @@ pub struct HttpCfg {
     pub listen: IpAddr,
     pub port: u16,
     pub handler_count: usize,
+    /// Max number of job log streams open at once. Each one holds a handler thread until its
+    /// job finishes.
+    pub max_log_streams: usize,
 }
 
 impl Default for HttpCfg {
@@ -111,6 +127,7 @@ impl Default for HttpCfg {
             listen: IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)),
             port: 9636,
             handler_count: Config::default_handler_count(),
+            max_log_streams: Config::default_handler_count() / 4,
         }
     }
 }
@@ -126,6 +143,45 @@ impl ToSocketAddrs for HttpCfg {
     }
 }
 
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct GitLabCfg {
+    /// URL to GitLab API, point this at a self-hosted GitLab's `/api/v4`
+    pub api_url: String,
+    /// Access token used to read plans and `.bldr.toml` files from GitLab repositories
+    pub token: String,
+}
+
+impl Default for GitLabCfg {
+    fn default() -> Self {
+        GitLabCfg {
+            api_url: "https://gitlab.com/api/v4".to_string(),
+            token: String::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct BitbucketCfg {
+    /// URL to Bitbucket API
+    pub api_url: String,
+    /// Username and app password used to read plans and `.bldr.toml` files from Bitbucket
+    /// repositories
+    pub username: String,
+    pub app_password: String,
+}
+
+impl Default for BitbucketCfg {
+    fn default() -> Self {
+        BitbucketCfg {
+            api_url: "https://api.bitbucket.org/2.0".to_string(),
+            username: String::new(),
+            app_password: String::new(),
+        }
+    }
+}
+
 #[derive(Debug, Default, Deserialize)]
 #[serde(default)]
 pub struct UiCfg {
@@ -136,6 +192,7 @@ pub struct UiCfg {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::PathBuf;
 
     #[test]
     fn config_from_file() {
@@ -148,6 +205,7 @@ mod tests {
         listen = "0:0:0:0:0:0:0:1"
         port = 9636
         handler_count = 128
+        max_log_streams = 16
 
         [ui]
         root = "/some/path"
@@ -180,8 +238,20 @@ mod tests {
         endpoint = "http://localhost:9000"
         bucket_name = "hibbity-bibbity-poopity-scoopity"
 
+        [storage]
+        backend = "local"
+        local_dir = "/hab/svc/builder-api/data/artifacts"
+
         [github]
         api_url = "https://api.github.com"
+
+        [gitlab]
+        api_url = "https://git.example.com/api/v4"
+        token = "glpat-abc123"
+
+        [bitbucket]
+        username = "bldr"
+        app_password = "app-password"
         "#;
 
         let config = Config::from_raw(&content).unwrap();
@@ -191,6 +261,7 @@ mod tests {
         assert_eq!(&format!("{}", config.http.listen), "::1");
         assert_eq!(config.http.port, 9636);
         assert_eq!(config.http.handler_count, 128);
+        assert_eq!(config.http.max_log_streams, 16);
         assert_eq!(&format!("{}", config.routers[0]), "172.18.0.2:9632");
 
         assert_eq!(config.oauth.client_id, "0c2f738a7d0bd300de10");
@@ -200,6 +271,11 @@ mod tests {
         );
 
         assert_eq!(config.github.api_url, "https://api.github.com");
+        assert_eq!(config.gitlab.api_url, "https://git.example.com/api/v4");
+        assert_eq!(config.gitlab.token, "glpat-abc123");
+        assert_eq!(config.bitbucket.api_url, "https://api.bitbucket.org/2.0");
+        assert_eq!(config.bitbucket.username, "bldr");
+        assert_eq!(config.bitbucket.app_password, "app-password");
         assert_eq!(config.ui.root, Some("/some/path".to_string()));
         assert_eq!(config.segment.url, "https://api.segment.io");
         assert_eq!(config.s3.backend, depot::config::S3Backend::Minio);
@@ -210,6 +286,14 @@ mod tests {
         );
         assert_eq!(config.s3.endpoint, "http://localhost:9000");
         assert_eq!(config.s3.bucket_name, "hibbity-bibbity-poopity-scoopity");
+        assert_eq!(
+            config.storage.backend,
+            depot::backend::StorageBackend::Local
+        );
+        assert_eq!(
+            config.storage.local_dir,
+            PathBuf::from("/hab/svc/builder-api/data/artifacts")
+        );
     }
 
     #[test]
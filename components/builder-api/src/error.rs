@@ -20,8 +20,11 @@ use std::result;
 use depot;
 use hab_core;
 use hab_net;
+use http_client;
 use hyper;
 use protobuf;
+use serde_json;
+use url;
 use zmq;
 
 #[derive(Debug)]
@@ -31,10 +34,17 @@ pub enum Error {
     HabitatCore(hab_core::Error),
     HyperError(hyper::error::Error),
     HTTP(hyper::status::StatusCode),
+    HttpClient(http_client::Error),
+    InvalidRepoUrl(String),
     IO(io::Error),
+    Json(serde_json::Error),
     NetError(hab_net::NetError),
     Protobuf(protobuf::ProtobufError),
+    UnknownBitbucketEvent(String),
     UnknownGitHubEvent(String),
+    UnknownGitLabEvent(String),
+    UnknownRepoHost(String),
+    UrlParse(url::ParseError),
     Zmq(zmq::Error),
 }
 
@@ -48,12 +58,25 @@ impl fmt::Display for Error {
             Error::HabitatCore(ref e) => format!("{}", e),
             Error::HyperError(ref e) => format!("{}", e),
             Error::HTTP(ref e) => format!("{}", e),
+            Error::HttpClient(ref e) => format!("{}", e),
+            Error::InvalidRepoUrl(ref e) => {
+                format!("Repository URL must be an https clone URL, {}", e)
+            }
             Error::IO(ref e) => format!("{}", e),
+            Error::Json(ref e) => format!("{}", e),
             Error::NetError(ref e) => format!("{}", e),
             Error::Protobuf(ref e) => format!("{}", e),
+            Error::UnknownBitbucketEvent(ref e) => {
+                format!("Unknown or unsupported Bitbucket event, {}", e)
+            }
             Error::UnknownGitHubEvent(ref e) => {
                 format!("Unknown or unsupported GitHub event, {}", e)
             }
+            Error::UnknownGitLabEvent(ref e) => {
+                format!("Unknown or unsupported GitLab event, {}", e)
+            }
+            Error::UnknownRepoHost(ref e) => format!("Unknown repository host, {}", e),
+            Error::UrlParse(ref e) => format!("{}", e),
             Error::Zmq(ref e) => format!("{}", e),
         };
         write!(f, "{}", msg)
@@ -68,12 +91,23 @@ impl error::Error for Error {
             Error::HabitatCore(ref err) => err.description(),
             Error::HyperError(ref err) => err.description(),
             Error::HTTP(_) => "Non-200 HTTP response.",
+            Error::HttpClient(ref err) => err.description(),
+            Error::InvalidRepoUrl(_) => "Repository URL is not an https clone URL",
             Error::IO(ref err) => err.description(),
+            Error::Json(ref err) => err.description(),
             Error::NetError(ref err) => err.description(),
             Error::Protobuf(ref err) => err.description(),
+            Error::UnknownBitbucketEvent(_) => {
+                "Unknown or unsupported Bitbucket event received in request"
+            }
             Error::UnknownGitHubEvent(_) => {
                 "Unknown or unsupported GitHub event received in request"
             }
+            Error::UnknownGitLabEvent(_) => {
+                "Unknown or unsupported GitLab event received in request"
+            }
+            Error::UnknownRepoHost(_) => "Unknown or unsupported repository host",
+            Error::UrlParse(ref err) => err.description(),
             Error::Zmq(ref err) => err.description(),
         }
     }
@@ -103,18 +137,36 @@ impl From<hyper::error::Error> for Error {
     }
 }
 
+impl From<http_client::Error> for Error {
+    fn from(err: http_client::Error) -> Self {
+        Error::HttpClient(err)
+    }
+}
+
 impl From<io::Error> for Error {
     fn from(err: io::Error) -> Self {
         Error::IO(err)
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
 impl From<protobuf::ProtobufError> for Error {
     fn from(err: protobuf::ProtobufError) -> Error {
         Error::Protobuf(err)
     }
 }
 
+impl From<url::ParseError> for Error {
+    fn from(err: url::ParseError) -> Self {
+        Error::UrlParse(err)
+    }
+}
+
 impl From<zmq::Error> for Error {
     fn from(err: zmq::Error) -> Error {
         Error::Zmq(err)
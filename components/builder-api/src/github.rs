@@ -16,6 +16,8 @@ use std::io::Read;
 use std::str::FromStr;
 
 use bldr_core::build_config::{BuildCfg, BLDR_CFG};
+use bldr_core::job::pull_request_channel_name;
+use bldr_core::plan_deps::{build_order, plan_deps};
 use bldr_core::metrics::CounterMetric;
 use constant_time_eq::constant_time_eq;
 use github_api_client::{AppToken, GitHubClient};
@@ -27,8 +29,12 @@ use openssl::hash::MessageDigest;
 use openssl::pkey::PKey;
 use openssl::sign::Signer;
 use persistent;
-use protocol::jobsrv::{JobGroup, JobGroupSpec, JobGroupTrigger};
-use protocol::originsrv::{OriginProject, OriginProjectGet};
+use protobuf::RepeatedField;
+use protocol::jobsrv::{JobGroup, JobGroupPlan, JobGroupSpec, JobGroupTrigger};
+use protocol::net::NetOk;
+use protocol::originsrv::{CheckOriginAccessRequest, CheckOriginAccessResponse, OriginChannel,
+                          OriginChannelDelete, OriginChannelGet, OriginMemberRole, OriginProject,
+                          OriginProjectGet, OriginProjectUpdate};
 use protocol::sessionsrv::{Account, AccountGet};
 use router::Router;
 use serde_json;
@@ -41,6 +47,7 @@ use types::*;
 pub enum GitHubEvent {
     Push,
     Ping,
+    PullRequest,
 }
 
 impl FromStr for GitHubEvent {
@@ -50,6 +57,7 @@ impl FromStr for GitHubEvent {
         match event {
             "ping" => Ok(GitHubEvent::Ping),
             "push" => Ok(GitHubEvent::Push),
+            "pull_request" => Ok(GitHubEvent::PullRequest),
             _ => Err(Error::UnknownGitHubEvent(event.to_string())),
         }
     }
@@ -100,6 +108,7 @@ pub fn handle_event(req: &mut Request) -> IronResult<Response> {
     match event {
         GitHubEvent::Ping => Ok(Response::with(status::Ok)),
         GitHubEvent::Push => handle_push(req, &payload),
+        GitHubEvent::PullRequest => handle_pull_request(req, &payload),
     }
 }
 
@@ -195,34 +204,244 @@ fn handle_push(req: &mut Request, body: &str) -> IronResult<Response> {
         Err(_) => None,
     };
 
-    let config = read_bldr_config(&*github, &token, &hook);
+    let config = read_bldr_config(&*github, &token, hook.repository.id);
     debug!("Config, {:?}", config);
     let plans = read_plans(&github, &token, &hook, &config);
     debug!("Triggered Plans, {:?}", plans);
+
+    // Build exactly the pushed commit, its status is reported back to GitHub. The scheduler only
+    // pins projects from the pushed repository to it, the rdeps the group rebuilds from other
+    // repositories are built from their default branch.
+    let mut request = JobGroupSpec::new();
+    request.set_git_ref(hook.after.clone());
     build_plans(
         req,
+        request,
         &hook.repository.clone_url,
         &hook.pusher.name,
         account_id,
         plans,
+        // Verified with the GitHub App's webhook secret
+        &|_| true,
+    )
+}
+
+fn handle_pull_request(req: &mut Request, body: &str) -> IronResult<Response> {
+    let hook = match serde_json::from_str::<GitHubWebhookPullRequest>(&body) {
+        Ok(hook) => hook,
+        Err(err) => {
+            return Ok(Response::with((
+                status::UnprocessableEntity,
+                err.to_string(),
+            )));
+        }
+    };
+    debug!(
+        "GITHUB-WEBHOOK builder_api::github::handle_pull_request: received hook; repository={} repository_id={} action={} number={} installation_id={}",
+        hook.repository.full_name,
+        hook.repository.id,
+        hook.action,
+        hook.number,
+        hook.installation.id
+    );
+
+    match hook.action.as_str() {
+        "opened" | "reopened" | "synchronize" | "closed" => (),
+        _ => return Ok(Response::with(status::Ok)),
+    }
+
+    let github = req.get::<persistent::Read<GitHubCli>>().unwrap();
+
+    debug!(
+        "GITHUB-CALL builder_api::github::handle_pull_request: Getting app_installation_token; installation_id={}",
+        hook.installation.id
+    );
+    let token = match github.app_installation_token(hook.installation.id) {
+        Ok(token) => token,
+        Err(err) => {
+            warn!("unable to generate github app token, {}", err);
+            return Ok(Response::with((status::BadGateway, err.to_string())));
+        }
+    };
+
+    // Pull requests don't carry the paths they change, so every project in the repository's
+    // .bldr.toml which builds on the base branch is built
+    let config = read_bldr_config(&*github, &token, hook.repository.id);
+    debug!("Config, {:?}", config);
+    let mut plans = Vec::with_capacity(config.projects().len());
+    for project in config.projects() {
+        if !project
+            .branches
+            .iter()
+            .any(|b| b == &hook.pull_request.base.git_ref)
+        {
+            continue;
+        }
+        let path = project.plan_file().to_string_lossy().into_owned();
+        if let Some(plan) = read_plan(&github, &token, hook.repository.id, &path, &project.labels)
+        {
+            plans.push(plan);
+        }
+    }
+    debug!("Pull Request Plans, {:?}", plans);
+
+    let channel = pull_request_channel_name(hook.repository.id, hook.number);
+    if hook.action == "closed" {
+        delete_pull_request_channel(req, &channel, &plans);
+        return Ok(Response::with(status::Ok));
+    }
+
+    let mut account_get = AccountGet::new();
+    account_get.set_name(hook.sender.login.clone());
+    let account_id = match route_message::<AccountGet, Account>(req, &account_get) {
+        Ok(account) => Some(account.get_id()),
+        Err(_) => None,
+    };
+
+    // Anyone can open a pull request from a fork, so those are only built for the origins their
+    // sender is a member of
+    let head_repo_id = hook.pull_request.head.repo.as_ref().map(|repo| repo.id);
+    if head_repo_id != Some(hook.repository.id) {
+        plans.retain(|triggered| match account_id {
+            Some(account_id) => is_origin_member(req, account_id, &triggered.plan.origin),
+            None => false,
+        });
+        if plans.is_empty() {
+            debug!(
+                "Not building pull request {} from a fork, {} isn't a member of its origins",
+                hook.number, hook.sender.login
+            );
+            return Ok(Response::with(status::Ok));
+        }
+    }
+
+    let mut request = JobGroupSpec::new();
+    request.set_git_ref(format!("refs/pull/{}/head", hook.number));
+    request.set_pull_request(hook.number);
+    request.set_channel(channel);
+    build_plans(
+        req,
+        request,
+        &hook.repository.clone_url,
+        &hook.sender.login,
+        account_id,
+        plans,
+        // Verified with the GitHub App's webhook secret
+        &|_| true,
     )
 }
 
-fn build_plans(
+fn is_origin_member(req: &mut Request, account_id: u64, origin: &str) -> bool {
+    let mut request = CheckOriginAccessRequest::new();
+    request.set_account_id(account_id);
+    request.set_origin_name(origin.to_string());
+    request.set_role(OriginMemberRole::Member);
+    match route_message::<CheckOriginAccessRequest, CheckOriginAccessResponse>(req, &request) {
+        Ok(response) => response.get_has_access(),
+        Err(err) => {
+            warn!("Failed to check membership of {}, {}", origin, err);
+            false
+        }
+    }
+}
+
+// Removes the channel a closed pull request's packages were built into from the origin of each
+// project the pull request built.
+fn delete_pull_request_channel(req: &mut Request, channel: &str, plans: &[TriggeredPlan]) {
+    let mut origins: Vec<&str> = plans.iter().map(|p| p.plan.origin.as_str()).collect();
+    origins.sort();
+    origins.dedup();
+
+    for origin in origins {
+        let mut channel_get = OriginChannelGet::new();
+        channel_get.set_origin_name(origin.to_string());
+        channel_get.set_name(channel.to_string());
+
+        let origin_channel =
+            match route_message::<OriginChannelGet, OriginChannel>(req, &channel_get) {
+                Ok(origin_channel) => origin_channel,
+                Err(err) => {
+                    debug!("Unable to retrieve channel {} in {}, {}", channel, origin, err);
+                    continue;
+                }
+            };
+
+        let mut channel_delete = OriginChannelDelete::new();
+        channel_delete.set_id(origin_channel.get_id());
+        channel_delete.set_origin_id(origin_channel.get_origin_id());
+
+        match route_message::<OriginChannelDelete, NetOk>(req, &channel_delete) {
+            Ok(_) => debug!("Deleted channel {} in {}", channel, origin),
+            Err(err) => warn!("Unable to delete channel {} in {}, {}", channel, origin, err),
+        }
+    }
+}
+
+/// A plan to build for a push, along with the worker labels its project requires and the
+/// origin/name of the deps read from the plan file.
+#[derive(Debug)]
+pub struct TriggeredPlan {
+    pub plan: Plan,
+    pub labels: Vec<String>,
+    pub deps: Vec<String>,
+}
+
+impl TriggeredPlan {
+    pub fn new(plan: Plan, labels: Vec<String>, content: &[u8]) -> Self {
+        TriggeredPlan {
+            plan: plan,
+            labels: labels,
+            deps: plan_deps(&String::from_utf8_lossy(content)),
+        }
+    }
+
+    fn name(&self) -> String {
+        format!("{}/{}", self.plan.origin, self.plan.name)
+    }
+}
+
+pub fn build_plans(
     req: &mut Request,
+    request: JobGroupSpec,
     repo_url: &str,
     pusher: &str,
     account_id: Option<u64>,
-    plans: Vec<Plan>,
+    plans: Vec<TriggeredPlan>,
+    verify_hook: &Fn(&OriginProject) -> bool,
 ) -> IronResult<Response> {
-    let mut request = JobGroupSpec::new();
+    let built = schedule_plans(
+        req,
+        request,
+        repo_url,
+        pusher,
+        account_id,
+        plans,
+        verify_hook,
+    );
+    let plans: Vec<&Plan> = built.iter().map(|p| &p.plan).collect();
+    Ok(render_json(status::Ok, &plans))
+}
+
+/// Schedules the builds of the plans of projects connected to `repo_url` whose hook secret the
+/// hook was verified with, grouping plans which depend on each other. Returns the plans which
+/// were scheduled.
+pub fn schedule_plans(
+    req: &mut Request,
+    mut request: JobGroupSpec,
+    repo_url: &str,
+    pusher: &str,
+    account_id: Option<u64>,
+    plans: Vec<TriggeredPlan>,
+    verify_hook: &Fn(&OriginProject) -> bool,
+) -> Vec<TriggeredPlan> {
+    let mut buildable = Vec::with_capacity(plans.len());
 
-    for plan in plans.iter() {
+    for triggered in plans.into_iter() {
         let mut project_get = OriginProjectGet::new();
-        project_get.set_name(format!("{}/{}", &plan.origin, &plan.name));
+        project_get.set_name(triggered.name());
 
         match route_message::<OriginProjectGet, OriginProject>(req, &project_get) {
-            Ok(project) => {
+            Ok(mut project) => {
                 if repo_url != project.get_vcs_data() {
                     warn!(
                         "Repo URL ({}) doesn't match project vcs data ({}). Aborting.",
@@ -231,36 +450,103 @@ fn build_plans(
                     );
                     continue;
                 }
+
+                if !verify_hook(&project) {
+                    warn!(
+                        "Hook couldn't be verified with the secret of {}. Aborting.",
+                        project.get_name()
+                    );
+                    continue;
+                }
+
+                // Labels declared in the .bldr.toml take precedence over the project's, and are
+                // cleared once the .bldr.toml no longer lists any
+                let labels = &triggered.labels;
+                if project.get_required_labels() != labels.as_slice() {
+                    project.set_required_labels(RepeatedField::from_vec(labels.clone()));
+                    let mut project_update = OriginProjectUpdate::new();
+                    project_update.set_requestor_id(account_id.unwrap_or(0));
+                    project_update.set_project(project);
+
+                    match route_message::<OriginProjectUpdate, NetOk>(req, &project_update) {
+                        Ok(_) => debug!("Updated required labels for {}", project_get.get_name()),
+                        Err(err) => warn!("Failed to update project labels, {}", err),
+                    }
+                }
             }
             Err(err) => {
                 warn!("Failed to fetch project, {}", err);
                 continue;
             }
         }
+        buildable.push(triggered);
+    }
 
-        debug!("Scheduling, {:?}", plan);
-        request.set_origin(plan.origin.clone());
-        request.set_package(plan.name.clone());
-        // JW TODO: We need to be able to determine which platform this build is for based on
-        // the directory structure the plan is found in or metadata inside the plan. We will need
-        // to have this done before we support building additional targets with Builder.
-        request.set_target("x86_64-linux".to_string());
-        request.set_trigger(JobGroupTrigger::Webhook);
-        request.set_requester_name(pusher.to_string());
-        if account_id.is_some() {
-            request.set_requester_id(account_id.unwrap());
+    // Plans changed by the same push are built in one group, ordered by the deps in the plans,
+    // so that a plan is built against the new revision of the plans it depends on
+    let order = if buildable.len() > 1 {
+        let deps: Vec<(String, Vec<String>)> = buildable
+            .iter()
+            .map(|p| (p.name(), p.deps.clone()))
+            .collect();
+        let order = build_order(&deps);
+        if order.is_none() {
+            warn!("Plans depend on each other in a cycle, building them in separate groups");
         }
+        order
+    } else {
+        None
+    };
 
-        match route_message::<JobGroupSpec, JobGroup>(req, &request) {
-            Ok(group) => debug!("JobGroup created, {:?}", group),
-            Err(err) => debug!("Failed to create group, {:?}", err),
+    match order {
+        Some(order) => {
+            let group_plans = order
+                .iter()
+                .map(|i| {
+                    let mut group_plan = JobGroupPlan::new();
+                    group_plan.set_name(buildable[*i].name());
+                    group_plan.set_deps(RepeatedField::from_vec(buildable[*i].deps.clone()));
+                    group_plan
+                })
+                .collect();
+            request.set_plans(group_plans);
+            schedule_plan(req, &mut request, &buildable[order[0]].plan, pusher, account_id);
         }
+        None => for triggered in buildable.iter() {
+            schedule_plan(req, &mut request, &triggered.plan, pusher, account_id);
+        },
+    }
+    buildable
+}
+
+fn schedule_plan(
+    req: &mut Request,
+    request: &mut JobGroupSpec,
+    plan: &Plan,
+    pusher: &str,
+    account_id: Option<u64>,
+) {
+    debug!("Scheduling, {:?}", plan);
+    request.set_origin(plan.origin.clone());
+    request.set_package(plan.name.clone());
+    // JW TODO: We need to be able to determine which platform this build is for based on
+    // the directory structure the plan is found in or metadata inside the plan. We will need
+    // to have this done before we support building additional targets with Builder.
+    request.set_target("x86_64-linux".to_string());
+    request.set_trigger(JobGroupTrigger::Webhook);
+    request.set_requester_name(pusher.to_string());
+    if account_id.is_some() {
+        request.set_requester_id(account_id.unwrap());
+    }
+
+    match route_message::<JobGroupSpec, JobGroup>(req, request) {
+        Ok(group) => debug!("JobGroup created, {:?}", group),
+        Err(err) => debug!("Failed to create group, {:?}", err),
     }
-    Ok(render_json(status::Ok, &plans))
 }
 
-fn read_bldr_config(github: &GitHubClient, token: &AppToken, hook: &GitHubWebhookPush) -> BuildCfg {
-    match github.contents(&token, hook.repository.id, BLDR_CFG) {
+fn read_bldr_config(github: &GitHubClient, token: &AppToken, repo_id: u32) -> BuildCfg {
+    match github.contents(&token, repo_id, BLDR_CFG) {
         Ok(Some(contents)) => match contents.decode() {
             Ok(ref bytes) => match BuildCfg::from_slice(bytes) {
                 Ok(cfg) => cfg,
@@ -287,11 +573,11 @@ fn read_plans(
     token: &AppToken,
     hook: &GitHubWebhookPush,
     config: &BuildCfg,
-) -> Vec<Plan> {
+) -> Vec<TriggeredPlan> {
     let mut plans = Vec::with_capacity(config.projects().len());
     for project in config.triggered_by(hook.branch(), hook.changed().as_slice()) {
-        if let Some(plan) = read_plan(github, &token, hook, &project.plan_file().to_string_lossy())
-        {
+        let path = project.plan_file().to_string_lossy().into_owned();
+        if let Some(plan) = read_plan(github, &token, hook.repository.id, &path, &project.labels) {
             plans.push(plan)
         }
     }
@@ -301,13 +587,14 @@ fn read_plans(
 fn read_plan(
     github: &GitHubClient,
     token: &AppToken,
-    hook: &GitHubWebhookPush,
+    repo_id: u32,
     path: &str,
-) -> Option<Plan> {
-    match github.contents(&token, hook.repository.id, path) {
+    labels: &[String],
+) -> Option<TriggeredPlan> {
+    match github.contents(&token, repo_id, path) {
         Ok(Some(contents)) => match contents.decode() {
             Ok(bytes) => match Plan::from_bytes(bytes.as_slice()) {
-                Ok(plan) => Some(plan),
+                Ok(plan) => Some(TriggeredPlan::new(plan, labels.to_vec(), &bytes)),
                 Err(err) => {
                     debug!("unable to read plan, {}, {}", path, err);
                     None
@@ -0,0 +1,163 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Read;
+use std::str::FromStr;
+
+use bldr_core::metrics::CounterMetric;
+use constant_time_eq::constant_time_eq;
+use http_gateway::http::controller::*;
+use iron::status;
+use persistent;
+use protocol::jobsrv::JobGroupSpec;
+use protocol::sessionsrv::{Account, AccountGet};
+use serde_json;
+
+use config::Config;
+use error::Error;
+use github::build_plans;
+use headers::*;
+use metrics::Counter;
+use repos::{self, RepoClient, RepoHost};
+use types::*;
+
+pub enum GitLabEvent {
+    Push,
+}
+
+impl FromStr for GitLabEvent {
+    type Err = Error;
+
+    fn from_str(event: &str) -> Result<Self, Self::Err> {
+        match event {
+            "Push Hook" => Ok(GitLabEvent::Push),
+            _ => Err(Error::UnknownGitLabEvent(event.to_string())),
+        }
+    }
+}
+
+pub fn handle_event(req: &mut Request) -> IronResult<Response> {
+    Counter::GitLabEvent.increment();
+
+    let event = match req.headers.get::<XGitLabEvent>() {
+        Some(&XGitLabEvent(ref event)) => match GitLabEvent::from_str(event) {
+            Ok(event) => event,
+            Err(err) => return Ok(Response::with((status::BadRequest, err.to_string()))),
+        },
+        _ => return Ok(Response::with(status::BadRequest)),
+    };
+
+    // GitLab sends the hook secret of the project as is, it's verified against the secret of
+    // every project the hook would build
+    let config = req.get::<persistent::Read<Config>>().unwrap();
+    let token = match req.headers.get::<XGitLabToken>() {
+        Some(&XGitLabToken(ref token)) => token.clone(),
+        None => {
+            warn!("Received a GitLab hook with no token");
+            return Ok(Response::with(status::BadRequest));
+        }
+    };
+
+    let mut payload = String::new();
+    if let Err(err) = req.body.read_to_string(&mut payload) {
+        warn!("Unable to read GitLab Hook request body, {}", err);
+        return Ok(Response::with(status::BadRequest));
+    }
+    trace!("handle-notify, {}", payload);
+
+    match event {
+        GitLabEvent::Push => handle_push(req, &config, &payload, &token),
+    }
+}
+
+fn verify_token(secret: &str, token: &str) -> bool {
+    !secret.is_empty() && constant_time_eq(token.as_bytes(), secret.as_bytes())
+}
+
+fn handle_push(
+    req: &mut Request,
+    config: &Config,
+    body: &str,
+    token: &str,
+) -> IronResult<Response> {
+    let hook = match serde_json::from_str::<GitLabWebhookPush>(&body) {
+        Ok(hook) => hook,
+        Err(err) => {
+            return Ok(Response::with((
+                status::UnprocessableEntity,
+                err.to_string(),
+            )));
+        }
+    };
+    debug!(
+        "GITLAB-WEBHOOK builder_api::gitlab::handle_push: received hook; repository={} ref={}",
+        hook.project.path_with_namespace, hook.git_ref
+    );
+
+    if hook.commits.is_empty() {
+        debug!("GITLAB-WEBHOOK builder_api::gitlab::handle_push: hook commits is empty!");
+        return Ok(Response::with(status::Ok));
+    }
+
+    let repo_url = match repos::normalize_repo_url(&hook.project.git_http_url) {
+        Ok(url) => url,
+        Err(err) => return Ok(Response::with((status::UnprocessableEntity, err.to_string()))),
+    };
+
+    let mut account_get = AccountGet::new();
+    account_get.set_name(hook.user_username.clone());
+    let account_id = match route_message::<AccountGet, Account>(req, &account_get) {
+        Ok(account) => Some(account.get_id()),
+        Err(_) => None,
+    };
+
+    let client = RepoClient::new(RepoHost::GitLab, config);
+    let repo = &hook.project.path_with_namespace;
+    let branch = hook.branch();
+    let bldr_config = client.bldr_config(repo, &hook.after);
+    debug!("Config, {:?}", bldr_config);
+    let plans = client.triggered_plans(
+        repo,
+        &hook.after,
+        &bldr_config,
+        branch,
+        hook.changed().as_slice(),
+    );
+    debug!("Triggered Plans, {:?}", plans);
+    // Build exactly the pushed commit the plans were read at
+    let mut request = JobGroupSpec::new();
+    request.set_git_ref(hook.after.clone());
+    build_plans(
+        req,
+        request,
+        &repo_url,
+        &hook.user_username,
+        account_id,
+        plans,
+        &|project| verify_token(project.get_hook_secret(), token),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_hook_token() {
+        assert!(verify_token("secret", "secret"));
+        assert!(!verify_token("secret", "secre"));
+        assert!(!verify_token("secret", "other!"));
+        assert!(!verify_token("", ""));
+    }
+}
@@ -12,6 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+header! { (LastEventId, "Last-Event-ID") => [u64] }
+header! { (PrivateToken, "PRIVATE-TOKEN") => [String] }
+header! { (XEventKey, "X-Event-Key") => [String] }
 header! { (XGitHubDelivery, "X-GitHub-Delivery") => [String] }
 header! { (XGitHubEvent, "X-GitHub-Event") => [String] }
+header! { (XGitLabEvent, "X-Gitlab-Event") => [String] }
+header! { (XGitLabToken, "X-Gitlab-Token") => [String] }
 header! { (XHubSignature, "X-Hub-Signature") => [String] }
@@ -48,13 +48,17 @@ extern crate staticfile;
 extern crate toml;
 extern crate typemap;
 extern crate unicase;
+extern crate url;
 extern crate zmq;
 
+pub mod bitbucket;
 pub mod config;
 pub mod error;
 pub mod github;
+pub mod gitlab;
 pub mod headers;
 pub mod metrics;
+pub mod repos;
 pub mod server;
 mod types;
 
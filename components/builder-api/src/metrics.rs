@@ -19,7 +19,9 @@ use bldr_core::metrics;
 use std::borrow::Cow;
 
 pub enum Counter {
+    BitbucketEvent,
     GitHubEvent,
+    GitLabEvent,
 }
 
 impl metrics::CounterMetric for Counter {}
@@ -27,7 +29,9 @@ impl metrics::CounterMetric for Counter {}
 impl metrics::Metric for Counter {
     fn id(&self) -> Cow<'static, str> {
         match *self {
+            Counter::BitbucketEvent => "bitbucket.event".into(),
             Counter::GitHubEvent => "github.event".into(),
+            Counter::GitLabEvent => "gitlab.event".into(),
         }
     }
 }
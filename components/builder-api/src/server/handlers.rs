@@ -15,47 +15,62 @@
 //! A collection of handlers for the HTTP server's router
 
 use std::env;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use bldr_core;
 use bldr_core::helpers::transition_visibility;
 use bodyparser;
 use hab_core::package::{Identifiable, Plan};
+use hex;
 use http_client::ApiClient;
 use http_gateway::http::controller::*;
 use http_gateway::http::helpers::{
-    self, check_origin_access, get_param, get_session_user_name, trigger_from_request,
-    validate_params,
+    self, check_origin_access, check_origin_role, get_param, get_session_user_name,
+    trigger_from_request, validate_params,
 };
 use hyper::header::{Accept, ContentType};
 use hyper::status::StatusCode;
+use iron::response::WriteBody;
 use iron::status;
 use oauth_client::error::Error as OAuthError;
+use openssl::rand::rand_bytes;
 use params::{FromValue, Params};
 use persistent;
+use protobuf::RepeatedField;
 use protocol::jobsrv::{
-    Job, JobGet, JobGroup, JobGroupCancel, JobGroupGet, JobLog, JobLogGet, JobState,
-    ProjectJobsGet, ProjectJobsGetResponse,
+    Job, JobGet, JobGroup, JobGroupCancel, JobGroupGet, JobGroupPause, JobGroupResume, JobLog,
+    JobLogGet, JobState, ProjectJobsGet, ProjectJobsGetResponse,
 };
 use protocol::jobsrv::{JobGraphPackageReverseDependencies, JobGraphPackageReverseDependenciesGet};
 use protocol::originsrv::*;
+use protocol::routesrv::{RouterStatus, RouterStatusGet};
 use protocol::sessionsrv::{
-    Account, AccountGet, AccountGetId, AccountInvitationListRequest, AccountInvitationListResponse,
-    AccountOriginListRequest, AccountOriginListResponse, AccountToken, AccountTokenCreate,
-    AccountTokenRevoke, AccountTokens, AccountTokensGet, AccountUpdate,
+    AccessTokenScope, Account, AccountGet, AccountGetId, AccountInvitationListRequest,
+    AccountInvitationListResponse, AccountOriginListRequest, AccountOriginListResponse,
+    AccountToken, AccountTokenCreate, AccountTokenRevoke, AccountTokens, AccountTokensGet,
+    AccountUpdate,
 };
 use router::Router;
 use serde_json;
 
+use super::log_stream::{LogStream, StreamSlot};
 use super::SegmentCli;
+use bitbucket;
 use config::Config;
 use github;
+use gitlab;
 use headers::*;
+use repos::{self, RepoClient, RepoHost};
 use types::*;
 
 // A default name for per-project integrations. Currently, there
 // can only be one.
 const DEFAULT_PROJECT_INTEGRATION: &'static str = "default";
 
+// Random bytes in the hook secret of GitLab and Bitbucket projects
+const HOOK_SECRET_LEN: usize = 32;
+
 const PRODUCT: &'static str = "builder-api";
 const VERSION: &'static str = include_str!(concat!(env!("OUT_DIR"), "/VERSION"));
 
@@ -82,6 +97,18 @@ pub fn account_show(req: &mut Request) -> IronResult<Response> {
     }
 }
 
+/// Shows the registrations and routing totals of whichever router answers the request.
+pub fn router_status(req: &mut Request) -> IronResult<Response> {
+    match route_message::<RouterStatusGet, RouterStatus>(req, &RouterStatusGet::new()) {
+        Ok(routes) => {
+            let mut response = render_json(status::Ok, &routes);
+            helpers::dont_cache_response(&mut response);
+            Ok(response)
+        }
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
 pub fn search(req: &mut Request) -> IronResult<Response> {
     match req.get::<bodyparser::Struct<SearchTerm>>() {
         Ok(Some(body)) => match &*body.entity.to_lowercase() {
@@ -221,9 +248,53 @@ pub fn get_access_tokens(req: &mut Request) -> IronResult<Response> {
 pub fn generate_access_token(req: &mut Request) -> IronResult<Response> {
     let (session_id, flags) = {
         let session = req.extensions.get::<Authenticated>().unwrap();
+        // Restricted tokens must not be able to mint tokens without their restrictions
+        if session.is_scoped() || !session.get_token_origins().is_empty() {
+            return Ok(Response::with(status::Forbidden));
+        }
         (session.get_id(), session.get_flags())
     };
 
+    // The body is optional, tokens created without one are unrestricted and never expire
+    let body = match req.get::<bodyparser::Struct<AccessTokenCreateReq>>() {
+        Ok(Some(body)) => body,
+        Ok(None) => AccessTokenCreateReq::default(),
+        Err(_) => return Ok(Response::with(status::UnprocessableEntity)),
+    };
+
+    let mut scopes = Vec::new();
+    for scope in body.scopes.iter() {
+        match AccessTokenScope::from_str(scope) {
+            Ok(scope) => scopes.push(scope),
+            Err(_) => {
+                return Ok(Response::with((
+                    status::UnprocessableEntity,
+                    format!("Unknown scope: `{}`", scope),
+                )))
+            }
+        }
+    }
+
+    for origin in body.origins.iter() {
+        if !check_origin_access(req, origin).unwrap_or(false) {
+            return Ok(Response::with(status::Forbidden));
+        }
+    }
+
+    let expires = match body.expires_in_days {
+        Some(0) => {
+            return Ok(Response::with((
+                status::UnprocessableEntity,
+                "Invalid value for field: `expires_in_days`",
+            )))
+        }
+        Some(days) => {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+            Some((now.as_secs() + days as u64 * 86_400) as i64)
+        }
+        None => None,
+    };
+
     let mut request = AccountGetId::new();
     request.set_id(session_id);
 
@@ -234,12 +305,20 @@ pub fn generate_access_token(req: &mut Request) -> IronResult<Response> {
 
     let mut request = AccountTokenCreate::new();
     let cfg = req.get::<persistent::Read<Config>>().unwrap();
-    let token =
-        bldr_core::access_token::generate_user_token(&cfg.depot.key_dir, account.get_id(), flags)
-            .unwrap();
+    let token = bldr_core::access_token::generate_user_token(
+        &cfg.depot.key_dir,
+        account.get_id(),
+        flags,
+        body.origins.clone(),
+        scopes.clone(),
+        expires,
+    ).unwrap();
 
     request.set_account_id(account.get_id());
     request.set_token(token);
+    request.set_origins(body.origins.into());
+    request.set_scopes(scopes);
+    request.set_expires(expires.unwrap_or(0));
 
     match route_message::<AccountTokenCreate, AccountToken>(req, &request) {
         Ok(account_token) => Ok(render_json(status::Ok, &account_token)),
@@ -358,7 +437,7 @@ pub fn job_group_cancel(req: &mut Request) -> IronResult<Response> {
     let name_split: Vec<&str> = group.get_project_name().split("/").collect();
     assert!(name_split.len() == 2);
 
-    if !check_origin_access(req, &name_split[0]).unwrap_or(false) {
+    if !check_origin_role(req, &name_split[0], OriginMemberRole::Member).unwrap_or(false) {
         return Ok(Response::with(status::Forbidden));
     }
 
@@ -374,6 +453,79 @@ pub fn job_group_cancel(req: &mut Request) -> IronResult<Response> {
     }
 }
 
+// This route is only available if jobsrv_enabled is true
+pub fn job_group_pause(req: &mut Request) -> IronResult<Response> {
+    job_group_pause_or_resume(req, true)
+}
+
+// This route is only available if jobsrv_enabled is true
+pub fn job_group_resume(req: &mut Request) -> IronResult<Response> {
+    job_group_pause_or_resume(req, false)
+}
+
+// Paused groups stop dispatching new projects, but let their in-flight jobs finish. Resumed
+// groups pick up from wherever their projects got to.
+fn job_group_pause_or_resume(req: &mut Request, pause: bool) -> IronResult<Response> {
+    let (session_id, mut session_name) = {
+        let session = req.extensions.get::<Authenticated>().unwrap();
+        (session.get_id(), session.get_name().to_string())
+    };
+
+    // Sessions created via Personal Access Tokens only have ids, so we may need
+    // to get the username explicitly.
+    if session_name.is_empty() {
+        session_name = get_session_user_name(req, session_id)
+    }
+
+    let group_id = match get_param(req, "id") {
+        Some(id) => match id.parse::<u64>() {
+            Ok(g) => g,
+            Err(e) => {
+                debug!("Error finding group. e = {:?}", e);
+                return Ok(Response::with(status::BadRequest));
+            }
+        },
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+
+    let mut jgg = JobGroupGet::new();
+    jgg.set_group_id(group_id);
+    jgg.set_include_projects(false);
+
+    let group = match route_message::<JobGroupGet, JobGroup>(req, &jgg) {
+        Ok(group) => group,
+        Err(err) => return Ok(render_net_error(&err)),
+    };
+
+    let name_split: Vec<&str> = group.get_project_name().split("/").collect();
+    assert!(name_split.len() == 2);
+
+    if !check_origin_role(req, &name_split[0], OriginMemberRole::Member).unwrap_or(false) {
+        return Ok(Response::with(status::Forbidden));
+    }
+
+    let result = if pause {
+        let mut jgp = JobGroupPause::new();
+        jgp.set_group_id(group_id);
+        jgp.set_trigger(trigger_from_request(req));
+        jgp.set_requester_id(session_id);
+        jgp.set_requester_name(session_name);
+        route_message::<JobGroupPause, NetOk>(req, &jgp)
+    } else {
+        let mut jgr = JobGroupResume::new();
+        jgr.set_group_id(group_id);
+        jgr.set_trigger(trigger_from_request(req));
+        jgr.set_requester_id(session_id);
+        jgr.set_requester_name(session_name);
+        route_message::<JobGroupResume, NetOk>(req, &jgr)
+    };
+
+    match result {
+        Ok(_) => Ok(Response::with(status::NoContent)),
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
 pub fn validate_registry_credentials(req: &mut Request) -> IronResult<Response> {
     let json_body = req.get::<bodyparser::Json>();
 
@@ -463,7 +615,7 @@ pub fn project_privacy_toggle(req: &mut Request) -> IronResult<Response> {
         Err(_) => return Ok(Response::with(status::BadRequest)),
     };
 
-    if !check_origin_access(req, &origin).unwrap_or(false) {
+    if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
         return Ok(Response::with(status::Forbidden));
     }
 
@@ -568,16 +720,12 @@ pub fn job_log(req: &mut Request) -> IronResult<Response> {
         .and_then(FromValue::from_value)
         .unwrap_or(false);
 
-    let mut job_get = JobGet::new();
     let mut request = JobLogGet::new();
     request.set_start(start);
 
     match get_param(req, "id") {
         Some(id) => match id.parse::<u64>() {
-            Ok(i) => {
-                request.set_id(i);
-                job_get.set_id(i);
-            }
+            Ok(i) => request.set_id(i),
             Err(e) => {
                 debug!("Error parsing id. e = {:?}", e);
                 return Ok(Response::with(status::BadRequest));
@@ -586,52 +734,130 @@ pub fn job_log(req: &mut Request) -> IronResult<Response> {
         None => return Ok(Response::with(status::BadRequest)),
     }
 
-    // Before fetching the logs, we need to check and see if the logs we want to fetch are for
-    // a job that's building a private package, and if so, do we have the right to see said
-    // package.
-    match route_message::<JobGet, Job>(req, &job_get) {
-        Ok(job) => {
-            // It's not sufficient to check the project that's on the job itself, since that
-            // project is reconstructed from information available in the jobsrv database and does
-            // not contain things like visibility settings. We need to fetch the project from
-            // originsrv.
-            let mut project_get = OriginProjectGet::new();
-            project_get.set_name(job.get_project().get_name().to_string());
-
-            let project = match route_message::<OriginProjectGet, OriginProject>(req, &project_get)
-            {
-                Ok(p) => p,
-                Err(err) => return Ok(render_net_error(&err)),
-            };
+    if let Some(response) = check_job_log_access(req, request.get_id()) {
+        return Ok(response);
+    }
 
-            if vec![
-                OriginPackageVisibility::Private,
-                OriginPackageVisibility::Hidden,
-            ].contains(&project.get_visibility())
-            {
-                if !check_origin_access(req, project.get_origin_name()).unwrap_or(false) {
-                    return Ok(Response::with(status::Forbidden));
-                }
+    match route_message::<JobLogGet, JobLog>(req, &request) {
+        Ok(mut log) => {
+            if !include_color {
+                log.strip_ansi();
             }
+            Ok(render_json(status::Ok, &log))
+        }
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
 
-            match route_message::<JobLogGet, JobLog>(req, &request) {
-                Ok(mut log) => {
-                    if !include_color {
-                        log.strip_ansi();
-                    }
-                    Ok(render_json(status::Ok, &log))
-                }
-                Err(err) => Ok(render_net_error(&err)),
+pub fn job_log_stream(req: &mut Request) -> IronResult<Response> {
+    // A reconnecting client tells us the last line it received, which takes precedence over
+    // the requested starting line.
+    let start = match req.headers.get::<LastEventId>() {
+        Some(&LastEventId(id)) => id + 1,
+        None => req.get_ref::<Params>()
+            .unwrap()
+            .find(&["start"])
+            .and_then(FromValue::from_value)
+            .unwrap_or(0),
+    };
+
+    let include_color = req.get_ref::<Params>()
+        .unwrap()
+        .find(&["color"])
+        .and_then(FromValue::from_value)
+        .unwrap_or(false);
+
+    let job_id = match get_param(req, "id") {
+        Some(id) => match id.parse::<u64>() {
+            Ok(i) => i,
+            Err(e) => {
+                debug!("Error parsing id. e = {:?}", e);
+                return Ok(Response::with(status::BadRequest));
             }
+        },
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+
+    if let Some(response) = check_job_log_access(req, job_id) {
+        return Ok(response);
+    }
+
+    let max_log_streams = req.get::<persistent::Read<Config>>()
+        .unwrap()
+        .http
+        .max_log_streams;
+    let slot = match StreamSlot::acquire(max_log_streams) {
+        Some(slot) => slot,
+        None => {
+            warn!("Too many open log streams, rejecting stream for job {}", job_id);
+            return Ok(Response::with(status::ServiceUnavailable));
+        }
+    };
+
+    // The stream outlives the request, so it takes over the request's connection to the router.
+    let conn = match req.extensions.remove::<XRouteClient>() {
+        Some(conn) => conn,
+        None => {
+            error!("No XRouteClient extension in request");
+            return Ok(Response::with(status::InternalServerError));
+        }
+    };
+    let stream = LogStream::new(conn, job_id, start, include_color, slot);
+
+    let mut response = Response::with((status::Ok, Box::new(stream) as Box<WriteBody>));
+    response
+        .headers
+        .set(ContentType("text/event-stream".parse().unwrap()));
+    helpers::dont_cache_response(&mut response);
+    Ok(response)
+}
+
+// Before fetching the logs, we need to check and see if the logs we want to fetch are for a job
+// that's building a private package, and if so, do we have the right to see said package. Returns
+// the response to send instead of the logs if not.
+fn check_job_log_access(req: &mut Request, job_id: u64) -> Option<Response> {
+    let mut job_get = JobGet::new();
+    job_get.set_id(job_id);
+
+    let job = match route_message::<JobGet, Job>(req, &job_get) {
+        Ok(job) => job,
+        Err(err) => return Some(render_net_error(&err)),
+    };
+
+    // It's not sufficient to check the project that's on the job itself, since that project is
+    // reconstructed from information available in the jobsrv database and does not contain
+    // things like visibility settings. We need to fetch the project from originsrv.
+    let mut project_get = OriginProjectGet::new();
+    project_get.set_name(job.get_project().get_name().to_string());
+
+    let project = match route_message::<OriginProjectGet, OriginProject>(req, &project_get) {
+        Ok(p) => p,
+        Err(err) => return Some(render_net_error(&err)),
+    };
+
+    if vec![
+        OriginPackageVisibility::Private,
+        OriginPackageVisibility::Hidden,
+    ].contains(&project.get_visibility())
+    {
+        if !check_origin_access(req, project.get_origin_name()).unwrap_or(false) {
+            return Some(Response::with(status::Forbidden));
         }
-        Err(e) => return Ok(render_net_error(&e)),
     }
+
+    None
 }
 
 pub fn notify(req: &mut Request) -> IronResult<Response> {
     if req.headers.has::<XGitHubEvent>() {
         return github::handle_event(req);
     }
+    if req.headers.has::<XGitLabEvent>() {
+        return gitlab::handle_event(req);
+    }
+    if req.headers.has::<XEventKey>() {
+        return bitbucket::handle_event(req);
+    }
     Ok(Response::with(status::BadRequest))
 }
 
@@ -693,10 +919,16 @@ pub fn project_create(req: &mut Request) -> IronResult<Response> {
                 )));
             }
 
-            if !check_origin_access(req, &body.origin).unwrap_or(false) {
+            if !check_origin_role(req, &body.origin, OriginMemberRole::Maintainer)
+                .unwrap_or(false)
+            {
                 return Ok(Response::with(status::Forbidden));
             }
 
+            if body.repo.vcs_type.is_some() {
+                return repo_project_create(req, body, session.get_id());
+            }
+
             debug!(
                 "GITHUB-CALL builder_api::server::handlers::project_create: Getting app_installation_token; repo_id={} installation_id={}",
                 body.repo_id,
@@ -716,6 +948,9 @@ pub fn project_create(req: &mut Request) -> IronResult<Response> {
             project.set_vcs_type(String::from("git"));
             project.set_vcs_installation_id(body.installation_id);
             project.set_auto_build(body.auto_build);
+            if let Some(labels) = body.required_labels {
+                project.set_required_labels(RepeatedField::from_vec(labels));
+            }
 
             match github.repo(&token, body.repo_id) {
                 Ok(Some(repo)) => project.set_vcs_data(repo.clone_url),
@@ -798,7 +1033,7 @@ pub fn project_delete(req: &mut Request) -> IronResult<Response> {
 
     project_del.set_name(format!("{}/{}", &origin, &name));
 
-    if !check_origin_access(req, origin).unwrap_or(false) {
+    if !check_origin_role(req, origin, OriginMemberRole::Maintainer).unwrap_or(false) {
         return Ok(Response::with(status::Forbidden));
     }
 
@@ -827,7 +1062,7 @@ pub fn project_update(req: &mut Request) -> IronResult<Response> {
         session.get_id()
     };
 
-    if !check_origin_access(req, &origin).unwrap_or(false) {
+    if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
         return Ok(Response::with(status::Forbidden));
     }
 
@@ -850,6 +1085,10 @@ pub fn project_update(req: &mut Request) -> IronResult<Response> {
                 )));
             }
 
+            if body.repo.vcs_type.is_some() {
+                return repo_project_update(req, project, body, session_id);
+            }
+
             debug!(
                 "GITHUB-CALL builder_api::server::handlers::project_update: Getting app_installation_token; repo_id={} installation_id={}",
                 body.repo_id,
@@ -865,8 +1104,15 @@ pub fn project_update(req: &mut Request) -> IronResult<Response> {
             };
 
             project.set_auto_build(body.auto_build);
+            if let Some(labels) = body.required_labels {
+                project.set_required_labels(RepeatedField::from_vec(labels));
+            }
             project.set_plan_path(body.plan_path);
+            project.set_vcs_type(String::from("git"));
             project.set_vcs_installation_id(body.installation_id);
+            project.clear_vcs_auth_user();
+            project.clear_vcs_auth_token();
+            project.clear_hook_secret();
             match github.repo(&token, body.repo_id) {
                 Ok(Some(repo)) => project.set_vcs_data(repo.clone_url),
                 Ok(None) => return Ok(Response::with((status::NotFound, "rg:pu:2"))),
@@ -916,6 +1162,145 @@ pub fn project_update(req: &mut Request) -> IronResult<Response> {
     }
 }
 
+/// Create a project whose plan lives in a GitLab or Bitbucket repository
+fn repo_project_create(
+    req: &mut Request,
+    body: ProjectCreateReq,
+    owner_id: u64,
+) -> IronResult<Response> {
+    let mut origin_get = OriginGet::new();
+    origin_get.set_name(body.origin);
+    let origin = match route_message::<OriginGet, Origin>(req, &origin_get) {
+        Ok(response) => response,
+        Err(err) => return Ok(render_net_error(&err)),
+    };
+
+    let mut project = OriginProject::new();
+    project.set_plan_path(body.plan_path);
+    project.set_auto_build(body.auto_build);
+    if let Some(labels) = body.required_labels {
+        project.set_required_labels(RepeatedField::from_vec(labels));
+    }
+    let plan = match set_project_repo(req, &mut project, &body.repo) {
+        Ok(plan) => plan,
+        Err(response) => return Ok(response),
+    };
+    project.set_origin_name(String::from(origin.get_name()));
+    project.set_origin_id(origin.get_id());
+    project.set_package_name(String::from(plan.name.trim_matches('"')));
+    project.set_owner_id(owner_id);
+    project.set_visibility(origin.get_default_package_visibility());
+
+    let mut request = OriginProjectCreate::new();
+    request.set_project(project);
+    match route_message::<OriginProjectCreate, OriginProject>(req, &request) {
+        Ok(response) => Ok(render_json(status::Created, &response)),
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
+/// Update a project whose plan lives in a GitLab or Bitbucket repository
+fn repo_project_update(
+    req: &mut Request,
+    mut project: OriginProject,
+    body: ProjectUpdateReq,
+    requestor_id: u64,
+) -> IronResult<Response> {
+    project.set_auto_build(body.auto_build);
+    if let Some(labels) = body.required_labels {
+        project.set_required_labels(RepeatedField::from_vec(labels));
+    }
+    project.set_plan_path(body.plan_path);
+    project.clear_vcs_installation_id();
+    let plan = match set_project_repo(req, &mut project, &body.repo) {
+        Ok(plan) => plan,
+        Err(response) => return Ok(response),
+    };
+    if plan.name != project.get_package_name() {
+        return Ok(Response::with((status::UnprocessableEntity, "rg:pu:7")));
+    }
+
+    let mut request = OriginProjectUpdate::new();
+    request.set_requestor_id(requestor_id);
+    request.set_project(project);
+    match route_message::<OriginProjectUpdate, NetOk>(req, &request) {
+        Ok(_) => Ok(Response::with(status::NoContent)),
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
+/// Point the project at the requested repository, reading its plan with the given deploy token
+/// or with the service credentials when none is given. A given deploy token is stored encrypted,
+/// otherwise the project's is cleared. Projects get a hook secret to verify their push hooks
+/// with, which they keep across updates.
+fn set_project_repo(
+    req: &mut Request,
+    project: &mut OriginProject,
+    repo: &ProjectRepoReq,
+) -> Result<Plan, Response> {
+    let host = match repo.vcs_type.as_ref().map(|t| RepoHost::from_str(t)) {
+        Some(Ok(host)) => host,
+        _ => return Err(Response::with((status::UnprocessableEntity, "rg:pr:1"))),
+    };
+    let repo_url = match repos::normalize_repo_url(&repo.repo_url) {
+        Ok(url) => url,
+        Err(e) => {
+            debug!("Invalid repository url, {}", e);
+            return Err(Response::with((status::UnprocessableEntity, "rg:pr:2")));
+        }
+    };
+    let repo_path = repos::repo_path(&repo_url).unwrap();
+
+    let config = req.get::<persistent::Read<Config>>().unwrap();
+    let mut client = RepoClient::new(host, &config);
+    if !repo.vcs_auth_token.is_empty() {
+        client = client.with_credentials(&repo.vcs_auth_user, &repo.vcs_auth_token);
+    }
+    let plan = match client.contents(&repo_path, None, project.get_plan_path()) {
+        Ok(Some(bytes)) => match Plan::from_bytes(bytes.as_slice()) {
+            Ok(plan) => plan,
+            Err(e) => {
+                debug!("Error matching Plan. e = {:?}", e);
+                return Err(Response::with((status::UnprocessableEntity, "rg:pr:3")));
+            }
+        },
+        Ok(None) => return Err(Response::with((status::NotFound, "rg:pr:4"))),
+        Err(e) => {
+            warn!("Error fetching contents from {}. e = {:?}", host, e);
+            return Err(Response::with((status::UnprocessableEntity, "rg:pr:5")));
+        }
+    };
+
+    project.set_vcs_type(host.to_string());
+    project.set_vcs_data(repo_url);
+    if !repo.vcs_auth_token.is_empty() {
+        let token = match bldr_core::integrations::encrypt(
+            &config.depot.key_dir,
+            repo.vcs_auth_token.as_bytes(),
+        ) {
+            Ok(token) => token,
+            Err(e) => {
+                error!("Unable to encrypt deploy token, {}", e);
+                return Err(Response::with(status::InternalServerError));
+            }
+        };
+        project.set_vcs_auth_user(repo.vcs_auth_user.clone());
+        project.set_vcs_auth_token(token);
+    } else {
+        project.clear_vcs_auth_user();
+        project.clear_vcs_auth_token();
+    }
+    if !project.has_hook_secret() {
+        let mut secret = [0u8; HOOK_SECRET_LEN];
+        if let Err(e) = rand_bytes(&mut secret) {
+            error!("Unable to generate hook secret, {}", e);
+            return Err(Response::with(status::InternalServerError));
+        }
+        project.set_hook_secret(hex::encode(secret));
+    }
+    Ok(plan)
+}
+
 /// Display the the given project's details
 // This route is only available if jobsrv_enabled is true
 pub fn project_show(req: &mut Request) -> IronResult<Response> {
@@ -1039,7 +1424,11 @@ pub fn project_jobs(req: &mut Request) -> IronResult<Response> {
 
 // This route is only available if jobsrv_enabled is true
 pub fn create_project_integration(req: &mut Request) -> IronResult<Response> {
-    let params = match validate_params(req, &["origin", "name", "integration"]) {
+    let params = match validate_params(
+        req,
+        &["origin", "name", "integration"],
+        OriginMemberRole::Maintainer,
+    ) {
         Ok(p) => p,
         Err(st) => return Ok(Response::with(st)),
     };
@@ -1057,7 +1446,7 @@ pub fn create_project_integration(req: &mut Request) -> IronResult<Response> {
         }
     };
 
-    if !check_origin_access(req, &params["origin"]).unwrap_or(false) {
+    if !check_origin_role(req, &params["origin"], OriginMemberRole::Maintainer).unwrap_or(false) {
         return Ok(Response::with(status::Forbidden));
     }
 
@@ -1090,12 +1479,16 @@ pub fn create_project_integration(req: &mut Request) -> IronResult<Response> {
 
 // This route is only available if jobsrv_enabled is true
 pub fn delete_project_integration(req: &mut Request) -> IronResult<Response> {
-    let params = match validate_params(req, &["origin", "name", "integration"]) {
+    let params = match validate_params(
+        req,
+        &["origin", "name", "integration"],
+        OriginMemberRole::Maintainer,
+    ) {
         Ok(p) => p,
         Err(st) => return Ok(Response::with(st)),
     };
 
-    if !check_origin_access(req, &params["origin"]).unwrap_or(false) {
+    if !check_origin_role(req, &params["origin"], OriginMemberRole::Maintainer).unwrap_or(false) {
         return Ok(Response::with(status::Forbidden));
     }
 
@@ -1115,7 +1508,11 @@ pub fn delete_project_integration(req: &mut Request) -> IronResult<Response> {
 
 // This route is only available if jobsrv_enabled is true
 pub fn get_project_integration(req: &mut Request) -> IronResult<Response> {
-    let params = match validate_params(req, &["origin", "name", "integration"]) {
+    let params = match validate_params(
+        req,
+        &["origin", "name", "integration"],
+        OriginMemberRole::Maintainer,
+    ) {
         Ok(p) => p,
         Err(st) => return Ok(Response::with(st)),
     };
@@ -0,0 +1,232 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Streams a job's log to an HTTP client as Server-Sent Events.
+//!
+//! The stream polls the job server for lines past the last one it sent, so it picks up lines as
+//! the `LogIngester` appends them to the on-disk log. Once the job server has received
+//! `JobLogComplete` it moves the log to the `LogArchiver` and answers every request from the
+//! archive, marking the log complete. The stream then sends any remaining lines followed by a
+//! final `complete` event and closes.
+//!
+//! Each open stream holds one of the HTTP handler threads until its job finishes, so only a
+//! limited number of streams may be open at once.
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+use std::thread;
+use std::time::Duration;
+
+use hab_net::conn::RouteClient;
+use hab_net::ErrCode;
+use iron::response::WriteBody;
+use protocol::jobsrv::{Job, JobGet, JobLog, JobLogGet, JobState};
+
+/// How long to wait before asking the job server for new lines.
+const POLL_INTERVAL_MS: u64 = 1_000;
+/// Number of idle polls between keep-alive comments. Writing a comment lets us notice a client
+/// that went away while the job was quiet.
+const KEEPALIVE_POLLS: u32 = 15;
+/// Number of idle polls to wait for a finished job's log to be archived before giving up on it.
+const ARCHIVE_GRACE_POLLS: u32 = 60;
+
+/// Number of log streams currently open.
+static OPEN_STREAMS: AtomicUsize = ATOMIC_USIZE_INIT;
+
+/// A reserved place among the open log streams, released when dropped.
+pub struct StreamSlot(&'static AtomicUsize);
+
+impl StreamSlot {
+    /// Reserves a slot for a new stream, unless `limit` streams are already open.
+    pub fn acquire(limit: usize) -> Option<Self> {
+        if reserve(&OPEN_STREAMS, limit) {
+            Some(StreamSlot(&OPEN_STREAMS))
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for StreamSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub struct LogStream {
+    conn: RouteClient,
+    job_id: u64,
+    /// Line number of the next line to send
+    start: u64,
+    include_color: bool,
+    _slot: StreamSlot,
+}
+
+impl LogStream {
+    pub fn new(
+        conn: RouteClient,
+        job_id: u64,
+        start: u64,
+        include_color: bool,
+        slot: StreamSlot,
+    ) -> Self {
+        LogStream {
+            conn: conn,
+            job_id: job_id,
+            start: start,
+            include_color: include_color,
+            _slot: slot,
+        }
+    }
+
+    /// Fetches the next segment of the log. A log which hasn't been written to yet, or which is
+    /// in the middle of being archived, is reported as an empty, incomplete segment.
+    fn fetch(&mut self) -> io::Result<JobLog> {
+        let mut request = JobLogGet::new();
+        request.set_id(self.job_id);
+        request.set_start(self.start);
+
+        match self.conn.route::<JobLogGet, JobLog>(&request) {
+            Ok(mut log) => {
+                if !self.include_color {
+                    log.strip_ansi();
+                }
+                Ok(log)
+            }
+            Err(ref err) if err.get_code() == ErrCode::ENTITY_NOT_FOUND => {
+                let mut log = JobLog::new();
+                log.set_start(self.start);
+                log.set_stop(self.start);
+                Ok(log)
+            }
+            Err(err) => Err(io::Error::new(io::ErrorKind::Other, err.to_string())),
+        }
+    }
+
+    fn job_finished(&mut self) -> bool {
+        let mut request = JobGet::new();
+        request.set_id(self.job_id);
+        match self.conn.route::<JobGet, Job>(&request) {
+            Ok(job) => match job.get_state() {
+                JobState::Complete
+                | JobState::Failed
+                | JobState::Rejected
+                | JobState::CancelComplete => true,
+                _ => false,
+            },
+            Err(err) => {
+                warn!("Unable to retrieve job {} for log stream, {}", self.job_id, err);
+                false
+            }
+        }
+    }
+}
+
+impl WriteBody for LogStream {
+    fn write_body(&mut self, res: &mut Write) -> io::Result<()> {
+        let mut idle_polls = 0;
+
+        loop {
+            let log = match self.fetch() {
+                Ok(log) => log,
+                Err(err) => {
+                    warn!("Unable to stream log for job {}, {}", self.job_id, err);
+                    writeln!(res, "event: error\ndata: {}\n", err)?;
+                    return res.flush();
+                }
+            };
+
+            for (offset, line) in log.get_content().iter().enumerate() {
+                write_line(res, log.get_start() + offset as u64, line)?;
+            }
+            if log.get_stop() > self.start {
+                self.start = log.get_stop();
+                idle_polls = 0;
+            } else {
+                idle_polls += 1;
+            }
+
+            if log.get_is_complete() {
+                writeln!(res, "event: complete\ndata: {}\n", self.start)?;
+                return res.flush();
+            }
+
+            if idle_polls > 0 {
+                if idle_polls % KEEPALIVE_POLLS == 0 {
+                    writeln!(res, ": keep-alive\n")?;
+                }
+                // A log which is never archived (for instance, because the worker died before
+                // sending `JobLogComplete`) would otherwise hold the connection open forever.
+                if idle_polls >= ARCHIVE_GRACE_POLLS && self.job_finished() {
+                    writeln!(res, "event: complete\ndata: {}\n", self.start)?;
+                    return res.flush();
+                }
+            }
+
+            res.flush()?;
+            thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+        }
+    }
+}
+
+/// Writes a single log line as a `line` event whose id is the line number, allowing clients to
+/// resume with the `Last-Event-ID` header. Carriage returns would be read as line breaks by the
+/// client, so each carriage return separated segment is sent as its own data field.
+fn write_line(res: &mut Write, line_number: u64, line: &str) -> io::Result<()> {
+    writeln!(res, "event: line\nid: {}", line_number)?;
+    for segment in line.split('\r') {
+        writeln!(res, "data: {}", segment)?;
+    }
+    writeln!(res)
+}
+
+/// Increments the count of open streams unless it has reached the limit.
+fn reserve(open: &AtomicUsize, limit: usize) -> bool {
+    let mut current = open.load(Ordering::SeqCst);
+    loop {
+        if current >= limit {
+            return false;
+        }
+        match open.compare_exchange(current, current + 1, Ordering::SeqCst, Ordering::SeqCst) {
+            Ok(_) => return true,
+            Err(actual) => current = actual,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_line_splits_carriage_returns() {
+        let mut out = Vec::new();
+        write_line(&mut out, 7, "downloading\r50%\r100%").unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "event: line\nid: 7\ndata: downloading\ndata: 50%\ndata: 100%\n\n"
+        );
+    }
+
+    #[test]
+    fn reserve_stops_at_limit() {
+        let open = AtomicUsize::new(0);
+        assert!(reserve(&open, 2));
+        assert!(reserve(&open, 2));
+        assert!(!reserve(&open, 2));
+        open.fetch_sub(1, Ordering::SeqCst);
+        assert!(reserve(&open, 2));
+        assert!(!reserve(&open, 0));
+    }
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod handlers;
+mod log_stream;
 
 use std::path::PathBuf;
 
@@ -25,6 +26,7 @@ use iron;
 use mount::Mount;
 use oauth_client::client::OAuth2Client;
 use persistent;
+use protocol::sessionsrv::AccessTokenScope;
 use segment_api_client::SegmentClient;
 use staticfile::Static;
 
@@ -59,6 +61,7 @@ impl HttpGateway for ApiSrv {
         let mut depot_config = config.depot.clone();
         depot_config.segment = config.segment.clone();
         depot_config.s3 = config.s3.clone();
+        depot_config.storage = config.storage.clone();
         let depot_chain = depot::server::router(depot_config).unwrap();
         let mut mount = Mount::new();
         if let Some(ref path) = config.ui.root {
@@ -73,25 +76,37 @@ impl HttpGateway for ApiSrv {
     fn router(config: Arc<Self::Config>) -> Router {
         let basic = Authenticated::new(config.depot.key_dir.clone());
         let admin = Authenticated::new(PathBuf::new()).require(FeatureFlags::ADMIN);
+        let promote = basic.clone().scope(AccessTokenScope::Promote);
+        let schedule = basic.clone().scope(AccessTokenScope::Schedule);
 
         let mut r = Router::new();
 
         if config.jobsrv_enabled {
             r.post(
                 "/jobs/group/:id/promote/:channel",
-                XHandler::new(job_group_promote).before(basic.clone()),
+                XHandler::new(job_group_promote).before(promote.clone()),
                 "job_group_promote",
             );
             r.post(
                 "/jobs/group/:id/demote/:channel",
-                XHandler::new(job_group_demote).before(basic.clone()),
+                XHandler::new(job_group_demote).before(promote.clone()),
                 "job_group_demote",
             );
             r.post(
                 "/jobs/group/:id/cancel",
-                XHandler::new(job_group_cancel).before(basic.clone()),
+                XHandler::new(job_group_cancel).before(schedule.clone()),
                 "job_group_cancel",
             );
+            r.post(
+                "/jobs/group/:id/pause",
+                XHandler::new(job_group_pause).before(schedule.clone()),
+                "job_group_pause",
+            );
+            r.post(
+                "/jobs/group/:id/resume",
+                XHandler::new(job_group_resume).before(schedule.clone()),
+                "job_group_resume",
+            );
             r.get("/rdeps/:origin/:name", rdeps_show, "rdeps");
             r.get(
                 "/jobs/:id",
@@ -103,6 +118,11 @@ impl HttpGateway for ApiSrv {
                 XHandler::new(job_log).before(basic.clone()),
                 "job_log",
             );
+            r.get(
+                "/jobs/:id/log/stream",
+                XHandler::new(job_log_stream).before(basic.clone()),
+                "job_log_stream",
+            );
             r.post(
                 "/projects",
                 XHandler::new(project_create).before(basic.clone()),
@@ -216,6 +236,11 @@ impl HttpGateway for ApiSrv {
             XHandler::new(account_show).before(admin.clone()),
             "admin_account",
         );
+        r.get(
+            "/admin/routes",
+            XHandler::new(router_status).before(admin.clone()),
+            "admin_routes",
+        );
 
         r
     }
@@ -23,17 +23,49 @@ pub struct JobCreateReq {
 pub struct ProjectCreateReq {
     pub origin: String,
     pub plan_path: String,
+    #[serde(default)]
     pub installation_id: u32,
+    #[serde(default)]
     pub repo_id: u32,
     pub auto_build: bool,
+    #[serde(default)]
+    pub required_labels: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub repo: ProjectRepoReq,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ProjectUpdateReq {
     pub plan_path: String,
+    #[serde(default)]
     pub installation_id: u32,
+    #[serde(default)]
     pub repo_id: u32,
     pub auto_build: bool,
+    #[serde(default)]
+    pub required_labels: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub repo: ProjectRepoReq,
+}
+
+/// Repository of a project hosted on GitLab or Bitbucket. GitHub projects leave these empty and
+/// are located by `installation_id` and `repo_id` instead.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectRepoReq {
+    pub vcs_type: Option<String>,
+    pub repo_url: String,
+    pub vcs_auth_user: String,
+    pub vcs_auth_token: String,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct AccessTokenCreateReq {
+    #[serde(default)]
+    pub origins: Vec<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    pub expires_in_days: Option<u32>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -50,3 +82,110 @@ pub struct GroupPromoteReq {
 pub struct GroupDemoteReq {
     pub idents: Vec<String>,
 }
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitLabWebhookPush {
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    /// Commit the ref was pushed to
+    pub after: String,
+    pub user_username: String,
+    pub project: GitLabWebhookProject,
+    pub commits: Vec<GitLabWebhookCommit>,
+}
+
+impl GitLabWebhookPush {
+    pub fn branch(&self) -> &str {
+        self.git_ref.trim_left_matches("refs/heads/")
+    }
+
+    pub fn changed(&self) -> Vec<&String> {
+        let mut paths = vec![];
+        for commit in self.commits.iter() {
+            paths.extend(&commit.added);
+            paths.extend(&commit.removed);
+            paths.extend(&commit.modified);
+        }
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitLabWebhookProject {
+    pub path_with_namespace: String,
+    pub git_http_url: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitLabWebhookCommit {
+    pub id: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BitbucketWebhookPush {
+    pub actor: BitbucketActor,
+    pub repository: BitbucketRepository,
+    pub push: BitbucketPush,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BitbucketActor {
+    pub nickname: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BitbucketRepository {
+    pub full_name: String,
+    pub links: BitbucketLinks,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BitbucketLinks {
+    pub html: BitbucketLink,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BitbucketLink {
+    pub href: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BitbucketPush {
+    pub changes: Vec<BitbucketChange>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BitbucketChange {
+    pub new: Option<BitbucketRef>,
+    pub old: Option<BitbucketRef>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BitbucketRef {
+    #[serde(rename = "type")]
+    pub ref_type: String,
+    pub name: String,
+    pub target: BitbucketTarget,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BitbucketTarget {
+    pub hash: String,
+}
@@ -40,13 +40,29 @@ pub fn generate_bldr_token(key_dir: &PathBuf) -> Result<String> {
     )
 }
 
-pub fn generate_user_token(key_dir: &PathBuf, account_id: u64, privileges: u32) -> Result<String> {
-    generate_access_token(
-        key_dir,
-        account_id,
-        privileges,
-        Duration::max_value(), // User tokens never expire, can only be revoked
-    )
+/// Generates a personal access token. The token may be limited to some origins and scopes, and
+/// given a Unix timestamp after which it expires. Tokens without one never expire and can only be
+/// revoked.
+pub fn generate_user_token(
+    key_dir: &PathBuf,
+    account_id: u64,
+    privileges: u32,
+    origins: Vec<String>,
+    scopes: Vec<sessionsrv::AccessTokenScope>,
+    expires: Option<i64>,
+) -> Result<String> {
+    let expires = match expires {
+        Some(expires) => expires,
+        None => chrono::MAX_DATE.and_hms(0, 0, 0).timestamp(),
+    };
+
+    let mut token = sessionsrv::AccessToken::new();
+    token.set_account_id(account_id);
+    token.set_flags(privileges);
+    token.set_expires(expires);
+    token.set_origins(origins.into());
+    token.set_scopes(scopes);
+    encode_access_token(key_dir, &token)
 }
 
 pub fn generate_access_token(
@@ -64,8 +80,11 @@ pub fn generate_access_token(
     token.set_account_id(account_id);
     token.set_flags(flags);
     token.set_expires(expires);
+    encode_access_token(key_dir, &token)
+}
 
-    let bytes = message::encode(&token).map_err(Error::Protocol)?;
+fn encode_access_token(key_dir: &PathBuf, token: &sessionsrv::AccessToken) -> Result<String> {
+    let bytes = message::encode(token).map_err(Error::Protocol)?;
     let ciphertext = encrypt(key_dir, &bytes)?;
 
     Ok(format!("{}{}", ACCESS_TOKEN_PREFIX, ciphertext))
@@ -161,6 +161,10 @@ pub struct ProjectCfg {
     /// Relative filepath to the project's Habitat Plan (default: "habitat").
     #[serde(default = "ProjectCfg::default_plan_path")]
     plan_path: PathBuf,
+    /// Worker labels required to build this project (e.g. "docker"). Only workers advertising
+    /// all of these labels will be dispatched the project's jobs.
+    #[serde(default)]
+    pub labels: Vec<String>,
 }
 
 impl ProjectCfg {
@@ -208,6 +212,7 @@ impl Default for ProjectCfg {
             channels: vec![],
             paths: vec![ProjectCfg::default_path()],
             plan_path: ProjectCfg::default_plan_path(),
+            labels: vec![],
         }
     }
 }
@@ -235,6 +240,9 @@ mod test {
     paths = [
       "components/net/*"
     ]
+    labels = [
+      "docker"
+    ]
 
     [default]
     "#;
@@ -260,6 +268,13 @@ mod test {
         assert_eq!(default.triggered_by("master", &["components"]), false);
     }
 
+    #[test]
+    fn labels() {
+        let cfg = BuildCfg::from_slice(CONFIG.as_bytes()).unwrap();
+        assert_eq!(cfg.get("builder-api").unwrap().labels, vec!["docker"]);
+        assert!(cfg.get("hab-sup").unwrap().labels.is_empty());
+    }
+
     #[test]
     fn triggered_by_default() {
         let cfg = BuildCfg::default();
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::net::IpAddr;
+
 use protocol::originsrv::OriginPackageVisibility;
 
 pub fn transition_visibility(
@@ -30,3 +32,73 @@ pub fn transition_visibility(
         (_, OriginPackageVisibility::Public) => OriginPackageVisibility::Public,
     }
 }
+
+/// Whether an address is reachable on the public internet, as opposed to loopback, private,
+/// link-local, shared or otherwise reserved addresses which Builder must not be made to call.
+pub fn is_public_addr(addr: &IpAddr) -> bool {
+    match *addr {
+        IpAddr::V4(ip) => {
+            let octets = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                || octets[0] == 0
+                || (octets[0] == 100 && octets[1] & 0xc0 == 64))
+        }
+        IpAddr::V6(ip) => {
+            if let Some(mapped) = ipv4_mapped(&ip.segments()) {
+                return is_public_addr(&IpAddr::V4(mapped.into()));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || first & 0xfe00 == 0xfc00
+                || first & 0xffc0 == 0xfe80)
+        }
+    }
+}
+
+fn ipv4_mapped(segments: &[u16; 8]) -> Option<[u8; 4]> {
+    if segments[..5].iter().all(|s| *s == 0) && segments[5] == 0xffff {
+        Some([
+            (segments[6] >> 8) as u8,
+            segments[6] as u8,
+            (segments[7] >> 8) as u8,
+            segments[7] as u8,
+        ])
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn public_addrs() {
+        for addr in &["8.8.8.8", "140.82.112.3", "2606:4700::1111"] {
+            assert!(is_public_addr(&addr.parse().unwrap()), "{}", addr);
+        }
+        for addr in &[
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_addr(&addr.parse().unwrap()), "{}", addr);
+        }
+    }
+}
@@ -18,6 +18,12 @@ use std::ops::{Deref, DerefMut};
 use protocol::jobsrv;
 use protocol::originsrv;
 
+/// Name of the channel the packages built for a pull request are promoted to. Pull request
+/// numbers are only unique within a repository.
+pub fn pull_request_channel_name(repo_id: u32, pull_request: u64) -> String {
+    format!("bldr-pr-{}-{}", repo_id, pull_request)
+}
+
 #[derive(Clone)]
 pub struct Job(jobsrv::Job);
 
@@ -72,13 +78,21 @@ impl fmt::Debug for Job {
             })
             .collect();
 
+        let mut project = self.0.get_project().clone();
+        if project.has_vcs_auth_token() {
+            project.set_vcs_auth_token("[secure]".to_string());
+        }
+
         f.debug_struct("Job")
             .field("id", &self.0.get_id())
             .field("owner_id", &self.0.get_owner_id())
             .field("state", &self.0.get_state())
-            .field("project", &self.0.get_project())
+            .field("project", &project)
             .field("created_at", &self.0.get_created_at())
             .field("channel", &self.0.get_channel())
+            .field("git_ref", &self.0.get_git_ref())
+            .field("git_sha", &self.0.get_git_sha())
+            .field("pull_request", &self.0.get_pull_request())
             .field("build_started_at", &self.0.get_build_started_at())
             .field("build_finished_at", &self.0.get_build_finished_at())
             .field("package_ident", &self.0.get_package_ident())
@@ -48,6 +48,7 @@ pub mod keys;
 pub mod logger;
 pub mod metrics;
 pub mod package_graph;
+pub mod plan_deps;
 pub mod rdeps;
 pub mod target_graph;
 
@@ -0,0 +1,189 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reads the dependencies a plan declares, so that plans changed by the same push can be built
+//! in dependency order before any of them has been uploaded.
+
+use std::collections::{HashMap, HashSet};
+
+const DEP_VARS: &'static [&'static str] = &["pkg_deps", "pkg_build_deps"];
+
+/// Returns the origin and name (e.g. `core/glibc`) of each of the plan's `pkg_deps` and
+/// `pkg_build_deps`. Entries built from shell variables can't be resolved and are left out.
+pub fn plan_deps(content: &str) -> Vec<String> {
+    let mut deps = Vec::new();
+    let mut in_array = false;
+
+    for line in content.lines() {
+        let mut line = strip_comment(line).trim();
+        if !in_array {
+            let rest = DEP_VARS
+                .iter()
+                .filter_map(|var| dep_array(line, var))
+                .next();
+            match rest {
+                Some(rest) => {
+                    in_array = true;
+                    line = rest;
+                }
+                None => continue,
+            }
+        }
+        let (items, closed) = match line.find(')') {
+            Some(idx) => (&line[..idx], true),
+            None => (line, false),
+        };
+        for item in items.split_whitespace() {
+            if let Some(dep) = dep_name(item) {
+                if !deps.contains(&dep) {
+                    deps.push(dep);
+                }
+            }
+        }
+        if closed {
+            in_array = false;
+        }
+    }
+    deps
+}
+
+/// Orders plans, given as their origin/name and dependencies, so that each comes after the
+/// plans it depends on. Dependencies outside of the given plans are ignored. Plans keep their
+/// given order where they don't depend on each other. Returns `None` if the plans depend on
+/// each other in a cycle.
+pub fn build_order(plans: &[(String, Vec<String>)]) -> Option<Vec<usize>> {
+    let index: HashMap<&str, usize> = plans
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.0.as_str(), i))
+        .collect();
+    let mut order = Vec::with_capacity(plans.len());
+    let mut done = HashSet::new();
+
+    while order.len() < plans.len() {
+        let next = (0..plans.len()).find(|i| {
+            !done.contains(i)
+                && plans[*i].1.iter().all(|dep| match index.get(dep.as_str()) {
+                    Some(d) => *d == *i || done.contains(d),
+                    None => true,
+                })
+        });
+        match next {
+            Some(i) => {
+                done.insert(i);
+                order.push(i);
+            }
+            None => return None,
+        }
+    }
+    Some(order)
+}
+
+// The rest of the line after `<var>=(` or `<var>+=(`
+fn dep_array<'a>(line: &'a str, var: &str) -> Option<&'a str> {
+    if !line.starts_with(var) {
+        return None;
+    }
+    let rest = line[var.len()..].trim_left();
+    let rest = if rest.starts_with("+=") {
+        &rest[2..]
+    } else if rest.starts_with('=') {
+        &rest[1..]
+    } else {
+        return None;
+    };
+    let rest = rest.trim_left();
+    if rest.starts_with('(') {
+        Some(&rest[1..])
+    } else {
+        None
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    match line.find('#') {
+        Some(0) => "",
+        Some(idx) if line[..idx].ends_with(char::is_whitespace) => &line[..idx],
+        _ => line,
+    }
+}
+
+fn dep_name(item: &str) -> Option<String> {
+    let item = item.trim_matches(|c| c == '"' || c == '\'');
+    let parts: Vec<&str> = item.split('/').collect();
+    if parts.len() < 2 || parts[..2].iter().any(|p| p.is_empty() || p.contains('$')) {
+        return None;
+    }
+    Some(format!("{}/{}", parts[0], parts[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deps_from_plan() {
+        let plan = r#"
+pkg_name=nginx
+pkg_origin=core
+pkg_deps=(core/glibc core/libedit/3.1 "core/openssl")
+pkg_build_deps=(
+  core/gcc # the compiler
+  # core/commented
+  core/make core/glibc
+  ${pkg_origin}/local
+)
+pkg_bin_dirs=(sbin)
+"#;
+        assert_eq!(
+            plan_deps(plan),
+            vec![
+                "core/glibc",
+                "core/libedit",
+                "core/openssl",
+                "core/gcc",
+                "core/make",
+            ]
+        );
+    }
+
+    #[test]
+    fn deps_appended_to() {
+        let plan = "pkg_deps=()\npkg_deps+=(core/zlib)\n";
+        assert_eq!(plan_deps(plan), vec!["core/zlib"]);
+    }
+
+    #[test]
+    fn order_by_deps() {
+        let plans = vec![
+            (
+                "core/app".to_string(),
+                vec!["core/lib".to_string(), "core/glibc".to_string()],
+            ),
+            ("core/lib".to_string(), vec!["core/base".to_string()]),
+            ("core/other".to_string(), vec![]),
+            ("core/base".to_string(), vec![]),
+        ];
+        assert_eq!(build_order(&plans), Some(vec![2, 3, 1, 0]));
+    }
+
+    #[test]
+    fn order_with_cycle() {
+        let plans = vec![
+            ("core/a".to_string(), vec!["core/b".to_string()]),
+            ("core/b".to_string(), vec!["core/a".to_string()]),
+        ];
+        assert_eq!(build_order(&plans), None);
+    }
+}
@@ -20,12 +20,13 @@ use std::thread;
 use std::time::Duration;
 
 use hab_net::conn::RouteClient;
+use postgres;
 use rand::{self, Rng};
 use threadpool::ThreadPool;
 use time::Duration as SteadyDuration;
 use time::SteadyTime;
 
-use error::Result;
+use error::{Error, Result};
 use pool::Pool;
 
 pub type DispatchKey = String;
@@ -38,6 +39,50 @@ pub enum EventOutcome {
 
 const BACKOFF_SLOT_TIME_MS: u64 = 100;
 const FAILURE_COUNT_UPPER_BOUND: usize = 10;
+const LOCKED_RETRY_MS: i64 = 1000;
+
+/// Advisory lock class for async events. The second lock key is a hash of the dispatch key, so
+/// each event runs on only one server sharing the database at a time.
+pub const EVENT_LOCK_CLASS: i32 = 0x6173_796e;
+
+/// Holds the advisory lock of a running event until dropped. The lock is taken on a connection
+/// of its own so that it doesn't keep one from the pool the events run their queries on.
+struct EventLock {
+    conn: postgres::Connection,
+    key: DispatchKey,
+}
+
+impl EventLock {
+    fn try_acquire(pool: &Pool, key: &DispatchKey) -> Result<Option<EventLock>> {
+        let conn = pool.connect()?;
+        let acquired: bool = {
+            let rows = conn.query(
+                "SELECT pg_try_advisory_lock($1, hashtext($2))",
+                &[&EVENT_LOCK_CLASS, key],
+            ).map_err(Error::AsyncLock)?;
+            rows.get(0).get(0)
+        };
+        if acquired {
+            Ok(Some(EventLock {
+                conn: conn,
+                key: key.clone(),
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl Drop for EventLock {
+    fn drop(&mut self) {
+        if let Err(e) = self.conn.execute(
+            "SELECT pg_advisory_unlock($1, hashtext($2))",
+            &[&EVENT_LOCK_CLASS, &self.key],
+        ) {
+            warn!("Unable to release lock of event {}, {}", self.key, e);
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct AsyncServer {
@@ -120,6 +165,23 @@ impl AsyncServer {
 
     pub fn run_event(&self, key: DispatchKey, event: EventFunction) {
         let remove_key = key.clone();
+        let lock = match EventLock::try_acquire(&self.pool, &key) {
+            Ok(Some(lock)) => lock,
+            Ok(None) => {
+                debug!("Event {} is running on another server, delaying", key);
+                self.delay_locked_event(key, event);
+                let mut running = self.running.write().expect("Running lock is poisoned");
+                running.remove(&remove_key);
+                return;
+            }
+            Err(e) => {
+                warn!("Event {} failed, {}", key, e);
+                self.retry_failed_event(key, event);
+                let mut running = self.running.write().expect("Running lock is poisoned");
+                running.remove(&remove_key);
+                return;
+            }
+        };
         let conn = RouteClient::new().expect("failed to create RouteClient");
         conn.connect(&*self.router_pipe)
             .expect("failed to connect RouteClient to inproc pipe");
@@ -141,10 +203,19 @@ impl AsyncServer {
                 self.retry_failed_event(key, event);
             }
         }
+        drop(lock);
         let mut running = self.running.write().expect("Running lock is poisoned");
         running.remove(&remove_key);
     }
 
+    // The server holding the lock may already have read past changes which scheduled this
+    // event, so run it again once the lock is free rather than dropping it.
+    fn delay_locked_event(&self, key: DispatchKey, event: EventFunction) {
+        let next_event = SteadyTime::now() + SteadyDuration::milliseconds(LOCKED_RETRY_MS);
+        let mut r = self.retry.write().expect("Async retry lock poisoned");
+        r.insert(key, (next_event, event));
+    }
+
     fn retry_failed_event(&self, key: DispatchKey, event: EventFunction) {
         warn!("Scheduling retry of {:?}", key);
         let failure_count = {
@@ -27,6 +27,7 @@ pub enum Error {
     AsyncMalformedShardId(String),
     AsyncFunctionCheck(postgres::error::Error),
     AsyncFunctionUpdate(postgres::error::Error),
+    AsyncLock(postgres::error::Error),
     ConnectionTimeout(r2d2::Error),
     FunctionCreate(postgres::error::Error),
     FunctionDrop(postgres::error::Error),
@@ -65,6 +66,7 @@ impl fmt::Display for Error {
             Error::AsyncFunctionUpdate(ref e) => {
                 format!("Async function database update failed, {}", e)
             }
+            Error::AsyncLock(ref e) => format!("Error getting async event lock, {}", e),
             Error::ConnectionTimeout(ref e) => format!("Connection timeout, {}", e),
             Error::FunctionCreate(ref e) => format!("Error creating a function: {}", e),
             Error::FunctionDrop(ref e) => format!("Error dropping a function: {}", e),
@@ -99,6 +101,7 @@ impl error::Error for Error {
             Error::AsyncMalformedShardId(_) => "Error parsing a channel strings shard id",
             Error::AsyncFunctionCheck(ref e) => e.description(),
             Error::AsyncFunctionUpdate(ref e) => e.description(),
+            Error::AsyncLock(ref e) => e.description(),
             Error::ConnectionTimeout(ref e) => e.description(),
             Error::FunctionCreate(_) => "Error creating database function",
             Error::FunctionDrop(_) => "Error dropping database function",
@@ -18,6 +18,7 @@ use std::thread;
 use std::time::Duration;
 
 use fnv::FnvHasher;
+use postgres;
 use r2d2;
 use r2d2_postgres::{self, PostgresConnectionManager, TlsMode};
 use rand::{self, Rng};
@@ -29,6 +30,7 @@ use protocol::{Routable, RouteKey, ShardId, SHARD_COUNT};
 #[derive(Clone)]
 pub struct Pool {
     inner: r2d2::Pool<PostgresConnectionManager>,
+    config: DataStoreCfg,
     pub shards: Vec<ShardId>,
 }
 
@@ -55,6 +57,7 @@ impl Pool {
                 Ok(pool) => {
                     return Ok(Pool {
                         inner: pool,
+                        config: config.clone(),
                         shards: shards,
                     })
                 }
@@ -74,6 +77,13 @@ impl Pool {
         Ok(conn)
     }
 
+    /// Opens a connection to the data store outside of the pool, for sessions which would
+    /// otherwise keep a pooled connection from the queries sharing the pool.
+    pub fn connect(&self) -> Result<postgres::Connection> {
+        postgres::Connection::connect(&self.config, postgres::TlsMode::None)
+            .map_err(Error::PostgresConnect)
+    }
+
     pub fn get_shard(
         &self,
         shard_id: u32,
@@ -87,6 +87,36 @@ fn retries() {
     });
 }
 
+#[test]
+fn waits_for_lock() {
+    with_pool!(pool, {
+        let conn = pool.get_raw().expect("Failed to get connection");
+        conn.execute(
+            "SELECT pg_advisory_lock($1, hashtext($2))",
+            &[&async::EVENT_LOCK_CLASS, &"event1"],
+        ).expect("Failed to take event lock");
+        let server = async::AsyncServer::new(pool, Arc::new("inproc://test".to_string()));
+        server.register("event1".to_string(), will_finish);
+        server.run_event("event1".to_string(), will_finish);
+        assert_eq!(
+            server
+                .retry
+                .read()
+                .expect("Dispatch lock poisoned")
+                .contains_key("event1"),
+            true
+        );
+        assert_eq!(
+            server
+                .failure_count
+                .read()
+                .expect("Dispatch lock poisoned")
+                .contains_key("event1"),
+            false
+        );
+    });
+}
+
 fn will_finish(_pool: Pool, _conn: RouteClient) -> Result<async::EventOutcome> {
     Ok(async::EventOutcome::Finished)
 }
@@ -0,0 +1,158 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pkg storage backend variant which stores harts in the local filesystem.
+//!
+//! Intended for installations without access to an object store. Each
+//! artifact is stored beneath the configured root directory at the same
+//! key the S3 backend uses, so the directory can be synced into a bucket
+//! (or the other way around) when moving between backends.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bldr_core::metrics::CounterMetric;
+use hab_core::package::{PackageArchive, PackageIdent, PackageTarget};
+use metrics::Counter;
+use walkdir::WalkDir;
+
+use super::{parse_storage_key, storage_key, PackageStorage};
+use error::{Error, Result};
+
+/// Wraps a `PathBuf` representing the root of the local artifact store.
+pub struct LocalStorage(PathBuf);
+
+impl LocalStorage {
+    pub fn new<T>(root: T) -> Result<LocalStorage>
+    where
+        T: AsRef<Path>,
+    {
+        let root = root.as_ref().to_path_buf();
+        fs::create_dir_all(&root)?;
+        Ok(LocalStorage(root))
+    }
+
+    fn artifact_path(&self, ident: &PackageIdent, target: &PackageTarget) -> Result<PathBuf> {
+        Ok(self.0.join(storage_key(ident, target)?))
+    }
+}
+
+impl PackageStorage for LocalStorage {
+    fn upload(&self, hart: &PathBuf, ident: &PackageIdent, target: &PackageTarget) -> Result<()> {
+        Counter::UploadRequests.increment();
+        let path = self.artifact_path(ident, target)?;
+        fs::create_dir_all(path.parent().unwrap())?; // Unwrap OK, keys are nested
+
+        // Copy next to the final location first so a failed copy never leaves a partial artifact
+        // where downloads would find it.
+        let tmp = path.with_extension("hart.tmp");
+        let copied = fs::copy(hart, &tmp).and_then(|_| fs::rename(&tmp, &path));
+        if let Err(e) = copied {
+            Counter::UploadFailures.increment();
+            warn!("Upload failed for {}: ({:?})", hart.display(), e);
+            let _ = fs::remove_file(&tmp);
+            return Err(Error::IO(e));
+        }
+
+        info!("Upload completed for {}", hart.display());
+        Ok(())
+    }
+
+    fn download(
+        &self,
+        loc: &PathBuf,
+        ident: &PackageIdent,
+        target: &PackageTarget,
+    ) -> Result<PackageArchive> {
+        Counter::DownloadRequests.increment();
+        let path = self.artifact_path(ident, target)?;
+        if let Err(e) = fs::copy(&path, loc) {
+            warn!("Failed to retrieve {} from storage: {:?}", path.display(), e);
+            return Err(Error::IO(e));
+        }
+        Ok(PackageArchive::new(loc))
+    }
+
+    fn exists(&self, ident: &PackageIdent, target: &PackageTarget) -> Result<bool> {
+        Ok(self.artifact_path(ident, target)?.is_file())
+    }
+
+    fn delete(&self, ident: &PackageIdent, target: &PackageTarget) -> Result<()> {
+        match fs::remove_file(self.artifact_path(ident, target)?) {
+            Ok(()) => Ok(()),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(Error::IO(e)),
+        }
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<(PackageIdent, PackageTarget)>> {
+        let mut artifacts = Vec::new();
+        for entry in WalkDir::new(&self.0) {
+            let entry = entry.map_err(|e| Error::IO(e.into()))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let key = match entry.path().strip_prefix(&self.0) {
+                Ok(key) => key.to_string_lossy().into_owned(),
+                Err(_) => continue,
+            };
+            if !key.starts_with(prefix) {
+                continue;
+            }
+            if let Some(artifact) = parse_storage_key(&key) {
+                artifacts.push(artifact);
+            }
+        }
+        Ok(artifacts)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs::File;
+    use std::io::Write;
+    use std::str::FromStr;
+    use tempfile;
+
+    #[test]
+    fn upload_list_delete() {
+        let root = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(root.path().join("artifacts")).unwrap();
+        let ident = PackageIdent::from_str("core/redis/4.0.10/20180701122201").unwrap();
+        let target = PackageTarget::from_str("x86_64-linux").unwrap();
+
+        let hart = root.path().join("upload.hart");
+        File::create(&hart).unwrap().write_all(b"HART-1").unwrap();
+
+        assert!(!storage.exists(&ident, &target).unwrap());
+        storage.upload(&hart, &ident, &target).unwrap();
+        assert!(storage.exists(&ident, &target).unwrap());
+
+        assert_eq!(
+            storage.list("core/redis").unwrap(),
+            vec![(ident.clone(), target.clone())]
+        );
+        assert!(storage.list("core/zlib").unwrap().is_empty());
+
+        let loc = root.path().join("download.hart");
+        storage.download(&loc, &ident, &target).unwrap();
+        assert_eq!(fs::read(&loc).unwrap(), b"HART-1");
+
+        storage.delete(&ident, &target).unwrap();
+        assert!(!storage.exists(&ident, &target).unwrap());
+        storage.delete(&ident, &target).unwrap();
+    }
+}
@@ -1 +1,157 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Contract for storage and retrieval of package artifacts.
+//!
+//! Uploaded harts are staged on the depot's local disk and then handed to a storage backend,
+//! which is also where they are fetched from on download. Every backend lays artifacts out using
+//! the same key, so that artifacts can be moved between backends by copying them.
+
+pub mod local;
 pub mod s3;
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use hab_core::package::{PackageArchive, PackageIdent, PackageTarget};
+use iron::typemap::Key;
+
+use config::Config;
+use error::Result;
+
+/// Currently implemented package storage backends
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    Local,
+    S3,
+}
+
+pub trait PackageStorage: Send + Sync {
+    /// Given the path to a hart, places it in storage as the artifact of the given package and
+    /// target.
+    fn upload(&self, hart: &PathBuf, ident: &PackageIdent, target: &PackageTarget) -> Result<()>;
+
+    /// Retrieves the artifact of the given package and target from storage, writing it to `loc`.
+    fn download(
+        &self,
+        loc: &PathBuf,
+        ident: &PackageIdent,
+        target: &PackageTarget,
+    ) -> Result<PackageArchive>;
+
+    /// Returns whether an artifact for the given package and target is in storage.
+    fn exists(&self, ident: &PackageIdent, target: &PackageTarget) -> Result<bool>;
+
+    /// Removes the artifact of the given package and target from storage. Removing an artifact
+    /// which isn't in storage is not an error.
+    fn delete(&self, ident: &PackageIdent, target: &PackageTarget) -> Result<()>;
+
+    /// Lists the artifacts in storage whose key starts with `prefix`, for example `core` or
+    /// `core/redis`. An empty prefix lists every artifact.
+    fn list(&self, prefix: &str) -> Result<Vec<(PackageIdent, PackageTarget)>>;
+}
+
+pub struct StorageCli;
+
+impl Key for StorageCli {
+    type Value = Box<PackageStorage>;
+}
+
+/// Create appropriate PackageStorage variant based on configuration values.
+pub fn from_config(config: &Config) -> Result<Box<PackageStorage>> {
+    match config.storage.backend {
+        StorageBackend::Local => Ok(Box::new(local::LocalStorage::new(
+            config.storage.local_dir.clone(),
+        )?)),
+        StorageBackend::S3 => Ok(Box::new(s3::S3Handler::new(config.s3.clone()))),
+    }
+}
+
+// Helper function for programmatic creation of
+// the storage key of an artifact
+fn storage_key(ident: &PackageIdent, target: &PackageTarget) -> Result<String> {
+    // Calling this method first ensures that the ident is fully qualified and the correct errors
+    // are returned in case of failure
+    let hart_name = ident.archive_name_with_target(target)?;
+
+    Ok(format!(
+        "{}/{}/{}",
+        ident.iter().collect::<Vec<&str>>().join("/"),
+        target.iter().collect::<Vec<&str>>().join("/"),
+        hart_name
+    ))
+}
+
+// Inverse of `storage_key`. Returns `None` for keys which weren't created by it.
+fn parse_storage_key(key: &str) -> Option<(PackageIdent, PackageTarget)> {
+    let parts: Vec<&str> = key.split('/').collect();
+    if parts.len() != 7 || !parts[6].ends_with(".hart") {
+        return None;
+    }
+    let ident = PackageIdent::new(parts[0], parts[1], Some(parts[2]), Some(parts[3]));
+    let target = match PackageTarget::from_str(&format!("{}-{}", parts[4], parts[5])) {
+        Ok(target) => target,
+        Err(_) => return None,
+    };
+    Some((ident, target))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use error::Error;
+    use hab_core;
+
+    #[test]
+    fn storage_key_fully_qualified_ident() {
+        let ident =
+            PackageIdent::from_str("bend-sinister/the-other-way/1.0.0/20180701122201").unwrap();
+        let target = PackageTarget::from_str("x86_64-linux").unwrap();
+
+        assert_eq!(
+            format!(
+                "{}/{}",
+                "bend-sinister/the-other-way/1.0.0/20180701122201/x86_64/linux",
+                "bend-sinister-the-other-way-1.0.0-20180701122201-x86_64-linux.hart"
+            ),
+            storage_key(&ident, &target).unwrap()
+        );
+    }
+
+    #[test]
+    fn storage_key_fuzzy_ident() {
+        let ident = PackageIdent::from_str("acme/not-enough").unwrap();
+        let target = PackageTarget::from_str("x86_64-linux").unwrap();
+
+        match storage_key(&ident, &target) {
+            Err(Error::HabitatCore(hab_core::Error::FullyQualifiedPackageIdentRequired(i))) => {
+                assert_eq!("acme/not-enough", i)
+            }
+            Err(e) => panic!("Wrong expected error, found={:?}", e),
+            Ok(s) => panic!("Should not have computed a result, returned={}", s),
+        }
+    }
+
+    #[test]
+    fn parse_storage_key_roundtrip() {
+        let ident =
+            PackageIdent::from_str("bend-sinister/the-other-way/1.0.0/20180701122201").unwrap();
+        let target = PackageTarget::from_str("x86_64-windows").unwrap();
+        let key = storage_key(&ident, &target).unwrap();
+
+        assert_eq!(parse_storage_key(&key), Some((ident, target)));
+        assert_eq!(parse_storage_key("bend-sinister/the-other-way"), None);
+    }
+}
@@ -32,16 +32,16 @@ use std::str::FromStr;
 use bldr_core::metrics::CounterMetric;
 use futures::{Future, Stream};
 use hab_core::package::{PackageArchive, PackageIdent, PackageTarget};
-use iron::typemap::Key;
 use metrics::Counter;
 use rusoto::{credential::StaticProvider, reactor::RequestDispatcher, Region};
 use rusoto_s3::{
     CompleteMultipartUploadRequest, CompletedMultipartUpload, CompletedPart, CreateBucketRequest,
-    CreateMultipartUploadRequest, GetObjectRequest, PutObjectRequest, S3, S3Client,
-    UploadPartRequest,
+    CreateMultipartUploadRequest, DeleteObjectRequest, GetObjectRequest, ListObjectsRequest,
+    PutObjectRequest, S3, S3Client, UploadPartRequest,
 };
 use time::PreciseTime;
 
+use super::{parse_storage_key, storage_key, PackageStorage};
 use config::{S3Backend, S3Cfg};
 use Config;
 use DepotUtil;
@@ -119,56 +119,6 @@ impl S3Handler {
         }
     }
 
-    pub fn upload(
-        &self,
-        hart: &PathBuf,
-        ident: &PackageIdent,
-        target: &PackageTarget,
-    ) -> Result<()> {
-        Counter::UploadRequests.increment();
-        let key = s3_key(ident, target)?;
-        let file = File::open(hart)?;
-        let path_attr = &hart.clone().into_os_string().into_string().unwrap();
-        info!("S3Handler::upload request started for s3_key: {}", key);
-        let size = file.metadata().unwrap().len() as usize;
-        if size < MINLIMIT {
-            self.single_upload(key, file, &path_attr)
-        } else {
-            self.multipart_upload(key, file, &path_attr)
-        }
-    }
-
-    pub fn download(
-        &self,
-        loc: &PathBuf,
-        ident: &PackageIdent,
-        target: &PackageTarget,
-    ) -> Result<PackageArchive> {
-        Counter::DownloadRequests.increment();
-        let mut request = GetObjectRequest::default();
-        let key = s3_key(ident, target)?;
-        request.bucket = self.bucket.to_owned();
-        request.key = key;
-
-        let payload = self.client.get_object(&request).sync();
-        let body = match payload {
-            Ok(response) => response.body,
-            Err(e) => {
-                warn!("Failed to retrieve object from S3: {:?}", e);
-                return Err(Error::PackageDownload(e));
-            }
-        };
-
-        let file = body.expect("Downloaded pkg archive empty!").concat2();
-        match Config::write_archive(&loc, &file.wait().unwrap()) {
-            Ok(result) => return Ok(result),
-            Err(e) => {
-                warn!("Unable to write file {:?} to archive, err={:?}", loc, e);
-                return Err(e);
-            }
-        }
-    }
-
     fn single_upload(&self, key: String, hart: File, path_attr: &str) -> Result<()> {
         Counter::SingleUploadRequests.increment();
         let start_time = PreciseTime::now();
@@ -280,61 +230,113 @@ impl S3Handler {
             }
         }
     }
-}
 
-pub struct S3Cli;
+    // Lists the keys of all objects starting with the given prefix. S3 returns at most 1000 keys
+    // per request, so this pages through the listing.
+    fn list_keys(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut request = ListObjectsRequest::default();
+        request.bucket = self.bucket.clone();
+        request.prefix = Some(prefix.to_string());
 
-impl Key for S3Cli {
-    type Value = S3Handler;
+        loop {
+            let output = match self.client.list_objects(&request).sync() {
+                Ok(output) => output,
+                Err(e) => {
+                    debug!("{:?}", e);
+                    return Err(Error::ObjectError(e));
+                }
+            };
+            let page: Vec<String> = output
+                .contents
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|object| object.key)
+                .collect();
+            if !output.is_truncated.unwrap_or(false) || page.is_empty() {
+                keys.extend(page);
+                return Ok(keys);
+            }
+            request.marker = page.last().cloned();
+            keys.extend(page);
+        }
+    }
 }
 
-// Helper function for programmatic creation of
-// the s3 object key
-fn s3_key(ident: &PackageIdent, target: &PackageTarget) -> Result<String> {
-    // Calling this method first ensures that the ident is fully qualified and the correct errors
-    // are returned in case of failure
-    let hart_name = ident.archive_name_with_target(target)?;
+impl PackageStorage for S3Handler {
+    fn upload(
+        &self,
+        hart: &PathBuf,
+        ident: &PackageIdent,
+        target: &PackageTarget,
+    ) -> Result<()> {
+        Counter::UploadRequests.increment();
+        let key = storage_key(ident, target)?;
+        let file = File::open(hart)?;
+        let path_attr = &hart.clone().into_os_string().into_string().unwrap();
+        info!("S3Handler::upload request started for s3_key: {}", key);
+        let size = file.metadata().unwrap().len() as usize;
+        if size < MINLIMIT {
+            self.single_upload(key, file, &path_attr)
+        } else {
+            self.multipart_upload(key, file, &path_attr)
+        }
+    }
 
-    Ok(format!(
-        "{}/{}/{}",
-        ident.iter().collect::<Vec<&str>>().join("/"),
-        target.iter().collect::<Vec<&str>>().join("/"),
-        hart_name
-    ))
-}
+    fn download(
+        &self,
+        loc: &PathBuf,
+        ident: &PackageIdent,
+        target: &PackageTarget,
+    ) -> Result<PackageArchive> {
+        Counter::DownloadRequests.increment();
+        let mut request = GetObjectRequest::default();
+        let key = storage_key(ident, target)?;
+        request.bucket = self.bucket.to_owned();
+        request.key = key;
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use hab_core;
+        let payload = self.client.get_object(&request).sync();
+        let body = match payload {
+            Ok(response) => response.body,
+            Err(e) => {
+                warn!("Failed to retrieve object from S3: {:?}", e);
+                return Err(Error::PackageDownload(e));
+            }
+        };
 
-    #[test]
-    fn s3_key_fully_qualified_ident() {
-        let ident =
-            PackageIdent::from_str("bend-sinister/the-other-way/1.0.0/20180701122201").unwrap();
-        let target = PackageTarget::from_str("x86_64-linux").unwrap();
+        let file = body.expect("Downloaded pkg archive empty!").concat2();
+        match Config::write_archive(&loc, &file.wait().unwrap()) {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                warn!("Unable to write file {:?} to archive, err={:?}", loc, e);
+                return Err(e);
+            }
+        }
+    }
 
-        assert_eq!(
-            format!(
-                "{}/{}",
-                "bend-sinister/the-other-way/1.0.0/20180701122201/x86_64/linux",
-                "bend-sinister-the-other-way-1.0.0-20180701122201-x86_64-linux.hart"
-            ),
-            s3_key(&ident, &target).unwrap()
-        );
+    fn exists(&self, ident: &PackageIdent, target: &PackageTarget) -> Result<bool> {
+        let key = storage_key(ident, target)?;
+        Ok(self.list_keys(&key)?.iter().any(|k| *k == key))
     }
 
-    #[test]
-    fn s3_key_fuzzy_ident() {
-        let ident = PackageIdent::from_str("acme/not-enough").unwrap();
-        let target = PackageTarget::from_str("x86_64-linux").unwrap();
+    fn delete(&self, ident: &PackageIdent, target: &PackageTarget) -> Result<()> {
+        let mut request = DeleteObjectRequest::default();
+        request.bucket = self.bucket.to_owned();
+        request.key = storage_key(ident, target)?;
 
-        match s3_key(&ident, &target) {
-            Err(Error::HabitatCore(hab_core::Error::FullyQualifiedPackageIdentRequired(i))) => {
-                assert_eq!("acme/not-enough", i)
+        match self.client.delete_object(&request).sync() {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                warn!("Failed to delete object from S3: {:?}", e);
+                Err(Error::PackageDelete(e))
             }
-            Err(e) => panic!("Wrong expected error, found={:?}", e),
-            Ok(s) => panic!("Should not have computed a result, returned={}", s),
         }
     }
+
+    fn list(&self, prefix: &str) -> Result<Vec<(PackageIdent, PackageTarget)>> {
+        Ok(self.list_keys(prefix)?
+            .iter()
+            .filter_map(|key| parse_storage_key(key))
+            .collect())
+    }
 }
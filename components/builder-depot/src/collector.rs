@@ -0,0 +1,206 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Garbage collector for package artifacts.
+//!
+//! Artifacts are written to storage before their package is created and removed after their
+//! package is deleted, so a failure in between leaves an artifact no package refers to. The
+//! collector periodically lists the storage backend and removes those artifacts. An artifact is
+//! only removed once it has been found orphaned on two consecutive passes, so that uploads which
+//! are still in flight are left alone.
+
+use std::collections::HashSet;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use bldr_core::access_token::{BUILDER_ACCOUNT_ID, BUILDER_ACCOUNT_NAME};
+use bldr_core::metrics::CounterMetric;
+use hab_core::package::{PackageIdent, PackageTarget};
+use hab_net::conn::RouteClient;
+use hab_net::{ErrCode, NetOk};
+use http_gateway::conn::RouteBroker;
+use http_gateway::http::helpers::all_visibilities;
+use protocol::originsrv::*;
+
+use backend::{self, PackageStorage};
+use config::Config;
+use error::{Error, Result};
+use metrics::Counter;
+
+type Artifact = (PackageIdent, PackageTarget);
+
+pub struct ArtifactCollector {
+    storage: Box<PackageStorage>,
+    interval: Duration,
+    /// Artifacts found orphaned on the previous pass
+    suspects: HashSet<Artifact>,
+}
+
+impl ArtifactCollector {
+    pub fn new(cfg: &Config) -> Result<Self> {
+        Ok(ArtifactCollector {
+            storage: backend::from_config(cfg)?,
+            interval: Duration::from_secs(cfg.storage.gc_interval_secs),
+            suspects: HashSet::new(),
+        })
+    }
+
+    /// Starts the collector on its own thread. Returns `None` if the collector is disabled.
+    pub fn start(cfg: &Config) -> Result<Option<JoinHandle<()>>> {
+        if cfg.storage.gc_interval_secs == 0 {
+            info!("artifact-collector is disabled");
+            return Ok(None);
+        }
+        let mut collector = Self::new(cfg)?;
+        let handle = thread::Builder::new()
+            .name("artifact-collector".to_string())
+            .spawn(move || collector.run())
+            .unwrap();
+        Ok(Some(handle))
+    }
+
+    fn run(&mut self) {
+        info!(
+            "artifact-collector is ready to go (interval: {}s).",
+            self.interval.as_secs()
+        );
+
+        loop {
+            thread::sleep(self.interval);
+            if let Err(err) = self.collect() {
+                warn!("Artifact collection failed, err={:?}", err);
+            }
+        }
+    }
+
+    /// Runs a single pass of the collector.
+    pub fn collect(&mut self) -> Result<()> {
+        let mut conn = RouteBroker::connect()?;
+        let collected = self.sweep(|artifact| is_orphaned(&mut conn, artifact))?;
+        for (ident, target) in collected {
+            audit_collect(&mut conn, &ident, &target);
+        }
+        Ok(())
+    }
+
+    /// Deletes the stored artifacts found orphaned on both this pass and the previous one and
+    /// returns them.
+    fn sweep<F>(&mut self, mut is_orphaned: F) -> Result<Vec<Artifact>>
+    where
+        F: FnMut(&Artifact) -> Result<bool>,
+    {
+        let mut orphans = HashSet::new();
+        for artifact in self.storage.list("")? {
+            if is_orphaned(&artifact)? {
+                orphans.insert(artifact);
+            }
+        }
+
+        let mut collected = vec![];
+        for artifact in orphans.intersection(&self.suspects) {
+            let (ref ident, ref target) = *artifact;
+            if let Err(err) = self.storage.delete(ident, target) {
+                warn!("Unable to collect artifact {} ({}), err={:?}", ident, target, err);
+                continue;
+            }
+            Counter::ArtifactsCollected.increment();
+            info!("Collected orphaned artifact {} ({})", ident, target);
+            collected.push(artifact.clone());
+        }
+
+        self.suspects = orphans;
+        Ok(collected)
+    }
+}
+
+/// An artifact is orphaned if there is no package with its ident, or if that package was built
+/// for another target.
+fn is_orphaned(conn: &mut RouteClient, artifact: &Artifact) -> Result<bool> {
+    let mut opg = OriginPackageGet::new();
+    opg.set_ident(OriginPackageIdent::from(artifact.0.clone()));
+    opg.set_visibilities(all_visibilities());
+
+    match conn.route::<OriginPackageGet, OriginPackage>(&opg) {
+        Ok(package) => Ok(package.get_target() != artifact.1.to_string()),
+        Err(ref err) if err.get_code() == ErrCode::ENTITY_NOT_FOUND => Ok(true),
+        Err(err) => Err(Error::NetError(err)),
+    }
+}
+
+fn audit_collect(conn: &mut RouteClient, ident: &PackageIdent, target: &PackageTarget) {
+    let mut audit = PackageDeleteAudit::new();
+    audit.set_ident(OriginPackageIdent::from(ident.clone()));
+    audit.set_target(target.to_string());
+    audit.set_operation(PackageDeleteOperation::ArtifactCollect);
+    audit.set_trigger(PackageChannelTrigger::Unknown);
+    audit.set_requester_id(BUILDER_ACCOUNT_ID);
+    audit.set_requester_name(BUILDER_ACCOUNT_NAME.to_string());
+
+    let mut origin_get = OriginGet::new();
+    origin_get.set_name(ident.origin.clone());
+    if let Ok(origin) = conn.route::<OriginGet, Origin>(&origin_get) {
+        audit.set_origin_id(origin.get_id());
+    }
+
+    if let Err(err) = conn.route::<PackageDeleteAudit, NetOk>(&audit) {
+        warn!("Failed to save artifact collection to audit log: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs::File;
+    use std::io::Write;
+    use std::str::FromStr;
+    use tempfile;
+
+    use backend::local::LocalStorage;
+
+    #[test]
+    fn sweep_collects_artifacts_orphaned_twice() {
+        let root = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(root.path().join("artifacts")).unwrap();
+        let target = PackageTarget::from_str("x86_64-linux").unwrap();
+        let hart = root.path().join("upload.hart");
+        File::create(&hart).unwrap().write_all(b"HART-1").unwrap();
+        let idents = [
+            "core/redis/4.0.10/20180701122201",
+            "core/zlib/1.2.11/20180701122201",
+        ];
+        for ident in idents.iter() {
+            let ident = PackageIdent::from_str(ident).unwrap();
+            storage.upload(&hart, &ident, &target).unwrap();
+        }
+        let redis = (PackageIdent::from_str(idents[0]).unwrap(), target.clone());
+        let zlib = (PackageIdent::from_str(idents[1]).unwrap(), target.clone());
+
+        let mut collector = ArtifactCollector {
+            storage: Box::new(storage),
+            interval: Duration::from_secs(1),
+            suspects: HashSet::new(),
+        };
+
+        // Both are orphaned, but an upload may still be in flight
+        assert!(collector.sweep(|_| Ok(true)).unwrap().is_empty());
+        // The redis package was created in the meantime
+        let collected = collector.sweep(|a| Ok(a == &zlib)).unwrap();
+        assert_eq!(collected, vec![zlib.clone()]);
+        assert!(!collector.storage.exists(&zlib.0, &zlib.1).unwrap());
+        assert!(collector.storage.exists(&redis.0, &redis.1).unwrap());
+        // An artifact is only a suspect after being found orphaned on the previous pass
+        assert!(collector.sweep(|_| Ok(true)).unwrap().is_empty());
+        assert_eq!(collector.sweep(|_| Ok(true)).unwrap(), vec![redis]);
+    }
+}
@@ -24,6 +24,7 @@ use hab_core::package::target::{self, PackageTarget};
 use http_gateway::config::prelude::*;
 use segment_api_client::SegmentCfg;
 
+use backend::StorageBackend;
 use error::Error;
 
 #[derive(Clone, Debug, Deserialize)]
@@ -32,8 +33,12 @@ pub struct Config {
     pub http: HttpCfg,
     /// List of net addresses for routing servers to connect to
     pub routers: Vec<RouterAddr>,
+    /// CurveZMQ keys used to connect to the routers
+    pub curve: CurveCfg,
     pub github: Option<GitHubCfg>,
     pub s3: S3Cfg,
+    /// Where package artifacts are stored
+    pub storage: StorageCfg,
     pub segment: SegmentCfg,
     /// Filepath to location on disk to store entities
     pub path: PathBuf,
@@ -67,8 +72,10 @@ impl Default for Config {
         Config {
             http: HttpCfg::default(),
             routers: vec![RouterAddr::default()],
+            curve: CurveCfg::default(),
             github: None,
             s3: S3Cfg::default(),
+            storage: StorageCfg::default(),
             segment: SegmentCfg::default(),
             path: PathBuf::from("/hab/svc/builder-api/data"),
             events_enabled: false, // TODO: change to default to true later
@@ -96,6 +103,10 @@ impl GatewayCfg for Config {
     fn route_addrs(&self) -> &[RouterAddr] {
         self.routers.as_slice()
     }
+
+    fn curve(&self) -> &CurveCfg {
+        &self.curve
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, PartialEq)]
@@ -128,6 +139,27 @@ impl Default for S3Cfg {
     }
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct StorageCfg {
+    pub backend: StorageBackend,
+    /// Root directory of the artifact store when using the local backend
+    pub local_dir: PathBuf,
+    /// Seconds between passes of the artifact collector, which removes artifacts belonging to
+    /// packages that no longer exist. Zero disables the collector.
+    pub gc_interval_secs: u64,
+}
+
+impl Default for StorageCfg {
+    fn default() -> Self {
+        StorageCfg {
+            backend: StorageBackend::S3,
+            local_dir: PathBuf::from("/hab/svc/builder-api/data/artifacts"),
+            gc_interval_secs: 86_400,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(default)]
 pub struct HttpCfg {
@@ -182,6 +214,11 @@ mod tests {
         endpoint = "https://aws"
         bucket_name = "mybucket"
 
+        [storage]
+        backend = "local"
+        local_dir = "/hab/svc/hab-depot/data/artifacts"
+        gc_interval_secs = 3600
+
         [[routers]]
         host = "172.18.0.2"
         port = 9001
@@ -208,6 +245,12 @@ mod tests {
         assert_eq!(config.s3.secret_key, "password".to_string());
         assert_eq!(config.s3.endpoint, "https://aws".to_string());
         assert_eq!(config.s3.bucket_name, "mybucket".to_string());
+        assert_eq!(config.storage.backend, StorageBackend::Local);
+        assert_eq!(
+            config.storage.local_dir,
+            PathBuf::from("/hab/svc/hab-depot/data/artifacts")
+        );
+        assert_eq!(config.storage.gc_interval_secs, 3600);
 
         assert_eq!(&format!("{}", config.http.listen), "127.0.0.1");
         assert_eq!(config.http.port, 9000);
@@ -228,5 +271,7 @@ mod tests {
         assert_eq!(config.http.port, 9000);
         assert_eq!(config.upstream_depot, None);
         assert_eq!(config.upstream_origins, vec!["core".to_string()]);
+        assert_eq!(config.storage.backend, StorageBackend::S3);
+        assert_eq!(config.storage.gc_interval_secs, 86_400);
     }
 }
@@ -33,6 +33,7 @@ pub enum Error {
     BadPort(String),
     ChannelAlreadyExists(String),
     ChannelDoesNotExist(String),
+    ConnErr(hab_net::conn::ConnErr),
     CreateBucketError(rusoto_s3::CreateBucketError),
     DepotClientError(depot_client::Error),
     HabitatCore(hab_core::Error),
@@ -50,6 +51,7 @@ pub enum Error {
     NoFilePart,
     NulError(ffi::NulError),
     ObjectError(rusoto_s3::ListObjectsError),
+    PackageDelete(rusoto_s3::DeleteObjectError),
     PackageIsAlreadyInChannel(String, String),
     PackageUpload(rusoto_s3::PutObjectError),
     PackageDownload(rusoto_s3::GetObjectError),
@@ -69,6 +71,7 @@ impl fmt::Display for Error {
             Error::BadPort(ref e) => format!("{} is an invalid port. Valid range 1-65535.", e),
             Error::ChannelAlreadyExists(ref e) => format!("{} already exists.", e),
             Error::ChannelDoesNotExist(ref e) => format!("{} does not exist.", e),
+            Error::ConnErr(ref e) => format!("{}", e),
             Error::CreateBucketError(ref e) => format!("{}", e),
             Error::DepotClientError(ref e) => format!("{}", e),
             Error::HabitatCore(ref e) => format!("{}", e),
@@ -97,6 +100,7 @@ impl fmt::Display for Error {
             ),
             Error::NulError(ref e) => format!("{}", e),
             Error::ObjectError(ref e) => format!("{}", e),
+            Error::PackageDelete(ref e) => format!("{}", e),
             Error::PackageIsAlreadyInChannel(ref p, ref c) => {
                 format!("{} is already in the {} channel.", p, c)
             }
@@ -129,6 +133,7 @@ impl error::Error for Error {
             Error::BadPort(_) => "Received an invalid port or a number outside of the valid range.",
             Error::ChannelAlreadyExists(_) => "Channel already exists.",
             Error::ChannelDoesNotExist(_) => "Channel does not exist.",
+            Error::ConnErr(ref err) => err.description(),
             Error::CreateBucketError(ref err) => err.description(),
             Error::DepotClientError(ref err) => err.description(),
             Error::HabitatCore(ref err) => err.description(),
@@ -147,6 +152,7 @@ impl error::Error for Error {
                 "An attempt was made to build a CString with a null byte inside it"
             }
             Error::ObjectError(ref err) => err.description(),
+            Error::PackageDelete(ref err) => err.description(),
             Error::PackageIsAlreadyInChannel(_, _) => "Package is already in channel",
             Error::PackageUpload(ref err) => err.description(),
             Error::PackageDownload(ref err) => err.description(),
@@ -191,6 +197,12 @@ impl From<hab_net::error::LibError> for Error {
     }
 }
 
+impl From<hab_net::conn::ConnErr> for Error {
+    fn from(err: hab_net::conn::ConnErr) -> Error {
+        Error::ConnErr(err)
+    }
+}
+
 impl From<hab_net::NetError> for Error {
     fn from(err: hab_net::NetError) -> Error {
         Error::NetError(err)
@@ -17,7 +17,7 @@ use std::collections::HashMap;
 use bldr_core;
 use bodyparser;
 use http_gateway::http::controller::*;
-use http_gateway::http::helpers::{self, check_origin_access};
+use http_gateway::http::helpers::{self, check_origin_role};
 use hyper::mime::{Attr, Mime, SubLevel, TopLevel, Value};
 use iron::status::{self, Status};
 
@@ -51,6 +51,7 @@ pub fn decrypt(req: &mut Request, content: &str) -> Result<String, Status> {
 pub fn validate_params(
     req: &mut Request,
     expected_params: &[&str],
+    role: OriginMemberRole,
 ) -> Result<HashMap<String, String>, Status> {
     let mut res = HashMap::new();
     // Get the expected params
@@ -66,7 +67,7 @@ pub fn validate_params(
         }
     }
 
-    if !check_origin_access(req, &res["origin"]).unwrap_or(false) {
+    if !check_origin_role(req, &res["origin"], role).unwrap_or(false) {
         debug!("Failed origin access check, origin: {}", &res["origin"]);
         return Err(status::Forbidden);
     }
@@ -75,7 +76,7 @@ pub fn validate_params(
 }
 
 pub fn fetch_origin_integrations(req: &mut Request) -> IronResult<Response> {
-    let params = match validate_params(req, &["origin"]) {
+    let params = match validate_params(req, &["origin"], OriginMemberRole::ReadOnly) {
         Ok(p) => p,
         Err(st) => return Ok(Response::with(st)),
     };
@@ -105,7 +106,11 @@ pub fn fetch_origin_integrations(req: &mut Request) -> IronResult<Response> {
 }
 
 pub fn fetch_origin_integration_names(req: &mut Request) -> IronResult<Response> {
-    let params = match validate_params(req, &["origin", "integration"]) {
+    let params = match validate_params(
+        req,
+        &["origin", "integration"],
+        OriginMemberRole::ReadOnly,
+    ) {
         Ok(p) => p,
         Err(st) => return Ok(Response::with(st)),
     };
@@ -124,7 +129,11 @@ pub fn fetch_origin_integration_names(req: &mut Request) -> IronResult<Response>
 }
 
 pub fn create_origin_integration(req: &mut Request) -> IronResult<Response> {
-    let params = match validate_params(req, &["origin", "integration", "name"]) {
+    let params = match validate_params(
+        req,
+        &["origin", "integration", "name"],
+        OriginMemberRole::Maintainer,
+    ) {
         Ok(p) => p,
         Err(st) => return Ok(Response::with(st)),
     };
@@ -159,13 +168,20 @@ pub fn create_origin_integration(req: &mut Request) -> IronResult<Response> {
     request.set_integration(oi);
 
     match route_message::<OriginIntegrationCreate, NetOk>(req, &request) {
-        Ok(_) => Ok(Response::with(status::NoContent)),
+        Ok(_) => {
+            audit_integration_change(req, &params, OriginAuditOperation::IntegrationCreate);
+            Ok(Response::with(status::NoContent))
+        }
         Err(err) => Ok(render_net_error(&err)),
     }
 }
 
 pub fn delete_origin_integration(req: &mut Request) -> IronResult<Response> {
-    let params = match validate_params(req, &["origin", "integration", "name"]) {
+    let params = match validate_params(
+        req,
+        &["origin", "integration", "name"],
+        OriginMemberRole::Maintainer,
+    ) {
         Ok(p) => p,
         Err(st) => return Ok(Response::with(st)),
     };
@@ -179,13 +195,20 @@ pub fn delete_origin_integration(req: &mut Request) -> IronResult<Response> {
     request.set_integration(oi);
 
     match route_message::<OriginIntegrationDelete, NetOk>(req, &request) {
-        Ok(_) => Ok(Response::with(status::NoContent)),
+        Ok(_) => {
+            audit_integration_change(req, &params, OriginAuditOperation::IntegrationDelete);
+            Ok(Response::with(status::NoContent))
+        }
         Err(err) => Ok(render_net_error(&err)),
     }
 }
 
 pub fn get_origin_integration(req: &mut Request) -> IronResult<Response> {
-    let params = match validate_params(req, &["origin", "integration", "name"]) {
+    let params = match validate_params(
+        req,
+        &["origin", "integration", "name"],
+        OriginMemberRole::Maintainer,
+    ) {
         Ok(p) => p,
         Err(st) => return Ok(Response::with(st)),
     };
@@ -223,3 +246,19 @@ pub fn get_origin_integration(req: &mut Request) -> IronResult<Response> {
         Err(err) => Ok(render_net_error(&err)),
     }
 }
+
+fn audit_integration_change(
+    req: &mut Request,
+    params: &HashMap<String, String>,
+    operation: OriginAuditOperation,
+) {
+    match helpers::get_origin(req, &params["origin"]) {
+        Ok(origin) => helpers::audit_origin_change(
+            req,
+            origin.get_id(),
+            operation,
+            &format!("{}/{}", params["integration"], params["name"]),
+        ),
+        Err(err) => warn!("Unable to audit {:?} in {}, {}", operation, params["origin"], err),
+    }
+}
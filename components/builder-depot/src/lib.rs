@@ -19,6 +19,7 @@ extern crate base64;
 extern crate bodyparser;
 extern crate builder_core as bldr_core;
 extern crate builder_http_gateway as http_gateway;
+extern crate chrono;
 extern crate crypto;
 extern crate futures;
 extern crate github_api_client;
@@ -57,6 +58,7 @@ extern crate walkdir;
 extern crate zmq;
 
 pub mod backend;
+pub mod collector;
 pub mod config;
 pub mod error;
 pub mod handlers;
@@ -25,6 +25,7 @@ pub enum Counter {
     MultipartUploadRequests,
     DownloadRequests,
     UploadFailures,
+    ArtifactsCollected,
 }
 
 impl metrics::CounterMetric for Counter {}
@@ -38,6 +39,7 @@ impl metrics::Metric for Counter {
             Counter::MultipartUploadRequests => "upload-multi".into(),
             Counter::DownloadRequests => "download-packages".into(),
             Counter::UploadFailures => "upload-failures".into(),
+            Counter::ArtifactsCollected => "artifacts-collected".into(),
         }
     }
 }
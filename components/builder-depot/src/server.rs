@@ -14,14 +14,16 @@
 
 use std::fs::{self, remove_file, File};
 use std::io::{self, BufWriter, Read, Write};
+use std::net::{IpAddr, ToSocketAddrs};
 use std::path::PathBuf;
 use std::result;
 use std::str::{from_utf8, FromStr};
 
 use bldr_core::access_token::{BUILDER_ACCOUNT_ID, BUILDER_ACCOUNT_NAME};
-use bldr_core::helpers::transition_visibility;
+use bldr_core::helpers::{is_public_addr, transition_visibility};
 use bldr_core::metrics::CounterMetric;
 use bodyparser;
+use chrono::DateTime;
 use depot_client::{Client as DepotClient, DisplayProgress};
 use hab_core::crypto::keys::{parse_key_str, parse_name_with_rev, PairType};
 use hab_core::crypto::BoxKeyPair;
@@ -33,9 +35,9 @@ use hab_net::{ErrCode, NetOk, NetResult};
 use http_gateway::conn::RouteBroker;
 use http_gateway::http::controller::*;
 use http_gateway::http::helpers::{
-    self, all_visibilities, check_origin_access, check_origin_owner, dont_cache_response,
-    get_param, get_session_id_and_name, trigger_from_request, validate_params,
-    visibility_for_optional_session,
+    self, all_visibilities, check_origin_access, check_origin_owner, check_origin_role,
+    dont_cache_response, get_param, get_session_id_and_name, trigger_from_request,
+    validate_params, visibility_for_optional_session,
 };
 use http_gateway::http::middleware::{SegmentCli, XRouteClient};
 use hyper::header::{Charset, ContentDisposition, DispositionParam, DispositionType};
@@ -47,12 +49,14 @@ use iron::typemap;
 use persistent;
 use protobuf;
 use protocol::jobsrv::{
-    JobGraphPackagePreCreate, JobGraphPackageStats, JobGraphPackageStatsGet, JobGroup,
-    JobGroupAbort, JobGroupGet, JobGroupOriginGet, JobGroupOriginResponse, JobGroupSpec,
-    JobGroupTrigger,
+    JobGraphPackagePreCreate, JobGraphPackageReverseDependencies,
+    JobGraphPackageReverseDependenciesGet, JobGraphPackageStats, JobGraphPackageStatsGet,
+    JobGroup, JobGroupAbort, JobGroupAuditListRequest, JobGroupAuditListResponse, JobGroupGet,
+    JobGroupOperation, JobGroupOriginActiveGet, JobGroupOriginGet, JobGroupOriginResponse,
+    JobGroupSpec, JobGroupTrigger,
 };
 use protocol::originsrv::*;
-use protocol::sessionsrv::{Account, AccountGet, AccountOriginRemove};
+use protocol::sessionsrv::{AccessTokenScope, Account, AccountGet, AccountOriginRemove};
 use regex::Regex;
 use router::{Params, Router};
 use segment_api_client::SegmentClient;
@@ -62,7 +66,8 @@ use url;
 use uuid::Uuid;
 
 use super::DepotUtil;
-use backend::{s3, s3::S3Cli};
+use backend::{self, StorageCli};
+use collector::ArtifactCollector;
 use config::Config;
 use error::{Error, Result};
 use handlers;
@@ -80,6 +85,26 @@ struct OriginUpdateReq {
     default_package_visibility: String,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+struct OriginMemberRoleReq {
+    role: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct OriginWebhookReq {
+    url: String,
+    secret: String,
+    events: Vec<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct ChannelRetentionReq {
+    #[serde(default)]
+    keep_latest: u32,
+    #[serde(default)]
+    max_age_days: u32,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 struct OriginSecretPayload {
     name: String,
@@ -88,6 +113,12 @@ struct OriginSecretPayload {
 
 const ONE_YEAR_IN_SECS: usize = 31536000;
 
+// Most deliveries of a webhook returned by its delivery history
+const WEBHOOK_DELIVERY_LIMIT: u64 = 100;
+
+// Highest job group priority an origin member may request; higher priorities need a maintainer
+const MEMBER_MAX_PRIORITY: i32 = 10;
+
 pub fn origin_update(req: &mut Request) -> IronResult<Response> {
     let mut request = OriginUpdate::new();
     match get_param(req, "name") {
@@ -95,7 +126,7 @@ pub fn origin_update(req: &mut Request) -> IronResult<Response> {
         None => return Ok(Response::with(status::BadRequest)),
     }
 
-    if !check_origin_access(req, request.get_name()).unwrap_or(false) {
+    if !check_origin_role(req, request.get_name(), OriginMemberRole::Maintainer).unwrap_or(false) {
         return Ok(Response::with(status::Forbidden));
     }
 
@@ -259,7 +290,18 @@ pub fn accept_invitation(req: &mut Request) -> IronResult<Response> {
     );
 
     match route_message::<OriginInvitationAcceptRequest, NetOk>(req, &request) {
-        Ok(_) => Ok(Response::with(status::NoContent)),
+        Ok(_) => {
+            if let Ok(origin) = helpers::get_origin(req, request.get_origin_name()) {
+                let (_, session_name) = get_session_id_and_name(req);
+                helpers::audit_origin_change(
+                    req,
+                    origin.get_id(),
+                    OriginAuditOperation::MemberAdd,
+                    &session_name,
+                );
+            }
+            Ok(Response::with(status::NoContent))
+        }
         Err(err) => Ok(render_net_error(&err)),
     }
 }
@@ -285,7 +327,7 @@ pub fn invite_to_origin(req: &mut Request) -> IronResult<Response> {
         &user_to_invite, &origin
     );
 
-    if !check_origin_access(req, &origin).unwrap_or(false) {
+    if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
         return Ok(Response::with(status::Forbidden));
     }
 
@@ -414,16 +456,529 @@ pub fn origin_member_delete(req: &mut Request) -> IronResult<Response> {
         }
         Err(err) => return Ok(render_net_error(&err)),
     }
-    session_request.set_account_name(account_name.to_string());
-    origin_request.set_account_name(account_name.to_string());
+    session_request.set_account_name(account_name.to_string());
+    origin_request.set_account_name(account_name.to_string());
+
+    if let Err(err) = route_message::<AccountOriginRemove, NetOk>(req, &session_request) {
+        return Ok(render_net_error(&err));
+    }
+
+    match route_message::<OriginMemberRemove, NetOk>(req, &origin_request) {
+        Ok(_) => {
+            helpers::audit_origin_change(
+                req,
+                origin_request.get_origin_id(),
+                OriginAuditOperation::MemberRemove,
+                &account_name,
+            );
+            Ok(Response::with(status::NoContent))
+        }
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
+pub fn origin_member_role_update(req: &mut Request) -> IronResult<Response> {
+    let session = req.extensions.get::<Authenticated>().unwrap().clone();
+    let origin = match get_param(req, "origin") {
+        Some(origin) => origin,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+    let account_name = match get_param(req, "username") {
+        Some(user) => user,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+    let role = match req.get::<bodyparser::Struct<OriginMemberRoleReq>>() {
+        Ok(Some(body)) => match OriginMemberRole::from_str(&body.role) {
+            Ok(role) => role,
+            Err(_) => return Ok(Response::with(status::UnprocessableEntity)),
+        },
+        _ => return Ok(Response::with(status::UnprocessableEntity)),
+    };
+
+    if !check_origin_owner(req, session.get_id(), &origin).unwrap_or(false) {
+        return Ok(Response::with(status::Forbidden));
+    }
+
+    // Do not allow owners to demote themselves which could leave the origin without an owner
+    if account_name == session.get_name() {
+        return Ok(Response::with(status::BadRequest));
+    }
+
+    let mut request = OriginMemberRoleUpdate::new();
+    match helpers::get_origin(req, origin) {
+        Ok(origin) => request.set_origin_id(origin.get_id()),
+        Err(err) => return Ok(render_net_error(&err)),
+    }
+    request.set_account_name(account_name);
+    request.set_role(role);
+
+    match route_message::<OriginMemberRoleUpdate, OriginMember>(req, &request) {
+        Ok(_) => {
+            helpers::audit_origin_change(
+                req,
+                request.get_origin_id(),
+                OriginAuditOperation::MemberRoleUpdate,
+                &format!("{}:{}", request.get_account_name(), request.get_role()),
+            );
+            Ok(Response::with(status::NoContent))
+        }
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
+pub fn origin_delete(req: &mut Request) -> IronResult<Response> {
+    let jobsrv_enabled = {
+        let lock = req.get::<persistent::State<Config>>()
+            .expect("depot not found");
+        let depot = lock.read().expect("depot read lock is poisoned");
+        depot.jobsrv_enabled
+    };
+    let session = req.extensions.get::<Authenticated>().unwrap().clone();
+    let origin = match get_param(req, "origin") {
+        Some(origin) => origin,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+
+    if !check_origin_owner(req, session.get_id(), &origin).unwrap_or(false) {
+        return Ok(Response::with(status::Forbidden));
+    }
+
+    // Purging the origin's packages and projects has to be confirmed with the origin's name
+    let purge = match helpers::extract_query_value("purge", req) {
+        Some(purge) => purge.parse::<bool>().unwrap_or(false),
+        None => false,
+    };
+    if purge && helpers::extract_query_value("confirm", req) != Some(origin.clone()) {
+        return Ok(Response::with((status::UnprocessableEntity, "ds:od:1")));
+    }
+
+    if jobsrv_enabled {
+        let mut request = JobGroupOriginActiveGet::new();
+        request.set_origin(origin.clone());
+        match route_message::<JobGroupOriginActiveGet, JobGroupOriginResponse>(req, &request) {
+            Ok(jgor) => {
+                if !jgor.get_job_groups().is_empty() {
+                    return Ok(Response::with((status::Conflict, "ds:od:2")));
+                }
+            }
+            Err(err) => return Ok(render_net_error(&err)),
+        }
+    }
+
+    let origin_id = match helpers::get_origin(req, &origin) {
+        Ok(origin) => origin.get_id(),
+        Err(err) => return Ok(render_net_error(&err)),
+    };
+    let mut members_request = OriginMemberListRequest::new();
+    members_request.set_origin_id(origin_id);
+    let members = match route_message::<OriginMemberListRequest, OriginMemberListResponse>(
+        req,
+        &members_request,
+    ) {
+        Ok(response) => response,
+        Err(err) => return Ok(render_net_error(&err)),
+    };
+
+    let mut request = OriginDelete::new();
+    request.set_name(origin.clone());
+    request.set_purge(purge);
+    if let Err(err) = route_message::<OriginDelete, NetOk>(req, &request) {
+        if err.get_code() == ErrCode::ENTITY_CONFLICT {
+            return Ok(Response::with((status::Conflict, "ds:od:3")));
+        }
+        return Ok(render_net_error(&err));
+    }
+
+    // Artifacts of purged packages are left to the artifact collector
+    for member in members.get_members() {
+        let mut session_request = AccountOriginRemove::new();
+        session_request.set_origin_id(origin_id);
+        session_request.set_account_name(member.to_string());
+        if let Err(err) = route_message::<AccountOriginRemove, NetOk>(req, &session_request) {
+            warn!(
+                "Failed to remove origin {} from account {}, err={:?}",
+                origin, member, err
+            );
+        }
+    }
+
+    info!("Origin {} deleted by {}", origin, session.get_name());
+    Ok(Response::with(status::NoContent))
+}
+
+pub fn origin_owner_transfer_create(req: &mut Request) -> IronResult<Response> {
+    let session = req.extensions.get::<Authenticated>().unwrap().clone();
+    let origin = match get_param(req, "origin") {
+        Some(origin) => origin,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+    let account_name = match get_param(req, "username") {
+        Some(user) => user,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+
+    // Only the owner of record may hand the origin over
+    let origin = match helpers::get_origin(req, &origin) {
+        Ok(origin) => origin,
+        Err(err) => return Ok(render_net_error(&err)),
+    };
+    if origin.get_owner_id() != session.get_id() || !session.permits_origin(origin.get_name()) {
+        return Ok(Response::with(status::Forbidden));
+    }
+    if account_name == session.get_name() {
+        return Ok(Response::with(status::BadRequest));
+    }
+
+    let mut account_request = AccountGet::new();
+    account_request.set_name(account_name);
+    let account = match route_message::<AccountGet, Account>(req, &account_request) {
+        Ok(account) => account,
+        Err(err) => return Ok(render_net_error(&err)),
+    };
+
+    let mut request = OriginOwnerTransferCreate::new();
+    request.set_origin_id(origin.get_id());
+    request.set_origin_name(origin.get_name().to_string());
+    request.set_owner_id(session.get_id());
+    request.set_account_id(account.get_id());
+    request.set_account_name(account.get_name().to_string());
+
+    match route_message::<OriginOwnerTransferCreate, OriginOwnerTransfer>(req, &request) {
+        Ok(transfer) => {
+            helpers::audit_origin_change(
+                req,
+                transfer.get_origin_id(),
+                OriginAuditOperation::OwnerTransferCreate,
+                transfer.get_account_name(),
+            );
+            Ok(render_json(status::Created, &transfer))
+        }
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
+pub fn origin_owner_transfer_show(req: &mut Request) -> IronResult<Response> {
+    let origin = match get_param(req, "origin") {
+        Some(origin) => origin,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+
+    if !check_origin_access(req, &origin).unwrap_or(false) {
+        return Ok(Response::with(status::Forbidden));
+    }
+
+    let mut request = OriginOwnerTransferGet::new();
+    match helpers::get_origin(req, &origin) {
+        Ok(origin) => request.set_origin_id(origin.get_id()),
+        Err(err) => return Ok(render_net_error(&err)),
+    }
+
+    match route_message::<OriginOwnerTransferGet, OriginOwnerTransfer>(req, &request) {
+        Ok(transfer) => {
+            let mut response = render_json(status::Ok, &transfer);
+            dont_cache_response(&mut response);
+            Ok(response)
+        }
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
+pub fn origin_owner_transfer_accept(req: &mut Request) -> IronResult<Response> {
+    let session = req.extensions.get::<Authenticated>().unwrap().clone();
+    let origin = match get_param(req, "origin") {
+        Some(origin) => origin,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+    if !session.permits_origin(&origin) {
+        return Ok(Response::with(status::Forbidden));
+    }
+
+    let mut request = OriginOwnerTransferAccept::new();
+    match helpers::get_origin(req, &origin) {
+        Ok(origin) => request.set_origin_id(origin.get_id()),
+        Err(err) => return Ok(render_net_error(&err)),
+    }
+    request.set_account_id(session.get_id());
+
+    match route_message::<OriginOwnerTransferAccept, Origin>(req, &request) {
+        Ok(origin) => {
+            helpers::audit_origin_change(
+                req,
+                origin.get_id(),
+                OriginAuditOperation::OwnerTransferAccept,
+                session.get_name(),
+            );
+            Ok(Response::with(status::NoContent))
+        }
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
+/// Cancels a pending transfer. Either the owner or the recipient of the transfer may cancel it.
+pub fn origin_owner_transfer_rescind(req: &mut Request) -> IronResult<Response> {
+    let session = req.extensions.get::<Authenticated>().unwrap().clone();
+    let origin = match get_param(req, "origin") {
+        Some(origin) => origin,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+    if !session.permits_origin(&origin) {
+        return Ok(Response::with(status::Forbidden));
+    }
+
+    let origin_id = match helpers::get_origin(req, &origin) {
+        Ok(origin) => origin.get_id(),
+        Err(err) => return Ok(render_net_error(&err)),
+    };
+    let mut get_request = OriginOwnerTransferGet::new();
+    get_request.set_origin_id(origin_id);
+    let transfer =
+        match route_message::<OriginOwnerTransferGet, OriginOwnerTransfer>(req, &get_request) {
+            Ok(transfer) => transfer,
+            Err(err) => return Ok(render_net_error(&err)),
+        };
+    if transfer.get_owner_id() != session.get_id() && transfer.get_account_id() != session.get_id()
+    {
+        return Ok(Response::with(status::Forbidden));
+    }
+
+    let mut request = OriginOwnerTransferRescind::new();
+    request.set_origin_id(origin_id);
+    match route_message::<OriginOwnerTransferRescind, NetOk>(req, &request) {
+        Ok(_) => {
+            helpers::audit_origin_change(
+                req,
+                origin_id,
+                OriginAuditOperation::OwnerTransferRescind,
+                transfer.get_account_name(),
+            );
+            Ok(Response::with(status::NoContent))
+        }
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
+fn list_origin_webhooks(req: &mut Request) -> IronResult<Response> {
+    let origin = match get_param(req, "origin") {
+        Some(origin) => origin,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+    if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
+        return Ok(Response::with(status::Forbidden));
+    }
+
+    let mut request = OriginWebhookListRequest::new();
+    match helpers::get_origin(req, &origin) {
+        Ok(origin) => request.set_origin_id(origin.get_id()),
+        Err(err) => return Ok(render_net_error(&err)),
+    }
+
+    match route_message::<OriginWebhookListRequest, OriginWebhookListResponse>(req, &request) {
+        Ok(webhooks) => {
+            let mut response = render_json(status::Ok, &webhooks);
+            dont_cache_response(&mut response);
+            Ok(response)
+        }
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
+/// Resolves the addresses a webhook URL would be delivered to.
+fn webhook_addrs(url: &url::Url) -> Vec<IpAddr> {
+    match url.host() {
+        Some(url::Host::Domain(domain)) => match (domain, 0).to_socket_addrs() {
+            Ok(addrs) => addrs.map(|addr| addr.ip()).collect(),
+            Err(_) => vec![],
+        },
+        Some(url::Host::Ipv4(ip)) => vec![IpAddr::V4(ip)],
+        Some(url::Host::Ipv6(ip)) => vec![IpAddr::V6(ip)],
+        None => vec![],
+    }
+}
+
+fn create_origin_webhook(req: &mut Request) -> IronResult<Response> {
+    let body = match req.get::<bodyparser::Struct<OriginWebhookReq>>() {
+        Ok(Some(body)) => body,
+        _ => return Ok(Response::with(status::UnprocessableEntity)),
+    };
+    match url::Url::parse(&body.url) {
+        Ok(ref url) if url.scheme() == "http" || url.scheme() == "https" => {
+            let addrs = webhook_addrs(url);
+            if addrs.is_empty() || !addrs.iter().all(is_public_addr) {
+                return Ok(Response::with((status::UnprocessableEntity, "ds:wh:4")));
+            }
+        }
+        _ => return Ok(Response::with((status::UnprocessableEntity, "ds:wh:1"))),
+    }
+    if body.secret.is_empty() {
+        return Ok(Response::with((status::UnprocessableEntity, "ds:wh:2")));
+    }
+    let mut events = Vec::new();
+    for event in body.events.iter() {
+        match OriginWebhookEvent::from_str(event) {
+            Ok(event) => events.push(event),
+            Err(_) => return Ok(Response::with((status::UnprocessableEntity, "ds:wh:3"))),
+        }
+    }
+    if events.is_empty() {
+        return Ok(Response::with((status::UnprocessableEntity, "ds:wh:3")));
+    }
+
+    let origin = match get_param(req, "origin") {
+        Some(origin) => origin,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+    if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
+        return Ok(Response::with(status::Forbidden));
+    }
+
+    let mut webhook = OriginWebhook::new();
+    match helpers::get_origin(req, &origin) {
+        Ok(origin) => webhook.set_origin_id(origin.get_id()),
+        Err(err) => return Ok(render_net_error(&err)),
+    }
+    webhook.set_origin_name(origin);
+    webhook.set_url(body.url);
+    webhook.set_secret(body.secret);
+    webhook.set_events(events);
+    let mut request = OriginWebhookCreate::new();
+    request.set_webhook(webhook);
+
+    match route_message::<OriginWebhookCreate, OriginWebhook>(req, &request) {
+        Ok(webhook) => Ok(render_json(status::Created, &webhook)),
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
+fn delete_origin_webhook(req: &mut Request) -> IronResult<Response> {
+    let origin = match get_param(req, "origin") {
+        Some(origin) => origin,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+    let id = match get_param(req, "id").and_then(|id| id.parse::<u64>().ok()) {
+        Some(id) => id,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+    if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
+        return Ok(Response::with(status::Forbidden));
+    }
+
+    let mut request = OriginWebhookDelete::new();
+    request.set_id(id);
+    match helpers::get_origin(req, &origin) {
+        Ok(origin) => request.set_origin_id(origin.get_id()),
+        Err(err) => return Ok(render_net_error(&err)),
+    }
+
+    match route_message::<OriginWebhookDelete, NetOk>(req, &request) {
+        Ok(_) => Ok(Response::with(status::NoContent)),
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
+/// Lists the most recent deliveries of a webhook, newest first.
+fn list_origin_webhook_deliveries(req: &mut Request) -> IronResult<Response> {
+    let origin = match get_param(req, "origin") {
+        Some(origin) => origin,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+    let id = match get_param(req, "id").and_then(|id| id.parse::<u64>().ok()) {
+        Some(id) => id,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+    let limit = match helpers::extract_query_value("limit", req) {
+        Some(limit) => match limit.parse::<u64>() {
+            Ok(limit) if limit > 0 => limit.min(WEBHOOK_DELIVERY_LIMIT),
+            _ => return Ok(Response::with(status::BadRequest)),
+        },
+        None => WEBHOOK_DELIVERY_LIMIT,
+    };
+    if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
+        return Ok(Response::with(status::Forbidden));
+    }
+
+    let mut request = OriginWebhookDeliveryListRequest::new();
+    request.set_webhook_id(id);
+    request.set_limit(limit);
+    match helpers::get_origin(req, &origin) {
+        Ok(origin) => request.set_origin_id(origin.get_id()),
+        Err(err) => return Ok(render_net_error(&err)),
+    }
+
+    match route_message::<OriginWebhookDeliveryListRequest, OriginWebhookDeliveryListResponse>(
+        req,
+        &request,
+    ) {
+        Ok(deliveries) => {
+            let mut response = render_json(status::Ok, &deliveries);
+            dont_cache_response(&mut response);
+            Ok(response)
+        }
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
+fn list_origin_audit(req: &mut Request) -> IronResult<Response> {
+    let origin = match get_param(req, "origin") {
+        Some(origin) => origin,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+    if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
+        return Ok(Response::with(status::Forbidden));
+    }
+    let (start, stop) = match helpers::extract_pagination(req) {
+        Ok(range) => range,
+        Err(response) => return Ok(response),
+    };
+
+    let mut request = OriginAuditListRequest::new();
+    request.set_start(start as u64);
+    request.set_stop(stop as u64);
+    match audit_time_bound(req, "from") {
+        Ok(from) => request.set_from(from),
+        Err(response) => return Ok(response),
+    }
+    match audit_time_bound(req, "to") {
+        Ok(to) => request.set_to(to),
+        Err(response) => return Ok(response),
+    }
+    if let Some(actor) = helpers::extract_query_value("actor", req) {
+        request.set_requester_name(actor);
+    }
+    if let Some(operation) = helpers::extract_query_value("operation", req) {
+        request.set_operation(operation);
+    }
+    if let Some(package) = helpers::extract_query_value("package", req) {
+        request.set_package(package);
+    }
+    match helpers::get_origin(req, &origin) {
+        Ok(origin) => request.set_origin_id(origin.get_id()),
+        Err(err) => return Ok(render_net_error(&err)),
+    }
 
-    if let Err(err) = route_message::<AccountOriginRemove, NetOk>(req, &session_request) {
-        return Ok(render_net_error(&err));
+    match route_message::<OriginAuditListRequest, OriginAuditListResponse>(req, &request) {
+        Ok(audit) => {
+            let mut response = helpers::paginated_response(
+                &audit.get_entries().to_vec(),
+                audit.get_count() as isize,
+                audit.get_start() as isize,
+                audit.get_stop() as isize,
+            )?;
+            dont_cache_response(&mut response);
+            Ok(response)
+        }
+        Err(err) => Ok(render_net_error(&err)),
     }
+}
 
-    match route_message::<OriginMemberRemove, NetOk>(req, &origin_request) {
-        Ok(_) => Ok(Response::with(status::NoContent)),
-        Err(err) => Ok(render_net_error(&err)),
+// Audit queries may be bounded by the optional `from` and `to` query parameters, given as
+// RFC 3339 timestamps. An absent bound is returned as an empty string.
+fn audit_time_bound(req: &mut Request, key: &str) -> result::Result<String, Response> {
+    match helpers::extract_query_value(key, req) {
+        Some(value) => match DateTime::parse_from_rfc3339(&value) {
+            Ok(_) => Ok(value),
+            Err(_) => Err(Response::with(status::BadRequest)),
+        },
+        None => Ok(String::new()),
     }
 }
 
@@ -455,7 +1010,7 @@ fn write_archive(filename: &PathBuf, body: &mut Body) -> Result<PackageArchive>
 }
 
 fn download_latest_origin_encryption_key(req: &mut Request) -> IronResult<Response> {
-    let params = match validate_params(req, &["origin"]) {
+    let params = match validate_params(req, &["origin"], OriginMemberRole::ReadOnly) {
         Ok(p) => p,
         Err(st) => return Ok(Response::with(st)),
     };
@@ -549,15 +1104,27 @@ fn generate_origin_keys(req: &mut Request) -> IronResult<Response> {
 
     match get_param(req, "origin") {
         Some(origin) => {
-            if !check_origin_access(req, &origin).unwrap_or(false) {
+            if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
                 return Ok(Response::with(status::Forbidden));
             }
 
             match helpers::get_origin(req, origin) {
-                Ok(origin) => match helpers::generate_origin_keys(req, session_id, origin) {
-                    Ok(_) => Ok(Response::with(status::Created)),
-                    Err(err) => Ok(render_net_error(&err)),
-                },
+                Ok(origin) => {
+                    let origin_id = origin.get_id();
+                    let origin_name = origin.get_name().to_string();
+                    match helpers::generate_origin_keys(req, session_id, origin) {
+                        Ok(_) => {
+                            helpers::audit_origin_change(
+                                req,
+                                origin_id,
+                                OriginAuditOperation::KeysGenerate,
+                                &origin_name,
+                            );
+                            Ok(Response::with(status::Created))
+                        }
+                        Err(err) => Ok(render_net_error(&err)),
+                    }
+                }
                 Err(err) => Ok(render_net_error(&err)),
             }
         }
@@ -578,7 +1145,7 @@ fn upload_origin_key(req: &mut Request) -> IronResult<Response> {
 
     let origin = match get_param(req, "origin") {
         Some(origin) => {
-            if !check_origin_access(req, &origin).unwrap_or(false) {
+            if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
                 return Ok(Response::with(status::Forbidden));
             }
 
@@ -629,6 +1196,12 @@ fn upload_origin_key(req: &mut Request) -> IronResult<Response> {
     request.set_owner_id(0);
     match route_message::<OriginPublicSigningKeyCreate, OriginPublicSigningKey>(req, &request) {
         Ok(_) => {
+            helpers::audit_origin_change(
+                req,
+                request.get_origin_id(),
+                OriginAuditOperation::PublicKeyUpload,
+                request.get_revision(),
+            );
             let mut response = Response::with((
                 status::Created,
                 format!("/origins/{}/keys/{}", &origin, &request.get_revision()),
@@ -650,7 +1223,7 @@ fn download_latest_origin_secret_key(req: &mut Request) -> IronResult<Response>
         None => return Ok(Response::with(status::BadRequest)),
     };
 
-    if !check_origin_access(req, &origin).unwrap_or(false) {
+    if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
         return Ok(Response::with(status::Forbidden));
     }
 
@@ -685,7 +1258,7 @@ fn upload_origin_secret_key(req: &mut Request) -> IronResult<Response> {
 
     match get_param(req, "origin") {
         Some(origin) => {
-            if !check_origin_access(req, &origin).unwrap_or(false) {
+            if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
                 return Ok(Response::with(status::Forbidden));
             }
 
@@ -735,14 +1308,22 @@ fn upload_origin_secret_key(req: &mut Request) -> IronResult<Response> {
     request.set_body(key_content);
     request.set_owner_id(0);
     match route_message::<OriginPrivateSigningKeyCreate, OriginPrivateSigningKey>(req, &request) {
-        Ok(_) => Ok(Response::with(status::Created)),
+        Ok(_) => {
+            helpers::audit_origin_change(
+                req,
+                request.get_origin_id(),
+                OriginAuditOperation::SecretKeyUpload,
+                request.get_revision(),
+            );
+            Ok(Response::with(status::Created))
+        }
         Err(err) => Ok(render_net_error(&err)),
     }
 }
 
 fn upload_package(req: &mut Request) -> IronResult<Response> {
     let ident = ident_from_req(req);
-    let s3handler = req.get::<persistent::Read<S3Cli>>().unwrap();
+    let storage = req.get::<persistent::Read<StorageCli>>().unwrap();
 
     let (session_id, session_name) = get_session_id_and_name(req);
 
@@ -754,7 +1335,7 @@ fn upload_package(req: &mut Request) -> IronResult<Response> {
         return Ok(Response::with(status::BadRequest));
     }
 
-    if !check_origin_access(req, &ident.get_origin()).unwrap_or(false) {
+    if !check_origin_role(req, &ident.get_origin(), OriginMemberRole::Member).unwrap_or(false) {
         debug!("Failed origin access check, ident: {}", ident);
 
         return Ok(Response::with(status::Forbidden));
@@ -882,11 +1463,11 @@ fn upload_package(req: &mut Request) -> IronResult<Response> {
         }
     }
 
-    if s3handler
+    if storage
         .upload(&filename, &temp_ident, &target_from_artifact)
         .is_err()
     {
-        error!("Unable to upload archive to s3!");
+        error!("Unable to upload archive to storage!");
         return Ok(Response::with(status::InternalServerError));
     } else {
         info!("File added to Depot: {:?}", &filename);
@@ -911,6 +1492,10 @@ fn upload_package(req: &mut Request) -> IronResult<Response> {
 
         let config = depot.clone();
         let builder_flag = helpers::extract_query_value("builder", req);
+        // Workers keep pull request builds out of unstable, publishing them to their own channel
+        if helpers::is_worker(req) && helpers::extract_query_value("skip_unstable", req).is_some() {
+            package.set_skip_unstable(true);
+        }
 
         match process_upload_for_package_archive(
             &ident,
@@ -923,6 +1508,17 @@ fn upload_package(req: &mut Request) -> IronResult<Response> {
             builder_flag,
         ) {
             Ok(_) => {
+                let payload = json!({
+                    "ident": package.get_ident().to_string(),
+                    "target": target_from_artifact.to_string(),
+                });
+                helpers::notify_origin_webhooks(
+                    req,
+                    ident.get_origin(),
+                    OriginWebhookEvent::PackageUploaded,
+                    &payload,
+                );
+
                 let mut response = Response::with((
                     status::Created,
                     format!("/pkgs/{}/download", package.get_ident()),
@@ -986,7 +1582,7 @@ fn schedule(req: &mut Request) -> IronResult<Response> {
         None => return Ok(Response::with(status::BadRequest)),
     };
 
-    if !check_origin_access(req, &origin_name).unwrap_or(false) {
+    if !check_origin_role(req, &origin_name, OriginMemberRole::Member).unwrap_or(false) {
         debug!("Failed origin access check, origin: {}", &origin_name);
         return Ok(Response::with(status::Forbidden));
     }
@@ -1002,19 +1598,56 @@ fn schedule(req: &mut Request) -> IronResult<Response> {
         Some(pkg) => pkg,
         None => return Ok(Response::with(status::BadRequest)),
     };
-    let target = match helpers::extract_query_value("target", req) {
-        Some(target) => target,
-        None => String::from("x86_64-linux"),
+    // Several targets may be requested at once as a comma-separated list
+    let targets: Vec<String> = match helpers::extract_query_value("target", req) {
+        Some(target) => target
+            .split(",")
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect(),
+        None => vec![String::from("x86_64-linux")],
     };
     let deps_only = helpers::extract_query_value("deps_only", req).is_some();
     let origin_only = helpers::extract_query_value("origin_only", req).is_some();
     let package_only = helpers::extract_query_value("package_only", req).is_some();
+    // Preview the group that would be scheduled without scheduling it
+    let dry_run = helpers::extract_query_value("dry_run", req).is_some();
+    // Priorities are stored as a signed integer by the scheduler
+    let priority = match helpers::extract_query_value("priority", req) {
+        Some(priority) => match priority.parse::<i32>() {
+            Ok(priority) if priority >= 0 => priority,
+            _ => return Ok(Response::with(status::BadRequest)),
+        },
+        None => 0,
+    };
+    if priority > MEMBER_MAX_PRIORITY
+        && !check_origin_role(req, &origin_name, OriginMemberRole::Maintainer).unwrap_or(false)
+    {
+        debug!("Priority {} requires maintainer, origin: {}", priority, &origin_name);
+        return Ok(Response::with(status::Forbidden));
+    }
+    // Branch, tag or commit SHA to build instead of the project's default branch
+    let git_ref = helpers::extract_query_value("ref", req);
+    if let Some(ref git_ref) = git_ref {
+        if git_ref.is_empty()
+            || git_ref.starts_with("-")
+            || git_ref.contains("..")
+            || git_ref.chars().any(|c| c.is_whitespace() || c.is_control())
+        {
+            return Ok(Response::with((status::UnprocessableEntity, "ds:sc:1")));
+        }
+    }
 
-    // We only support building for Linux x64 only currently
-    if target != "x86_64-linux" {
-        info!("Rejecting build with target: {}", target);
+    // We only support building for Linux and Windows x64 currently
+    if targets.is_empty() {
         return Ok(Response::with(status::BadRequest));
     }
+    for target in targets.iter() {
+        if target != "x86_64-linux" && target != "x86_64-windows" {
+            info!("Rejecting build with target: {}", target);
+            return Ok(Response::with(status::BadRequest));
+        }
+    }
 
     let mut secret_key_request = OriginPrivateSigningKeyGet::new();
     let origin = match helpers::get_origin(req, &origin_name) {
@@ -1042,7 +1675,7 @@ fn schedule(req: &mut Request) -> IronResult<Response> {
         Err(_) => true,
     };
 
-    if need_keys {
+    if need_keys && !dry_run {
         if let Err(err) = helpers::generate_origin_keys(req, session_id, origin) {
             return Ok(render_net_error(&err));
         }
@@ -1051,22 +1684,30 @@ fn schedule(req: &mut Request) -> IronResult<Response> {
     let mut request = JobGroupSpec::new();
     request.set_origin(origin_name);
     request.set_package(package);
-    request.set_target(target);
+    request.set_target(targets[0].clone());
+    request.set_targets(protobuf::RepeatedField::from_vec(targets));
     request.set_deps_only(deps_only);
     request.set_origin_only(origin_only);
     request.set_package_only(package_only);
+    request.set_dry_run(dry_run);
+    request.set_priority(priority as u32);
+    if let Some(git_ref) = git_ref {
+        request.set_git_ref(git_ref);
+    }
     request.set_trigger(trigger_from_request(req));
     request.set_requester_id(session_id);
     request.set_requester_name(session_name);
 
     match route_message::<JobGroupSpec, JobGroup>(req, &request) {
         Ok(group) => {
-            let msg = format!("Scheduled job group for {}", group.get_project_name());
+            if !dry_run {
+                let msg = format!("Scheduled job group for {}", group.get_project_name());
 
-            // We don't really want to abort anything just because a call to segment failed. Let's
-            // just log it and move on.
-            if let Err(e) = segment.track(&account_name, &msg) {
-                warn!("Error tracking scheduling of job group in segment, {}", e);
+                // We don't really want to abort anything just because a call to segment failed.
+                // Let's just log it and move on.
+                if let Err(e) = segment.track(&account_name, &msg) {
+                    warn!("Error tracking scheduling of job group in segment, {}", e);
+                }
             }
 
             let mut response = render_json(status::Ok, &group);
@@ -1102,6 +1743,57 @@ fn get_origin_schedule_status(req: &mut Request) -> IronResult<Response> {
     }
 }
 
+// This route is unreachable when jobsrv_enabled is false
+fn list_job_group_audit(req: &mut Request) -> IronResult<Response> {
+    let origin = match get_param(req, "origin") {
+        Some(origin) => origin,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+    if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
+        return Ok(Response::with(status::Forbidden));
+    }
+    let (start, stop) = match helpers::extract_pagination(req) {
+        Ok(range) => range,
+        Err(response) => return Ok(response),
+    };
+
+    let mut request = JobGroupAuditListRequest::new();
+    request.set_origin(origin);
+    request.set_start(start as u64);
+    request.set_stop(stop as u64);
+    match audit_time_bound(req, "from") {
+        Ok(from) => request.set_from(from),
+        Err(response) => return Ok(response),
+    }
+    match audit_time_bound(req, "to") {
+        Ok(to) => request.set_to(to),
+        Err(response) => return Ok(response),
+    }
+    if let Some(actor) = helpers::extract_query_value("actor", req) {
+        request.set_requester_name(actor);
+    }
+    if let Some(operation) = helpers::extract_query_value("operation", req) {
+        match JobGroupOperation::from_str(&operation) {
+            Ok(operation) => request.set_operation(operation),
+            Err(_) => return Ok(Response::with(status::BadRequest)),
+        }
+    }
+
+    match route_message::<JobGroupAuditListRequest, JobGroupAuditListResponse>(req, &request) {
+        Ok(audit) => {
+            let mut response = helpers::paginated_response(
+                &audit.get_entries().to_vec(),
+                audit.get_count() as isize,
+                audit.get_start() as isize,
+                audit.get_stop() as isize,
+            )?;
+            dont_cache_response(&mut response);
+            Ok(response)
+        }
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
 // This route is unreachable when jobsrv_enabled is false
 fn get_schedule(req: &mut Request) -> IronResult<Response> {
     let group_id = {
@@ -1232,12 +1924,135 @@ fn package_channels(req: &mut Request) -> IronResult<Response> {
     }
 }
 
+fn delete_package(req: &mut Request) -> IronResult<Response> {
+    let jobsrv_enabled = {
+        let lock = req.get::<persistent::State<Config>>()
+            .expect("depot not found");
+        let depot = lock.read().expect("depot read lock is poisoned");
+        depot.jobsrv_enabled
+    };
+    let storage = req.get::<persistent::Read<StorageCli>>().unwrap();
+    let (session_id, session_name) = get_session_id_and_name(req);
+    let ident = ident_from_req(req);
+
+    if !ident.valid() || !ident.fully_qualified() {
+        info!(
+            "Invalid or not fully qualified package identifier: {}",
+            ident
+        );
+        return Ok(Response::with(status::BadRequest));
+    }
+
+    if !check_origin_owner(req, session_id, ident.get_origin()).unwrap_or(false) {
+        return Ok(Response::with(status::Forbidden));
+    }
+
+    let mut opg = OriginPackageGet::new();
+    opg.set_ident(ident.clone());
+    opg.set_visibilities(all_visibilities());
+
+    let package = match route_message::<OriginPackageGet, OriginPackage>(req, &opg) {
+        Ok(package) => package,
+        Err(err) => return Ok(render_net_error(&err)),
+    };
+    let target = match PackageTarget::from_str(package.get_target()) {
+        Ok(target) => target,
+        Err(err) => {
+            warn!("Invalid target for package {}, err={:?}", ident, err);
+            return Ok(Response::with(status::InternalServerError));
+        }
+    };
+
+    // Packages which have been promoted must be demoted before they can be deleted
+    let mut ocl = OriginPackageChannelListRequest::new();
+    ocl.set_ident(ident.clone());
+    ocl.set_visibilities(all_visibilities());
+
+    match route_message::<OriginPackageChannelListRequest, OriginPackageChannelListResponse>(
+        req, &ocl,
+    ) {
+        Ok(channels) => {
+            if is_promoted(&channels) {
+                return Ok(Response::with((status::Conflict, "ds:del:1")));
+            }
+        }
+        Err(err) => return Ok(render_net_error(&err)),
+    }
+
+    // The job graph only tracks the latest release of each package, so that is the only release
+    // other packages can depend on
+    if jobsrv_enabled {
+        let mut rdeps_get = JobGraphPackageReverseDependenciesGet::new();
+        rdeps_get.set_origin(ident.get_origin().to_string());
+        rdeps_get.set_name(ident.get_name().to_string());
+        rdeps_get.set_target(target.to_string());
+
+        match route_message::<
+            JobGraphPackageReverseDependenciesGet,
+            JobGraphPackageReverseDependencies,
+        >(req, &rdeps_get)
+        {
+            Ok(rdeps) => {
+                if has_dependents(&ident, &rdeps) {
+                    return Ok(Response::with((status::Conflict, "ds:del:2")));
+                }
+            }
+            Err(ref err) if err.get_code() == ErrCode::ENTITY_NOT_FOUND => (),
+            Err(err) => return Ok(render_net_error(&err)),
+        }
+    }
+
+    let mut opd = OriginPackageDelete::new();
+    opd.set_id(package.get_id());
+    opd.set_ident(ident.clone());
+
+    if let Err(err) = route_message::<OriginPackageDelete, NetOk>(req, &opd) {
+        return Ok(render_net_error(&err));
+    }
+
+    // The package is gone at this point. An artifact left behind is removed by the artifact
+    // collector.
+    let pkg_ident: PackageIdent = ident.clone().into();
+    if let Err(err) = storage.delete(&pkg_ident, &target) {
+        warn!("Unable to remove artifact for {}, err={:?}", pkg_ident, err);
+    }
+
+    let mut audit = PackageDeleteAudit::new();
+    audit.set_ident(ident);
+    audit.set_target(target.to_string());
+    audit.set_operation(PackageDeleteOperation::PackageDelete);
+    audit.set_trigger(PackageChannelTrigger::from(trigger_from_request(req)));
+    audit.set_requester_id(session_id);
+    audit.set_requester_name(session_name);
+    audit.set_origin_id(package.get_origin_id());
+
+    if let Err(err) = route_message::<PackageDeleteAudit, NetOk>(req, &audit) {
+        warn!("Failed to save package deletion to audit log: {}", err);
+    }
+
+    Ok(Response::with(status::NoContent))
+}
+
+/// Returns true if the package is in any channel besides `unstable`.
+fn is_promoted(channels: &OriginPackageChannelListResponse) -> bool {
+    channels
+        .get_channels()
+        .iter()
+        .any(|channel| channel.get_name() != "unstable")
+}
+
+/// Returns true if other packages depend on the given release. Reverse dependencies are only
+/// tracked for the latest release of a package.
+fn has_dependents(ident: &OriginPackageIdent, rdeps: &JobGraphPackageReverseDependencies) -> bool {
+    rdeps.get_ident() == ident.to_string() && !rdeps.get_rdeps().is_empty()
+}
+
 fn download_package(req: &mut Request) -> IronResult<Response> {
     let lock = req.get::<persistent::State<Config>>()
         .expect("depot not found");
     let depot = lock.read().expect("depot read lock is poisoned");
     let session_id = helpers::get_optional_session_id(req);
-    let s3handler = req.get::<persistent::Read<S3Cli>>().unwrap();
+    let storage = req.get::<persistent::Read<StorageCli>>().unwrap();
     let mut ident_req = OriginPackageGet::new();
     let ident = ident_from_req(req);
     let mut vis = visibility_for_optional_session(req, session_id, &ident.get_origin());
@@ -1259,7 +2074,7 @@ fn download_package(req: &mut Request) -> IronResult<Response> {
             let file_path = dir.path()
                 .join(Config::archive_name(&(&package).into(), &target));
             let temp_ident = ident.to_owned().into();
-            match s3handler.download(&file_path, &temp_ident, &target) {
+            match storage.download(&file_path, &temp_ident, &target) {
                 Ok(archive) => download_response_for_archive(archive, dir),
                 Err(e) => {
                     warn!("Failed to download package, err={:?}", e);
@@ -1430,7 +2245,7 @@ fn package_privacy_toggle(req: &mut Request) -> IronResult<Response> {
         Err(_) => return Ok(Response::with(status::BadRequest)),
     };
 
-    if !check_origin_access(req, &origin).unwrap_or(false) {
+    if !check_origin_role(req, &origin, OriginMemberRole::Member).unwrap_or(false) {
         return Ok(Response::with(status::Forbidden));
     }
 
@@ -1635,12 +2450,105 @@ fn create_channel(req: &mut Request) -> IronResult<Response> {
         None => return Ok(Response::with(status::BadRequest)),
     };
 
+    if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
+        return Ok(Response::with(status::Forbidden));
+    }
+
     match helpers::create_channel(req, &origin, &channel) {
         Ok(origin_channel) => Ok(render_json(status::Created, &origin_channel)),
         Err(err) => Ok(render_net_error(&err)),
     }
 }
 
+fn channel_from_req(req: &mut Request) -> result::Result<OriginChannel, Response> {
+    let origin = match get_param(req, "origin") {
+        Some(origin) => origin,
+        None => return Err(Response::with(status::BadRequest)),
+    };
+    let channel = match get_param(req, "channel") {
+        Some(channel) => channel,
+        None => return Err(Response::with(status::BadRequest)),
+    };
+
+    let mut channel_req = OriginChannelGet::new();
+    channel_req.set_origin_name(origin);
+    channel_req.set_name(channel);
+    route_message::<OriginChannelGet, OriginChannel>(req, &channel_req)
+        .map_err(|err| render_net_error(&err))
+}
+
+fn get_channel_retention(req: &mut Request) -> IronResult<Response> {
+    let origin_channel = match channel_from_req(req) {
+        Ok(origin_channel) => origin_channel,
+        Err(response) => return Ok(response),
+    };
+
+    let mut request = OriginChannelRetentionGet::new();
+    request.set_channel_id(origin_channel.get_id());
+    request.set_origin_id(origin_channel.get_origin_id());
+
+    match route_message::<OriginChannelRetentionGet, OriginChannelRetention>(req, &request) {
+        Ok(retention) => {
+            let mut response = render_json(status::Ok, &retention);
+            dont_cache_response(&mut response);
+            Ok(response)
+        }
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
+fn set_channel_retention(req: &mut Request) -> IronResult<Response> {
+    let body = match req.get::<bodyparser::Struct<ChannelRetentionReq>>() {
+        Ok(Some(body)) => body,
+        _ => return Ok(Response::with(status::UnprocessableEntity)),
+    };
+    if body.keep_latest == 0 && body.max_age_days == 0 {
+        return Ok(Response::with((status::UnprocessableEntity, "ds:cr:1")));
+    }
+
+    let origin_channel = match channel_from_req(req) {
+        Ok(origin_channel) => origin_channel,
+        Err(response) => return Ok(response),
+    };
+    let origin = get_param(req, "origin").unwrap(); // Unwrap OK, checked in channel_from_req
+    if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
+        return Ok(Response::with(status::Forbidden));
+    }
+
+    let mut retention = OriginChannelRetention::new();
+    retention.set_channel_id(origin_channel.get_id());
+    retention.set_origin_id(origin_channel.get_origin_id());
+    retention.set_keep_latest(body.keep_latest);
+    retention.set_max_age_days(body.max_age_days);
+    let mut request = OriginChannelRetentionSet::new();
+    request.set_retention(retention);
+
+    match route_message::<OriginChannelRetentionSet, OriginChannelRetention>(req, &request) {
+        Ok(retention) => Ok(render_json(status::Ok, &retention)),
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
+fn delete_channel_retention(req: &mut Request) -> IronResult<Response> {
+    let origin_channel = match channel_from_req(req) {
+        Ok(origin_channel) => origin_channel,
+        Err(response) => return Ok(response),
+    };
+    let origin = get_param(req, "origin").unwrap(); // Unwrap OK, checked in channel_from_req
+    if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
+        return Ok(Response::with(status::Forbidden));
+    }
+
+    let mut request = OriginChannelRetentionDelete::new();
+    request.set_channel_id(origin_channel.get_id());
+    request.set_origin_id(origin_channel.get_origin_id());
+
+    match route_message::<OriginChannelRetentionDelete, NetOk>(req, &request) {
+        Ok(_) => Ok(Response::with(status::NoContent)),
+        Err(err) => Ok(render_net_error(&err)),
+    }
+}
+
 fn delete_channel(req: &mut Request) -> IronResult<Response> {
     let origin = match get_param(req, "origin") {
         Some(origin) => origin,
@@ -1657,7 +2565,7 @@ fn delete_channel(req: &mut Request) -> IronResult<Response> {
     match route_message::<OriginChannelGet, OriginChannel>(req, &channel_req) {
         Ok(origin_channel) => {
             // make sure the person trying to create the channel has access to do so
-            if !check_origin_access(req, &origin).unwrap_or(false) {
+            if !check_origin_role(req, &origin, OriginMemberRole::Maintainer).unwrap_or(false) {
                 return Ok(Response::with(status::Forbidden));
             }
 
@@ -1835,6 +2743,19 @@ fn search_packages(req: &mut Request) -> IronResult<Response> {
         request.set_distinct(true);
     }
 
+    // Full text searches rank packages by how well their names, manifests, exposed ports and
+    // dependencies match the query, and may be narrowed to a target or channel, e.g.
+    // blah?full_text=true&target=x86_64-linux&channel=stable
+    if helpers::extract_query_value("full_text", req).is_some() {
+        request.set_full_text(true);
+        if let Some(target) = helpers::extract_query_value("target", req) {
+            request.set_target(target);
+        }
+        if let Some(channel) = helpers::extract_query_value("channel", req) {
+            request.set_channel(channel);
+        }
+    }
+
     match route_message::<OriginPackageSearchRequest, OriginPackageListResponse>(req, &request) {
         Ok(packages) => {
             debug!(
@@ -1920,7 +2841,7 @@ fn promote_package(req: &mut Request) -> IronResult<Response> {
         None => return Ok(Response::with(status::BadRequest)),
     };
 
-    if !check_origin_access(req, ident.get_origin()).unwrap_or(false) {
+    if !check_origin_role(req, ident.get_origin(), OriginMemberRole::Maintainer).unwrap_or(false) {
         let err = NetError::new(ErrCode::ACCESS_DENIED, "core:promote-package-to-channel:0");
         return Ok(render_net_error(&err));
     }
@@ -1971,7 +2892,19 @@ fn promote_package(req: &mut Request) -> IronResult<Response> {
                 PackageChannelOperation::Promote,
                 origin_id,
             ) {
-                Ok(_) => Ok(Response::with(status::Ok)),
+                Ok(_) => {
+                    let payload = json!({
+                        "channel": channel,
+                        "idents": [ident.to_string()],
+                    });
+                    helpers::notify_origin_webhooks(
+                        req,
+                        ident.get_origin(),
+                        OriginWebhookEvent::PackagePromoted,
+                        &payload,
+                    );
+                    Ok(Response::with(status::Ok))
+                }
                 Err(err) => return Ok(render_net_error(&err)),
             },
             Err(err) => return Ok(render_net_error(&err)),
@@ -2008,7 +2941,7 @@ fn demote_package(req: &mut Request) -> IronResult<Response> {
         return Ok(Response::with(status::Forbidden));
     }
 
-    if !check_origin_access(req, &ident.get_origin()).unwrap_or(false) {
+    if !check_origin_role(req, &ident.get_origin(), OriginMemberRole::Maintainer).unwrap_or(false) {
         return Ok(Response::with(status::Forbidden));
     }
 
@@ -2056,7 +2989,7 @@ fn demote_package(req: &mut Request) -> IronResult<Response> {
 }
 
 pub fn create_origin_secret(req: &mut Request) -> IronResult<Response> {
-    let params = match validate_params(req, &["origin"]) {
+    let params = match validate_params(req, &["origin"], OriginMemberRole::Maintainer) {
         Ok(p) => p,
         Err(st) => return Ok(Response::with(st)),
     };
@@ -2186,13 +3119,21 @@ pub fn create_origin_secret(req: &mut Request) -> IronResult<Response> {
     request.set_secret(secret);
 
     match route_message::<OriginSecretCreate, OriginSecret>(req, &request) {
-        Ok(_) => Ok(Response::with(status::Created)),
+        Ok(_) => {
+            helpers::audit_origin_change(
+                req,
+                request.get_secret().get_origin_id(),
+                OriginAuditOperation::SecretCreate,
+                request.get_secret().get_name(),
+            );
+            Ok(Response::with(status::Created))
+        }
         Err(err) => Ok(render_net_error(&err)),
     }
 }
 
 pub fn list_origin_secrets(req: &mut Request) -> IronResult<Response> {
-    let params = match validate_params(req, &["origin"]) {
+    let params = match validate_params(req, &["origin"], OriginMemberRole::ReadOnly) {
         Ok(p) => p,
         Err(st) => return Ok(Response::with(st)),
     };
@@ -2214,7 +3155,7 @@ pub fn list_origin_secrets(req: &mut Request) -> IronResult<Response> {
 }
 
 fn delete_origin_secret(req: &mut Request) -> IronResult<Response> {
-    let params = match validate_params(req, &["origin", "secret"]) {
+    let params = match validate_params(req, &["origin", "secret"], OriginMemberRole::Maintainer) {
         Ok(p) => p,
         Err(st) => return Ok(Response::with(st)),
     };
@@ -2226,7 +3167,15 @@ fn delete_origin_secret(req: &mut Request) -> IronResult<Response> {
     }
     request.set_name(params["secret"].to_string());
     match route_message::<OriginSecretDelete, NetOk>(req, &request) {
-        Ok(_) => Ok(Response::with(status::Ok)),
+        Ok(_) => {
+            helpers::audit_origin_change(
+                req,
+                request.get_origin_id(),
+                OriginAuditOperation::SecretDelete,
+                request.get_name(),
+            );
+            Ok(Response::with(status::Ok))
+        }
         Err(err) => Ok(render_net_error(&err)),
     }
 }
@@ -2668,7 +3617,8 @@ pub fn routes<M>(basic: Authenticated, worker: M, depot: &Config) -> Router
 where
     M: BeforeMiddleware + Clone,
 {
-    let opt = basic.clone().optional();
+    // Package reads are the only routes open to tokens with just the read scope
+    let opt = basic.clone().optional().scope(AccessTokenScope::Read);
     let mut r = Router::new();
 
     if depot.jobsrv_enabled {
@@ -2679,7 +3629,7 @@ where
         );
         r.post(
             "/pkgs/schedule/:origin/:pkg",
-            XHandler::new(schedule).before(basic.clone()),
+            XHandler::new(schedule).before(basic.clone().scope(AccessTokenScope::Schedule)),
             "schedule",
         );
         r.get("/pkgs/schedule/:groupid", get_schedule, "schedule_get");
@@ -2688,6 +3638,11 @@ where
             get_origin_schedule_status,
             "schedule_get_global",
         );
+        r.get(
+            "/pkgs/schedule/:origin/audit",
+            XHandler::new(list_job_group_audit).before(basic.clone()),
+            "schedule_audit",
+        );
         r.delete(
             "/pkgs/schedule/:groupid",
             XHandler::new(abort_schedule).before(worker.clone()),
@@ -2728,12 +3683,12 @@ where
     );
     r.put(
         "/channels/:origin/:channel/pkgs/:pkg/:version/:release/promote",
-        XHandler::new(promote_package).before(basic.clone()),
+        XHandler::new(promote_package).before(basic.clone().scope(AccessTokenScope::Promote)),
         "channel_package_promote",
     );
     r.put(
         "/channels/:origin/:channel/pkgs/:pkg/:version/:release/demote",
-        XHandler::new(demote_package).before(basic.clone()),
+        XHandler::new(demote_package).before(basic.clone().scope(AccessTokenScope::Promote)),
         "channel_package_demote",
     );
     r.post(
@@ -2746,6 +3701,21 @@ where
         XHandler::new(delete_channel).before(basic.clone()),
         "channel_delete",
     );
+    r.get(
+        "/channels/:origin/:channel/retention",
+        get_channel_retention,
+        "channel_retention",
+    );
+    r.put(
+        "/channels/:origin/:channel/retention",
+        XHandler::new(set_channel_retention).before(basic.clone()),
+        "channel_retention_set",
+    );
+    r.delete(
+        "/channels/:origin/:channel/retention",
+        XHandler::new(delete_channel_retention).before(basic.clone()),
+        "channel_retention_delete",
+    );
     r.get(
         "/pkgs/search/:query",
         XHandler::new(search_packages).before(opt.clone()),
@@ -2803,9 +3773,14 @@ where
     );
     r.post(
         "/pkgs/:origin/:pkg/:version/:release",
-        XHandler::new(upload_package).before(basic.clone()),
+        XHandler::new(upload_package).before(basic.clone().scope(AccessTokenScope::Upload)),
         "package_upload",
     );
+    r.delete(
+        "/pkgs/:origin/:pkg/:version/:release",
+        XHandler::new(delete_package).before(basic.clone()),
+        "package_delete",
+    );
     r.patch(
         "/pkgs/:origin/:pkg/:version/:release/:visibility",
         XHandler::new(package_privacy_toggle).before(basic.clone()),
@@ -2823,6 +3798,11 @@ where
     );
     r.get("/origins/:origin", origin_show, "origin");
     r.get("/origins/:origin/keys", list_origin_keys, "origin_keys");
+    r.get(
+        "/origins/:origin/audit",
+        XHandler::new(list_origin_audit).before(basic.clone()),
+        "origin_audit",
+    );
     r.get(
         "/origins/:origin/keys/latest",
         download_latest_origin_key,
@@ -2933,6 +3913,56 @@ where
         XHandler::new(origin_member_delete).before(basic.clone()),
         "origin_member_delete",
     );
+    r.delete(
+        "/origins/:origin",
+        XHandler::new(origin_delete).before(basic.clone()),
+        "origin_delete",
+    );
+    r.get(
+        "/origins/:origin/transfer",
+        XHandler::new(origin_owner_transfer_show).before(basic.clone()),
+        "origin_owner_transfer",
+    );
+    r.post(
+        "/origins/:origin/transfer/:username",
+        XHandler::new(origin_owner_transfer_create).before(basic.clone()),
+        "origin_owner_transfer_create",
+    );
+    r.put(
+        "/origins/:origin/transfer/accept",
+        XHandler::new(origin_owner_transfer_accept).before(basic.clone()),
+        "origin_owner_transfer_accept",
+    );
+    r.delete(
+        "/origins/:origin/transfer",
+        XHandler::new(origin_owner_transfer_rescind).before(basic.clone()),
+        "origin_owner_transfer_rescind",
+    );
+    r.get(
+        "/origins/:origin/webhooks",
+        XHandler::new(list_origin_webhooks).before(basic.clone()),
+        "origin_webhooks",
+    );
+    r.post(
+        "/origins/:origin/webhooks",
+        XHandler::new(create_origin_webhook).before(basic.clone()),
+        "origin_webhook_create",
+    );
+    r.delete(
+        "/origins/:origin/webhooks/:id",
+        XHandler::new(delete_origin_webhook).before(basic.clone()),
+        "origin_webhook_delete",
+    );
+    r.get(
+        "/origins/:origin/webhooks/:id/deliveries",
+        XHandler::new(list_origin_webhook_deliveries).before(basic.clone()),
+        "origin_webhook_deliveries",
+    );
+    r.put(
+        "/origins/:origin/users/:username/role",
+        XHandler::new(origin_member_role_update).before(basic.clone()),
+        "origin_member_role_update",
+    );
 
     r
 }
@@ -2948,11 +3978,12 @@ pub fn router(depot: Config) -> Result<Chain> {
         depot.segment.clone(),
     )));
 
-    chain.link(persistent::Read::<S3Cli>::both(s3::S3Handler::new(
-        depot.s3.to_owned(),
-    )));
+    chain.link(persistent::Read::<StorageCli>::both(backend::from_config(
+        &depot,
+    )?));
 
     UpstreamMgr::start(&depot)?;
+    ArtifactCollector::start(&depot)?;
     let upstream_cli = UpstreamClient::default();
     chain.link(persistent::Read::<UpstreamCli>::both(upstream_cli));
 
@@ -2961,3 +3992,45 @@ pub fn router(depot: Config) -> Result<Chain> {
     chain.link_after(Cors);
     Ok(chain)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use protobuf::RepeatedField;
+
+    fn channels(names: &[&str]) -> OriginPackageChannelListResponse {
+        let mut response = OriginPackageChannelListResponse::new();
+        response.set_channels(RepeatedField::from_vec(
+            names
+                .iter()
+                .map(|name| {
+                    let mut channel = OriginChannel::new();
+                    channel.set_name(name.to_string());
+                    channel
+                })
+                .collect(),
+        ));
+        response
+    }
+
+    #[test]
+    fn package_in_unstable_only_is_not_promoted() {
+        assert!(!is_promoted(&channels(&[])));
+        assert!(!is_promoted(&channels(&["unstable"])));
+        assert!(is_promoted(&channels(&["unstable", "stable"])));
+    }
+
+    #[test]
+    fn only_latest_release_has_dependents() {
+        let ident = OriginPackageIdent::from_str("core/zlib/1.2.11/20180701122201").unwrap();
+        let mut rdeps = JobGraphPackageReverseDependencies::new();
+        rdeps.set_ident(ident.to_string());
+        assert!(!has_dependents(&ident, &rdeps));
+
+        rdeps.set_rdeps(RepeatedField::from_vec(vec!["core/openssl".to_string()]));
+        assert!(has_dependents(&ident, &rdeps));
+
+        rdeps.set_ident("core/zlib/1.2.11/20180801122201".to_string());
+        assert!(!has_dependents(&ident, &rdeps));
+    }
+}
@@ -33,6 +33,7 @@
 //!     pub struct SrvConfig {
 //!         pub http: HttpCfg,
 //!         pub routers: Vec<RouterAddr>,
+//!         pub curve: CurveCfg,
 //!     }
 //!
 //!     impl GatewayCfg for SrvConfig {
@@ -47,6 +48,10 @@
 //!         fn route_addrs(&self) -> &[RouterAddr] {
 //!             self.routers.as_slice()
 //!         }
+//!
+//!         fn curve(&self) -> &CurveCfg {
+//!             &self.curve
+//!         }
 //!     }
 //!
 //!     pub struct HttpCfg {
@@ -164,6 +169,6 @@ where
         cfg.listen_port()
     );
     info!("{} is ready to go.", T::APP_NAME);
-    RouteBroker::start(socket::srv_ident(), cfg.route_addrs())?;
+    RouteBroker::start(socket::srv_ident(), cfg.route_addrs(), cfg.curve())?;
     Ok(())
 }
@@ -17,6 +17,7 @@ pub mod prelude;
 use std::net::IpAddr;
 
 use hab_net::app::config::RouterAddr;
+use hab_net::curve::CurveCfg;
 use num_cpus;
 
 pub trait GatewayCfg {
@@ -36,4 +37,7 @@ pub trait GatewayCfg {
 
     /// Return a list of router addresses
     fn route_addrs(&self) -> &[RouterAddr];
+
+    /// Return the CurveZMQ keys used to connect to the routers
+    fn curve(&self) -> &CurveCfg;
 }
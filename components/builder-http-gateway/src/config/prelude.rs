@@ -17,3 +17,4 @@ pub use std::net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs};
 pub use super::GatewayCfg;
 pub use core::config::ConfigFile;
 pub use hab_net::app::config::RouterAddr;
+pub use hab_net::curve::CurveCfg;
@@ -14,6 +14,7 @@
 
 use hab_net::app::config::RouterAddr;
 use hab_net::conn::{ConnErr, RouteClient, RECV_TIMEOUT_MS, SEND_TIMEOUT_MS};
+use hab_net::curve::CurveCfg;
 use hab_net::socket::{ToAddrString, DEFAULT_CONTEXT};
 use iron::typemap;
 use zmq;
@@ -45,8 +46,12 @@ impl RouteBroker {
         Ok(conn)
     }
 
-    pub fn start(net_ident: String, routers: &[RouterAddr]) -> Result<(), ConnErr> {
-        let mut broker = Self::new(net_ident)?;
+    pub fn start(
+        net_ident: String,
+        routers: &[RouterAddr],
+        curve: &CurveCfg,
+    ) -> Result<(), ConnErr> {
+        let mut broker = Self::new(net_ident, curve)?;
         broker.run(routers)
     }
 
@@ -56,17 +61,19 @@ impl RouteBroker {
     ///
     /// * A socket cannot be created within the given `zmq::Context`
     /// * A socket cannot be configured
+    /// * The CurveZMQ keys are invalid
     ///
     /// # Panics
     ///
     /// * Could not read `zmq::Context` due to deadlock or poisoning
-    fn new(net_ident: String) -> Result<Self, ConnErr> {
+    fn new(net_ident: String, curve: &CurveCfg) -> Result<Self, ConnErr> {
         let client_sock = (**DEFAULT_CONTEXT).as_mut().socket(zmq::ROUTER)?;
         let router_sock = (**DEFAULT_CONTEXT).as_mut().socket(zmq::DEALER)?;
         router_sock.set_identity(net_ident.as_bytes())?;
         router_sock.set_rcvtimeo(RECV_TIMEOUT_MS)?;
         router_sock.set_sndtimeo(SEND_TIMEOUT_MS)?;
         router_sock.set_immediate(true)?;
+        curve.configure_client(&router_sock)?;
         Ok(RouteBroker {
             client_sock: client_sock,
             router_sock: router_sock,
@@ -30,13 +30,14 @@ use protocol::jobsrv::{
 };
 use protocol::originsrv::{
     CheckOriginAccessRequest, CheckOriginAccessResponse, CheckOriginOwnerRequest,
-    CheckOriginOwnerResponse, Origin, OriginChannel, OriginChannelCreate, OriginChannelGet,
-    OriginGet, OriginPackage, OriginPackageChannelListRequest, OriginPackageChannelListResponse,
-    OriginPackageGet, OriginPackageGroupDemote, OriginPackageGroupPromote, OriginPackageIdent,
+    CheckOriginOwnerResponse, Origin, OriginAudit, OriginAuditOperation, OriginChannel,
+    OriginChannelCreate, OriginChannelGet, OriginGet, OriginMemberRole, OriginPackage,
+    OriginPackageChannelListRequest, OriginPackageChannelListResponse, OriginPackageGet,
+    OriginPackageGroupDemote, OriginPackageGroupPromote, OriginPackageIdent,
     OriginPackagePlatformListRequest, OriginPackagePlatformListResponse, OriginPackageVisibility,
     OriginPrivateSigningKey, OriginPrivateSigningKeyCreate, OriginPublicSigningKey,
-    OriginPublicSigningKeyCreate, PackageChannelOperation, PackageChannelTrigger,
-    PackageGroupChannelAudit,
+    OriginPublicSigningKeyCreate, OriginWebhookEvent, OriginWebhookNotify,
+    PackageChannelOperation, PackageChannelTrigger, PackageGroupChannelAudit,
 };
 use protocol::sessionsrv::{Account, AccountGetId};
 use serde::Serialize;
@@ -62,6 +63,7 @@ pub fn is_request_from_hab(req: &mut Request) -> bool {
 pub fn validate_params(
     req: &mut Request,
     expected_params: &[&str],
+    role: OriginMemberRole,
 ) -> Result<HashMap<String, String>, Status> {
     let mut res = HashMap::new();
     // Get the expected params
@@ -78,7 +80,7 @@ pub fn validate_params(
     }
     // Check that we have origin access
     {
-        if !check_origin_access(req, &res["origin"]).unwrap_or(false) {
+        if !check_origin_role(req, &res["origin"], role).unwrap_or(false) {
             debug!("Failed origin access check, origin: {}", &res["origin"]);
             return Err(status::Forbidden);
         }
@@ -272,6 +274,18 @@ pub fn get_param(req: &mut Request, name: &str) -> Option<String> {
 }
 
 pub fn check_origin_access<T>(req: &mut Request, origin: T) -> IronResult<bool>
+where
+    T: ToString,
+{
+    check_origin_role(req, origin, OriginMemberRole::ReadOnly)
+}
+
+/// Checks that the session is a member of the origin with at least the given role.
+pub fn check_origin_role<T>(
+    req: &mut Request,
+    origin: T,
+    role: OriginMemberRole,
+) -> IronResult<bool>
 where
     T: ToString,
 {
@@ -279,11 +293,19 @@ where
         return Ok(true);
     }
 
-    let session_id = get_session_id(req);
+    let session_id = {
+        let session = req.extensions.get::<Authenticated>().unwrap();
+        // Access tokens may be limited to some of the account's origins
+        if !session.permits_origin(&origin.to_string()) {
+            return Ok(false);
+        }
+        session.get_id()
+    };
 
     let mut request = CheckOriginAccessRequest::new();
     request.set_account_id(session_id);
     request.set_origin_name(origin.to_string());
+    request.set_role(role);
     match route_message::<CheckOriginAccessRequest, CheckOriginAccessResponse>(req, &request) {
         Ok(response) => Ok(response.get_has_access()),
         Err(err) => {
@@ -298,6 +320,12 @@ pub fn check_origin_owner<T>(req: &mut Request, account_id: u64, origin: T) -> I
 where
     T: ToString,
 {
+    if let Some(session) = req.extensions.get::<Authenticated>() {
+        if !session.permits_origin(&origin.to_string()) {
+            return Ok(false);
+        }
+    }
+
     let mut request = CheckOriginOwnerRequest::new();
     request.set_account_id(account_id);
     request.set_origin_name(origin.to_string());
@@ -425,6 +453,17 @@ pub fn promote_or_demote_job_group(
                 pgca.set_group_id(group_id);
 
                 route_message::<PackageGroupChannelAudit, NetOk>(req, &pgca)?;
+
+                if promote {
+                    let idents: Vec<&str> = projects.iter().map(|p| p.get_ident()).collect();
+                    let payload = json!({
+                        "channel": channel,
+                        "idents": idents,
+                        "group_id": group_id.to_string(),
+                    });
+                    let event = OriginWebhookEvent::PackagePromoted;
+                    notify_origin_webhooks(req, origin, event, &payload);
+                }
             }
             Err(e) => {
                 if e.get_code() != ErrCode::ACCESS_DENIED {
@@ -438,6 +477,48 @@ pub fn promote_or_demote_job_group(
     Ok(NetOk::new())
 }
 
+/// Queues delivery of an event to the origin's webhooks. Failures are only logged, webhooks never
+/// fail the request which triggered them.
+pub fn notify_origin_webhooks(
+    req: &mut Request,
+    origin: &str,
+    event: OriginWebhookEvent,
+    payload: &serde_json::Value,
+) {
+    let mut notify = OriginWebhookNotify::new();
+    notify.set_origin_name(origin.to_string());
+    notify.set_event(event);
+    notify.set_payload(payload.to_string());
+
+    if let Err(err) = route_message::<OriginWebhookNotify, NetOk>(req, &notify) {
+        warn!("Unable to notify webhooks of {} in {}, {}", event, origin, err);
+    }
+}
+
+/// Records a change to an origin's members, owner, keys, secrets or integrations in its audit
+/// log.
+/// Failures are logged rather than returned so the change itself still succeeds.
+pub fn audit_origin_change(
+    req: &mut Request,
+    origin_id: u64,
+    operation: OriginAuditOperation,
+    subject: &str,
+) {
+    let mut audit = OriginAudit::new();
+    audit.set_origin_id(origin_id);
+    audit.set_operation(operation);
+    audit.set_subject(subject.to_string());
+    audit.set_trigger(PackageChannelTrigger::from(trigger_from_request(req)));
+
+    let (session_id, session_name) = get_session_id_and_name(req);
+    audit.set_requester_id(session_id);
+    audit.set_requester_name(session_name);
+
+    if let Err(err) = route_message::<OriginAudit, NetOk>(req, &audit) {
+        warn!("Unable to audit {:?} of {} in origin {}, {}", operation, subject, origin_id, err);
+    }
+}
+
 pub fn get_optional_session_id(req: &mut Request) -> Option<u64> {
     match req.extensions.get::<Authenticated>() {
         Some(session) => Some(session.get_id()),
@@ -536,7 +617,7 @@ fn do_group_promotion_or_demotion(
     origin: &str,
     promote: bool,
 ) -> NetResult<Vec<u64>> {
-    if !check_origin_access(req, origin).unwrap_or(false) {
+    if !check_origin_role(req, origin, OriginMemberRole::Maintainer).unwrap_or(false) {
         return Err(NetError::new(
             ErrCode::ACCESS_DENIED,
             "hg:promote-or-demote-job-group:1",
@@ -595,7 +676,7 @@ fn do_group_promotion_or_demotion(
     Ok(package_ids)
 }
 
-fn is_worker(req: &mut Request) -> bool {
+pub fn is_worker(req: &mut Request) -> bool {
     match req.extensions.get::<Authenticated>() {
         Some(session) => {
             let flags = FeatureFlags::from_bits(session.get_flags()).unwrap();
@@ -605,7 +686,3 @@ fn is_worker(req: &mut Request) -> bool {
     }
 }
 
-fn get_session_id(req: &mut Request) -> u64 {
-    let session = req.extensions.get::<Authenticated>().unwrap();
-    session.get_id()
-}
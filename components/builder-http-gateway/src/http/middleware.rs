@@ -129,6 +129,7 @@ pub struct Authenticated {
     features: FeatureFlags,
     key_dir: PathBuf,
     optional: bool,
+    scope: Option<AccessTokenScope>,
 }
 
 impl Authenticated {
@@ -137,6 +138,7 @@ impl Authenticated {
             features: FeatureFlags::empty(),
             key_dir: key_dir,
             optional: false,
+            scope: None,
         }
     }
 
@@ -145,6 +147,31 @@ impl Authenticated {
         self
     }
 
+    /// Allows sessions from scoped access tokens to use the route if they have the given scope.
+    pub fn scope(mut self, scope: AccessTokenScope) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Checks the session against the scopes of the access token it was created from. Routes
+    /// which don't declare a scope, such as those serving origin keys and secrets, are closed to
+    /// scoped tokens. Optional routes treat a scoped token lacking the scope as anonymous.
+    fn check_scope(&self, session: &Session) -> IronResult<()> {
+        if !session.is_scoped() {
+            return Ok(());
+        }
+        let permitted = match self.scope {
+            Some(scope) => session.permits_scope(scope),
+            None => false,
+        };
+        if permitted {
+            Ok(())
+        } else {
+            let err = NetError::new(ErrCode::ACCESS_DENIED, "net:auth:token-scope");
+            Err(IronError::new(err, Status::Forbidden))
+        }
+    }
+
     pub fn optional(mut self) -> Self {
         self.optional = true;
         self
@@ -223,6 +250,12 @@ impl BeforeMiddleware for Authenticated {
         };
 
         let session = self.authenticate(req, &token)?;
+        if let Err(err) = self.check_scope(&session) {
+            if self.optional {
+                return Ok(());
+            }
+            return Err(err);
+        }
         req.extensions.insert::<Self>(session);
         Ok(())
     }
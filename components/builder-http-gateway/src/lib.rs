@@ -40,6 +40,7 @@ extern crate segment_api_client;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
+#[macro_use]
 extern crate serde_json;
 extern crate staticfile;
 extern crate toml;
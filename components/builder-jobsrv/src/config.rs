@@ -14,12 +14,15 @@
 
 //! Configuration for a Habitat JobSrv service
 
+use std::collections::HashMap;
 use std::env;
 use std::net::{IpAddr, Ipv4Addr};
 use std::path::PathBuf;
 
 use db::config::DataStoreCfg;
+use github_api_client::config::GitHubCfg;
 use hab_net::app::config::*;
+use hab_net::curve::CurveCfg;
 use server::log_archiver::ArchiveBackend;
 
 use error::Error;
@@ -42,6 +45,14 @@ pub struct Config {
     pub log_path: PathBuf,
     /// Max time (in minutes) allowed for a build job
     pub job_timeout: u64,
+    /// Fair-share scheduling policy across origins
+    pub scheduler: SchedulerCfg,
+    /// Retry policy for jobs that fail with a transient error
+    pub retry: RetryCfg,
+    /// GitHub App used to report commit statuses
+    pub github: GitHubCfg,
+    /// Reporting of job results back to GitHub as commit statuses
+    pub commit_status: CommitStatusCfg,
 }
 
 impl Default for Config {
@@ -57,6 +68,10 @@ impl Default for Config {
             key_dir: PathBuf::from("/hab/svc/hab-depot/files"),
             log_path: PathBuf::from("/tmp"),
             job_timeout: 60,
+            scheduler: SchedulerCfg::default(),
+            retry: RetryCfg::default(),
+            github: GitHubCfg::default(),
+            commit_status: CommitStatusCfg::default(),
         }
     }
 }
@@ -86,6 +101,8 @@ pub struct NetCfg {
     pub log_ingestion_listen: IpAddr,
     /// Worker Log Ingestion socket's port
     pub log_ingestion_port: u16,
+    /// CurveZMQ keys of JobSrv and of the workers allowed to connect to the worker sockets
+    pub curve: CurveCfg,
 }
 
 impl NetCfg {
@@ -120,6 +137,7 @@ impl Default for NetCfg {
             worker_heartbeat_port: 5567,
             log_ingestion_listen: IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)),
             log_ingestion_port: 5568,
+            curve: CurveCfg::default(),
         }
     }
 }
@@ -159,6 +177,114 @@ impl Default for ArchiveCfg {
     }
 }
 
+////////////////////////////////////////////////////////////////////////
+// Scheduler Configuration
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct SchedulerCfg {
+    /// Fair-share policy for individual origins, keyed by origin name. Origins that are not
+    /// listed get a weight of 1 and no dispatch cap.
+    pub origins: HashMap<String, OriginShareCfg>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct OriginShareCfg {
+    /// Relative share of the workers given to the origin when other origins have work waiting
+    pub weight: u32,
+    /// Max number of jobs from the origin that may be dispatched at once (0 is unlimited)
+    pub max_dispatched: u32,
+}
+
+impl Default for OriginShareCfg {
+    fn default() -> Self {
+        OriginShareCfg {
+            weight: 1,
+            max_dispatched: 0,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////
+// Retry Configuration
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct RetryCfg {
+    /// Policy for jobs whose project and origin have no policy of their own
+    pub default: RetryPolicyCfg,
+    /// Policies keyed by origin (e.g. `core`) or by project (e.g. `core/glibc`). A project's
+    /// policy takes precedence over its origin's.
+    pub policies: HashMap<String, RetryPolicyCfg>,
+}
+
+impl RetryCfg {
+    /// Returns the retry policy for the given project (e.g. `core/glibc`).
+    pub fn policy(&self, project_name: &str) -> &RetryPolicyCfg {
+        if let Some(policy) = self.policies.get(project_name) {
+            return policy;
+        }
+        let origin = project_name.split("/").next().unwrap_or("");
+        self.policies.get(origin).unwrap_or(&self.default)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicyCfg {
+    /// Max number of times a job is run, including the first run (1 disables retries)
+    pub max_attempts: u32,
+    /// Delay (in seconds) before the first retry; doubled for each retry after that
+    pub backoff_secs: u64,
+    /// Upper bound (in seconds) on the delay before a retry
+    pub max_backoff_secs: u64,
+}
+
+impl RetryPolicyCfg {
+    /// Returns the delay (in seconds) before the given retry, counting from zero.
+    pub fn backoff(&self, retry: u32) -> u64 {
+        let factor = 1u64.checked_shl(retry).unwrap_or(u64::max_value());
+        self.backoff_secs
+            .saturating_mul(factor)
+            .min(self.max_backoff_secs)
+    }
+}
+
+impl Default for RetryPolicyCfg {
+    fn default() -> Self {
+        RetryPolicyCfg {
+            max_attempts: 1,
+            backoff_secs: 30,
+            max_backoff_secs: 900,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////
+// Commit Status Configuration
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct CommitStatusCfg {
+    /// Report the state of jobs built from GitHub webhooks back to GitHub
+    pub enabled: bool,
+    /// URL of the Builder UI, statuses link to the job's build log there
+    pub web_url: String,
+    /// Prefix of the status context, followed by the project name (e.g. `bldr/core/nginx`)
+    pub context: String,
+}
+
+impl Default for CommitStatusCfg {
+    fn default() -> Self {
+        CommitStatusCfg {
+            enabled: false,
+            web_url: "https://bldr.habitat.sh".to_string(),
+            context: "bldr".to_string(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,6 +300,11 @@ mod tests {
         log_ingestion_listen = "2.2.2.2"
         log_ingestion_port = 9999
 
+        [net.curve]
+        public_key = "rq:rM>}U?@Lns47E1%kR.o@n%FcmmsL/@{H8]yf7"
+        secret_key = "JTKVSB%%)wK0E.X)V>+}o?pNmC{O&4W4b!Ni{Lh6"
+        allowed_keys = ["Yne@$w-vo<fVvi]a<NY6T1ed:M$fCG*[IaLV{hID"]
+
         [archive]
         backend = "s3"
         key = "THIS_IS_THE_KEY"
@@ -181,6 +312,20 @@ mod tests {
         bucket = "bukkit"
         endpoint = "http://minio.mycompany.com:9000"
 
+        [scheduler.origins.core]
+        weight = 4
+        max_dispatched = 10
+
+        [scheduler.origins.mycompany]
+        weight = 2
+
+        [github]
+        app_id = 1234
+
+        [commit_status]
+        enabled = true
+        web_url = "https://bldr.mycompany.com"
+
         [datastore]
         host = "1.1.1.1"
         port = 9000
@@ -206,6 +351,8 @@ mod tests {
         assert_eq!(config.net.worker_command_port, 9000);
         assert_eq!(config.net.worker_heartbeat_port, 9000);
         assert_eq!(config.net.log_ingestion_port, 9999);
+        assert!(config.net.curve.is_enabled());
+        assert_eq!(config.net.curve.allowed_keys.len(), 1);
         assert_eq!(config.datastore.port, 9000);
         assert_eq!(config.datastore.user, "test");
         assert_eq!(config.datastore.database, "test_jobsrv");
@@ -227,5 +374,52 @@ mod tests {
         );
         assert_eq!(config.archive.region, "us-east-1");
         assert_eq!(config.archive.local_dir, None);
+
+        assert_eq!(config.scheduler.origins.len(), 2);
+        assert_eq!(config.scheduler.origins["core"].weight, 4);
+        assert_eq!(config.scheduler.origins["core"].max_dispatched, 10);
+        assert_eq!(config.scheduler.origins["mycompany"].weight, 2);
+        assert_eq!(config.scheduler.origins["mycompany"].max_dispatched, 0);
+
+        assert_eq!(config.github.app_id, 1234);
+        assert_eq!(config.commit_status.enabled, true);
+        assert_eq!(config.commit_status.web_url, "https://bldr.mycompany.com");
+        assert_eq!(config.commit_status.context, "bldr");
+    }
+
+    #[test]
+    fn retry_policy_lookup() {
+        let content = r#"
+        [retry.default]
+        max_attempts = 2
+
+        [retry.policies.core]
+        max_attempts = 3
+        backoff_secs = 10
+
+        [retry.policies."core/glibc"]
+        max_attempts = 5
+        "#;
+
+        let config = Config::from_raw(&content).unwrap();
+        assert_eq!(config.retry.policy("core/glibc").max_attempts, 5);
+        assert_eq!(config.retry.policy("core/zlib").max_attempts, 3);
+        assert_eq!(config.retry.policy("core/zlib").backoff_secs, 10);
+        assert_eq!(config.retry.policy("other/zlib").max_attempts, 2);
+        assert_eq!(config.retry.policy("other/zlib").backoff_secs, 30);
+    }
+
+    #[test]
+    fn retry_policy_backoff() {
+        let policy = RetryPolicyCfg {
+            max_attempts: 10,
+            backoff_secs: 30,
+            max_backoff_secs: 200,
+        };
+        assert_eq!(policy.backoff(0), 30);
+        assert_eq!(policy.backoff(1), 60);
+        assert_eq!(policy.backoff(2), 120);
+        assert_eq!(policy.backoff(3), 200);
+        assert_eq!(policy.backoff(100), 200);
     }
 }
@@ -95,7 +95,7 @@ impl DataStore {
             None
         };
 
-        if job.get_project().get_vcs_type() == "git" {
+        if is_known_vcs(job.get_project().get_vcs_type()) {
             let project = job.get_project();
             let install_id: Option<String> = {
                 if project.has_vcs_installation_id() {
@@ -105,8 +105,21 @@ impl DataStore {
                 }
             };
 
+            let target = if job.has_target() {
+                job.get_target()
+            } else {
+                "x86_64-linux"
+            };
+
+            let git_ref = if job.has_git_ref() {
+                Some(job.get_git_ref())
+            } else {
+                None
+            };
+
             let rows = conn.query(
-                "SELECT * FROM insert_job_v2($1, $2, $3, $4, $5, $6, $7, $8)",
+                "SELECT * FROM insert_job_v6($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, \
+                 $13)",
                 &[
                     &(job.get_owner_id() as i64),
                     &(project.get_id() as i64),
@@ -116,6 +129,11 @@ impl DataStore {
                     &project.get_vcs_type(),
                     &vec![Some(project.get_vcs_data().to_string()), install_id],
                     &channel,
+                    &(job.get_priority() as i32),
+                    &target,
+                    &project.get_required_labels().to_vec(),
+                    &git_ref,
+                    &(job.get_pull_request() as i64),
                 ],
             ).map_err(Error::JobCreate)?;
             let job = row_to_job(&rows.get(0))?;
@@ -139,12 +157,36 @@ impl DataStore {
             &[&(get_job.get_id() as i64)],
         ).map_err(Error::JobGet)?;
         for row in rows {
-            let job = row_to_job(&row)?;
+            let mut job = row_to_job(&row)?;
+            if job.get_retry_count() > 0 {
+                job.set_attempts(self.get_job_attempts(job.get_id())?);
+            }
+            job.set_sections(self.get_job_sections(job.get_id())?);
             return Ok(Some(job));
         }
         Ok(None)
     }
 
+    /// Get the previous, failed runs of a job that has been retried.
+    ///
+    /// # Errors
+    ///
+    /// * If a connection cannot be gotten from the pool
+    /// * If the attempts cannot be selected from the database
+    pub fn get_job_attempts(&self, job_id: u64) -> Result<RepeatedField<jobsrv::JobAttempt>> {
+        let conn = self.pool.get_shard(0)?;
+        let rows = &conn.query(
+            "SELECT * FROM get_job_attempts_v1($1)",
+            &[&(job_id as i64)],
+        ).map_err(Error::JobGet)?;
+
+        let mut attempts = RepeatedField::new();
+        for row in rows {
+            attempts.push(row_to_job_attempt(&row));
+        }
+        Ok(attempts)
+    }
+
     /// Get the 50 most recently-created jobs for a given project
     /// (specified as an origin-qualified name, e.g., "core/nginx").
     ///
@@ -183,15 +225,26 @@ impl DataStore {
     /// Get the next pending job from the list of pending jobs
     /// Atomically set the job state to Dispatching, and set the worker id
     ///
+    /// Jobs are ordered by priority, then by the fair-share policy of their origin (see
+    /// `upsert_origin_share`), then by creation time. Jobs waiting out a retry backoff are
+    /// skipped, as are jobs for another target or that require labels the worker lacks.
+    ///
     /// # Errors
     ///
     /// * If a connection cannot be gotten from the pool
     /// * If the pending jobs cannot be selected from the database
     /// * If the row returned cannot be translated into a Job
-    pub fn next_pending_job(&self, worker: &str) -> Result<Option<jobsrv::Job>> {
+    pub fn next_pending_job(
+        &self,
+        worker: &str,
+        target: &str,
+        labels: &[String],
+    ) -> Result<Option<jobsrv::Job>> {
         let conn = self.pool.get_shard(0)?;
-        let rows = &conn.query("SELECT * FROM next_pending_job_v1($1)", &[&worker])
-            .map_err(Error::JobPending)?;
+        let rows = &conn.query(
+            "SELECT * FROM next_pending_job_v5($1, $2, $3)",
+            &[&worker, &target, &labels.to_vec()],
+        ).map_err(Error::JobPending)?;
 
         if rows.len() != 0 {
             let row = rows.get(0);
@@ -202,6 +255,30 @@ impl DataStore {
         }
     }
 
+    /// Create or update the fair-share policy for an origin. The weight is the origin's relative
+    /// share of dispatched jobs, and `max_dispatched` caps the number of jobs from the origin that
+    /// may be dispatched at once (0 means no cap).
+    ///
+    /// # Errors
+    ///
+    /// * If a connection cannot be gotten from the pool
+    /// * If the origin share cannot be upserted
+    pub fn upsert_origin_share(
+        &self,
+        origin: &str,
+        weight: u32,
+        max_dispatched: u32,
+    ) -> Result<()> {
+        let conn = self.pool.get_shard(0)?;
+
+        conn.execute(
+            "SELECT FROM upsert_origin_share_v1($1, $2, $3)",
+            &[&origin, &(weight as i32), &(max_dispatched as i32)],
+        ).map_err(Error::OriginShareUpsert)?;
+
+        Ok(())
+    }
+
     /// Get a list of cancel-pending jobs
     ///
     /// # Errors
@@ -288,8 +365,15 @@ impl DataStore {
             (None, None)
         };
 
+        // Only known once the worker has checked out the source, earlier updates keep it
+        let git_sha = if job.has_git_sha() {
+            Some(job.get_git_sha())
+        } else {
+            None
+        };
+
         conn.execute(
-            "SELECT update_job_v3($1, $2, $3, $4, $5, $6, $7)",
+            "SELECT update_job_v4($1, $2, $3, $4, $5, $6, $7, $8)",
             &[
                 &job_id,
                 &job_state,
@@ -298,12 +382,120 @@ impl DataStore {
                 &ident,
                 &err_code,
                 &err_msg,
+                &git_sha,
             ],
         ).map_err(Error::JobSetState)?;
 
         Ok(())
     }
 
+    /// Get the sections of a job, in the order they were started.
+    ///
+    /// # Errors
+    ///
+    /// * If a connection cannot be gotten from the pool
+    /// * If the sections cannot be selected from the database
+    pub fn get_job_sections(&self, job_id: u64) -> Result<RepeatedField<jobsrv::JobSection>> {
+        let conn = self.pool.get_shard(0)?;
+        let rows = &conn.query(
+            "SELECT * FROM get_job_sections_v1($1)",
+            &[&(job_id as i64)],
+        ).map_err(Error::JobSectionsGet)?;
+
+        let mut sections = RepeatedField::new();
+        for row in rows {
+            let mut section = row_to_job_section(&row)?;
+            section.set_job_id(job_id);
+            sections.push(section);
+        }
+        Ok(sections)
+    }
+
+    /// Records the timing and status of a job section, as reported by the worker when the
+    /// section starts and when it ends.
+    ///
+    /// # Errors
+    ///
+    /// * If a connection cannot be gotten from the pool
+    /// * If the section cannot be written to the database
+    pub fn upsert_job_section(&self, section: &jobsrv::JobSection) -> Result<()> {
+        let conn = self.pool.get_shard(0)?;
+
+        let started_at = section
+            .get_started_at()
+            .parse::<DateTime<Utc>>()
+            .unwrap_or(Utc::now());
+        let finished_at = if section.has_finished_at() {
+            section.get_finished_at().parse::<DateTime<Utc>>().ok()
+        } else {
+            None
+        };
+
+        conn.execute(
+            "SELECT upsert_job_section_v1($1, $2, $3, $4, $5)",
+            &[
+                &(section.get_job_id() as i64),
+                &section.get_name(),
+                &section.get_status().to_string(),
+                &started_at,
+                &finished_at,
+            ],
+        ).map_err(Error::JobSectionUpsert)?;
+
+        Ok(())
+    }
+
+    /// Records the current run of a failed job as an attempt, and requeues the job so that it
+    /// can be dispatched again once `delay_secs` have passed. Returns `false` if the job is no
+    /// longer running, e.g. because it was canceled meanwhile, and so wasn't requeued.
+    ///
+    /// # Errors
+    ///
+    /// * If a connection cannot be gotten from the pool
+    /// * If the job cannot be updated in the database
+    pub fn retry_job(&self, job: &jobsrv::Job, delay_secs: u64) -> Result<bool> {
+        let conn = self.pool.get_shard(0)?;
+
+        let build_started_at = if job.has_build_started_at() {
+            Some(job.get_build_started_at().parse::<DateTime<Utc>>().unwrap())
+        } else {
+            None
+        };
+
+        let build_finished_at = if job.has_build_finished_at() {
+            Some(
+                job.get_build_finished_at()
+                    .parse::<DateTime<Utc>>()
+                    .unwrap(),
+            )
+        } else {
+            None
+        };
+
+        let (err_code, err_msg) = if job.has_error() {
+            (
+                Some(job.get_error().get_code() as i32),
+                Some(job.get_error().get_msg()),
+            )
+        } else {
+            (None, None)
+        };
+
+        let rows = conn.query(
+            "SELECT * FROM retry_job_v1($1, $2, $3, $4, $5, $6)",
+            &[
+                &(job.get_id() as i64),
+                &build_started_at,
+                &build_finished_at,
+                &err_code,
+                &err_msg,
+                &(delay_secs as i64),
+            ],
+        ).map_err(Error::JobRetry)?;
+
+        Ok(!rows.is_empty())
+    }
+
     /// Marks a given job's logs as having been archived. The location
     /// and mechanism for retrieval are dependent on the configured archiving
     /// mechanism.
@@ -468,27 +660,43 @@ impl DataStore {
     pub fn is_job_group_active(&self, project_name: &str) -> Result<bool> {
         let conn = self.pool.get_shard(0)?;
 
-        let rows = &conn.query("SELECT * FROM check_active_group_v1($1)", &[&project_name])
+        let rows = &conn.query("SELECT * FROM check_active_group_v2($1)", &[&project_name])
             .map_err(Error::JobGroupGet)?;
 
         // If we get any rows back, we found one or more active groups
         Ok(rows.len() >= 1)
     }
 
-    pub fn get_queued_job_group(&self, project_name: &str) -> Result<Option<jobsrv::JobGroup>> {
+    /// Get the queued group of a project building the given ref and pull request. Empty and zero
+    /// stand for the default branch and for no pull request.
+    pub fn get_queued_job_group(
+        &self,
+        project_name: &str,
+        git_ref: &str,
+        pull_request: u64,
+    ) -> Result<Option<jobsrv::JobGroup>> {
         let conn = self.pool.get_shard(0)?;
 
         let rows = &conn.query("SELECT * FROM get_queued_group_v1($1)", &[&project_name])
             .map_err(Error::JobGroupGet)?;
 
-        if rows.is_empty() {
-            debug!("JobGroup {} not queued (not found)", project_name);
-            return Ok(None);
+        // A project has at most one queued group for each ref and pull request
+        let mut queued = None;
+        for row in rows.iter() {
+            let group = self.row_to_job_group(&row)?;
+            if group.get_git_ref() == git_ref && group.get_pull_request() == pull_request {
+                queued = Some(group);
+                break;
+            }
         }
 
-        assert!(rows.len() == 1); // should never have more than one
-
-        let mut group = self.row_to_job_group(&rows.get(0))?;
+        let mut group = match queued {
+            Some(group) => group,
+            None => {
+                debug!("JobGroup {} not queued (not found)", project_name);
+                return Ok(None);
+            }
+        };
         let group_id = group.get_id();
 
         let project_rows = &conn.query(
@@ -519,10 +727,26 @@ impl DataStore {
         Ok(groups)
     }
 
+    pub fn get_resuming_job_groups(&self) -> Result<RepeatedField<jobsrv::JobGroup>> {
+        let mut groups = RepeatedField::new();
+
+        let conn = self.pool.get_shard(0)?;
+
+        let rows = &conn.query("SELECT * FROM get_resuming_groups_v1()", &[])
+            .map_err(Error::JobGroupGet)?;
+
+        for row in rows {
+            let group = self.row_to_job_group(&row)?;
+            groups.push(group);
+        }
+
+        Ok(groups)
+    }
+
     pub fn create_job_group(
         &self,
         msg: &jobsrv::JobGroupSpec,
-        project_tuples: Vec<(String, String)>,
+        project_tuples: Vec<(String, String, String)>,
     ) -> Result<jobsrv::JobGroup> {
         let conn = self.pool.get_shard(0)?;
 
@@ -530,21 +754,62 @@ impl DataStore {
 
         let root_project = format!("{}/{}", msg.get_origin(), msg.get_package());
 
-        let (project_names, project_idents): (Vec<String>, Vec<String>) =
-            project_tuples.iter().cloned().unzip();
+        let mut project_names = Vec::new();
+        let mut project_idents = Vec::new();
+        let mut project_targets = Vec::new();
+        let mut project_plan_deps = Vec::new();
+
+        for &(ref name, ref ident, ref target) in project_tuples.iter() {
+            project_names.push(name.clone());
+            project_idents.push(ident.clone());
+            project_targets.push(target.clone());
+            project_plan_deps.push(
+                msg.get_plans()
+                    .iter()
+                    .find(|p| p.get_name() == name.as_str())
+                    .map(|p| p.get_deps().join(",")),
+            );
+        }
+
+        let git_ref = if msg.has_git_ref() {
+            Some(msg.get_git_ref())
+        } else {
+            None
+        };
+        let channel = if msg.has_channel() {
+            Some(msg.get_channel())
+        } else {
+            None
+        };
 
         let rows = conn.query(
-            "SELECT * FROM insert_group_v2($1, $2, $3)",
-            &[&root_project, &project_names, &project_idents],
+            "SELECT * FROM insert_group_v7($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+            &[
+                &root_project,
+                &project_names,
+                &project_idents,
+                &project_targets,
+                &(msg.get_priority() as i32),
+                &git_ref,
+                &(msg.get_pull_request() as i64),
+                &channel,
+                &(msg.get_trigger() as i16),
+                &project_plan_deps,
+            ],
         ).map_err(Error::JobGroupCreate)?;
 
         let mut group = self.row_to_job_group(&rows.get(0))?;
         let mut projects = RepeatedField::new();
 
-        for (name, ident) in project_tuples {
+        for (name, ident, target) in project_tuples {
             let mut project = jobsrv::JobGroupProject::new();
+            if let Some(plan) = msg.get_plans().iter().find(|p| p.get_name() == name) {
+                project.set_from_plan(true);
+                project.set_plan_deps(RepeatedField::from_vec(plan.get_deps().to_vec()));
+            }
             project.set_name(name);
             project.set_ident(ident);
+            project.set_target(target);
             project.set_state(jobsrv::JobGroupProjectState::NotStarted);
             projects.push(project);
         }
@@ -564,6 +829,24 @@ impl DataStore {
         Ok(())
     }
 
+    /// Pauses a pending or dispatching group. Returns false if the group is in any other state.
+    pub fn pause_job_group(&self, group_id: u64) -> Result<bool> {
+        let conn = self.pool.get_shard(0)?;
+        let rows = conn.query("SELECT * FROM pause_group_v1($1)", &[&(group_id as i64)])
+            .map_err(Error::JobGroupSetState)?;
+
+        Ok(rows.len() == 1)
+    }
+
+    /// Hands a paused group back to the scheduler. Returns false if the group isn't paused.
+    pub fn resume_job_group(&self, group_id: u64) -> Result<bool> {
+        let conn = self.pool.get_shard(0)?;
+        let rows = conn.query("SELECT * FROM resume_group_v1($1)", &[&(group_id as i64)])
+            .map_err(Error::JobGroupSetState)?;
+
+        Ok(rows.len() == 1)
+    }
+
     pub fn create_audit_entry(&self, msg: &jobsrv::JobGroupAudit) -> Result<()> {
         let conn = self.pool.get_shard(0)?;
         conn.query(
@@ -580,6 +863,65 @@ impl DataStore {
         Ok(())
     }
 
+    /// Lists audit entries for job groups of projects in an origin, newest first
+    pub fn list_job_group_audit(
+        &self,
+        msg: &jobsrv::JobGroupAuditListRequest,
+    ) -> Result<jobsrv::JobGroupAuditListResponse> {
+        let operation = if msg.has_operation() {
+            Some(msg.get_operation() as i16)
+        } else {
+            None
+        };
+        let from = msg.get_from().parse::<DateTime<Utc>>().ok();
+        let to = msg.get_to().parse::<DateTime<Utc>>().ok();
+
+        let conn = self.pool.get_shard(0)?;
+        let rows = &conn.query(
+            "SELECT COUNT(*) OVER () AS total_count, * FROM get_job_group_audit_v1($1, $2, $3, $4, $5) ORDER BY created_at DESC LIMIT $6 OFFSET $7",
+            &[
+                &msg.get_origin(),
+                &msg.get_requester_name(),
+                &operation,
+                &from,
+                &to,
+                &msg.limit(),
+                &(msg.get_start() as i64),
+            ],
+        ).map_err(Error::JobGroupAuditList)?;
+
+        let mut entries = RepeatedField::new();
+        let mut response = jobsrv::JobGroupAuditListResponse::new();
+        response.set_start(msg.get_start());
+        response.set_stop(self.last_index(msg, &rows));
+
+        for row in rows {
+            let count: i64 = row.get("total_count");
+            response.set_count(count as u64);
+
+            let mut entry = jobsrv::JobGroupAuditEntry::new();
+            let group_id: i64 = row.get("group_id");
+            entry.set_group_id(group_id as u64);
+            entry.set_project_name(row.get("project_name"));
+            let operation: i16 = row.get("operation");
+            if let Some(operation) = jobsrv::JobGroupOperation::from_i32(operation as i32) {
+                entry.set_operation(operation);
+            }
+            let trigger: i16 = row.get("trigger");
+            if let Some(trigger) = jobsrv::JobGroupTrigger::from_i32(trigger as i32) {
+                entry.set_trigger(trigger);
+            }
+            let requester_id: i64 = row.get("requester_id");
+            entry.set_requester_id(requester_id as u64);
+            entry.set_requester_name(row.get("requester_name"));
+            let created_at = row.get::<&str, DateTime<Utc>>("created_at");
+            entry.set_created_at(created_at.to_rfc3339());
+            entries.push(entry);
+        }
+        response.set_entries(entries);
+        Ok(response)
+    }
+
     pub fn get_job_group_origin(
         &self,
         msg: &jobsrv::JobGroupOriginGet,
@@ -605,6 +947,28 @@ impl DataStore {
         Ok(response)
     }
 
+    /// Get the groups of an origin which are queued, running or paused.
+    pub fn get_active_job_groups_origin(
+        &self,
+        origin: &str,
+    ) -> Result<jobsrv::JobGroupOriginResponse> {
+        let conn = self.pool.get_shard(0)?;
+        let rows = &conn.query(
+            "SELECT * FROM get_active_groups_for_origin_v1($1)",
+            &[&origin],
+        ).map_err(Error::JobGroupOriginGet)?;
+
+        let mut response = jobsrv::JobGroupOriginResponse::new();
+        let mut job_groups = RepeatedField::new();
+
+        for row in rows {
+            job_groups.push(self.row_to_job_group(&row)?);
+        }
+
+        response.set_job_groups(job_groups);
+        Ok(response)
+    }
+
     pub fn get_job_group(&self, msg: &jobsrv::JobGroupGet) -> Result<Option<jobsrv::JobGroup>> {
         let group_id = msg.get_group_id();
         let include_projects = msg.get_include_projects();
@@ -688,6 +1052,24 @@ impl DataStore {
         let project_name: String = row.get("project_name");
         group.set_project_name(project_name);
 
+        let priority: i32 = row.get("priority");
+        group.set_priority(priority as u32);
+
+        if let Some(Ok(git_ref)) = row.get_opt::<&str, String>("git_ref") {
+            group.set_git_ref(git_ref);
+        }
+        if let Some(Ok(pull_request)) = row.get_opt::<&str, i64>("pull_request") {
+            group.set_pull_request(pull_request as u64);
+        }
+        if let Some(Ok(channel)) = row.get_opt::<&str, String>("channel") {
+            group.set_channel(channel);
+        }
+        if let Some(Ok(trigger)) = row.get_opt::<&str, i16>("trigger") {
+            if let Some(trigger) = jobsrv::JobGroupTrigger::from_i32(trigger as i32) {
+                group.set_trigger(trigger);
+            }
+        }
+
         Ok(group)
     }
 
@@ -708,6 +1090,14 @@ impl DataStore {
         project.set_state(project_state);
         project.set_job_id(job_id as u64);
 
+        if let Some(Ok(target)) = row.get_opt::<&str, String>("target") {
+            project.set_target(target);
+        }
+        if let Some(Ok(Some(plan_deps))) = row.get_opt::<&str, Option<Vec<String>>>("plan_deps") {
+            project.set_from_plan(true);
+            project.set_plan_deps(RepeatedField::from_vec(plan_deps));
+        }
+
         Ok(project)
     }
 
@@ -739,26 +1129,57 @@ impl DataStore {
         Ok(())
     }
 
+    /// Moves a group to `group_state` only if it is still in `from_state`. Returns false if the
+    /// group's state was changed in the meantime, such as by a pause.
+    pub fn transition_job_group_state(
+        &self,
+        group_id: u64,
+        from_state: jobsrv::JobGroupState,
+        group_state: jobsrv::JobGroupState,
+    ) -> Result<bool> {
+        let conn = self.pool.get_shard(0)?;
+        let rows = conn.query(
+            "SELECT * FROM set_group_state_from_v1($1, $2, $3)",
+            &[
+                &(group_id as i64),
+                &from_state.to_string(),
+                &group_state.to_string(),
+            ],
+        ).map_err(Error::JobGroupSetState)?;
+
+        Ok(rows.len() == 1)
+    }
+
     pub fn set_job_group_project_state(
         &self,
         group_id: u64,
         project_name: &str,
+        target: &str,
         project_state: jobsrv::JobGroupProjectState,
     ) -> Result<()> {
         let conn = self.pool.get_shard(0)?;
         let state = project_state.to_string();
         conn.execute(
-            "SELECT set_group_project_name_state_v1($1, $2, $3)",
-            &[&(group_id as i64), &project_name, &state],
+            "SELECT set_group_project_name_state_v2($1, $2, $3, $4)",
+            &[&(group_id as i64), &project_name, &target, &state],
         ).map_err(Error::JobGroupProjectSetState)?;
         Ok(())
     }
 
     pub fn set_job_group_job_state(&self, job: &jobsrv::Job) -> Result<()> {
         let conn = self.pool.get_shard(0)?;
+        let target = if job.has_target() {
+            job.get_target()
+        } else {
+            "x86_64-linux"
+        };
         let rows = &conn.query(
-            "SELECT * FROM find_group_project_v1($1, $2)",
-            &[&(job.get_owner_id() as i64), &job.get_project().get_name()],
+            "SELECT * FROM find_group_project_v2($1, $2, $3)",
+            &[
+                &(job.get_owner_id() as i64),
+                &job.get_project().get_name(),
+                &target,
+            ],
         ).map_err(Error::JobGroupProjectSetState)?;
 
         // No rows means this job might not be one we care about
@@ -803,7 +1224,7 @@ impl DataStore {
         let mut groups = Vec::new();
 
         let conn = self.pool.get_shard(0)?;
-        let group_rows = &conn.query("SELECT * FROM pending_groups_v1($1)", &[&count])
+        let group_rows = &conn.query("SELECT * FROM pending_groups_v2($1)", &[&count])
             .map_err(Error::JobGroupPending)?;
 
         for group_row in group_rows {
@@ -872,6 +1293,14 @@ fn row_to_busy_worker(row: &postgres::rows::Row) -> Result<jobsrv::BusyWorker> {
 ///
 /// * If the job state is unknown
 /// * If the VCS type is unknown
+/// Returns true for the version control systems workers know how to clone from.
+fn is_known_vcs(vcs_type: &str) -> bool {
+    match vcs_type {
+        "git" | "gitlab" | "bitbucket" => true,
+        _ => false,
+    }
+}
+
 fn row_to_job(row: &postgres::rows::Row) -> Result<jobsrv::Job> {
     let mut job = jobsrv::Job::new();
     let id: i64 = row.get("id");
@@ -924,9 +1353,9 @@ fn row_to_job(row: &postgres::rows::Row) -> Result<jobsrv::Job> {
 
     let rvcs: String = row.get("vcs");
     match rvcs.as_ref() {
-        "git" => {
+        "git" | "gitlab" | "bitbucket" => {
             let mut vcsa: Vec<Option<String>> = row.get("vcs_arguments");
-            project.set_vcs_type(String::from("git"));
+            project.set_vcs_type(rvcs.clone());
             project.set_vcs_data(vcsa.remove(0).expect("expected vcs data"));
             if vcsa.len() > 0 {
                 if let Some(install_id) = vcsa.remove(0) {
@@ -941,6 +1370,10 @@ fn row_to_job(row: &postgres::rows::Row) -> Result<jobsrv::Job> {
             return Err(Error::UnknownVCS);
         }
     }
+    if let Some(Ok(labels)) = row.get_opt::<&str, Vec<String>>("required_labels") {
+        project.set_required_labels(RepeatedField::from_vec(labels));
+    }
+
     job.set_project(project);
 
     if let Some(Ok(err_msg)) = row.get_opt::<&str, String>("net_error_msg") {
@@ -964,5 +1397,86 @@ fn row_to_job(row: &postgres::rows::Row) -> Result<jobsrv::Job> {
         job.set_worker(worker);
     };
 
+    if let Some(Ok(priority)) = row.get_opt::<&str, i32>("priority") {
+        job.set_priority(priority as u32);
+    };
+
+    if let Some(Ok(retry_count)) = row.get_opt::<&str, i32>("retry_count") {
+        job.set_retry_count(retry_count as u32);
+    };
+
+    if let Some(Ok(target)) = row.get_opt::<&str, String>("target") {
+        job.set_target(target);
+    };
+
+    if let Some(Ok(git_ref)) = row.get_opt::<&str, String>("git_ref") {
+        job.set_git_ref(git_ref);
+    };
+
+    if let Some(Ok(git_sha)) = row.get_opt::<&str, String>("git_sha") {
+        job.set_git_sha(git_sha);
+    };
+
+    if let Some(Ok(pull_request)) = row.get_opt::<&str, i64>("pull_request") {
+        job.set_pull_request(pull_request as u64);
+    };
+
     Ok(job)
 }
+
+/// Translate a database `job_sections` row to a `jobsrv::JobSection`.
+///
+fn row_to_job_section(row: &postgres::rows::Row) -> Result<jobsrv::JobSection> {
+    let mut section = jobsrv::JobSection::new();
+    section.set_name(row.get("name"));
+
+    let status: String = row.get("status");
+    section.set_status(status.parse().map_err(Error::UnknownJobSectionStatus)?);
+
+    let started_at = row.get::<&str, DateTime<Utc>>("started_at");
+    section.set_started_at(started_at.to_rfc3339());
+
+    // Note: these are null until the section has ended
+    if let Some(Ok(stop)) = row.get_opt::<&str, DateTime<Utc>>("finished_at") {
+        section.set_finished_at(stop.to_rfc3339());
+    }
+    if let Some(Ok(duration)) = row.get_opt::<&str, i64>("duration_ms") {
+        section.set_duration_ms(duration as u64);
+    }
+
+    Ok(section)
+}
+
+/// Translate a database `job_attempts` row to a `jobsrv::JobAttempt`.
+///
+fn row_to_job_attempt(row: &postgres::rows::Row) -> jobsrv::JobAttempt {
+    let mut attempt = jobsrv::JobAttempt::new();
+    let number: i32 = row.get("attempt");
+    attempt.set_attempt(number as u32);
+
+    if let Some(Ok(worker)) = row.get_opt::<&str, String>("worker") {
+        attempt.set_worker(worker);
+    }
+
+    let created_at = row.get::<&str, DateTime<Utc>>("created_at");
+    attempt.set_created_at(created_at.to_rfc3339());
+
+    if let Some(Ok(start)) = row.get_opt::<&str, DateTime<Utc>>("build_started_at") {
+        attempt.set_build_started_at(start.to_rfc3339());
+    }
+    if let Some(Ok(stop)) = row.get_opt::<&str, DateTime<Utc>>("build_finished_at") {
+        attempt.set_build_finished_at(stop.to_rfc3339());
+    }
+
+    if let Some(Ok(err_msg)) = row.get_opt::<&str, String>("net_error_msg") {
+        let err_code: i32 = row.get("net_error_code");
+        if let Some(net_err_code) = ErrCode::from_i32(err_code) {
+            let mut err = NetError::new();
+            err.set_code(net_err_code);
+            err.set_msg(err_msg);
+            attempt.set_error(err);
+        }
+    }
+
+    attempt
+}
@@ -46,6 +46,7 @@ pub enum Error {
     InvalidUrl,
     IO(io::Error),
     JobGroupAudit(postgres::error::Error),
+    JobGroupAuditList(postgres::error::Error),
     JobGroupCreate(postgres::error::Error),
     JobGroupCancel(postgres::error::Error),
     JobGroupGet(postgres::error::Error),
@@ -63,6 +64,9 @@ pub enum Error {
     JobMarkArchived(postgres::error::Error),
     JobPending(postgres::error::Error),
     JobReset(postgres::error::Error),
+    JobRetry(postgres::error::Error),
+    JobSectionsGet(postgres::error::Error),
+    JobSectionUpsert(postgres::error::Error),
     JobSetLogUrl(postgres::error::Error),
     JobSetState(postgres::error::Error),
     SyncJobs(postgres::error::Error),
@@ -70,6 +74,7 @@ pub enum Error {
     LogDirIsNotDir(PathBuf),
     LogDirNotWritable(PathBuf),
     NetError(hab_net::NetError),
+    OriginShareUpsert(postgres::error::Error),
     ParseVCSInstallationId(num::ParseIntError),
     ProjectJobsGet(postgres::error::Error),
     Protobuf(protobuf::ProtobufError),
@@ -79,6 +84,7 @@ pub enum Error {
     UnknownJobGroupState,
     UnknownJobGraphPackage,
     UnknownJobGroupProjectState,
+    UnknownJobSectionStatus(protocol::ProtocolError),
     UnknownJobState(protocol::ProtocolError),
     Zmq(zmq::Error),
 }
@@ -117,6 +123,9 @@ impl fmt::Display for Error {
             Error::InvalidUrl => format!("Bad URL!"),
             Error::IO(ref e) => format!("{}", e),
             Error::JobGroupAudit(ref e) => format!("Database error creating audit entry, {}", e),
+            Error::JobGroupAuditList(ref e) => {
+                format!("Database error listing audit entries, {}", e)
+            }
             Error::JobGroupCreate(ref e) => format!("Database error creating a new group, {}", e),
             Error::JobGroupCancel(ref e) => format!("Database error canceling a job group, {}", e),
             Error::JobGroupGet(ref e) => format!("Database error getting group data, {}", e),
@@ -150,6 +159,11 @@ impl fmt::Display for Error {
             }
             Error::JobPending(ref e) => format!("Database error getting pending jobs, {}", e),
             Error::JobReset(ref e) => format!("Database error reseting jobs, {}", e),
+            Error::JobRetry(ref e) => format!("Database error requeueing job for retry, {}", e),
+            Error::JobSectionsGet(ref e) => format!("Database error getting job sections, {}", e),
+            Error::JobSectionUpsert(ref e) => {
+                format!("Database error recording job section, {}", e)
+            }
             Error::JobSetLogUrl(ref e) => format!("Database error setting job log URL, {}", e),
             Error::JobSetState(ref e) => format!("Database error setting job state, {}", e),
             Error::SyncJobs(ref e) => format!("Database error retrieving sync jobs, {}", e),
@@ -163,6 +177,9 @@ impl fmt::Display for Error {
                 format!("Build log directory {:?} is not writable!", path)
             }
             Error::NetError(ref e) => format!("{}", e),
+            Error::OriginShareUpsert(ref e) => {
+                format!("Database error creating or updating an origin share, {}", e)
+            }
             Error::ParseVCSInstallationId(ref e) => {
                 format!("VCS installation id could not be parsed as u64, {}", e)
             }
@@ -176,6 +193,7 @@ impl fmt::Display for Error {
             Error::UnknownJobGraphPackage => format!("Unknown Package"),
             Error::UnknownJobGroupProjectState => format!("Unknown Project State"),
             Error::UnknownVCS => format!("Unknown VCS"),
+            Error::UnknownJobSectionStatus(ref e) => format!("{}", e),
             Error::UnknownJobState(ref e) => format!("{}", e),
             Error::Zmq(ref e) => format!("{}", e),
         };
@@ -201,6 +219,7 @@ impl error::Error for Error {
             Error::IO(ref err) => err.description(),
             Error::InvalidUrl => "Bad Url!",
             Error::JobGroupAudit(ref err) => err.description(),
+            Error::JobGroupAuditList(ref err) => err.description(),
             Error::JobGroupCreate(ref err) => err.description(),
             Error::JobGroupCancel(ref err) => err.description(),
             Error::JobGroupGet(ref err) => err.description(),
@@ -218,6 +237,9 @@ impl error::Error for Error {
             Error::JobMarkArchived(ref err) => err.description(),
             Error::JobPending(ref err) => err.description(),
             Error::JobReset(ref err) => err.description(),
+            Error::JobRetry(ref err) => err.description(),
+            Error::JobSectionsGet(ref err) => err.description(),
+            Error::JobSectionUpsert(ref err) => err.description(),
             Error::JobSetLogUrl(ref err) => err.description(),
             Error::JobSetState(ref err) => err.description(),
             Error::SyncJobs(ref err) => err.description(),
@@ -225,10 +247,12 @@ impl error::Error for Error {
             Error::LogDirIsNotDir(_) => "Build log directory is not a directory",
             Error::LogDirNotWritable(_) => "Build log directory is not writable",
             Error::NetError(ref err) => err.description(),
+            Error::OriginShareUpsert(ref err) => err.description(),
             Error::ParseVCSInstallationId(_) => "VCS installation id could not be parsed as u64",
             Error::ProjectJobsGet(ref err) => err.description(),
             Error::Protobuf(ref err) => err.description(),
             Error::Protocol(ref err) => err.description(),
+            Error::UnknownJobSectionStatus(ref err) => err.description(),
             Error::UnknownJobState(ref err) => err.description(),
             Error::UnknownJobGroup => "Unknown Group",
             Error::UnknownJobGroupState => "Unknown Group State",
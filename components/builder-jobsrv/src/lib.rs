@@ -21,6 +21,7 @@ extern crate chrono;
 extern crate diesel;
 #[macro_use]
 extern crate diesel_migrations;
+extern crate github_api_client;
 extern crate habitat_builder_db as db;
 extern crate habitat_builder_protocol as protocol;
 extern crate habitat_core as hab_core;
@@ -38,6 +39,7 @@ extern crate rand;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
+extern crate serde_json;
 extern crate sha2;
 extern crate time;
 extern crate toml;
@@ -0,0 +1,8 @@
+DROP FUNCTION IF EXISTS upsert_origin_share_v1(text, integer, integer);
+DROP FUNCTION IF EXISTS insert_job_v3(bigint, bigint, text, bigint, text, text, text[], text, integer);
+DROP FUNCTION IF EXISTS next_pending_job_v2(text);
+DROP FUNCTION IF EXISTS insert_group_v3(text, text[], text[], integer);
+DROP FUNCTION IF EXISTS pending_groups_v2(integer);
+DROP TABLE IF EXISTS origin_shares;
+ALTER TABLE groups DROP COLUMN IF EXISTS priority;
+ALTER TABLE jobs DROP COLUMN IF EXISTS priority;
@@ -0,0 +1,125 @@
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS priority integer DEFAULT 0;
+ALTER TABLE groups ADD COLUMN IF NOT EXISTS priority integer DEFAULT 0;
+
+-- Fair-share policy for each origin. Origins without an entry get a weight
+-- of 1 and no cap on the number of concurrently dispatched jobs.
+CREATE TABLE IF NOT EXISTS origin_shares (
+  origin text PRIMARY KEY,
+  weight integer DEFAULT 1,
+  max_dispatched integer DEFAULT 0,
+  created_at timestamptz DEFAULT now(),
+  updated_at timestamptz
+);
+
+CREATE OR REPLACE FUNCTION upsert_origin_share_v1 (
+  p_origin text,
+  p_weight integer,
+  p_max_dispatched integer
+) RETURNS SETOF origin_shares AS $$
+  INSERT INTO origin_shares (origin, weight, max_dispatched)
+  VALUES (p_origin, p_weight, p_max_dispatched)
+  ON CONFLICT(origin)
+  DO UPDATE SET weight=p_weight, max_dispatched=p_max_dispatched, updated_at=now()
+  RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION insert_job_v3 (
+  p_owner_id bigint,
+  p_project_id bigint,
+  p_project_name text,
+  p_project_owner_id bigint,
+  p_project_plan_path text,
+  p_vcs text,
+  p_vcs_arguments text[],
+  p_channel text,
+  p_priority integer
+  ) RETURNS SETOF jobs AS $$
+      INSERT INTO jobs (owner_id, job_state, project_id, project_name, project_owner_id, project_plan_path, vcs, vcs_arguments, channel, priority)
+      VALUES (p_owner_id, 'Pending', p_project_id, p_project_name, p_project_owner_id, p_project_plan_path, p_vcs, p_vcs_arguments, p_channel, p_priority)
+      RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
+
+-- Pending jobs are handed out by priority first. Between jobs of the same
+-- priority, the origin with the fewest in-flight jobs relative to its weight
+-- goes next, so a large rebuild in one origin can't starve the others. Origins
+-- at their max_dispatched cap are passed over entirely.
+CREATE OR REPLACE FUNCTION next_pending_job_v2 (p_worker text) RETURNS SETOF jobs AS
+$$
+DECLARE
+    r jobs % rowtype;
+BEGIN
+    FOR r IN
+        WITH dispatched AS (
+            SELECT split_part(project_name, '/', 1) AS origin, COUNT(*) AS job_count
+            FROM jobs
+            WHERE job_state IN ('Dispatched', 'Processing')
+            GROUP BY 1
+        )
+        SELECT j.* FROM jobs j
+        LEFT JOIN dispatched d ON d.origin = split_part(j.project_name, '/', 1)
+        LEFT JOIN origin_shares s ON s.origin = split_part(j.project_name, '/', 1)
+        WHERE j.job_state = 'Pending'
+        AND (COALESCE(s.max_dispatched, 0) = 0 OR COALESCE(d.job_count, 0) < s.max_dispatched)
+        ORDER BY j.priority DESC,
+                 COALESCE(d.job_count, 0)::float / GREATEST(COALESCE(s.weight, 1), 1) ASC,
+                 j.created_at ASC
+        FOR UPDATE OF j SKIP LOCKED
+        LIMIT 1
+    LOOP
+        UPDATE jobs SET job_state='Dispatched', scheduler_sync=false, worker=p_worker, updated_at=now()
+        WHERE id=r.id
+        RETURNING * INTO r;
+        RETURN NEXT r;
+    END LOOP;
+  RETURN;
+END
+$$ LANGUAGE plpgsql VOLATILE;
+
+CREATE OR REPLACE FUNCTION insert_group_v3 (
+  root_project text,
+  project_names text[],
+  project_idents text[],
+  p_priority integer
+) RETURNS SETOF groups
+  LANGUAGE SQL
+  VOLATILE AS $$
+  WITH my_group AS (
+          INSERT INTO groups (project_name, group_state, priority)
+          VALUES (root_project, 'Queued', p_priority) RETURNING *
+      ), my_project AS (
+          INSERT INTO group_projects (owner_id, project_name, project_ident, project_state)
+          SELECT g.id, project_info.name, project_info.ident, 'NotStarted'
+          FROM my_group AS g, unnest(project_names, project_idents) AS project_info(name, ident)
+      )
+  SELECT * FROM my_group;
+$$;
+
+-- Same ordering as next_pending_job_v2, weighing each origin by the number
+-- of its groups that are currently dispatching.
+CREATE OR REPLACE FUNCTION pending_groups_v2 (integer) RETURNS SETOF groups AS $$
+DECLARE
+    r groups % rowtype;
+BEGIN
+    FOR r IN
+        WITH dispatching AS (
+            SELECT split_part(project_name, '/', 1) AS origin, COUNT(*) AS group_count
+            FROM groups
+            WHERE group_state = 'Dispatching'
+            GROUP BY 1
+        )
+        SELECT g.* FROM groups g
+        LEFT JOIN dispatching d ON d.origin = split_part(g.project_name, '/', 1)
+        LEFT JOIN origin_shares s ON s.origin = split_part(g.project_name, '/', 1)
+        WHERE g.group_state = 'Pending'
+        ORDER BY g.priority DESC,
+                 COALESCE(d.group_count, 0)::float / GREATEST(COALESCE(s.weight, 1), 1) ASC,
+                 g.created_at ASC
+        FOR UPDATE OF g SKIP LOCKED
+        LIMIT $1
+    LOOP
+        UPDATE groups SET group_state='Dispatching', updated_at=now() WHERE id=r.id RETURNING * INTO r;
+        RETURN NEXT r;
+    END LOOP;
+  RETURN;
+END
+$$ LANGUAGE plpgsql VOLATILE;
@@ -0,0 +1,6 @@
+DROP FUNCTION IF EXISTS retry_job_v1(bigint, timestamptz, timestamptz, int, text, bigint);
+DROP FUNCTION IF EXISTS get_job_attempts_v1(bigint);
+DROP FUNCTION IF EXISTS next_pending_job_v3(text);
+DROP TABLE IF EXISTS job_attempts;
+ALTER TABLE jobs DROP COLUMN IF EXISTS retry_after;
+ALTER TABLE jobs DROP COLUMN IF EXISTS retry_count;
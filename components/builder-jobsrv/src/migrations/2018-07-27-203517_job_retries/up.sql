@@ -0,0 +1,97 @@
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS retry_count integer DEFAULT 0;
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS retry_after timestamptz DEFAULT NULL;
+
+CREATE TABLE IF NOT EXISTS job_attempts (
+  id bigserial PRIMARY KEY,
+  job_id bigint,
+  attempt integer,
+  worker text,
+  net_error_code int,
+  net_error_msg text,
+  build_started_at timestamptz,
+  build_finished_at timestamptz,
+  created_at timestamptz DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS job_attempts_job_id_index_v1 ON job_attempts(job_id);
+
+-- Records the failed run of a job as an attempt, and puts the job back in the
+-- pending queue. The job won't be handed to a worker until the delay is up.
+-- Only jobs which are still running are retried, so nothing is returned for a
+-- job which was canceled or had already finished.
+CREATE OR REPLACE FUNCTION retry_job_v1 (
+  p_job_id bigint,
+  p_build_started_at timestamptz,
+  p_build_finished_at timestamptz,
+  p_err_code int,
+  p_err_msg text,
+  p_delay_secs bigint
+) RETURNS SETOF jobs
+LANGUAGE SQL VOLATILE AS $$
+  WITH failed AS (
+    SELECT id, retry_count, worker FROM jobs
+    WHERE id = p_job_id
+    AND job_state IN ('Dispatched', 'Processing')
+    FOR UPDATE
+  ), attempt AS (
+    INSERT INTO job_attempts (job_id, attempt, worker, net_error_code, net_error_msg, build_started_at, build_finished_at)
+    SELECT id, retry_count + 1, worker, p_err_code, p_err_msg, p_build_started_at, p_build_finished_at
+    FROM failed
+  )
+  UPDATE jobs
+  SET job_state = 'Pending',
+      retry_count = jobs.retry_count + 1,
+      retry_after = now() + (p_delay_secs * interval '1 second'),
+      worker = NULL,
+      archived = false,
+      scheduler_sync = false,
+      sync_count = sync_count + 1,
+      updated_at = now(),
+      build_started_at = NULL,
+      build_finished_at = NULL,
+      package_ident = NULL,
+      net_error_code = NULL,
+      net_error_msg = NULL
+  FROM failed
+  WHERE jobs.id = failed.id
+  RETURNING jobs.*;
+$$;
+
+CREATE OR REPLACE FUNCTION get_job_attempts_v1 (p_job_id bigint) RETURNS SETOF job_attempts AS $$
+  SELECT * FROM job_attempts
+  WHERE job_id = p_job_id
+  ORDER BY attempt ASC
+$$ LANGUAGE SQL STABLE;
+
+CREATE OR REPLACE FUNCTION next_pending_job_v3 (p_worker text) RETURNS SETOF jobs AS
+$$
+DECLARE
+    r jobs % rowtype;
+BEGIN
+    FOR r IN
+        WITH dispatched AS (
+            SELECT split_part(project_name, '/', 1) AS origin, COUNT(*) AS job_count
+            FROM jobs
+            WHERE job_state IN ('Dispatched', 'Processing')
+            GROUP BY 1
+        )
+        SELECT j.* FROM jobs j
+        LEFT JOIN dispatched d ON d.origin = split_part(j.project_name, '/', 1)
+        LEFT JOIN origin_shares s ON s.origin = split_part(j.project_name, '/', 1)
+        WHERE j.job_state = 'Pending'
+        AND (j.retry_after IS NULL OR j.retry_after <= now())
+        AND (COALESCE(s.max_dispatched, 0) = 0 OR COALESCE(d.job_count, 0) < s.max_dispatched)
+        ORDER BY j.priority DESC,
+                 COALESCE(d.job_count, 0)::float / GREATEST(COALESCE(s.weight, 1), 1) ASC,
+                 j.created_at ASC
+        FOR UPDATE OF j SKIP LOCKED
+        LIMIT 1
+    LOOP
+        UPDATE jobs SET job_state='Dispatched', scheduler_sync=false, worker=p_worker, updated_at=now()
+        WHERE id=r.id
+        RETURNING * INTO r;
+        RETURN NEXT r;
+    END LOOP;
+  RETURN;
+END
+$$ LANGUAGE plpgsql VOLATILE;
@@ -0,0 +1,7 @@
+DROP FUNCTION IF EXISTS insert_job_v4(bigint, bigint, text, bigint, text, text, text[], text, integer, text);
+DROP FUNCTION IF EXISTS next_pending_job_v4(text, text);
+DROP FUNCTION IF EXISTS insert_group_v4(text, text[], text[], text[], integer);
+DROP FUNCTION IF EXISTS find_group_project_v2(bigint, text, text);
+DROP FUNCTION IF EXISTS set_group_project_name_state_v2(bigint, text, text, text);
+ALTER TABLE group_projects DROP COLUMN IF EXISTS target;
+ALTER TABLE jobs DROP COLUMN IF EXISTS target;
@@ -0,0 +1,83 @@
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS target text DEFAULT 'x86_64-linux';
+ALTER TABLE group_projects ADD COLUMN IF NOT EXISTS target text DEFAULT 'x86_64-linux';
+
+CREATE OR REPLACE FUNCTION insert_job_v4 (
+  p_owner_id bigint,
+  p_project_id bigint,
+  p_project_name text,
+  p_project_owner_id bigint,
+  p_project_plan_path text,
+  p_vcs text,
+  p_vcs_arguments text[],
+  p_channel text,
+  p_priority integer,
+  p_target text
+  ) RETURNS SETOF jobs AS $$
+      INSERT INTO jobs (owner_id, job_state, project_id, project_name, project_owner_id, project_plan_path, vcs, vcs_arguments, channel, priority, target)
+      VALUES (p_owner_id, 'Pending', p_project_id, p_project_name, p_project_owner_id, p_project_plan_path, p_vcs, p_vcs_arguments, p_channel, p_priority, p_target)
+      RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
+
+-- Same as next_pending_job_v3, but only hands out jobs that can be built on
+-- the worker's platform.
+CREATE OR REPLACE FUNCTION next_pending_job_v4 (p_worker text, p_target text) RETURNS SETOF jobs AS
+$$
+DECLARE
+    r jobs % rowtype;
+BEGIN
+    FOR r IN
+        WITH dispatched AS (
+            SELECT split_part(project_name, '/', 1) AS origin, COUNT(*) AS job_count
+            FROM jobs
+            WHERE job_state IN ('Dispatched', 'Processing')
+            GROUP BY 1
+        )
+        SELECT j.* FROM jobs j
+        LEFT JOIN dispatched d ON d.origin = split_part(j.project_name, '/', 1)
+        LEFT JOIN origin_shares s ON s.origin = split_part(j.project_name, '/', 1)
+        WHERE j.job_state = 'Pending'
+        AND j.target = p_target
+        AND (j.retry_after IS NULL OR j.retry_after <= now())
+        AND (COALESCE(s.max_dispatched, 0) = 0 OR COALESCE(d.job_count, 0) < s.max_dispatched)
+        ORDER BY j.priority DESC,
+                 COALESCE(d.job_count, 0)::float / GREATEST(COALESCE(s.weight, 1), 1) ASC,
+                 j.created_at ASC
+        FOR UPDATE OF j SKIP LOCKED
+        LIMIT 1
+    LOOP
+        UPDATE jobs SET job_state='Dispatched', scheduler_sync=false, worker=p_worker, updated_at=now()
+        WHERE id=r.id
+        RETURNING * INTO r;
+        RETURN NEXT r;
+    END LOOP;
+  RETURN;
+END
+$$ LANGUAGE plpgsql VOLATILE;
+
+CREATE OR REPLACE FUNCTION insert_group_v4 (
+  root_project text,
+  project_names text[],
+  project_idents text[],
+  project_targets text[],
+  p_priority integer
+) RETURNS SETOF groups
+  LANGUAGE SQL
+  VOLATILE AS $$
+  WITH my_group AS (
+          INSERT INTO groups (project_name, group_state, priority)
+          VALUES (root_project, 'Queued', p_priority) RETURNING *
+      ), my_project AS (
+          INSERT INTO group_projects (owner_id, project_name, project_ident, target, project_state)
+          SELECT g.id, project_info.name, project_info.ident, project_info.target, 'NotStarted'
+          FROM my_group AS g, unnest(project_names, project_idents, project_targets) AS project_info(name, ident, target)
+      )
+  SELECT * FROM my_group;
+$$;
+
+CREATE OR REPLACE FUNCTION find_group_project_v2 (gid bigint, name text, p_target text) RETURNS SETOF group_projects AS $$
+  SELECT * FROM group_projects WHERE owner_id = gid AND project_name = name AND target = p_target;
+$$ LANGUAGE SQL STABLE;
+
+CREATE OR REPLACE FUNCTION set_group_project_name_state_v2 (gid bigint, pname text, p_target text, state text) RETURNS void AS $$
+  UPDATE group_projects SET project_state=state, updated_at=now() WHERE owner_id=gid AND project_name=pname AND target=p_target;
+$$ LANGUAGE SQL VOLATILE;
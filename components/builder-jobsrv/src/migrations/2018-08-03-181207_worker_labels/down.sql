@@ -0,0 +1,3 @@
+DROP FUNCTION IF EXISTS insert_job_v5(bigint, bigint, text, bigint, text, text, text[], text, integer, text, text[]);
+DROP FUNCTION IF EXISTS next_pending_job_v5(text, text, text[]);
+ALTER TABLE jobs DROP COLUMN IF EXISTS required_labels;
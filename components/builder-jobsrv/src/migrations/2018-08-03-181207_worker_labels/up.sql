@@ -0,0 +1,56 @@
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS required_labels text[] NOT NULL DEFAULT '{}';
+
+CREATE OR REPLACE FUNCTION insert_job_v5 (
+  p_owner_id bigint,
+  p_project_id bigint,
+  p_project_name text,
+  p_project_owner_id bigint,
+  p_project_plan_path text,
+  p_vcs text,
+  p_vcs_arguments text[],
+  p_channel text,
+  p_priority integer,
+  p_target text,
+  p_required_labels text[]
+  ) RETURNS SETOF jobs AS $$
+      INSERT INTO jobs (owner_id, job_state, project_id, project_name, project_owner_id, project_plan_path, vcs, vcs_arguments, channel, priority, target, required_labels)
+      VALUES (p_owner_id, 'Pending', p_project_id, p_project_name, p_project_owner_id, p_project_plan_path, p_vcs, p_vcs_arguments, p_channel, p_priority, p_target, p_required_labels)
+      RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
+
+-- Same as next_pending_job_v4, but only hands out jobs whose required labels are all
+-- offered by the worker.
+CREATE OR REPLACE FUNCTION next_pending_job_v5 (p_worker text, p_target text, p_labels text[]) RETURNS SETOF jobs AS
+$$
+DECLARE
+    r jobs % rowtype;
+BEGIN
+    FOR r IN
+        WITH dispatched AS (
+            SELECT split_part(project_name, '/', 1) AS origin, COUNT(*) AS job_count
+            FROM jobs
+            WHERE job_state IN ('Dispatched', 'Processing')
+            GROUP BY 1
+        )
+        SELECT j.* FROM jobs j
+        LEFT JOIN dispatched d ON d.origin = split_part(j.project_name, '/', 1)
+        LEFT JOIN origin_shares s ON s.origin = split_part(j.project_name, '/', 1)
+        WHERE j.job_state = 'Pending'
+        AND j.target = p_target
+        AND j.required_labels <@ p_labels
+        AND (j.retry_after IS NULL OR j.retry_after <= now())
+        AND (COALESCE(s.max_dispatched, 0) = 0 OR COALESCE(d.job_count, 0) < s.max_dispatched)
+        ORDER BY j.priority DESC,
+                 COALESCE(d.job_count, 0)::float / GREATEST(COALESCE(s.weight, 1), 1) ASC,
+                 j.created_at ASC
+        FOR UPDATE OF j SKIP LOCKED
+        LIMIT 1
+    LOOP
+        UPDATE jobs SET job_state='Dispatched', scheduler_sync=false, worker=p_worker, updated_at=now()
+        WHERE id=r.id
+        RETURNING * INTO r;
+        RETURN NEXT r;
+    END LOOP;
+  RETURN;
+END
+$$ LANGUAGE plpgsql VOLATILE;
@@ -0,0 +1,3 @@
+DROP FUNCTION IF EXISTS get_job_sections_v1(bigint);
+DROP FUNCTION IF EXISTS upsert_job_section_v1(bigint, text, text, timestamptz, timestamptz);
+DROP TABLE IF EXISTS job_sections;
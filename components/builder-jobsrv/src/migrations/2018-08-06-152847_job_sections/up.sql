@@ -0,0 +1,39 @@
+CREATE TABLE IF NOT EXISTS job_sections (
+  id bigserial PRIMARY KEY,
+  job_id bigint NOT NULL,
+  name text NOT NULL,
+  status text NOT NULL,
+  started_at timestamptz NOT NULL,
+  finished_at timestamptz,
+  created_at timestamptz DEFAULT now(),
+  updated_at timestamptz DEFAULT now(),
+  UNIQUE (job_id, name)
+);
+
+-- A section is recorded when it starts and again when it ends. A retried job runs its sections
+-- again, in which case the latest run replaces the timing of the previous one.
+CREATE OR REPLACE FUNCTION upsert_job_section_v1 (
+  p_job_id bigint,
+  p_name text,
+  p_status text,
+  p_started_at timestamptz,
+  p_finished_at timestamptz
+) RETURNS VOID
+LANGUAGE SQL VOLATILE AS $$
+  INSERT INTO job_sections (job_id, name, status, started_at, finished_at)
+  VALUES (p_job_id, p_name, p_status, p_started_at, p_finished_at)
+  ON CONFLICT (job_id, name) DO UPDATE
+  SET status = excluded.status,
+      started_at = excluded.started_at,
+      finished_at = excluded.finished_at,
+      updated_at = now();
+$$;
+
+CREATE OR REPLACE FUNCTION get_job_sections_v1 (p_job_id bigint)
+RETURNS TABLE(name text, status text, started_at timestamptz, finished_at timestamptz, duration_ms bigint) AS $$
+  SELECT name, status, started_at, finished_at,
+         (EXTRACT(EPOCH FROM (finished_at - started_at)) * 1000)::bigint AS duration_ms
+  FROM job_sections
+  WHERE job_id = p_job_id
+  ORDER BY started_at ASC
+$$ LANGUAGE SQL STABLE;
@@ -0,0 +1,9 @@
+DROP FUNCTION IF EXISTS insert_job_v6(bigint, bigint, text, bigint, text, text, text[], text, integer, text, text[], text, bigint);
+DROP FUNCTION IF EXISTS update_job_v4(bigint, text, timestamptz, timestamptz, text, int, text, text);
+DROP FUNCTION IF EXISTS insert_group_v5(text, text[], text[], text[], integer, text, bigint, text);
+ALTER TABLE groups DROP COLUMN IF EXISTS channel;
+ALTER TABLE groups DROP COLUMN IF EXISTS pull_request;
+ALTER TABLE groups DROP COLUMN IF EXISTS git_ref;
+ALTER TABLE jobs DROP COLUMN IF EXISTS pull_request;
+ALTER TABLE jobs DROP COLUMN IF EXISTS git_sha;
+ALTER TABLE jobs DROP COLUMN IF EXISTS git_ref;
@@ -0,0 +1,75 @@
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS git_ref text DEFAULT NULL;
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS git_sha text DEFAULT NULL;
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS pull_request bigint NOT NULL DEFAULT 0;
+ALTER TABLE groups ADD COLUMN IF NOT EXISTS git_ref text DEFAULT NULL;
+ALTER TABLE groups ADD COLUMN IF NOT EXISTS pull_request bigint NOT NULL DEFAULT 0;
+ALTER TABLE groups ADD COLUMN IF NOT EXISTS channel text DEFAULT NULL;
+
+CREATE OR REPLACE FUNCTION insert_job_v6 (
+  p_owner_id bigint,
+  p_project_id bigint,
+  p_project_name text,
+  p_project_owner_id bigint,
+  p_project_plan_path text,
+  p_vcs text,
+  p_vcs_arguments text[],
+  p_channel text,
+  p_priority integer,
+  p_target text,
+  p_required_labels text[],
+  p_git_ref text,
+  p_pull_request bigint
+  ) RETURNS SETOF jobs AS $$
+      INSERT INTO jobs (owner_id, job_state, project_id, project_name, project_owner_id, project_plan_path, vcs, vcs_arguments, channel, priority, target, required_labels, git_ref, pull_request)
+      VALUES (p_owner_id, 'Pending', p_project_id, p_project_name, p_project_owner_id, p_project_plan_path, p_vcs, p_vcs_arguments, p_channel, p_priority, p_target, p_required_labels, p_git_ref, p_pull_request)
+      RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
+
+-- Same as update_job_v3, but also records the commit the worker built
+CREATE OR REPLACE FUNCTION update_job_v4(
+  p_job_id bigint,
+  p_state text,
+  p_build_started_at timestamptz,
+  p_build_finished_at timestamptz,
+  p_package_ident text,
+  p_err_code int,
+  p_err_msg text,
+  p_git_sha text)
+RETURNS VOID
+LANGUAGE SQL VOLATILE AS $$
+  UPDATE jobs
+  SET job_state = p_state,
+      scheduler_sync = false,
+      sync_count = sync_count + 1,
+      updated_at = now(),
+      build_started_at = p_build_started_at,
+      build_finished_at = p_build_finished_at,
+      package_ident = p_package_ident,
+      net_error_code = p_err_code,
+      net_error_msg = p_err_msg,
+      git_sha = COALESCE(p_git_sha, git_sha)
+  WHERE id = p_job_id;
+$$;
+
+CREATE OR REPLACE FUNCTION insert_group_v5 (
+  root_project text,
+  project_names text[],
+  project_idents text[],
+  project_targets text[],
+  p_priority integer,
+  p_git_ref text,
+  p_pull_request bigint,
+  p_channel text
+) RETURNS SETOF groups
+  LANGUAGE SQL
+  VOLATILE AS $$
+  WITH my_group AS (
+          INSERT INTO groups (project_name, group_state, priority, git_ref, pull_request, channel)
+          VALUES (root_project, 'Queued', p_priority, p_git_ref, p_pull_request, p_channel) RETURNING *
+      ), my_project AS (
+          INSERT INTO group_projects (owner_id, project_name, project_ident, target, project_state)
+          SELECT g.id, project_info.name, project_info.ident, project_info.target, 'NotStarted'
+          FROM my_group AS g, unnest(project_names, project_idents, project_targets) AS project_info(name, ident, target)
+      )
+  SELECT * FROM my_group;
+$$;
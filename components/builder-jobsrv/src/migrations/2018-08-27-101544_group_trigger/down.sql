@@ -0,0 +1,2 @@
+DROP FUNCTION IF EXISTS insert_group_v6(text, text[], text[], text[], integer, text, bigint, text, smallint);
+ALTER TABLE groups DROP COLUMN IF EXISTS trigger;
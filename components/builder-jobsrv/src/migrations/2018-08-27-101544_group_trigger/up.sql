@@ -0,0 +1,25 @@
+ALTER TABLE groups ADD COLUMN IF NOT EXISTS trigger smallint NOT NULL DEFAULT 0;
+
+CREATE OR REPLACE FUNCTION insert_group_v6 (
+  root_project text,
+  project_names text[],
+  project_idents text[],
+  project_targets text[],
+  p_priority integer,
+  p_git_ref text,
+  p_pull_request bigint,
+  p_channel text,
+  p_trigger smallint
+) RETURNS SETOF groups
+  LANGUAGE SQL
+  VOLATILE AS $$
+  WITH my_group AS (
+          INSERT INTO groups (project_name, group_state, priority, git_ref, pull_request, channel, trigger)
+          VALUES (root_project, 'Queued', p_priority, p_git_ref, p_pull_request, p_channel, p_trigger) RETURNING *
+      ), my_project AS (
+          INSERT INTO group_projects (owner_id, project_name, project_ident, target, project_state)
+          SELECT g.id, project_info.name, project_info.ident, project_info.target, 'NotStarted'
+          FROM my_group AS g, unnest(project_names, project_idents, project_targets) AS project_info(name, ident, target)
+      )
+  SELECT * FROM my_group;
+$$;
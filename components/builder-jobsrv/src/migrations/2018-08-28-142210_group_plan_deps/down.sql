@@ -0,0 +1,2 @@
+DROP FUNCTION IF EXISTS insert_group_v7(text, text[], text[], text[], integer, text, bigint, text, smallint, text[]);
+ALTER TABLE group_projects DROP COLUMN IF EXISTS plan_deps;
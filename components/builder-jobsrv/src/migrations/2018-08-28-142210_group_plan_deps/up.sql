@@ -0,0 +1,28 @@
+ALTER TABLE group_projects ADD COLUMN IF NOT EXISTS plan_deps text[] DEFAULT NULL;
+
+-- Each element of project_plan_deps is the comma separated deps read from the project's plan,
+-- or NULL when the project is ordered by the graph
+CREATE OR REPLACE FUNCTION insert_group_v7 (
+  root_project text,
+  project_names text[],
+  project_idents text[],
+  project_targets text[],
+  p_priority integer,
+  p_git_ref text,
+  p_pull_request bigint,
+  p_channel text,
+  p_trigger smallint,
+  project_plan_deps text[]
+) RETURNS SETOF groups
+  LANGUAGE SQL
+  VOLATILE AS $$
+  WITH my_group AS (
+          INSERT INTO groups (project_name, group_state, priority, git_ref, pull_request, channel, trigger)
+          VALUES (root_project, 'Queued', p_priority, p_git_ref, p_pull_request, p_channel, p_trigger) RETURNING *
+      ), my_project AS (
+          INSERT INTO group_projects (owner_id, project_name, project_ident, target, project_state, plan_deps)
+          SELECT g.id, project_info.name, project_info.ident, project_info.target, 'NotStarted', string_to_array(project_info.deps, ',')
+          FROM my_group AS g, unnest(project_names, project_idents, project_targets, project_plan_deps) AS project_info(name, ident, target, deps)
+      )
+  SELECT * FROM my_group;
+$$;
@@ -0,0 +1,5 @@
+DROP FUNCTION IF EXISTS set_group_state_from_v1(bigint, text, text);
+DROP FUNCTION IF EXISTS pause_group_v1(bigint);
+DROP FUNCTION IF EXISTS resume_group_v1(bigint);
+DROP FUNCTION IF EXISTS get_resuming_groups_v1();
+DROP FUNCTION IF EXISTS check_active_group_v2(text);
@@ -0,0 +1,31 @@
+CREATE OR REPLACE FUNCTION pause_group_v1 (in_gid bigint) RETURNS SETOF groups AS $$
+  UPDATE groups SET group_state='Paused', updated_at=now()
+    WHERE id = in_gid
+    AND group_state IN ('Pending', 'Dispatching')
+    RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION resume_group_v1 (in_gid bigint) RETURNS SETOF groups AS $$
+  UPDATE groups SET group_state='Resuming', updated_at=now()
+    WHERE id = in_gid
+    AND group_state = 'Paused'
+    RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION get_resuming_groups_v1 () RETURNS SETOF groups AS $$
+  SELECT * FROM groups
+  WHERE group_state = 'Resuming'
+$$ LANGUAGE SQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION check_active_group_v2 (pname text) RETURNS SETOF groups AS $$
+  SELECT * FROM groups
+  WHERE project_name = pname
+  AND group_state IN ('Pending', 'Dispatching', 'Paused', 'Resuming')
+$$ LANGUAGE SQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION set_group_state_from_v1 (gid bigint, from_gstate text, gstate text) RETURNS SETOF groups AS $$
+  UPDATE groups SET group_state=gstate, updated_at=now()
+    WHERE id = gid
+    AND group_state = from_gstate
+    RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
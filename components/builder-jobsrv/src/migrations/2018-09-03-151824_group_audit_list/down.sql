@@ -0,0 +1,2 @@
+DROP FUNCTION IF EXISTS get_job_group_audit_v1(text, text, smallint, timestamptz, timestamptz);
+DROP INDEX IF EXISTS audit_group_id_index_v1;
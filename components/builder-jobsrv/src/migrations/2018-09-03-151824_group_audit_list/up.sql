@@ -0,0 +1,18 @@
+CREATE INDEX IF NOT EXISTS audit_group_id_index_v1 ON audit(group_id);
+
+CREATE OR REPLACE FUNCTION get_job_group_audit_v1 (
+  p_origin text,
+  p_requester_name text,
+  p_operation smallint,
+  p_from timestamptz,
+  p_to timestamptz
+) RETURNS TABLE(group_id bigint, project_name text, operation smallint, trigger smallint, requester_id bigint, requester_name text, created_at timestamptz) AS $$
+  SELECT a.group_id, g.project_name, a.operation, a.trigger, a.requester_id, a.requester_name, a.created_at
+  FROM audit a
+  INNER JOIN groups g ON g.id = a.group_id
+  WHERE split_part(g.project_name, '/', 1) = p_origin
+  AND (p_requester_name = '' OR a.requester_name = p_requester_name)
+  AND (p_operation IS NULL OR a.operation = p_operation)
+  AND a.created_at >= COALESCE(p_from, '-infinity')
+  AND a.created_at <= COALESCE(p_to, 'infinity')
+$$ LANGUAGE SQL STABLE;
@@ -0,0 +1 @@
+DROP FUNCTION IF EXISTS get_active_groups_for_origin_v1(text);
@@ -0,0 +1,5 @@
+CREATE OR REPLACE FUNCTION get_active_groups_for_origin_v1 (op_origin text) RETURNS SETOF groups AS $$
+  SELECT * FROM groups
+  WHERE project_name LIKE (op_origin || '/%')
+  AND group_state IN ('Queued', 'Pending', 'Dispatching', 'Paused', 'Resuming')
+$$ LANGUAGE SQL STABLE;
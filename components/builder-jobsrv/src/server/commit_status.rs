@@ -0,0 +1,265 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reports the state of jobs built from GitHub webhooks back to GitHub as commit statuses, so
+//! that developers can see whether their change built without leaving GitHub.
+//!
+//! Statuses are posted from a thread of their own, as talking to GitHub is too slow to be done
+//! from the worker manager's loop.
+
+use std::sync::mpsc;
+use std::thread;
+
+use bldr_core::job::Job;
+use github_api_client::types::{CommitState, CommitStatus};
+use github_api_client::GitHubClient;
+use protocol::{jobsrv, originsrv};
+
+use config::{CommitStatusCfg, Config};
+use data_store::DataStore;
+
+#[derive(Clone)]
+pub struct CommitStatusClient {
+    tx: Option<mpsc::Sender<Job>>,
+}
+
+impl CommitStatusClient {
+    /// Queues a status update for the job's commit. Nothing is reported when commit statuses
+    /// are disabled.
+    pub fn report(&self, job: &Job) {
+        if let Some(ref tx) = self.tx {
+            if let Err(err) = tx.send(job.clone()) {
+                warn!("Unable to queue commit status for job {}, {}", job.get_id(), err);
+            }
+        }
+    }
+}
+
+pub struct CommitStatusReporter {
+    config: CommitStatusCfg,
+    github: GitHubClient,
+    datastore: DataStore,
+}
+
+impl CommitStatusReporter {
+    pub fn start(cfg: &Config, datastore: DataStore) -> CommitStatusClient {
+        if !cfg.commit_status.enabled {
+            return CommitStatusClient { tx: None };
+        }
+
+        let reporter = CommitStatusReporter {
+            config: cfg.commit_status.clone(),
+            github: GitHubClient::new(cfg.github.clone()),
+            datastore: datastore,
+        };
+        let (tx, rx) = mpsc::channel();
+        thread::Builder::new()
+            .name("commit-status".to_string())
+            .spawn(move || reporter.run(rx))
+            .unwrap();
+
+        CommitStatusClient { tx: Some(tx) }
+    }
+
+    fn run(&self, rx: mpsc::Receiver<Job>) {
+        for job in rx.iter() {
+            self.report(&job);
+        }
+    }
+
+    fn report(&self, job: &Job) {
+        let project = job.get_project();
+        if !is_github_project(project) {
+            return;
+        }
+        let (state, description) = match status_of(job.get_state()) {
+            Some(status) => status,
+            None => return,
+        };
+        let sha = match commit_of(job) {
+            Some(sha) => sha,
+            None => {
+                debug!("Not reporting status of job {}, commit unknown", job.get_id());
+                return;
+            }
+        };
+        let repo = match repo_full_name(project.get_vcs_data()) {
+            Some(repo) => repo,
+            None => {
+                warn!("Unable to find GitHub repository of {}", project.get_vcs_data());
+                return;
+            }
+        };
+        if !self.from_webhook(job) {
+            return;
+        }
+
+        let status = CommitStatus {
+            state: state,
+            target_url: Some(format!(
+                "{}/#/pkgs/{}/builds/{}",
+                self.config.web_url.trim_right_matches('/'),
+                project.get_name(),
+                job.get_id()
+            )),
+            description: Some(description.to_string()),
+            context: format!("{}/{}", self.config.context, project.get_name()),
+        };
+
+        let token = match self.github
+            .app_installation_token(project.get_vcs_installation_id())
+        {
+            Ok(token) => token,
+            Err(err) => {
+                warn!("Unable to generate GitHub app token, {}", err);
+                return;
+            }
+        };
+        match self.github.create_status(&token, &repo, sha, &status) {
+            Ok(()) => debug!("Reported {:?} for job {} on {}", state, job.get_id(), sha),
+            Err(err) => warn!("Unable to report status of job {}, {}", job.get_id(), err),
+        }
+    }
+
+    // Only jobs of groups triggered by a push or pull request are reported
+    fn from_webhook(&self, job: &Job) -> bool {
+        let mut msg = jobsrv::JobGroupGet::new();
+        msg.set_group_id(job.get_owner_id());
+        msg.set_include_projects(false);
+        match self.datastore.get_job_group(&msg) {
+            Ok(Some(group)) => group.get_trigger() == jobsrv::JobGroupTrigger::Webhook,
+            Ok(None) => false,
+            Err(err) => {
+                warn!("Unable to retrieve group {}, {:?}", job.get_owner_id(), err);
+                false
+            }
+        }
+    }
+}
+
+// GitHub projects are git projects connected through the Builder GitHub app
+fn is_github_project(project: &originsrv::OriginProject) -> bool {
+    project.get_vcs_type() == "git" && project.get_vcs_installation_id() != 0
+}
+
+fn status_of(state: jobsrv::JobState) -> Option<(CommitState, &'static str)> {
+    match state {
+        jobsrv::JobState::Dispatched => Some((CommitState::Pending, "Build started")),
+        jobsrv::JobState::Complete => Some((CommitState::Success, "Build succeeded")),
+        jobsrv::JobState::Failed => Some((CommitState::Failure, "Build failed")),
+        jobsrv::JobState::CancelComplete => Some((CommitState::Error, "Build canceled")),
+        _ => None,
+    }
+}
+
+// The commit the worker built, or the commit the job was pinned to before it was built
+fn commit_of(job: &Job) -> Option<&str> {
+    if job.has_git_sha() {
+        return Some(job.get_git_sha());
+    }
+    let git_ref = job.get_git_ref();
+    if git_ref.len() == 40 && git_ref.chars().all(|c| c.is_digit(16)) {
+        Some(git_ref)
+    } else {
+        None
+    }
+}
+
+// Full name, e.g. `habitat-sh/core-plans`, of a repository from its clone URL
+fn repo_full_name(clone_url: &str) -> Option<String> {
+    let path = clone_url
+        .trim_left_matches("https://")
+        .splitn(2, '/')
+        .nth(1)?
+        .trim_right_matches('/')
+        .trim_right_matches(".git");
+    if path.split('/').count() == 2 && !path.starts_with('/') && !path.ends_with('/') {
+        Some(path.to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repo_full_name_from_clone_url() {
+        assert_eq!(
+            repo_full_name("https://github.com/habitat-sh/core-plans.git"),
+            Some("habitat-sh/core-plans".to_string())
+        );
+        assert_eq!(
+            repo_full_name("https://github.com/habitat-sh/core-plans"),
+            Some("habitat-sh/core-plans".to_string())
+        );
+        assert_eq!(repo_full_name("https://github.com/habitat-sh"), None);
+    }
+
+    #[test]
+    fn only_github_projects_are_reported() {
+        let mut project = originsrv::OriginProject::new();
+        project.set_vcs_type("git".to_string());
+        project.set_vcs_installation_id(1234);
+        assert!(is_github_project(&project));
+
+        project.set_vcs_installation_id(0);
+        assert!(!is_github_project(&project));
+
+        project.set_vcs_type("gitlab".to_string());
+        project.set_vcs_installation_id(1234);
+        assert!(!is_github_project(&project));
+    }
+
+    #[test]
+    fn only_started_and_finished_jobs_are_reported() {
+        assert_eq!(status_of(jobsrv::JobState::Pending), None);
+        assert_eq!(
+            status_of(jobsrv::JobState::Dispatched).unwrap().0,
+            CommitState::Pending
+        );
+        assert_eq!(
+            status_of(jobsrv::JobState::Complete).unwrap().0,
+            CommitState::Success
+        );
+        assert_eq!(
+            status_of(jobsrv::JobState::Failed).unwrap().0,
+            CommitState::Failure
+        );
+        assert_eq!(
+            status_of(jobsrv::JobState::CancelComplete).unwrap().0,
+            CommitState::Error
+        );
+    }
+
+    #[test]
+    fn commit_of_job() {
+        let mut job = Job::new(jobsrv::Job::new());
+        job.set_git_ref("master".to_string());
+        assert_eq!(commit_of(&job), None);
+
+        job.set_git_ref("0123456789abcdef0123456789abcdef01234567".to_string());
+        assert_eq!(
+            commit_of(&job),
+            Some("0123456789abcdef0123456789abcdef01234567")
+        );
+
+        job.set_git_sha("fedcba9876543210fedcba9876543210fedcba98".to_string());
+        assert_eq!(
+            commit_of(&job),
+            Some("fedcba9876543210fedcba9876543210fedcba98")
+        );
+    }
+}
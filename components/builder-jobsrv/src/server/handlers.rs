@@ -108,7 +108,7 @@ pub fn job_log_get(req: &mut Message, conn: &mut RouteConn, state: &mut ServerSt
                 let num_lines = lines.len() as u64;
                 let segment;
 
-                if start > num_lines - 1 {
+                if start >= num_lines {
                     segment = vec![];
                 } else {
                     segment = lines[start as usize..].to_vec();
@@ -295,32 +295,166 @@ pub fn job_group_cancel(
     Ok(())
 }
 
+pub fn job_group_pause(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> Result<()> {
+    let msg = req.parse::<jobsrv::JobGroupPause>()?;
+    debug!("job_group_pause message: {:?}", msg);
+
+    match state.datastore.pause_job_group(msg.get_group_id()) {
+        Ok(true) => (),
+        Ok(false) => {
+            // Either the group doesn't exist, or it can't be paused from its current state
+            let mut jgg = jobsrv::JobGroupGet::new();
+            jgg.set_group_id(msg.get_group_id());
+            jgg.set_include_projects(false);
+            let err = match state.datastore.get_job_group(&jgg) {
+                Ok(Some(_)) => NetError::new(ErrCode::ENTITY_CONFLICT, "jb:job-group-pause:1"),
+                _ => NetError::new(ErrCode::ENTITY_NOT_FOUND, "jb:job-group-pause:2"),
+            };
+            conn.route_reply(req, &*err)?;
+            return Ok(());
+        }
+        Err(err) => {
+            warn!(
+                "Unable to pause job group {}, err: {:?}",
+                msg.get_group_id(),
+                err
+            );
+            let err = NetError::new(ErrCode::DATA_STORE, "jb:job-group-pause:3");
+            conn.route_reply(req, &*err)?;
+            return Ok(());
+        }
+    };
+
+    // Add audit entry
+    let mut jga = jobsrv::JobGroupAudit::new();
+    jga.set_group_id(msg.get_group_id());
+    jga.set_operation(jobsrv::JobGroupOperation::JobGroupOpPause);
+    jga.set_trigger(msg.get_trigger());
+    jga.set_requester_id(msg.get_requester_id());
+    jga.set_requester_name(msg.get_requester_name().to_string());
+
+    match state.datastore.create_audit_entry(&jga) {
+        Ok(_) => (),
+        Err(err) => {
+            warn!("Failed to create audit entry, err={:?}", err);
+        }
+    };
+
+    conn.route_reply(req, &net::NetOk::new())?;
+    Ok(())
+}
+
+pub fn job_group_resume(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> Result<()> {
+    let msg = req.parse::<jobsrv::JobGroupResume>()?;
+    debug!("job_group_resume message: {:?}", msg);
+
+    match state.datastore.resume_job_group(msg.get_group_id()) {
+        Ok(true) => (),
+        Ok(false) => {
+            // Either the group doesn't exist, or it can't be resumed from its current state
+            let mut jgg = jobsrv::JobGroupGet::new();
+            jgg.set_group_id(msg.get_group_id());
+            jgg.set_include_projects(false);
+            let err = match state.datastore.get_job_group(&jgg) {
+                Ok(Some(_)) => NetError::new(ErrCode::ENTITY_CONFLICT, "jb:job-group-resume:1"),
+                _ => NetError::new(ErrCode::ENTITY_NOT_FOUND, "jb:job-group-resume:2"),
+            };
+            conn.route_reply(req, &*err)?;
+            return Ok(());
+        }
+        Err(err) => {
+            warn!(
+                "Unable to resume job group {}, err: {:?}",
+                msg.get_group_id(),
+                err
+            );
+            let err = NetError::new(ErrCode::DATA_STORE, "jb:job-group-resume:3");
+            conn.route_reply(req, &*err)?;
+            return Ok(());
+        }
+    };
+
+    // Add audit entry
+    let mut jga = jobsrv::JobGroupAudit::new();
+    jga.set_group_id(msg.get_group_id());
+    jga.set_operation(jobsrv::JobGroupOperation::JobGroupOpResume);
+    jga.set_trigger(msg.get_trigger());
+    jga.set_requester_id(msg.get_requester_id());
+    jga.set_requester_name(msg.get_requester_name().to_string());
+
+    match state.datastore.create_audit_entry(&jga) {
+        Ok(_) => (),
+        Err(err) => {
+            warn!("Failed to create audit entry, err={:?}", err);
+        }
+    };
+
+    state.schedule_cli.notify()?;
+    conn.route_reply(req, &net::NetOk::new())?;
+    Ok(())
+}
+
 fn is_project_buildable(conn: &mut RouteConn, project_name: &str) -> bool {
+    project_skip_reason(conn, project_name).is_none()
+}
+
+// Why the project can't be built automatically, if it can't
+fn project_skip_reason(
+    conn: &mut RouteConn,
+    project_name: &str,
+) -> Option<jobsrv::JobGroupSkipReason> {
     let mut project_get = originsrv::OriginProjectGet::new();
     project_get.set_name(String::from(project_name));
 
     match conn.route::<originsrv::OriginProjectGet, originsrv::OriginProject>(&project_get) {
-        Ok(project) => project.get_auto_build(),
+        Ok(project) => if project.get_auto_build() {
+            None
+        } else {
+            Some(jobsrv::JobGroupSkipReason::AutoBuildDisabled)
+        },
         Err(err) => {
-            if err.get_code() == ErrCode::ENTITY_NOT_FOUND {
-                false
-            } else {
+            if err.get_code() != ErrCode::ENTITY_NOT_FOUND {
                 warn!(
                     "Unable to retrieve project: {:?}, error: {:?}",
                     project_name, err
                 );
-                false
             }
+            Some(jobsrv::JobGroupSkipReason::NoProject)
         }
     }
 }
 
+fn skipped_project(
+    name: &str,
+    ident: &str,
+    target: &str,
+    reason: jobsrv::JobGroupSkipReason,
+) -> jobsrv::JobGroupProject {
+    let mut project = jobsrv::JobGroupProject::new();
+    project.set_name(name.to_string());
+    project.set_ident(ident.to_string());
+    project.set_target(target.to_string());
+    project.set_state(jobsrv::JobGroupProjectState::Skipped);
+    project.set_skip_reason(reason);
+    project
+}
+
 fn populate_build_projects(
     msg: &jobsrv::JobGroupSpec,
+    target: &str,
     conn: &mut RouteConn,
     state: &mut ServerState,
     rdeps: &Vec<(String, String)>,
-    projects: &mut Vec<(String, String)>,
+    projects: &mut Vec<(String, String, String)>,
+    skipped: &mut Vec<jobsrv::JobGroupProject>,
 ) {
     let mut excluded = HashSet::new();
     let mut start_time;
@@ -329,22 +463,29 @@ fn populate_build_projects(
     for s in rdeps {
         // Skip immediately if black-listed
         if excluded.contains(&s.0) {
+            skipped.push(skipped_project(
+                &s.0,
+                &s.1,
+                target,
+                jobsrv::JobGroupSkipReason::DependencySkipped,
+            ));
             continue;
         };
 
         // If the project is not linked to Builder, or is not auto-buildable
         // then we will skip it, as well as any later projects that depend on it
         // TODO (SA): Move the project list creation/vetting to background thread
-        if !is_project_buildable(conn, &s.0) {
+        if let Some(reason) = project_skip_reason(conn, &s.0) {
             debug!(
                 "Project is not linked to Builder or not auto-buildable - not adding: {}",
                 &s.0
             );
+            skipped.push(skipped_project(&s.0, &s.1, target, reason));
             excluded.insert(s.0.clone());
 
             let rdeps_opt = {
                 let target_graph = state.graph.read().unwrap();
-                let graph = target_graph.graph(msg.get_target()).unwrap(); // Unwrap OK
+                let graph = target_graph.graph(target).unwrap(); // Unwrap OK
                 start_time = PreciseTime::now();
                 let ret = graph.rdeps(&s.0);
                 end_time = PreciseTime::now();
@@ -375,14 +516,101 @@ fn populate_build_projects(
 
         // If the origin_only flag is true, make sure the origin matches
         if !msg.get_origin_only() || origin == msg.get_origin() {
-            debug!("Adding to projects: {} ({})", s.0, s.1);
-            projects.push(s.clone());
+            debug!("Adding to projects: {} ({}, {})", s.0, s.1, target);
+            projects.push((s.0.clone(), s.1.clone(), target.to_string()));
         } else {
             debug!("Skipping non-origin project: {} ({})", s.0, s.1);
+            skipped.push(skipped_project(
+                &s.0,
+                &s.1,
+                target,
+                jobsrv::JobGroupSkipReason::OtherOrigin,
+            ));
+        }
+    }
+}
+
+fn populate_plan_projects(
+    msg: &jobsrv::JobGroupSpec,
+    plan_name: &str,
+    targets: &[String],
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+    projects: &mut Vec<(String, String, String)>,
+    skipped: &mut Vec<jobsrv::JobGroupProject>,
+) {
+    if let Some(reason) = project_skip_reason(conn, plan_name) {
+        match msg.get_trigger() {
+            jobsrv::JobGroupTrigger::HabClient | jobsrv::JobGroupTrigger::BuilderUI => (),
+            _ => {
+                debug!(
+                    "Project is not linked to Builder or not auto-buildable - not adding: {}",
+                    plan_name
+                );
+                for target in targets {
+                    skipped.push(skipped_project(plan_name, "", target, reason));
+                }
+                return;
+            }
+        }
+    }
+
+    for target in targets {
+        let rdeps_opt = {
+            let target_graph = state.graph.read().unwrap();
+            let graph = match target_graph.graph(target) {
+                Some(g) => g,
+                None => continue,
+            };
+            // A plan that has never been uploaded isn't in the graph, it's built all the same
+            let ident = graph.resolve(plan_name).unwrap_or(String::from(""));
+            projects.push((plan_name.to_string(), ident, target.clone()));
+
+            if msg.get_package_only() {
+                None
+            } else {
+                graph.rdeps(plan_name)
+            }
+        };
+
+        if let Some(rdeps) = rdeps_opt {
+            debug!("Graph rdeps of {} ({}): {} items", plan_name, target, rdeps.len());
+            populate_build_projects(msg, target, conn, state, &rdeps, projects, skipped);
         }
     }
 }
 
+// The group a spec would create, without creating it. Projects are listed in build order,
+// followed by the projects that would be skipped.
+fn dry_run_group(
+    project_name: &str,
+    projects: Vec<(String, String, String)>,
+    skipped: Vec<jobsrv::JobGroupProject>,
+) -> jobsrv::JobGroup {
+    let mut group = jobsrv::JobGroup::new();
+    group.set_id(0);
+    group.set_project_name(project_name.to_string());
+    group.set_job_count(projects.len() as u64);
+    if projects.is_empty() {
+        group.set_state(jobsrv::JobGroupState::GroupComplete);
+    } else {
+        group.set_state(jobsrv::JobGroupState::GroupPending);
+    }
+
+    let mut group_projects = RepeatedField::new();
+    for (name, ident, target) in projects {
+        let mut project = jobsrv::JobGroupProject::new();
+        project.set_name(name);
+        project.set_ident(ident);
+        project.set_target(target);
+        project.set_state(jobsrv::JobGroupProjectState::NotStarted);
+        group_projects.push(project);
+    }
+    group_projects.extend(skipped);
+    group.set_projects(group_projects);
+    group
+}
+
 pub fn job_group_create(
     req: &mut Message,
     conn: &mut RouteConn,
@@ -393,45 +621,72 @@ pub fn job_group_create(
 
     let project_name = format!("{}/{}", msg.get_origin(), msg.get_package());
     let mut projects = Vec::new();
+    let mut skipped = Vec::new();
+
+    // A group may build for several targets at once; older clients only set `target`
+    let targets: Vec<String> = if msg.get_targets().is_empty() {
+        vec![msg.get_target().to_string()]
+    } else {
+        msg.get_targets().to_vec()
+    };
 
-    // Get the ident for the root package
+    // Get the ident for the root package on each target
     let mut start_time;
     let mut end_time;
+    let mut project_idents = Vec::new();
 
-    let project_ident = {
-        let mut target_graph = state.graph.write().unwrap();
-        let graph = match target_graph.graph_mut(msg.get_target()) {
-            Some(g) => g,
-            None => {
-                warn!(
-                    "JobGroupSpec, no graph found for target {}",
-                    msg.get_target()
-                );
-                let err = NetError::new(ErrCode::ENTITY_NOT_FOUND, "jb:job-group-create:2");
-                conn.route_reply(req, &*err)?;
-                return Ok(());
-            }
-        };
+    for target in targets.iter() {
+        let project_ident = {
+            let mut target_graph = state.graph.write().unwrap();
+            let graph = match target_graph.graph_mut(target) {
+                Some(g) => g,
+                None => {
+                    warn!("JobGroupSpec, no graph found for target {}", target);
+                    let err = NetError::new(ErrCode::ENTITY_NOT_FOUND, "jb:job-group-create:2");
+                    conn.route_reply(req, &*err)?;
+                    return Ok(());
+                }
+            };
 
-        start_time = PreciseTime::now();
-        let ret = match graph.resolve(&project_name) {
-            Some(s) => s,
-            None => {
-                warn!("JobGroupSpec, project ident not found for {}", project_name);
-                // If a package has never been uploaded, we won't see it in the graph
-                // Carry on with stiff upper lip
-                String::from("")
-            }
+            start_time = PreciseTime::now();
+            let ret = match graph.resolve(&project_name) {
+                Some(s) => s,
+                None => {
+                    warn!(
+                        "JobGroupSpec, project ident not found for {} ({})",
+                        project_name, target
+                    );
+                    // If a package has never been uploaded, we won't see it in the graph
+                    // Carry on with stiff upper lip
+                    String::from("")
+                }
+            };
+            end_time = PreciseTime::now();
+            ret
         };
-        end_time = PreciseTime::now();
-        ret
-    };
-    debug!("Resolved project name: {} sec\n", start_time.to(end_time));
+        debug!(
+            "Resolved project name ({}): {} sec\n",
+            target,
+            start_time.to(end_time)
+        );
+        project_idents.push((target.clone(), project_ident));
+    }
 
     // Bail if auto-build is false, and the project has not been manually kicked off
-    if !is_project_buildable(conn, &project_name) {
+    if let Some(reason) = project_skip_reason(conn, &project_name) {
         match msg.get_trigger() {
             jobsrv::JobGroupTrigger::HabClient | jobsrv::JobGroupTrigger::BuilderUI => (),
+            _ if msg.get_dry_run() => {
+                let skipped = project_idents
+                    .iter()
+                    .map(|&(ref target, ref ident)| {
+                        skipped_project(&project_name, ident, target, reason)
+                    })
+                    .collect();
+                let group = dry_run_group(&project_name, Vec::new(), skipped);
+                conn.route_reply(req, &group)?;
+                return Ok(());
+            }
             _ => {
                 let err = NetError::new(ErrCode::ENTITY_NOT_FOUND, "jb:job-group-create:3");
                 conn.route_reply(req, &*err)?;
@@ -440,39 +695,71 @@ pub fn job_group_create(
         }
     }
 
-    // Add the root package if needed
-    if !msg.get_deps_only() || msg.get_package_only() {
-        projects.push((project_name.clone(), project_ident.clone()));
-    }
+    for (target, project_ident) in project_idents {
+        // Add the root package if needed
+        if !msg.get_deps_only() || msg.get_package_only() {
+            projects.push((project_name.clone(), project_ident, target.clone()));
+        }
 
-    // Search the packages graph to find the reverse dependencies
-    if !msg.get_package_only() {
-        let rdeps_opt = {
-            let target_graph = state.graph.read().unwrap();
-            let graph = target_graph.graph(msg.get_target()).unwrap(); // Unwrap OK
-            start_time = PreciseTime::now();
-            let ret = graph.rdeps(&project_name);
-            end_time = PreciseTime::now();
-            ret
-        };
+        // Search the packages graph to find the reverse dependencies
+        if !msg.get_package_only() {
+            let rdeps_opt = {
+                let target_graph = state.graph.read().unwrap();
+                let graph = target_graph.graph(&target).unwrap(); // Unwrap OK
+                start_time = PreciseTime::now();
+                let ret = graph.rdeps(&project_name);
+                end_time = PreciseTime::now();
+                ret
+            };
 
-        match rdeps_opt {
-            Some(rdeps) => {
-                debug!(
-                    "Graph rdeps: {} items ({} sec)\n",
-                    rdeps.len(),
-                    start_time.to(end_time)
-                );
+            match rdeps_opt {
+                Some(rdeps) => {
+                    debug!(
+                        "Graph rdeps ({}): {} items ({} sec)\n",
+                        target,
+                        rdeps.len(),
+                        start_time.to(end_time)
+                    );
 
-                populate_build_projects(&msg, conn, state, &rdeps, &mut projects);
-            }
-            None => {
-                debug!("Graph rdeps: no entries found");
+                    populate_build_projects(
+                        &msg,
+                        &target,
+                        conn,
+                        state,
+                        &rdeps,
+                        &mut projects,
+                        &mut skipped,
+                    );
+                }
+                None => {
+                    debug!("Graph rdeps ({}): no entries found", target);
+                }
             }
         }
     }
 
-    let group = if projects.is_empty() {
+    // A push changing several plans builds them, and their rdeps, in the root project's group
+    for plan in msg.get_plans()
+        .iter()
+        .filter(|p| p.get_name() != project_name)
+    {
+        populate_plan_projects(
+            &msg,
+            plan.get_name(),
+            &targets,
+            conn,
+            state,
+            &mut projects,
+            &mut skipped,
+        );
+    }
+    let mut seen = HashSet::new();
+    projects.retain(|p| seen.insert((p.0.clone(), p.2.clone())));
+    skipped.retain(|p| seen.insert((p.get_name().to_string(), p.get_target().to_string())));
+
+    let group = if msg.get_dry_run() {
+        dry_run_group(&project_name, projects, skipped)
+    } else if projects.is_empty() {
         debug!("No projects need building - group is complete");
 
         let mut new_group = jobsrv::JobGroup::new();
@@ -485,7 +772,12 @@ pub fn job_group_create(
         // If already have a queued job group (queue length: 1 per project),
         // then return that group, else create a new job group
         // TODO (SA) - update the group's projects instead of just returning the group
-        let new_group = match state.datastore.get_queued_job_group(&project_name)? {
+        let queued = state.datastore.get_queued_job_group(
+            &project_name,
+            msg.get_git_ref(),
+            msg.get_pull_request(),
+        )?;
+        let new_group = match queued {
             Some(group) => {
                 debug!("JobGroupSpec, project {} is already queued", project_name);
                 group
@@ -543,6 +835,9 @@ pub fn job_graph_package_reverse_dependencies_get(
     let mut rd_reply = jobsrv::JobGraphPackageReverseDependencies::new();
     rd_reply.set_origin(msg.get_origin().to_string());
     rd_reply.set_name(msg.get_name().to_string());
+    if let Some(latest) = graph.resolve(&ident) {
+        rd_reply.set_ident(latest);
+    }
 
     match rdeps {
         Some(rd) => {
@@ -583,6 +878,42 @@ pub fn job_group_origin_get(
     Ok(())
 }
 
+pub fn job_group_origin_active_get(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> Result<()> {
+    let msg = req.parse::<jobsrv::JobGroupOriginActiveGet>()?;
+
+    match state.datastore.get_active_job_groups_origin(msg.get_origin()) {
+        Ok(ref jgor) => conn.route_reply(req, jgor)?,
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "jb:job-group-origin-active-get:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn job_group_audit_list(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> Result<()> {
+    let msg = req.parse::<jobsrv::JobGroupAuditListRequest>()?;
+
+    match state.datastore.list_job_group_audit(&msg) {
+        Ok(ref response) => conn.route_reply(req, response)?,
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "jb:job-group-audit-list:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn job_group_get(
     req: &mut Message,
     conn: &mut RouteConn,
@@ -20,7 +20,7 @@ use std::thread::{self, JoinHandle};
 
 use hab_net::socket::DEFAULT_CONTEXT;
 use protobuf::parse_from_bytes;
-use protocol::jobsrv::{JobLogChunk, JobLogComplete};
+use protocol::jobsrv::{JobLogChunk, JobLogComplete, JobSection};
 use server::log_archiver::{self, LogArchiver};
 use server::log_directory::LogDirectory;
 use zmq;
@@ -33,6 +33,8 @@ use error::Result;
 const LOG_LINE: &'static str = "L";
 /// ZMQ protocol frame to indicate a log has finished
 const LOG_COMPLETE: &'static str = "C";
+/// ZMQ protocol frame to indicate a log section has started or ended
+const LOG_SECTION: &'static str = "S";
 
 /// Listens for log messages from builders and consolidates output for
 /// both streaming to clients and long-term storage.
@@ -49,6 +51,7 @@ impl LogIngester {
     pub fn new(config: &Config, log_dir: Arc<LogDirectory>, data_store: DataStore) -> Result<Self> {
         let intake_sock = (**DEFAULT_CONTEXT).as_mut().socket(zmq::ROUTER)?;
         intake_sock.set_router_mandatory(true)?;
+        config.net.curve.configure_server(&intake_sock)?;
         Ok(LogIngester {
             intake_sock: intake_sock,
             msg: zmq::Message::new()?,
@@ -88,6 +91,7 @@ impl LogIngester {
             // 2: a single-character code indicating message type:
             //    L = a line of log output
             //    C = the log is complete
+            //    S = a section of the log has started or ended
             // 3: a protobuf message
             self.intake_sock.recv(&mut self.msg, 0)?; // identity frame
 
@@ -135,6 +139,19 @@ impl LogIngester {
                         }
                     }
                 }
+                LOG_SECTION => {
+                    self.intake_sock.recv(&mut self.msg, 0)?; // protobuf message frame
+                    match parse_from_bytes::<JobSection>(&self.msg) {
+                        Ok(section) => {
+                            if let Err(e) = self.data_store.upsert_job_section(&section) {
+                                warn!("Error recording log section: {}", e);
+                            }
+                        }
+                        Err(e) => {
+                            warn!("ERROR parsing JobSection: {:?}", e);
+                        }
+                    }
+                }
                 other => {
                     warn!("UNRECOGNIZED LOG PROTOCOL CODE: {:?}", other);
                 }
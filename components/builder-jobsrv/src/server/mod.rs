@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod commit_status;
 mod handlers;
 pub mod log_archiver;
 mod log_directory;
@@ -25,6 +26,7 @@ use time::PreciseTime;
 use bldr_core::target_graph::TargetGraph;
 use hab_net::app::prelude::*;
 use hab_net::conn::RouteClient;
+use hab_net::socket::DEFAULT_CONTEXT;
 use protocol::jobsrv::*;
 
 use self::log_archiver::LogArchiver;
@@ -58,6 +60,14 @@ lazy_static! {
             JobGroupCancel::descriptor_static(None),
             handlers::job_group_cancel,
         );
+        map.register(
+            JobGroupPause::descriptor_static(None),
+            handlers::job_group_pause,
+        );
+        map.register(
+            JobGroupResume::descriptor_static(None),
+            handlers::job_group_resume,
+        );
         map.register(
             JobGroupGet::descriptor_static(None),
             handlers::job_group_get,
@@ -66,6 +76,14 @@ lazy_static! {
             JobGroupOriginGet::descriptor_static(None),
             handlers::job_group_origin_get,
         );
+        map.register(
+            JobGroupOriginActiveGet::descriptor_static(None),
+            handlers::job_group_origin_active_get,
+        );
+        map.register(
+            JobGroupAuditListRequest::descriptor_static(None),
+            handlers::job_group_audit_list,
+        );
         map.register(
             JobGraphPackageCreate::descriptor_static(None),
             handlers::job_graph_package_create,
@@ -148,6 +166,10 @@ impl Dispatcher for JobSrv {
         router_pipe: Arc<String>,
     ) -> Result<<Self::State as AppState>::InitState> {
         let datastore = DataStore::new(&config.datastore)?;
+        for (origin, share) in config.scheduler.origins.iter() {
+            datastore.upsert_origin_share(origin, share.weight, share.max_dispatched)?;
+        }
+
         let mut graph = TargetGraph::new();
         let packages = datastore.get_job_graph_packages()?;
         let start_time = PreciseTime::now();
@@ -164,6 +186,10 @@ impl Dispatcher for JobSrv {
 
         let state = InitServerState::new(config.clone(), datastore, graph)?;
 
+        config
+            .net
+            .curve
+            .start_authenticator((**DEFAULT_CONTEXT).as_mut())?;
         LogIngester::start(&config, state.log_dir.clone(), state.datastore.clone())?;
         let conn = RouteClient::new()?;
         conn.connect(&*router_pipe)?;
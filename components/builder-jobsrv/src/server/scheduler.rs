@@ -19,7 +19,9 @@ use std::thread::{self, JoinHandle};
 
 use hab_net::conn::RouteClient;
 use hab_net::socket::DEFAULT_CONTEXT;
-use hab_net::ErrCode;
+use hab_net::{ErrCode, NetOk};
+use protobuf::RepeatedField;
+use serde_json;
 use zmq;
 
 use data_store::DataStore;
@@ -142,6 +144,10 @@ impl ScheduleMgr {
                 warn!("Scheduler unable to process queue: err {:?}", err);
             }
 
+            if let Err(err) = self.process_resuming() {
+                warn!("Scheduler unable to process resuming groups: err {:?}", err);
+            }
+
             if let Err(err) = self.process_work() {
                 warn!("Scheduler unable to process work: err {:?}", err);
             }
@@ -181,6 +187,19 @@ impl ScheduleMgr {
         Ok(())
     }
 
+    // Groups resumed after a pause continue from their projects' current states
+    fn process_resuming(&mut self) -> Result<()> {
+        let groups = self.datastore.get_resuming_job_groups()?;
+
+        for group in groups.iter() {
+            assert!(group.get_state() == jobsrv::JobGroupState::GroupResuming);
+            debug!("Resuming group {}", group.get_id());
+            self.update_group_state(group.get_id())?;
+        }
+
+        Ok(())
+    }
+
     fn process_work(&mut self) -> Result<()> {
         loop {
             // Take one group from the pending list
@@ -210,16 +229,26 @@ impl ScheduleMgr {
         let dispatchable = self.dispatchable_projects(&group)?;
 
         for project in dispatchable {
-            if skipped.contains_key(project.get_name()) {
+            let key = (
+                project.get_target().to_string(),
+                project.get_name().to_string(),
+            );
+            if skipped.contains_key(&key) {
                 continue;
             }
 
+            // Jobs already dispatched are left to finish when a group is paused
+            if self.is_group_paused(group.get_id()) {
+                debug!("Group {} paused, not dispatching further", group.get_id());
+                break;
+            }
+
             debug!("Dispatching project: {:?}", project.get_name());
             self.logger.log_group_project(&group, &project);
 
             assert!(project.get_state() == jobsrv::JobGroupProjectState::NotStarted);
 
-            match self.schedule_job(group.get_id(), project.get_name()) {
+            match self.schedule_job(group, &project) {
                 Ok(job_opt) => match job_opt {
                     Some(job) => self.datastore.set_job_group_job_state(&job).unwrap(),
                     None => {
@@ -227,10 +256,15 @@ impl ScheduleMgr {
                         self.datastore.set_job_group_project_state(
                             group.get_id(),
                             project.get_name(),
+                            project.get_target(),
                             jobsrv::JobGroupProjectState::Skipped,
                         )?;
 
-                        let skip_list = match self.skip_projects(&group, project.get_name()) {
+                        let skip_list = match self.skip_projects(
+                            &group,
+                            project.get_name(),
+                            project.get_target(),
+                        ) {
                             Ok(v) => v,
                             Err(e) => {
                                 self.log_error(format!(
@@ -243,7 +277,7 @@ impl ScheduleMgr {
                             }
                         };
                         for name in skip_list {
-                            skipped.insert(name, true);
+                            skipped.insert((project.get_target().to_string(), name), true);
                         }
                     }
                 },
@@ -261,6 +295,7 @@ impl ScheduleMgr {
                     self.datastore.set_job_group_project_state(
                         group.get_id(),
                         project.get_name(),
+                        project.get_target(),
                         jobsrv::JobGroupProjectState::Failure,
                     )?;
 
@@ -268,6 +303,7 @@ impl ScheduleMgr {
                     let mut updated_group = group.clone();
                     updated_group.set_state(jobsrv::JobGroupState::GroupFailed);
                     self.logger.log_group(&updated_group);
+                    self.delete_group_channels(&updated_group);
 
                     break;
                 }
@@ -288,25 +324,13 @@ impl ScheduleMgr {
         {
             // Check the deps for the project. If we don't find any dep that
             // is in our project list and needs to be built, we can dispatch the project.
-            let dispatchable = if project.get_ident().is_empty() {
-                true
-            } else {
-                let mut check_status = true;
-                let package = self.datastore.get_job_graph_package(&project.get_ident())?;
-                let deps = package.get_deps();
-
-                for dep in deps {
-                    let parts: Vec<&str> = dep.split("/").collect();
-                    assert!(parts.len() >= 2);
-                    let name = format!("{}/{}", parts[0], parts[1]);
-
-                    if !self.check_dispatchable(group, &name) {
-                        check_status = false;
-                        break;
-                    };
-                }
-                check_status
-            };
+            let mut dispatchable = true;
+            for name in self.project_deps(project)? {
+                if !self.check_dispatchable(group, &name, project.get_target()) {
+                    dispatchable = false;
+                    break;
+                };
+            }
 
             if dispatchable {
                 projects.push(project.clone());
@@ -320,9 +344,33 @@ impl ScheduleMgr {
         Ok(projects)
     }
 
-    fn check_dispatchable(&mut self, group: &jobsrv::JobGroup, name: &str) -> bool {
+    // The origin/name of the project's deps. Projects of a group created from the plans changed
+    // by a push use the deps read from their plan, others those of their last built package.
+    fn project_deps(&mut self, project: &jobsrv::JobGroupProject) -> Result<Vec<String>> {
+        if project.get_from_plan() {
+            return Ok(project.get_plan_deps().to_vec());
+        }
+        if project.get_ident().is_empty() {
+            return Ok(vec![]);
+        }
+
+        let package = self.datastore.get_job_graph_package(&project.get_ident())?;
+        let deps = package
+            .get_deps()
+            .iter()
+            .map(|dep| {
+                let parts: Vec<&str> = dep.split("/").collect();
+                assert!(parts.len() >= 2);
+                format!("{}/{}", parts[0], parts[1])
+            })
+            .collect();
+        Ok(deps)
+    }
+
+    fn check_dispatchable(&mut self, group: &jobsrv::JobGroup, name: &str, target: &str) -> bool {
         for project in group.get_projects() {
             if (project.get_name() == name)
+                && (project.get_target() == target)
                 && (project.get_state() != jobsrv::JobGroupProjectState::Success)
             {
                 return false;
@@ -335,19 +383,20 @@ impl ScheduleMgr {
         &mut self,
         group: &jobsrv::JobGroup,
         project_name: &str,
+        target: &str,
     ) -> Result<Vec<String>> {
         let mut skipped = HashMap::new();
         skipped.insert(project_name.to_string(), true);
 
-        for project in group
-            .get_projects()
-            .into_iter()
-            .filter(|x| x.get_state() == jobsrv::JobGroupProjectState::NotStarted)
-        {
+        // A failure on one platform says nothing about the others, so only
+        // projects building for the same target are skipped.
+        for project in group.get_projects().into_iter().filter(|x| {
+            x.get_state() == jobsrv::JobGroupProjectState::NotStarted && x.get_target() == target
+        }) {
             // Check the deps for the project. If we find any dep that is in the
             // skipped list, we set the project status to Skipped and add it to the list
-            let package = match self.datastore.get_job_graph_package(&project.get_ident()) {
-                Ok(package) => package,
+            let deps = match self.project_deps(project) {
+                Ok(deps) => deps,
                 Err(err) => {
                     warn!(
                         "Unable to retrieve job graph package {}, err: {:?}",
@@ -357,18 +406,14 @@ impl ScheduleMgr {
                     continue;
                 }
             };
-            let deps = package.get_deps();
-
-            for dep in deps {
-                let parts: Vec<&str> = dep.split("/").collect();
-                assert!(parts.len() >= 2);
-                let name = format!("{}/{}", parts[0], parts[1]);
 
+            for name in deps {
                 if skipped.contains_key(&name) {
                     debug!("Skipping project {:?}", project.get_name());
                     self.datastore.set_job_group_project_state(
                         group.get_id(),
                         project.get_name(),
+                        target,
                         jobsrv::JobGroupProjectState::Skipped,
                     )?;
                     skipped.insert(project.get_name().to_string(), true);
@@ -380,7 +425,14 @@ impl ScheduleMgr {
         Ok(skipped.keys().map(|s| s.to_string()).collect())
     }
 
-    fn schedule_job(&mut self, group_id: u64, project_name: &str) -> Result<Option<jobsrv::Job>> {
+    fn schedule_job(
+        &mut self,
+        group: &jobsrv::JobGroup,
+        group_project: &jobsrv::JobGroupProject,
+    ) -> Result<Option<jobsrv::Job>> {
+        let group_id = group.get_id();
+        let project_name = group_project.get_name();
+
         let mut project_get = originsrv::OriginProjectGet::new();
         project_get.set_name(String::from(project_name));
 
@@ -406,10 +458,25 @@ impl ScheduleMgr {
             }
         };
 
+        // A group's ref names a commit of the repository it was triggered from, so projects from
+        // other repositories are built from their default branch
+        let pin_ref = group.has_git_ref() && self.is_root_repo(group, &project);
+
         let mut job_spec = jobsrv::JobSpec::new();
         job_spec.set_owner_id(group_id);
         job_spec.set_project(project);
-        job_spec.set_channel(bldr_channel_name(group_id));
+        // Groups may build into a channel that outlives them, such as a pull request's
+        if group.has_channel() {
+            job_spec.set_channel(group.get_channel().to_string());
+        } else {
+            job_spec.set_channel(bldr_channel_name(group_id));
+        }
+        job_spec.set_pull_request(group.get_pull_request());
+        if pin_ref {
+            job_spec.set_git_ref(group.get_git_ref().to_string());
+        }
+        job_spec.set_priority(group.get_priority());
+        job_spec.set_target(group_project.get_target().to_string());
 
         let mut job: jobsrv::Job = job_spec.into();
         match self.datastore.create_job(&mut job) {
@@ -425,6 +492,52 @@ impl ScheduleMgr {
         }
     }
 
+    // Whether a project comes from the same repository as the project the group was created for
+    fn is_root_repo(
+        &mut self,
+        group: &jobsrv::JobGroup,
+        project: &originsrv::OriginProject,
+    ) -> bool {
+        if project.get_name() == group.get_project_name() {
+            return true;
+        }
+
+        let mut project_get = originsrv::OriginProjectGet::new();
+        project_get.set_name(group.get_project_name().to_string());
+        match self.route_conn
+            .route::<originsrv::OriginProjectGet, originsrv::OriginProject>(&project_get)
+        {
+            Ok(root) => root.get_vcs_data() == project.get_vcs_data(),
+            Err(err) => {
+                self.log_error(format!(
+                    "Unable to retrieve root project: {:?} (group: {}), error: {:?}",
+                    group.get_project_name(),
+                    group.get_id(),
+                    err
+                ));
+                false
+            }
+        }
+    }
+
+    fn is_group_paused(&mut self, group_id: u64) -> bool {
+        let mut msg = jobsrv::JobGroupGet::new();
+        msg.set_group_id(group_id);
+        msg.set_include_projects(false);
+
+        match self.datastore.get_job_group(&msg) {
+            Ok(Some(group)) => group.get_state() == jobsrv::JobGroupState::GroupPaused,
+            Ok(None) => false,
+            Err(err) => {
+                self.log_error(format!(
+                    "Failed to get state of group {}: {:?}",
+                    group_id, err
+                ));
+                false
+            }
+        }
+    }
+
     fn get_group(&mut self, group_id: u64) -> Result<jobsrv::JobGroup> {
         let mut msg: jobsrv::JobGroupGet = jobsrv::JobGroupGet::new();
         msg.set_group_id(group_id);
@@ -471,7 +584,11 @@ impl ScheduleMgr {
             match self.datastore.set_job_group_job_state(&job) {
                 Ok(_) => {
                     if job.get_state() == jobsrv::JobState::Failed {
-                        match self.skip_projects(&group, job.get_project().get_name()) {
+                        match self.skip_projects(
+                            &group,
+                            job.get_project().get_name(),
+                            job.get_target(),
+                        ) {
                             Ok(_) => (),
                             Err(e) => {
                                 self.log_error(format!(
@@ -525,10 +642,16 @@ impl ScheduleMgr {
         // |     Dispatching         |   no remaining   |      Complete       |
         // |     Dispatching         |   dispatchable?  |      Pending        |
         // |     Dispatching         |   otherwise      |      Dispatching    |
+        // |     Paused              |     N/A          |        N/A          |
+        // |     Resuming            |   no remaining   |      Complete       |
+        // |     Resuming            |   dispatchable?  |      Pending        |
+        // |     Resuming            |   otherwise      |      Dispatching    |
         // |     Complete            |     N/A          |        N/A          |
         // |     Failed              |     N/A          |        N/A          |
 
-        if group.get_state() == jobsrv::JobGroupState::GroupDispatching {
+        if group.get_state() == jobsrv::JobGroupState::GroupDispatching
+            || group.get_state() == jobsrv::JobGroupState::GroupResuming
+        {
             let mut failed = 0;
             let mut succeeded = 0;
             let mut skipped = 0;
@@ -558,7 +681,16 @@ impl ScheduleMgr {
                 jobsrv::JobGroupState::GroupDispatching
             };
 
-            self.datastore.set_job_group_state(group_id, new_state)?;
+            // The group may have been paused since it was read, which must not be overwritten
+            if !self.datastore
+                .transition_job_group_state(group_id, group.get_state(), new_state)?
+            {
+                debug!(
+                    "Skipping group update because state changed for group id: {}",
+                    group_id
+                );
+                return Ok(());
+            }
 
             if new_state == jobsrv::JobGroupState::GroupPending {
                 self.schedule_cli.notify()?;
@@ -567,6 +699,14 @@ impl ScheduleMgr {
                 let mut updated_group = group.clone();
                 updated_group.set_state(new_state);
                 self.logger.log_group(&updated_group);
+
+                if new_state != jobsrv::JobGroupState::GroupDispatching {
+                    self.delete_group_channels(&updated_group);
+                }
+
+                if new_state == jobsrv::JobGroupState::GroupComplete {
+                    self.notify_group_complete(&updated_group);
+                }
             }
         } else {
             debug!(
@@ -578,4 +718,88 @@ impl ScheduleMgr {
 
         Ok(())
     }
+
+    // Deletes the ephemeral channel the group's packages were uploaded to from each origin with
+    // a project in the group. Failures are only logged, as the channel isn't needed once the
+    // group has finished. Channels the group was given, such as a pull request's, are kept.
+    fn delete_group_channels(&mut self, group: &jobsrv::JobGroup) {
+        if group.has_channel() {
+            return;
+        }
+        let channel = bldr_channel_name(group.get_id());
+
+        for origin in group_origins(group) {
+            let mut channel_get = originsrv::OriginChannelGet::new();
+            channel_get.set_origin_name(origin.to_string());
+            channel_get.set_name(channel.clone());
+
+            let origin_channel = match self.route_conn
+                .route::<originsrv::OriginChannelGet, originsrv::OriginChannel>(&channel_get)
+            {
+                Ok(origin_channel) => origin_channel,
+                Err(err) => {
+                    if err.get_code() != ErrCode::ENTITY_NOT_FOUND {
+                        warn!("Unable to retrieve channel {} in {}, {}", channel, origin, err);
+                    }
+                    continue;
+                }
+            };
+
+            let mut channel_delete = originsrv::OriginChannelDelete::new();
+            channel_delete.set_id(origin_channel.get_id());
+            channel_delete.set_origin_id(origin_channel.get_origin_id());
+
+            match self.route_conn
+                .route::<originsrv::OriginChannelDelete, NetOk>(&channel_delete)
+            {
+                Ok(_) => debug!("Deleted channel {} in {}", channel, origin),
+                Err(err) => warn!("Unable to delete channel {} in {}, {}", channel, origin, err),
+            }
+        }
+    }
+
+    fn notify_group_complete(&mut self, group: &jobsrv::JobGroup) {
+        for origin in group_origins(group) {
+            let payload = serde_json::to_string(&origin_group(group, origin)).unwrap();
+            let mut notify = originsrv::OriginWebhookNotify::new();
+            notify.set_origin_name(origin.to_string());
+            notify.set_event(originsrv::OriginWebhookEvent::GroupComplete);
+            notify.set_payload(payload);
+
+            if let Err(err) = self.route_conn
+                .route::<originsrv::OriginWebhookNotify, NetOk>(&notify)
+            {
+                warn!("Unable to notify webhooks of group in {}, {}", origin, err);
+            }
+        }
+    }
+}
+
+/// Returns the distinct origins of the projects in a group.
+fn group_origins(group: &jobsrv::JobGroup) -> Vec<&str> {
+    let mut origins: Vec<&str> = group
+        .get_projects()
+        .iter()
+        .filter_map(|project| project.get_name().split('/').next())
+        .collect();
+    origins.sort();
+    origins.dedup();
+    origins
+}
+
+/// Returns the part of a group an origin may see, leaving out the projects of other origins.
+fn origin_group(group: &jobsrv::JobGroup, origin: &str) -> jobsrv::JobGroup {
+    let prefix = format!("{}/", origin);
+    let projects: Vec<jobsrv::JobGroupProject> = group
+        .get_projects()
+        .iter()
+        .filter(|project| project.get_name().starts_with(&prefix))
+        .cloned()
+        .collect();
+    let mut origin_group = group.clone();
+    if !group.get_project_name().starts_with(&prefix) {
+        origin_group.clear_project_name();
+    }
+    origin_group.set_projects(RepeatedField::from_vec(projects));
+    origin_group
 }
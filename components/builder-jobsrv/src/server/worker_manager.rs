@@ -27,19 +27,22 @@ use hab_net::socket::DEFAULT_CONTEXT;
 use linked_hash_map::LinkedHashMap;
 use protobuf::{parse_from_bytes, Message, RepeatedField};
 use protocol::jobsrv;
+use protocol::net::{ErrCode, NetOk};
 use protocol::originsrv::{
     Origin, OriginGet, OriginIntegrationRequest, OriginIntegrationResponse,
-    OriginPrivateEncryptionKey, OriginPrivateEncryptionKeyGet, OriginProjectIntegrationRequest,
-    OriginProjectIntegrationResponse, OriginPublicEncryptionKey,
+    OriginPrivateEncryptionKey, OriginPrivateEncryptionKeyGet, OriginProject, OriginProjectGet,
+    OriginProjectIntegrationRequest, OriginProjectIntegrationResponse, OriginPublicEncryptionKey,
     OriginPublicEncryptionKeyLatestGet, OriginSecret, OriginSecretDecrypted, OriginSecretList,
-    OriginSecretListGet,
+    OriginSecretListGet, OriginWebhookEvent, OriginWebhookNotify,
 };
+use serde_json;
 use zmq;
 
-use config::Config;
+use config::{Config, RetryCfg};
 use data_store::DataStore;
 use error::{Error, Result};
 
+use super::commit_status::{CommitStatusClient, CommitStatusReporter};
 use super::scheduler::ScheduleClient;
 
 const WORKER_MGR_ADDR: &'static str = "inproc://work-manager";
@@ -78,6 +81,8 @@ pub struct Worker {
     pub job_id: Option<u64>,
     pub job_expiry: Option<Instant>,
     pub canceling: bool,
+    pub os: jobsrv::Os,
+    pub labels: Vec<String>,
 }
 
 impl Worker {
@@ -89,6 +94,18 @@ impl Worker {
             job_id: None,
             job_expiry: None,
             canceling: false,
+            os: jobsrv::Os::Linux,
+            labels: vec![],
+        }
+    }
+
+    /// The package target this worker is able to build, based on the platform it reported in
+    /// its last heartbeat.
+    pub fn target(&self) -> &'static str {
+        match self.os {
+            jobsrv::Os::Linux => "x86_64-linux",
+            jobsrv::Os::Windows => "x86_64-windows",
+            jobsrv::Os::Darwin => "x86_64-darwin",
         }
     }
 
@@ -152,6 +169,8 @@ pub struct WorkerMgr {
     worker_heartbeat: String,
     schedule_cli: ScheduleClient,
     job_timeout: u64,
+    retry: RetryCfg,
+    commit_status: CommitStatusClient,
 }
 
 impl WorkerMgr {
@@ -161,10 +180,14 @@ impl WorkerMgr {
         let work_mgr_sock = (**DEFAULT_CONTEXT).as_mut().socket(zmq::DEALER)?;
         rq_sock.set_router_mandatory(true)?;
         hb_sock.set_subscribe(&[])?;
+        cfg.net.curve.configure_server(&hb_sock)?;
+        cfg.net.curve.configure_server(&rq_sock)?;
 
         let mut schedule_cli = ScheduleClient::default();
         schedule_cli.connect()?;
 
+        let commit_status = CommitStatusReporter::start(cfg, datastore.clone());
+
         Ok(WorkerMgr {
             datastore: datastore,
             key_dir: cfg.key_dir.clone(),
@@ -178,6 +201,8 @@ impl WorkerMgr {
             worker_heartbeat: cfg.net.worker_heartbeat_addr(),
             schedule_cli: schedule_cli,
             job_timeout: cfg.job_timeout,
+            retry: cfg.retry.clone(),
+            commit_status: commit_status,
         })
     }
 
@@ -390,20 +415,18 @@ impl WorkerMgr {
     }
 
     fn process_work(&mut self) -> Result<()> {
-        loop {
-            // Exit if we don't have any Ready workers
-            let worker_ident = match self.workers
-                .iter()
-                .find(|t| t.1.state == jobsrv::WorkerState::Ready)
-            {
-                Some(t) => t.0.clone(),
-                None => return Ok(()),
-            };
-
-            // Take one job from the pending list
-            let job_opt = self.datastore.next_pending_job(&worker_ident)?;
+        // Exit if we don't have any Ready workers
+        let ready_workers: Vec<(String, &'static str, Vec<String>)> = self.workers
+            .iter()
+            .filter(|t| t.1.state == jobsrv::WorkerState::Ready)
+            .map(|t| (t.0.clone(), t.1.target(), t.1.labels.clone()))
+            .collect();
+
+        for (worker_ident, target, labels) in ready_workers {
+            // Take one job this worker's platform and labels can build from the pending list
+            let job_opt = self.datastore.next_pending_job(&worker_ident, target, &labels)?;
             if job_opt.is_none() {
-                break;
+                continue;
             }
 
             let mut job = Job::new(job_opt.unwrap()); // unwrap Ok
@@ -411,6 +434,7 @@ impl WorkerMgr {
             self.add_integrations_to_job(&mut job);
             self.add_project_integrations_to_job(&mut job);
             self.add_secrets_to_job(&mut job)?;
+            self.add_vcs_credentials_to_job(&mut job);
 
             match self.worker_start_job(&job, &worker_ident) {
                 Ok(()) => {
@@ -418,6 +442,8 @@ impl WorkerMgr {
                     worker.busy(job.get_id(), self.job_timeout);
                     self.save_worker(&worker)?;
                     self.workers.insert(worker_ident, worker);
+                    self.notify_webhooks(&job, OriginWebhookEvent::JobStarted);
+                    self.commit_status.report(&job);
                 }
                 Err(err) => {
                     warn!(
@@ -509,6 +535,42 @@ impl WorkerMgr {
         }
     }
 
+    /// Adds the decrypted deploy token of GitLab and Bitbucket projects, which jobs don't store.
+    /// Projects without a token are cloned anonymously.
+    fn add_vcs_credentials_to_job(&mut self, job: &mut Job) {
+        match job.get_project().get_vcs_type() {
+            "gitlab" | "bitbucket" => (),
+            _ => return,
+        }
+
+        let mut project_get = OriginProjectGet::new();
+        project_get.set_name(job.get_project().get_name().to_string());
+        let project = match self.route_conn
+            .route::<OriginProjectGet, OriginProject>(&project_get)
+        {
+            Ok(project) => project,
+            Err(e) => {
+                warn!("Error fetching project for VCS credentials. e = {:?}", e);
+                return;
+            }
+        };
+        if !project.has_vcs_auth_token() {
+            return;
+        }
+
+        match bldr_core::integrations::decrypt(&self.key_dir, project.get_vcs_auth_token()) {
+            Ok(bytes) => match String::from_utf8(bytes) {
+                Ok(token) => {
+                    let job_project = job.mut_project();
+                    job_project.set_vcs_auth_user(project.get_vcs_auth_user().to_string());
+                    job_project.set_vcs_auth_token(token);
+                }
+                Err(e) => debug!("Error converting to string. e = {:?}", e),
+            },
+            Err(e) => warn!("Error decrypting VCS credentials. e = {:?}", e),
+        }
+    }
+
     fn add_secrets_to_job(&mut self, job: &mut Job) -> Result<()> {
         let mut secrets = RepeatedField::new();
         let mut secrets_request = OriginSecretListGet::new();
@@ -685,11 +747,13 @@ impl WorkerMgr {
         Ok(())
     }
 
-    fn is_job_complete(&mut self, job_id: u64) -> Result<bool> {
+    fn is_job_complete(&mut self, job_id: u64, worker_ident: &str) -> Result<bool> {
         let mut req = jobsrv::JobGet::new();
         req.set_id(job_id);
 
         let ret = match self.datastore.get_job(&req)? {
+            // A job that was requeued for a retry is done as far as this worker is concerned
+            Some(ref job) if job.get_worker() != worker_ident => true,
             Some(job) => match job.get_state() {
                 jobsrv::JobState::Pending
                 | jobsrv::JobState::Processing
@@ -751,7 +815,7 @@ impl WorkerMgr {
                 worker.refresh();
             }
             (jobsrv::WorkerState::Busy, jobsrv::WorkerState::Ready) => {
-                if !self.is_job_complete(worker.job_id.unwrap())? {
+                if !self.is_job_complete(worker.job_id.unwrap(), &worker_ident)? {
                     // Handle potential race condition where a Ready heartbeat
                     // is received right *after* the job has been dispatched
                     warn!(
@@ -767,6 +831,9 @@ impl WorkerMgr {
             _ => worker.ready(),
         };
 
+        worker.os = heartbeat.get_os();
+        worker.labels = heartbeat.get_labels().to_vec();
+
         assert!(!worker.is_expired());
         self.workers.insert(worker_ident, worker);
         Ok(())
@@ -778,9 +845,80 @@ impl WorkerMgr {
 
         let job = Job::new(parse_from_bytes::<jobsrv::Job>(&self.msg)?);
         debug!("Got job status: {:?}", job);
+
+        if job.get_state() == jobsrv::JobState::Failed && self.retry_job(&job)? {
+            return Ok(());
+        }
+
         self.datastore.update_job(&job)?;
         self.schedule_cli.notify()?;
+        self.commit_status.report(&job);
+
+        if job.get_state() == jobsrv::JobState::Failed {
+            self.notify_webhooks(&job, OriginWebhookEvent::JobFailed);
+        }
 
         Ok(())
     }
+
+    fn notify_webhooks(&mut self, job: &Job, event: OriginWebhookEvent) {
+        let origin = job.get_project().get_origin_name();
+        let mut notify = OriginWebhookNotify::new();
+        notify.set_origin_name(origin.to_string());
+        notify.set_event(event);
+        notify.set_payload(serde_json::to_string(&**job).unwrap());
+
+        if let Err(err) = self.route_conn
+            .route::<OriginWebhookNotify, NetOk>(&notify)
+        {
+            warn!("Unable to notify webhooks of {} in {}, {}", event, origin, err);
+        }
+    }
+
+    /// Requeues a failed job if it failed with a transient error and its retry policy allows
+    /// another attempt. Returns `true` if the job was requeued.
+    fn retry_job(&mut self, job: &Job) -> Result<bool> {
+        if !job.has_error() || !is_transient(job.get_error().get_code()) {
+            return Ok(false);
+        }
+
+        let (max_attempts, delay) = {
+            let policy = self.retry.policy(job.get_project().get_name());
+            (policy.max_attempts, policy.backoff(job.get_retry_count()))
+        };
+
+        if job.get_retry_count() + 1 >= max_attempts {
+            debug!(
+                "Not retrying job {}, {} of {} attempts used",
+                job.get_id(),
+                job.get_retry_count() + 1,
+                max_attempts
+            );
+            return Ok(false);
+        }
+
+        if !self.datastore.retry_job(&job, delay)? {
+            debug!("Not retrying job {}, it is no longer running", job.get_id());
+            return Ok(false);
+        }
+        info!(
+            "Retrying job {} in {} sec (attempt {} of {}), err: {:?}",
+            job.get_id(),
+            delay,
+            job.get_retry_count() + 2,
+            max_attempts,
+            job.get_error()
+        );
+
+        Ok(true)
+    }
+}
+
+/// Worker errors that are usually caused by a flaky network or upstream service rather than by
+/// the plan itself, and so are worth retrying.
+fn is_transient(code: ErrCode) -> bool {
+    match code {
+        ErrCode::VCS_CLONE | ErrCode::SECRET_KEY_FETCH => true,
+        _ => false,
+    }
 }
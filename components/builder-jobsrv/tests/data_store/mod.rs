@@ -15,7 +15,7 @@
 extern crate protobuf;
 use self::protobuf::RepeatedField;
 use jobsrv::data_store::DataStore;
-use protocol::jobsrv;
+use protocol::{jobsrv, net};
 
 #[test]
 fn migration() {
@@ -101,7 +101,7 @@ fn next_pending_job() {
 
     // Get one job, it should be FIFO, and it should have its status set to Dispatched,
     // and worker set to the passed in worker id
-    let pending_job = ds.next_pending_job("worker1")
+    let pending_job = ds.next_pending_job("worker1", "x86_64-linux", &[])
         .expect("Failed to get pending job");
     assert!(pending_job.is_some(), "Failed to find a pending job");
     assert_eq!(
@@ -121,7 +121,7 @@ fn next_pending_job() {
 
     // Get second job, it should be FIFO, and it should have its status set to Dispatched,
     // and worker set to the passed in worker id
-    let pending_job_2 = ds.next_pending_job("worker2")
+    let pending_job_2 = ds.next_pending_job("worker2", "x86_64-linux", &[])
         .expect("Failed to get pending job");
     assert!(pending_job_2.is_some(), "Failed to find a pending job");
     assert_eq!(
@@ -139,11 +139,207 @@ fn next_pending_job() {
     assert_eq!(job2_dispatched.get_worker(), "worker2");
 
     // No jobs returns an empty array
-    let no_job = ds.next_pending_job("worker3")
+    let no_job = ds.next_pending_job("worker3", "x86_64-linux", &[])
         .expect("Failed to get empty pending jobs");
     assert!(no_job.is_none());
 }
 
+fn test_origin_job(project_name: &str) -> jobsrv::Job {
+    let mut job = test_job();
+    job.mut_project().set_name(project_name.to_string());
+    job
+}
+
+#[test]
+fn next_pending_job_interleaves_origins() {
+    let ds = datastore_test!(DataStore);
+    ds.setup().expect("Failed to migrate data");
+
+    // One large rebuild in core, queued before a smaller one in another origin
+    let core1 = ds.create_job(&test_origin_job("core/gcc"))
+        .expect("Failed to create job");
+    let core2 = ds.create_job(&test_origin_job("core/glibc"))
+        .expect("Failed to create job");
+    let core3 = ds.create_job(&test_origin_job("core/zlib"))
+        .expect("Failed to create job");
+    let other1 = ds.create_job(&test_origin_job("other/app"))
+        .expect("Failed to create job");
+    let other2 = ds.create_job(&test_origin_job("other/lib"))
+        .expect("Failed to create job");
+
+    let mut dispatched = Vec::new();
+    for i in 0..5 {
+        let job = ds.next_pending_job(&format!("worker{}", i), "x86_64-linux", &[])
+            .expect("Failed to get pending job")
+            .expect("Failed to find a pending job");
+        dispatched.push(job.get_id());
+    }
+
+    assert_eq!(
+        dispatched,
+        vec![
+            core1.get_id(),
+            other1.get_id(),
+            core2.get_id(),
+            other2.get_id(),
+            core3.get_id(),
+        ],
+        "Origins were not interleaved"
+    );
+
+    let no_job = ds.next_pending_job("worker5", "x86_64-linux", &[])
+        .expect("Failed to get empty pending jobs");
+    assert!(no_job.is_none());
+}
+
+#[test]
+fn next_pending_job_honors_origin_weight() {
+    let ds = datastore_test!(DataStore);
+    ds.setup().expect("Failed to migrate data");
+    ds.upsert_origin_share("core", 2, 0)
+        .expect("Failed to upsert origin share");
+
+    let core1 = ds.create_job(&test_origin_job("core/gcc"))
+        .expect("Failed to create job");
+    let core2 = ds.create_job(&test_origin_job("core/glibc"))
+        .expect("Failed to create job");
+    let core3 = ds.create_job(&test_origin_job("core/zlib"))
+        .expect("Failed to create job");
+    let other1 = ds.create_job(&test_origin_job("other/app"))
+        .expect("Failed to create job");
+    let other2 = ds.create_job(&test_origin_job("other/lib"))
+        .expect("Failed to create job");
+
+    let mut dispatched = Vec::new();
+    for i in 0..5 {
+        let job = ds.next_pending_job(&format!("worker{}", i), "x86_64-linux", &[])
+            .expect("Failed to get pending job")
+            .expect("Failed to find a pending job");
+        dispatched.push(job.get_id());
+    }
+
+    // With twice the weight, core gets a second job in flight before other gets its second
+    assert_eq!(
+        dispatched,
+        vec![
+            core1.get_id(),
+            other1.get_id(),
+            core2.get_id(),
+            core3.get_id(),
+            other2.get_id(),
+        ],
+        "Origin weight was not honored"
+    );
+}
+
+#[test]
+fn next_pending_job_honors_max_dispatched() {
+    let ds = datastore_test!(DataStore);
+    ds.setup().expect("Failed to migrate data");
+    ds.upsert_origin_share("core", 1, 1)
+        .expect("Failed to upsert origin share");
+
+    let mut core1 = ds.create_job(&test_origin_job("core/gcc"))
+        .expect("Failed to create job");
+    let core2 = ds.create_job(&test_origin_job("core/glibc"))
+        .expect("Failed to create job");
+
+    let job = ds.next_pending_job("worker1", "x86_64-linux", &[])
+        .expect("Failed to get pending job")
+        .expect("Failed to find a pending job");
+    assert_eq!(job.get_id(), core1.get_id());
+
+    // core is at its cap, so nothing else can be dispatched
+    let no_job = ds.next_pending_job("worker2", "x86_64-linux", &[])
+        .expect("Failed to get pending job");
+    assert!(no_job.is_none(), "Dispatched past the origin cap");
+
+    // Once the in-flight job finishes, the next core job can go out
+    core1.set_state(jobsrv::JobState::Complete);
+    ds.update_job(&core1).expect("Failed to update job state");
+
+    let job = ds.next_pending_job("worker2", "x86_64-linux", &[])
+        .expect("Failed to get pending job")
+        .expect("Failed to find a pending job");
+    assert_eq!(job.get_id(), core2.get_id());
+}
+
+#[test]
+fn next_pending_job_honors_priority() {
+    let ds = datastore_test!(DataStore);
+    ds.setup().expect("Failed to migrate data");
+
+    let core1 = ds.create_job(&test_origin_job("core/gcc"))
+        .expect("Failed to create job");
+    let mut urgent = test_origin_job("core/openssl");
+    urgent.set_priority(10);
+    let urgent = ds.create_job(&urgent).expect("Failed to create job");
+    assert_eq!(urgent.get_priority(), 10);
+
+    let job = ds.next_pending_job("worker1", "x86_64-linux", &[])
+        .expect("Failed to get pending job")
+        .expect("Failed to find a pending job");
+    assert_eq!(job.get_id(), urgent.get_id(), "Priority was not honored");
+
+    let job = ds.next_pending_job("worker2", "x86_64-linux", &[])
+        .expect("Failed to get pending job")
+        .expect("Failed to find a pending job");
+    assert_eq!(job.get_id(), core1.get_id());
+}
+
+#[test]
+fn next_pending_job_matches_target() {
+    let ds = datastore_test!(DataStore);
+    ds.setup().expect("Failed to migrate data");
+
+    let linux = ds.create_job(&mut test_job())
+        .expect("Failed to create job");
+    let mut windows_job = test_job();
+    windows_job.set_target(String::from("x86_64-windows"));
+    let windows = ds.create_job(&mut windows_job)
+        .expect("Failed to create job");
+    assert_eq!(linux.get_target(), "x86_64-linux");
+    assert_eq!(windows.get_target(), "x86_64-windows");
+
+    let job = ds.next_pending_job("worker1", "x86_64-windows", &[])
+        .expect("Failed to get pending job")
+        .expect("Failed to find a pending job");
+    assert_eq!(job.get_id(), windows.get_id());
+
+    let no_job = ds.next_pending_job("worker2", "x86_64-windows", &[])
+        .expect("Failed to get pending job");
+    assert!(no_job.is_none());
+
+    let job = ds.next_pending_job("worker3", "x86_64-linux", &[])
+        .expect("Failed to get pending job")
+        .expect("Failed to find a pending job");
+    assert_eq!(job.get_id(), linux.get_id());
+}
+
+#[test]
+fn next_pending_job_matches_labels() {
+    let ds = datastore_test!(DataStore);
+    ds.setup().expect("Failed to migrate data");
+
+    let mut docker_job = test_job();
+    docker_job
+        .mut_project()
+        .set_required_labels(RepeatedField::from_vec(vec![String::from("docker")]));
+    let docker = ds.create_job(&mut docker_job)
+        .expect("Failed to create job");
+    assert_eq!(docker.get_project().get_required_labels(), &["docker".to_string()]);
+
+    let no_job = ds.next_pending_job("worker1", "x86_64-linux", &[])
+        .expect("Failed to get pending job");
+    assert!(no_job.is_none());
+
+    let labels = vec![String::from("docker"), String::from("large-memory")];
+    let job = ds.next_pending_job("worker2", "x86_64-linux", &labels)
+        .expect("Failed to get pending job")
+        .expect("Failed to find a pending job");
+    assert_eq!(job.get_id(), docker.get_id());
+}
+
 #[test]
 fn update_job() {
     let mut job1 = test_job();
@@ -166,9 +362,122 @@ fn update_job() {
     assert_eq!(failed_job.get_state(), jobsrv::JobState::Failed);
 }
 
+#[test]
+fn retry_job() {
+    let ds = datastore_test!(DataStore);
+    ds.setup().expect("Failed to migrate data");
+    let rjob = ds.create_job(&test_job()).expect("Failed to create job");
+
+    let mut job = ds.next_pending_job("worker1", "x86_64-linux", &[])
+        .expect("Failed to get pending job")
+        .expect("Failed to find a pending job");
+    job.set_state(jobsrv::JobState::Failed);
+    job.set_error(net::err(net::ErrCode::VCS_CLONE, "wk:run:clone:1"));
+
+    // Retry right away
+    let retried = ds.retry_job(&job, 0).expect("Failed to retry job");
+    assert!(retried);
+
+    let mut get_job = jobsrv::JobGet::new();
+    get_job.set_id(rjob.get_id());
+    let retried = ds.get_job(&get_job)
+        .expect("Failed to get job")
+        .expect("Job should exist");
+    assert_eq!(retried.get_state(), jobsrv::JobState::Pending);
+    assert_eq!(retried.get_retry_count(), 1);
+    assert!(!retried.has_error());
+    assert_eq!(retried.get_attempts().len(), 1);
+    assert_eq!(retried.get_attempts()[0].get_attempt(), 1);
+    assert_eq!(retried.get_attempts()[0].get_worker(), "worker1");
+    assert_eq!(
+        retried.get_attempts()[0].get_error().get_code(),
+        net::ErrCode::VCS_CLONE
+    );
+
+    let mut job = ds.next_pending_job("worker2", "x86_64-linux", &[])
+        .expect("Failed to get pending job")
+        .expect("Retried job was not dispatched");
+    assert_eq!(job.get_id(), rjob.get_id());
+    assert_eq!(job.get_worker(), "worker2");
+
+    // Retry with a backoff; the job must not be dispatched until it has passed
+    job.set_state(jobsrv::JobState::Failed);
+    job.set_error(net::err(net::ErrCode::VCS_CLONE, "wk:run:clone:1"));
+    let retried = ds.retry_job(&job, 3600).expect("Failed to retry job");
+    assert!(retried);
+
+    // The job is pending again, so another report of its failure doesn't retry it
+    let retried = ds.retry_job(&job, 0).expect("Failed to retry job");
+    assert!(!retried);
+
+    let no_job = ds.next_pending_job("worker3", "x86_64-linux", &[])
+        .expect("Failed to get pending job");
+    assert!(no_job.is_none(), "Dispatched a job during its backoff");
+
+    let retried = ds.get_job(&get_job)
+        .expect("Failed to get job")
+        .expect("Job should exist");
+    assert_eq!(retried.get_retry_count(), 2);
+    assert_eq!(retried.get_attempts().len(), 2);
+    assert_eq!(retried.get_attempts()[1].get_worker(), "worker2");
+}
+
+#[test]
+fn job_sections() {
+    let ds = datastore_test!(DataStore);
+    ds.setup().expect("Failed to migrate data");
+    let job = ds.create_job(&test_job()).expect("Failed to create job");
+
+    let mut clone = jobsrv::JobSection::new();
+    clone.set_job_id(job.get_id());
+    clone.set_name(String::from("clone_repository"));
+    clone.set_started_at(String::from("2018-08-06T10:00:00+00:00"));
+    clone.set_status(jobsrv::JobSectionStatus::SectionRunning);
+    ds.upsert_job_section(&clone)
+        .expect("Failed to record section start");
+
+    clone.set_finished_at(String::from("2018-08-06T10:00:02.500+00:00"));
+    clone.set_status(jobsrv::JobSectionStatus::SectionSucceeded);
+    ds.upsert_job_section(&clone)
+        .expect("Failed to record section end");
+
+    let mut build = jobsrv::JobSection::new();
+    build.set_job_id(job.get_id());
+    build.set_name(String::from("build_package"));
+    build.set_started_at(String::from("2018-08-06T10:00:03+00:00"));
+    build.set_status(jobsrv::JobSectionStatus::SectionRunning);
+    ds.upsert_job_section(&build)
+        .expect("Failed to record section start");
+
+    let mut get_job = jobsrv::JobGet::new();
+    get_job.set_id(job.get_id());
+    let job = ds.get_job(&get_job)
+        .expect("Failed to get job")
+        .expect("Job should exist");
+    let sections = job.get_sections();
+    assert_eq!(sections.len(), 2);
+    assert_eq!(sections[0].get_name(), "clone_repository");
+    assert_eq!(
+        sections[0].get_status(),
+        jobsrv::JobSectionStatus::SectionSucceeded
+    );
+    assert_eq!(sections[0].get_duration_ms(), 2500);
+    assert_eq!(sections[1].get_name(), "build_package");
+    assert_eq!(
+        sections[1].get_status(),
+        jobsrv::JobSectionStatus::SectionRunning
+    );
+    assert!(!sections[1].has_finished_at());
+    assert!(!sections[1].has_duration_ms());
+}
+
 #[test]
 fn create_job_group() {
-    let project_names = vec![(String::from("Foo/Bar"), String::from("Foo/Bar/0/Baz"))];
+    let project_names = vec![(
+        String::from("Foo/Bar"),
+        String::from("Foo/Bar/0/Baz"),
+        String::from("x86_64-linux"),
+    )];
     let mut msg = jobsrv::JobGroupSpec::new();
     msg.set_origin(String::from("Foo"));
     msg.set_package(String::from("Bar"));
@@ -180,7 +489,11 @@ fn create_job_group() {
 
 #[test]
 fn get_job_group() {
-    let project_names = vec![(String::from("Foo/Bar"), String::from("Foo/Bar/0/Baz"))];
+    let project_names = vec![(
+        String::from("Foo/Bar"),
+        String::from("Foo/Bar/0/Baz"),
+        String::from("x86_64-linux"),
+    )];
     let mut msg = jobsrv::JobGroupSpec::new();
     msg.set_origin(String::from("Foo"));
     msg.set_package(String::from("Bar"));
@@ -225,9 +538,64 @@ fn get_group_does_not_exist() {
     assert!(result.is_none());
 }
 
+#[test]
+fn multi_target_job_group() {
+    let project_names = vec![
+        (
+            String::from("Foo/Bar"),
+            String::from("Foo/Bar/0/Baz"),
+            String::from("x86_64-linux"),
+        ),
+        (
+            String::from("Foo/Bar"),
+            String::from("Foo/Bar/0/Qux"),
+            String::from("x86_64-windows"),
+        ),
+    ];
+    let mut msg = jobsrv::JobGroupSpec::new();
+    msg.set_origin(String::from("Foo"));
+    msg.set_package(String::from("Bar"));
+
+    let ds = datastore_test!(DataStore);
+    let group = ds.create_job_group(&msg, project_names)
+        .expect("Failed to create a group");
+
+    ds.set_job_group_project_state(
+        group.get_id(),
+        "Foo/Bar",
+        "x86_64-windows",
+        jobsrv::JobGroupProjectState::Skipped,
+    ).expect("Failed to set project state");
+
+    let mut get_msg = jobsrv::JobGroupGet::new();
+    get_msg.set_group_id(group.get_id());
+    get_msg.set_include_projects(true);
+    let group = ds.get_job_group(&get_msg)
+        .expect("Failed to get group")
+        .expect("Group should exist");
+
+    assert_eq!(group.get_projects().len(), 2);
+    for project in group.get_projects() {
+        match project.get_target() {
+            "x86_64-linux" => assert_eq!(
+                project.get_state(),
+                jobsrv::JobGroupProjectState::NotStarted
+            ),
+            "x86_64-windows" => {
+                assert_eq!(project.get_state(), jobsrv::JobGroupProjectState::Skipped)
+            }
+            target => panic!("Unexpected target {}", target),
+        }
+    }
+}
+
 #[test]
 fn pending_groups() {
-    let project_names = vec![(String::from("Foo/Bar"), String::from("Foo/Bar/0/Baz"))];
+    let project_names = vec![(
+        String::from("Foo/Bar"),
+        String::from("Foo/Bar/0/Baz"),
+        String::from("x86_64-linux"),
+    )];
     let mut msg = jobsrv::JobGroupSpec::new();
     msg.set_origin(String::from("Foo"));
     msg.set_package(String::from("Bar"));
@@ -312,9 +680,91 @@ fn pending_groups() {
     assert_eq!(no_groups.len(), 0);
 }
 
+#[test]
+fn pending_groups_interleave_origins() {
+    let project_names = vec![(
+        String::from("core/gcc"),
+        String::from("core/gcc/1/2"),
+        String::from("x86_64-linux"),
+    )];
+    let ds = datastore_test!(DataStore);
+
+    let mut group_ids = Vec::new();
+    for &(origin, package) in [
+        ("core", "gcc"),
+        ("core", "glibc"),
+        ("other", "app"),
+        ("other", "lib"),
+    ].iter()
+    {
+        let mut msg = jobsrv::JobGroupSpec::new();
+        msg.set_origin(String::from(origin));
+        msg.set_package(String::from(package));
+        let group = ds.create_job_group(&msg, project_names.clone())
+            .expect("Failed to create a group");
+        ds.set_job_group_state(group.get_id(), jobsrv::JobGroupState::GroupPending)
+            .expect("Failed to set group state");
+        group_ids.push(group.get_id());
+    }
+
+    // core already has a group dispatching, so other goes next
+    let first = ds.pending_job_groups(1).expect("Failed to get pending group");
+    assert_eq!(first[0].get_id(), group_ids[0]);
+    let second = ds.pending_job_groups(1).expect("Failed to get pending group");
+    assert_eq!(second[0].get_id(), group_ids[2], "Origins were not interleaved");
+    let third = ds.pending_job_groups(1).expect("Failed to get pending group");
+    assert_eq!(third[0].get_id(), group_ids[1]);
+    let fourth = ds.pending_job_groups(1).expect("Failed to get pending group");
+    assert_eq!(fourth[0].get_id(), group_ids[3]);
+}
+
+#[test]
+fn pending_groups_honor_priority() {
+    let project_names = vec![(
+        String::from("core/gcc"),
+        String::from("core/gcc/1/2"),
+        String::from("x86_64-linux"),
+    )];
+    let ds = datastore_test!(DataStore);
+
+    let mut msg = jobsrv::JobGroupSpec::new();
+    msg.set_origin(String::from("core"));
+    msg.set_package(String::from("gcc"));
+    let group1 = ds.create_job_group(&msg, project_names.clone())
+        .expect("Failed to create a group");
+
+    let mut msg2 = jobsrv::JobGroupSpec::new();
+    msg2.set_origin(String::from("core"));
+    msg2.set_package(String::from("openssl"));
+    msg2.set_priority(5);
+    let group2 = ds.create_job_group(&msg2, project_names.clone())
+        .expect("Failed to create a group");
+    assert_eq!(group2.get_priority(), 5);
+
+    ds.set_job_group_state(group1.get_id(), jobsrv::JobGroupState::GroupPending)
+        .expect("Failed to set group state");
+    ds.set_job_group_state(group2.get_id(), jobsrv::JobGroupState::GroupPending)
+        .expect("Failed to set group state");
+
+    let pending_groups = ds.pending_job_groups(2)
+        .expect("Failed to get pending groups");
+    assert_eq!(pending_groups.len(), 2);
+    assert_eq!(
+        pending_groups[0].get_id(),
+        group2.get_id(),
+        "Priority was not honored"
+    );
+    assert_eq!(pending_groups[0].get_priority(), 5);
+    assert_eq!(pending_groups[1].get_id(), group1.get_id());
+}
+
 #[test]
 fn set_job_group_state() {
-    let project_names = vec![(String::from("Foo/Bar"), String::from("Foo/Bar/0/Baz"))];
+    let project_names = vec![(
+        String::from("Foo/Bar"),
+        String::from("Foo/Bar/0/Baz"),
+        String::from("x86_64-linux"),
+    )];
     let mut msg = jobsrv::JobGroupSpec::new();
     msg.set_origin(String::from("Foo"));
     msg.set_package(String::from("Bar"));
@@ -347,6 +797,141 @@ fn set_job_group_state() {
     );
 }
 
+#[test]
+fn pause_and_resume_job_group() {
+    let project_names = vec![(
+        String::from("Foo/Bar"),
+        String::from("Foo/Bar/0/Baz"),
+        String::from("x86_64-linux"),
+    )];
+    let mut msg = jobsrv::JobGroupSpec::new();
+    msg.set_origin(String::from("Foo"));
+    msg.set_package(String::from("Bar"));
+
+    let ds = datastore_test!(DataStore);
+
+    let group = ds.create_job_group(&msg, project_names.clone())
+        .expect("Failed to create a group");
+
+    let mut get_msg = jobsrv::JobGroupGet::new();
+    get_msg.set_group_id(group.get_id());
+
+    // Queued groups can't be paused, nor can groups which aren't paused be resumed
+    let paused = ds.pause_job_group(group.get_id())
+        .expect("Failed to pause group");
+    assert!(!paused);
+    let resumed = ds.resume_job_group(group.get_id())
+        .expect("Failed to resume group");
+    assert!(!resumed);
+
+    ds.set_job_group_state(group.get_id(), jobsrv::JobGroupState::GroupDispatching)
+        .expect("Failed to update group state");
+    let paused = ds.pause_job_group(group.get_id())
+        .expect("Failed to pause group");
+    assert!(paused);
+
+    let paused_group = ds.get_job_group(&get_msg)
+        .expect("Failed to get group from database")
+        .expect("No group found");
+    assert_eq!(paused_group.get_state(), jobsrv::JobGroupState::GroupPaused);
+    let active = ds.is_job_group_active("Foo/Bar")
+        .expect("Failed to check for active groups");
+    assert!(active);
+
+    let resumed = ds.resume_job_group(group.get_id())
+        .expect("Failed to resume group");
+    assert!(resumed);
+    let resumed = ds.resume_job_group(group.get_id())
+        .expect("Failed to resume group");
+    assert!(!resumed);
+
+    let resuming_group = ds.get_job_group(&get_msg)
+        .expect("Failed to get group from database")
+        .expect("No group found");
+    assert_eq!(
+        resuming_group.get_state(),
+        jobsrv::JobGroupState::GroupResuming
+    );
+}
+
+#[test]
+fn active_job_groups_origin() {
+    let project_names = vec![(
+        String::from("Foo/Bar"),
+        String::from("Foo/Bar/0/Baz"),
+        String::from("x86_64-linux"),
+    )];
+    let mut msg = jobsrv::JobGroupSpec::new();
+    msg.set_origin(String::from("Foo"));
+    msg.set_package(String::from("Bar"));
+
+    let ds = datastore_test!(DataStore);
+
+    let group = ds.create_job_group(&msg, project_names.clone())
+        .expect("Failed to create a group");
+
+    // Queued groups are active
+    let response = ds.get_active_job_groups_origin("Foo")
+        .expect("Failed to get active groups");
+    assert_eq!(response.get_job_groups().len(), 1);
+    assert_eq!(response.get_job_groups()[0].get_id(), group.get_id());
+    let response = ds.get_active_job_groups_origin("Fo")
+        .expect("Failed to get active groups");
+    assert!(response.get_job_groups().is_empty());
+
+    ds.set_job_group_state(group.get_id(), jobsrv::JobGroupState::GroupComplete)
+        .expect("Failed to update group state");
+    let response = ds.get_active_job_groups_origin("Foo")
+        .expect("Failed to get active groups");
+    assert!(response.get_job_groups().is_empty());
+}
+
+#[test]
+fn transition_job_group_state() {
+    let project_names = vec![(
+        String::from("Foo/Bar"),
+        String::from("Foo/Bar/0/Baz"),
+        String::from("x86_64-linux"),
+    )];
+    let mut msg = jobsrv::JobGroupSpec::new();
+    msg.set_origin(String::from("Foo"));
+    msg.set_package(String::from("Bar"));
+
+    let ds = datastore_test!(DataStore);
+
+    let group = ds.create_job_group(&msg, project_names.clone())
+        .expect("Failed to create a group");
+    ds.set_job_group_state(group.get_id(), jobsrv::JobGroupState::GroupDispatching)
+        .expect("Failed to update group state");
+    let paused = ds.pause_job_group(group.get_id())
+        .expect("Failed to pause group");
+    assert!(paused);
+
+    // A group paused while the scheduler was looking at it stays paused
+    let updated = ds.transition_job_group_state(
+        group.get_id(),
+        jobsrv::JobGroupState::GroupDispatching,
+        jobsrv::JobGroupState::GroupPending,
+    ).expect("Failed to update group state");
+    assert!(!updated);
+    let updated = ds.transition_job_group_state(
+        group.get_id(),
+        jobsrv::JobGroupState::GroupPaused,
+        jobsrv::JobGroupState::GroupResuming,
+    ).expect("Failed to update group state");
+    assert!(updated);
+
+    let mut get_msg = jobsrv::JobGroupGet::new();
+    get_msg.set_group_id(group.get_id());
+    let updated_group = ds.get_job_group(&get_msg)
+        .expect("Failed to get group from database")
+        .expect("No group found");
+    assert_eq!(
+        updated_group.get_state(),
+        jobsrv::JobGroupState::GroupResuming
+    );
+}
+
 #[test]
 fn create_graph_package() {
     let mut msg = jobsrv::JobGraphPackageCreate::new();
@@ -26,6 +26,10 @@ pub struct Config {
     pub datastore: DataStoreCfg,
     /// Whether jobsrv is present or not
     pub jobsrv_enabled: bool,
+    /// Seconds between enforcements of channel retention policies
+    pub retention_interval_secs: u64,
+    /// Seconds between attempts at delivering pending webhook events
+    pub webhook_interval_secs: u64,
 }
 
 impl Default for Config {
@@ -36,6 +40,8 @@ impl Default for Config {
             app: AppCfg::default(),
             datastore: datastore,
             jobsrv_enabled: true,
+            retention_interval_secs: 3600,
+            webhook_interval_secs: 30,
         }
     }
 }
@@ -57,6 +63,9 @@ mod tests {
     #[test]
     fn config_from_file() {
         let content = r#"
+        retention_interval_secs = 600
+        webhook_interval_secs = 10
+
         [datastore]
         host = "1.1.1.1"
         port = 9000
@@ -76,5 +85,7 @@ mod tests {
         assert_eq!(config.datastore.connection_timeout_sec, 4800);
         assert_eq!(config.datastore.connection_test, true);
         assert_eq!(config.datastore.pool_size, 1);
+        assert_eq!(config.retention_interval_secs, 600);
+        assert_eq!(config.webhook_interval_secs, 10);
     }
 }
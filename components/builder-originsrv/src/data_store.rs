@@ -19,8 +19,11 @@ use std::fmt::Display;
 use std::io;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
 use bldr_core::helpers::transition_visibility;
+use chrono::{DateTime, Utc};
 use db::async::{AsyncServer, EventOutcome};
 use db::config::{DataStoreCfg, ShardId};
 use db::diesel_pool::DieselPool;
@@ -34,12 +37,19 @@ use hab_net::conn::{RouteClient, RouteConn};
 use hab_net::{ErrCode, NetError};
 use postgres;
 use postgres::rows::Rows;
-use protobuf;
+use protobuf::{self, ProtobufEnum};
 use protocol::net::NetOk;
 use protocol::originsrv::Pageable;
 use protocol::{jobsrv, originsrv, sessionsrv};
 
 use error::{SrvError, SrvResult};
+use webhook;
+
+/// Most webhook deliveries attempted on a shard each time deliveries are processed
+const WEBHOOK_DELIVERY_BATCH: i64 = 100;
+
+/// Seconds a batch of webhook deliveries stays claimed by the originsrv attempting it
+const WEBHOOK_DELIVERY_CLAIM_SECS: i64 = 3600;
 
 #[derive(Clone)]
 pub struct DataStore {
@@ -104,6 +114,10 @@ impl DataStore {
             .register("sync_invitations".to_string(), sync_invitations);
         self.async
             .register("sync_origins".to_string(), sync_origins);
+        self.async
+            .register("prune_channels".to_string(), prune_channels);
+        self.async
+            .register("deliver_webhooks".to_string(), deliver_webhooks);
 
         if jobsrv_enabled {
             self.async
@@ -116,6 +130,21 @@ impl DataStore {
         async_thread.start(4);
     }
 
+    /// Runs a registered async event again every `interval`, for events which perform periodic
+    /// upkeep rather than sync changes to other services.
+    pub fn schedule_periodic(&self, dispatch_key: &'static str, interval: Duration) {
+        let async_server = self.async.clone();
+        thread::Builder::new()
+            .name(format!("{}-timer", dispatch_key))
+            .spawn(move || loop {
+                thread::sleep(interval);
+                if let Err(e) = async_server.schedule(dispatch_key) {
+                    warn!("Unable to schedule {}, {}", dispatch_key, e);
+                }
+            })
+            .unwrap();
+    }
+
     pub fn package_channel_audit(&self, pca: &originsrv::PackageChannelAudit) -> SrvResult<()> {
         let conn = self.pool.get(pca)?;
 
@@ -135,6 +164,25 @@ impl DataStore {
         Ok(())
     }
 
+    pub fn package_delete_audit(&self, pda: &originsrv::PackageDeleteAudit) -> SrvResult<()> {
+        let conn = self.pool.get(pda)?;
+
+        conn.query(
+            "SELECT * FROM add_audit_package_delete_entry_v1($1, $2, $3, $4, $5, $6, $7)",
+            &[
+                &(pda.get_origin_id() as i64),
+                &pda.get_ident().to_string(),
+                &pda.get_target().to_string(),
+                &(pda.get_operation() as i16),
+                &(pda.get_trigger() as i16),
+                &(pda.get_requester_id() as i64),
+                &pda.get_requester_name().to_string(),
+            ],
+        ).map_err(SrvError::PackageDeleteAudit)?;
+
+        Ok(())
+    }
+
     pub fn package_group_channel_audit(
         &self,
         pgca: &originsrv::PackageGroupChannelAudit,
@@ -164,6 +212,83 @@ impl DataStore {
         Ok(())
     }
 
+    pub fn origin_audit(&self, oa: &originsrv::OriginAudit) -> SrvResult<()> {
+        let conn = self.pool.get(oa)?;
+
+        conn.query(
+            "SELECT * FROM add_audit_origin_entry_v1($1, $2, $3, $4, $5, $6)",
+            &[
+                &(oa.get_origin_id() as i64),
+                &(oa.get_operation() as i16),
+                &oa.get_subject(),
+                &(oa.get_trigger() as i16),
+                &(oa.get_requester_id() as i64),
+                &oa.get_requester_name(),
+            ],
+        ).map_err(SrvError::OriginAudit)?;
+
+        Ok(())
+    }
+
+    pub fn list_origin_audit(
+        &self,
+        oalr: &originsrv::OriginAuditListRequest,
+    ) -> SrvResult<originsrv::OriginAuditListResponse> {
+        let conn = self.pool.get(oalr)?;
+        let from = parse_timestamp(oalr.get_from());
+        let to = parse_timestamp(oalr.get_to());
+
+        let rows = conn.query(
+            "SELECT COUNT(*) OVER () AS the_real_total, * FROM get_origin_audit_v1($1, $2, $3, $4, $5, $6) ORDER BY created_at DESC LIMIT $7 OFFSET $8",
+            &[
+                &(oalr.get_origin_id() as i64),
+                &oalr.get_requester_name(),
+                &oalr.get_operation(),
+                &oalr.get_package(),
+                &from,
+                &to,
+                &oalr.limit(),
+                &(oalr.get_start() as i64),
+            ],
+        ).map_err(SrvError::OriginAuditList)?;
+
+        let mut response = originsrv::OriginAuditListResponse::new();
+        response.set_start(oalr.get_start());
+        response.set_stop(self.last_index(oalr, &rows));
+        let mut entries = protobuf::RepeatedField::new();
+        for row in rows.iter() {
+            let count: i64 = row.get("the_real_total");
+            response.set_count(count as u64);
+
+            let mut entry = originsrv::OriginAuditEntry::new();
+            entry.set_operation(row.get("operation"));
+            if let Some(Ok(package)) = row.get_opt::<&str, String>("package") {
+                entry.set_package(package);
+            }
+            if let Some(Ok(channel)) = row.get_opt::<&str, String>("channel") {
+                entry.set_channel(channel);
+            }
+            if let Some(Ok(subject)) = row.get_opt::<&str, String>("subject") {
+                entry.set_subject(subject);
+            }
+            let trigger: i16 = row.get("trigger");
+            if let Some(trigger) = originsrv::PackageChannelTrigger::from_i32(trigger as i32) {
+                entry.set_trigger(trigger.to_string());
+            }
+            let requester_id: i64 = row.get("requester_id");
+            entry.set_requester_id(requester_id as u64);
+            entry.set_requester_name(row.get("requester_name"));
+            let created_at = row.get::<&str, DateTime<Utc>>("created_at");
+            entry.set_created_at(created_at.to_rfc3339());
+            if let Some(Ok(group_id)) = row.get_opt::<&str, i64>("group_id") {
+                entry.set_group_id(group_id as u64);
+            }
+            entries.push(entry);
+        }
+        response.set_entries(entries);
+        Ok(response)
+    }
+
     pub fn update_origin_package(&self, opu: &originsrv::OriginPackageUpdate) -> SrvResult<()> {
         let conn = self.pool.get(opu)?;
         let pkg = opu.get_pkg();
@@ -193,9 +318,11 @@ impl DataStore {
     pub fn update_origin_project(&self, opc: &originsrv::OriginProjectUpdate) -> SrvResult<()> {
         let conn = self.pool.get(opc)?;
         let project = opc.get_project();
+        let (vcs_auth_user, vcs_auth_token) = vcs_auth(project);
 
         conn.execute(
-            "SELECT update_origin_project_v4($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+            "SELECT update_origin_project_v6($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, \
+             $13, $14)",
             &[
                 &(project.get_id() as i64),
                 &(project.get_origin_id() as i64),
@@ -207,6 +334,10 @@ impl DataStore {
                 &(project.get_vcs_installation_id() as i64),
                 &project.get_visibility().to_string(),
                 &project.get_auto_build(),
+                &project.get_required_labels().to_vec(),
+                &vcs_auth_user,
+                &vcs_auth_token,
+                &hook_secret(project),
             ],
         ).map_err(SrvError::OriginProjectUpdate)?;
 
@@ -285,10 +416,26 @@ impl DataStore {
         project.set_vcs_data(row.get("vcs_data"));
         project.set_auto_build(row.get("auto_build"));
 
+        if let Some(Ok(labels)) = row.get_opt::<&str, Vec<String>>("required_labels") {
+            project.set_required_labels(protobuf::RepeatedField::from_vec(labels));
+        }
+
         if let Some(Ok(install_id)) = row.get_opt::<&str, i64>("vcs_installation_id") {
             project.set_vcs_installation_id(install_id as u32);
         }
 
+        if let Some(Ok(user)) = row.get_opt::<&str, String>("vcs_auth_user") {
+            project.set_vcs_auth_user(user);
+        }
+
+        if let Some(Ok(token)) = row.get_opt::<&str, String>("vcs_auth_token") {
+            project.set_vcs_auth_token(token);
+        }
+
+        if let Some(Ok(secret)) = row.get_opt::<&str, String>("hook_secret") {
+            project.set_hook_secret(secret);
+        }
+
         let pv: String = row.get("visibility");
         let pv2: originsrv::OriginPackageVisibility = pv.parse()
             .map_err(SrvError::UnknownOriginPackageVisibility)?;
@@ -310,8 +457,10 @@ impl DataStore {
                 None
             }
         };
+        let (vcs_auth_user, vcs_auth_token) = vcs_auth(project);
         let rows = conn.query(
-            "SELECT * FROM insert_origin_project_v5($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+            "SELECT * FROM insert_origin_project_v7($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, \
+             $12, $13)",
             &[
                 &project.get_origin_name(),
                 &project.get_package_name(),
@@ -322,6 +471,10 @@ impl DataStore {
                 &install_id,
                 &project.get_visibility().to_string(),
                 &project.get_auto_build(),
+                &project.get_required_labels().to_vec(),
+                &vcs_auth_user,
+                &vcs_auth_token,
+                &hook_secret(project),
             ],
         ).map_err(SrvError::OriginProjectCreate)?;
         let row = rows.get(0);
@@ -446,11 +599,14 @@ impl DataStore {
     ) -> SrvResult<bool> {
         let conn = self.pool.get(coar)?;
         let rows = &conn.query(
-            "SELECT * FROM check_account_in_origin_members_v1($1, $2)",
+            "SELECT * FROM get_origin_member_role_v1($1, $2)",
             &[&coar.get_origin_name(), &(coar.get_account_id() as i64)],
         ).map_err(SrvError::OriginAccountInOrigin)?;
         if rows.len() != 0 {
-            Ok(true)
+            let role: String = rows.get(0).get("role");
+            let role = originsrv::OriginMemberRole::from_str(&role)
+                .map_err(SrvError::UnknownOriginMemberRole)?;
+            Ok(role.permits(coar.get_role()))
         } else {
             Ok(false)
         }
@@ -484,7 +640,7 @@ impl DataStore {
     ) -> SrvResult<originsrv::OriginMemberListResponse> {
         let conn = self.pool.get(omlr)?;
         let rows = &conn.query(
-            "SELECT * FROM list_origin_members_v1($1)",
+            "SELECT * FROM list_origin_members_v2($1)",
             &[&(omlr.get_origin_id() as i64)],
         ).map_err(SrvError::OriginMemberList)?;
 
@@ -492,14 +648,55 @@ impl DataStore {
         response.set_origin_id(omlr.get_origin_id());
 
         let mut members = protobuf::RepeatedField::new();
+        let mut roles = protobuf::RepeatedField::new();
         for row in rows {
-            members.push(row.get("account_name"));
+            let member = self.row_to_origin_member(&row)?;
+            members.push(member.get_account_name().to_string());
+            roles.push(member);
         }
 
         response.set_members(members);
+        response.set_roles(roles);
         Ok(response)
     }
 
+    pub fn update_origin_member_role(
+        &self,
+        omru: &originsrv::OriginMemberRoleUpdate,
+    ) -> SrvResult<Option<originsrv::OriginMember>> {
+        let conn = self.pool.get(omru)?;
+        let rows = &conn.query(
+            "SELECT * FROM update_origin_member_role_v1($1, $2, $3)",
+            &[
+                &(omru.get_origin_id() as i64),
+                &omru.get_account_name(),
+                &omru.get_role().to_string(),
+            ],
+        ).map_err(SrvError::OriginMemberRoleUpdate)?;
+
+        if rows.len() != 0 {
+            Ok(Some(self.row_to_origin_member(&rows.get(0))?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn row_to_origin_member(
+        &self,
+        row: &postgres::rows::Row,
+    ) -> SrvResult<originsrv::OriginMember> {
+        let mut member = originsrv::OriginMember::new();
+        let account_id: i64 = row.get("account_id");
+        member.set_account_id(account_id as u64);
+        member.set_account_name(row.get("account_name"));
+        let role: String = row.get("role");
+        member.set_role(
+            originsrv::OriginMemberRole::from_str(&role)
+                .map_err(SrvError::UnknownOriginMemberRole)?,
+        );
+        Ok(member)
+    }
+
     // This function can fail if the corresponding sessionsrv shard is down - this is so that the
     // user won't experience delay on seeing the invitation be accepted.
     pub fn accept_origin_invitation(
@@ -1022,7 +1219,7 @@ impl DataStore {
         }
 
         let rows = conn.query(
-            "SELECT * FROM insert_origin_v2($1, $2, $3, $4)",
+            "SELECT * FROM insert_origin_v3($1, $2, $3, $4)",
             &[
                 &origin.get_name(),
                 &(origin.get_owner_id() as i64),
@@ -1102,7 +1299,8 @@ impl DataStore {
         let ident = opc.get_ident();
 
         let rows = conn.query(
-            "SELECT * FROM insert_origin_package_v3($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)",
+            "SELECT * FROM insert_origin_package_v4($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, \
+             $12, $13)",
             &[
                 &(opc.get_origin_id() as i64),
                 &(opc.get_owner_id() as i64),
@@ -1115,7 +1313,8 @@ impl DataStore {
                 &self.into_delimited(opc.get_deps().to_vec()),
                 &self.into_delimited(opc.get_tdeps().to_vec()),
                 &self.into_delimited(opc.get_exposes().to_vec()),
-                &opc.get_visibility().to_string()
+                &opc.get_visibility().to_string(),
+                &opc.get_skip_unstable(),
             ],
         ).map_err(SrvError::OriginPackageCreate)?;
 
@@ -1148,6 +1347,14 @@ impl DataStore {
         }
     }
 
+    pub fn delete_origin_package(&self, opd: &originsrv::OriginPackageDelete) -> SrvResult<()> {
+        let conn = self.pool.get(opd)?;
+
+        conn.execute("SELECT delete_origin_package_v1($1)", &[&(opd.get_id() as i64)])
+            .map_err(SrvError::OriginPackageDelete)?;
+        Ok(())
+    }
+
     pub fn get_origin_channel_package(
         &self,
         ocpg: &originsrv::OriginChannelPackageGet,
@@ -1459,7 +1666,23 @@ impl DataStore {
     ) -> SrvResult<originsrv::OriginPackageListResponse> {
         let conn = self.pool.get(ops)?;
 
-        let rows = if *&ops.get_distinct() {
+        let rows = if ops.get_full_text() {
+            // When searching an origin, its packages are the ones of interest, not other
+            // origins' packages that mention it
+            conn.query(
+                "SELECT COUNT(*) OVER () AS the_real_total, * FROM search_all_origin_packages_ranked_v1($1, $2, $3, $4, $5, $6) ORDER BY rank DESC, ident LIMIT $7 OFFSET $8",
+                &[
+                    &ops.get_query(),
+                    &ops.get_origin(),
+                    &ops.get_target(),
+                    &ops.get_channel(),
+                    &self.vec_to_delimited_string(ops.get_my_origins()),
+                    &ops.get_distinct(),
+                    &ops.limit(),
+                    &(ops.get_start() as i64),
+                ],
+            ).map_err(SrvError::OriginPackageSearch)?
+        } else if *&ops.get_distinct() {
             conn.query(
                 "SELECT COUNT(*) OVER () AS the_real_total, * FROM search_all_origin_packages_dynamic_v6($1, $2) ORDER BY ident LIMIT $3 OFFSET $4",
                 &[
@@ -1504,7 +1727,10 @@ impl DataStore {
             idents.push(self.row_to_origin_package_ident(&row));
         }
 
-        idents.sort_by(|a, b| a.cmp(b));
+        // Full text results are already in order of relevance
+        if !ops.get_full_text() {
+            idents.sort_by(|a, b| a.cmp(b));
+        }
         response.set_idents(idents);
         Ok(response)
     }
@@ -1735,6 +1961,72 @@ impl DataStore {
         Ok(())
     }
 
+    pub fn get_origin_channel_retention(
+        &self,
+        ocrg: &originsrv::OriginChannelRetentionGet,
+    ) -> SrvResult<Option<originsrv::OriginChannelRetention>> {
+        let conn = self.pool.get(ocrg)?;
+        let rows = conn.query(
+            "SELECT * FROM get_origin_channel_retention_v1($1)",
+            &[&(ocrg.get_channel_id() as i64)],
+        ).map_err(SrvError::OriginChannelRetentionGet)?;
+
+        if rows.len() != 0 {
+            let row = rows.get(0);
+            Ok(Some(self.row_to_origin_channel_retention(&row)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn set_origin_channel_retention(
+        &self,
+        ocrs: &originsrv::OriginChannelRetentionSet,
+    ) -> SrvResult<originsrv::OriginChannelRetention> {
+        let conn = self.pool.get(ocrs)?;
+        let retention = ocrs.get_retention();
+        let rows = conn.query(
+            "SELECT * FROM upsert_origin_channel_retention_v1($1, $2, $3, $4)",
+            &[
+                &(retention.get_channel_id() as i64),
+                &(retention.get_origin_id() as i64),
+                &(retention.get_keep_latest() as i32),
+                &(retention.get_max_age_days() as i32),
+            ],
+        ).map_err(SrvError::OriginChannelRetentionSet)?;
+
+        let row = rows.get(0);
+        Ok(self.row_to_origin_channel_retention(&row))
+    }
+
+    pub fn delete_origin_channel_retention(
+        &self,
+        ocrd: &originsrv::OriginChannelRetentionDelete,
+    ) -> SrvResult<()> {
+        let conn = self.pool.get(ocrd)?;
+        conn.execute(
+            "SELECT delete_origin_channel_retention_v1($1)",
+            &[&(ocrd.get_channel_id() as i64)],
+        ).map_err(SrvError::OriginChannelRetentionDelete)?;
+        Ok(())
+    }
+
+    fn row_to_origin_channel_retention(
+        &self,
+        row: &postgres::rows::Row,
+    ) -> originsrv::OriginChannelRetention {
+        let mut retention = originsrv::OriginChannelRetention::new();
+        let channel_id: i64 = row.get("channel_id");
+        retention.set_channel_id(channel_id as u64);
+        let origin_id: i64 = row.get("origin_id");
+        retention.set_origin_id(origin_id as u64);
+        let keep_latest: i32 = row.get("keep_latest");
+        retention.set_keep_latest(keep_latest as u32);
+        let max_age_days: i32 = row.get("max_age_days");
+        retention.set_max_age_days(max_age_days as u32);
+        retention
+    }
+
     pub fn upsert_origin_integration(
         &self,
         oic: &originsrv::OriginIntegrationCreate,
@@ -1843,6 +2135,265 @@ impl DataStore {
         }
     }
 
+    /// Deletes the origin. Returns false, and leaves the origin alone, if it still has packages or
+    /// projects and the request doesn't ask for them to be purged.
+    pub fn delete_origin(
+        &self,
+        od: &originsrv::OriginDelete,
+        origin_id: u64,
+    ) -> SrvResult<bool> {
+        let conn = self.pool.get(od)?;
+        let tr = conn.transaction().map_err(SrvError::DbTransactionStart)?;
+
+        let rows = tr.query(
+            "SELECT * FROM count_origin_contents_v1($1)",
+            &[&(origin_id as i64)],
+        ).map_err(SrvError::OriginDelete)?;
+        let row = rows.get(0);
+        let packages: i64 = row.get("packages");
+        let projects: i64 = row.get("projects");
+        if (packages > 0 || projects > 0) && !od.get_purge() {
+            return Ok(false);
+        }
+
+        tr.execute("SELECT delete_origin_v1($1)", &[&(origin_id as i64)])
+            .map_err(SrvError::OriginDelete)?;
+        tr.commit().map_err(SrvError::DbTransactionCommit)?;
+        Ok(true)
+    }
+
+    pub fn create_origin_owner_transfer(
+        &self,
+        ootc: &originsrv::OriginOwnerTransferCreate,
+    ) -> SrvResult<Option<originsrv::OriginOwnerTransfer>> {
+        let conn = self.pool.get(ootc)?;
+        let rows = &conn.query(
+            "SELECT * FROM upsert_origin_owner_transfer_v1($1, $2, $3, $4, $5)",
+            &[
+                &(ootc.get_origin_id() as i64),
+                &ootc.get_origin_name(),
+                &(ootc.get_owner_id() as i64),
+                &(ootc.get_account_id() as i64),
+                &ootc.get_account_name(),
+            ],
+        ).map_err(SrvError::OriginOwnerTransferCreate)?;
+
+        if rows.len() != 0 {
+            Ok(Some(self.row_to_origin_owner_transfer(&rows.get(0))))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn get_origin_owner_transfer(
+        &self,
+        ootg: &originsrv::OriginOwnerTransferGet,
+    ) -> SrvResult<Option<originsrv::OriginOwnerTransfer>> {
+        let conn = self.pool.get(ootg)?;
+        let rows = &conn.query(
+            "SELECT * FROM get_origin_owner_transfer_v1($1)",
+            &[&(ootg.get_origin_id() as i64)],
+        ).map_err(SrvError::OriginOwnerTransferGet)?;
+
+        if rows.len() != 0 {
+            Ok(Some(self.row_to_origin_owner_transfer(&rows.get(0))))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Makes the recipient of the origin's pending transfer its owner. Returns the updated
+    /// origin, or `None` if there is no transfer to the account.
+    pub fn accept_origin_owner_transfer(
+        &self,
+        oota: &originsrv::OriginOwnerTransferAccept,
+    ) -> SrvResult<Option<originsrv::Origin>> {
+        let conn = self.pool.get(oota)?;
+        let rows = conn.query(
+            "SELECT * FROM accept_origin_owner_transfer_v1($1, $2)",
+            &[
+                &(oota.get_origin_id() as i64),
+                &(oota.get_account_id() as i64),
+            ],
+        ).map_err(SrvError::OriginOwnerTransferAccept)?;
+
+        if rows.len() != 0 {
+            Ok(Some(self.row_to_origin(rows.get(0))?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn rescind_origin_owner_transfer(
+        &self,
+        ootr: &originsrv::OriginOwnerTransferRescind,
+    ) -> SrvResult<bool> {
+        let conn = self.pool.get(ootr)?;
+        let rows = &conn.query(
+            "SELECT * FROM delete_origin_owner_transfer_v1($1)",
+            &[&(ootr.get_origin_id() as i64)],
+        ).map_err(SrvError::OriginOwnerTransferRescind)?;
+        Ok(rows.len() != 0)
+    }
+
+    fn row_to_origin_owner_transfer(
+        &self,
+        row: &postgres::rows::Row,
+    ) -> originsrv::OriginOwnerTransfer {
+        let mut transfer = originsrv::OriginOwnerTransfer::new();
+        let origin_id: i64 = row.get("origin_id");
+        transfer.set_origin_id(origin_id as u64);
+        transfer.set_origin_name(row.get("origin_name"));
+        let owner_id: i64 = row.get("owner_id");
+        transfer.set_owner_id(owner_id as u64);
+        let account_id: i64 = row.get("account_id");
+        transfer.set_account_id(account_id as u64);
+        transfer.set_account_name(row.get("account_name"));
+        transfer
+    }
+
+    pub fn create_origin_webhook(
+        &self,
+        owc: &originsrv::OriginWebhookCreate,
+    ) -> SrvResult<originsrv::OriginWebhook> {
+        let conn = self.pool.get(owc)?;
+        let webhook = owc.get_webhook();
+        let events: Vec<String> = webhook.get_events().iter().map(|e| e.to_string()).collect();
+        let rows = conn.query(
+            "SELECT * FROM insert_origin_webhook_v1($1, $2, $3, $4, $5)",
+            &[
+                &(webhook.get_origin_id() as i64),
+                &webhook.get_origin_name(),
+                &webhook.get_url(),
+                &webhook.get_secret(),
+                &events,
+            ],
+        ).map_err(SrvError::OriginWebhookCreate)?;
+        self.row_to_origin_webhook(&rows.get(0))
+    }
+
+    pub fn list_origin_webhooks(
+        &self,
+        owlr: &originsrv::OriginWebhookListRequest,
+    ) -> SrvResult<originsrv::OriginWebhookListResponse> {
+        let conn = self.pool.get(owlr)?;
+        let rows = conn.query(
+            "SELECT * FROM list_origin_webhooks_v1($1)",
+            &[&(owlr.get_origin_id() as i64)],
+        ).map_err(SrvError::OriginWebhookList)?;
+
+        let mut response = originsrv::OriginWebhookListResponse::new();
+        let mut webhooks = protobuf::RepeatedField::new();
+        for row in rows.iter() {
+            webhooks.push(self.row_to_origin_webhook(&row)?);
+        }
+        response.set_webhooks(webhooks);
+        Ok(response)
+    }
+
+    /// Deletes a webhook along with its delivery history. Returns `false` if the origin has no
+    /// such webhook.
+    pub fn delete_origin_webhook(&self, owd: &originsrv::OriginWebhookDelete) -> SrvResult<bool> {
+        let conn = self.pool.get(owd)?;
+        let rows = conn.query(
+            "SELECT * FROM delete_origin_webhook_v1($1, $2)",
+            &[&(owd.get_id() as i64), &(owd.get_origin_id() as i64)],
+        ).map_err(SrvError::OriginWebhookDelete)?;
+        Ok(rows.len() != 0)
+    }
+
+    /// Queues a delivery of the event to each webhook of the origin subscribed to it, returning
+    /// the number of deliveries queued.
+    pub fn notify_origin_webhooks(&self, own: &originsrv::OriginWebhookNotify) -> SrvResult<usize> {
+        let conn = self.pool.get(own)?;
+        let event = own.get_event().to_string();
+        let body = webhook::body(&event, own.get_origin_name(), own.get_payload());
+        let rows = conn.query(
+            "SELECT * FROM insert_origin_webhook_deliveries_v1($1, $2, $3)",
+            &[&own.get_origin_name(), &event, &body],
+        ).map_err(SrvError::OriginWebhookNotify)?;
+
+        if rows.len() > 0 {
+            self.async.schedule("deliver_webhooks")?;
+        }
+        Ok(rows.len())
+    }
+
+    pub fn list_origin_webhook_deliveries(
+        &self,
+        owdlr: &originsrv::OriginWebhookDeliveryListRequest,
+    ) -> SrvResult<originsrv::OriginWebhookDeliveryListResponse> {
+        let conn = self.pool.get(owdlr)?;
+        let rows = conn.query(
+            "SELECT * FROM list_origin_webhook_deliveries_v1($1, $2, $3)",
+            &[
+                &(owdlr.get_webhook_id() as i64),
+                &(owdlr.get_origin_id() as i64),
+                &(owdlr.get_limit() as i64),
+            ],
+        ).map_err(SrvError::OriginWebhookDeliveryList)?;
+
+        let mut response = originsrv::OriginWebhookDeliveryListResponse::new();
+        let mut deliveries = protobuf::RepeatedField::new();
+        for row in rows.iter() {
+            deliveries.push(self.row_to_origin_webhook_delivery(&row)?);
+        }
+        response.set_deliveries(deliveries);
+        Ok(response)
+    }
+
+    fn row_to_origin_webhook(
+        &self,
+        row: &postgres::rows::Row,
+    ) -> SrvResult<originsrv::OriginWebhook> {
+        let mut webhook = originsrv::OriginWebhook::new();
+        let id: i64 = row.get("id");
+        webhook.set_id(id as u64);
+        let origin_id: i64 = row.get("origin_id");
+        webhook.set_origin_id(origin_id as u64);
+        webhook.set_origin_name(row.get("origin_name"));
+        webhook.set_url(row.get("url"));
+        webhook.set_secret(row.get("secret"));
+        let names: Vec<String> = row.get("events");
+        let mut events = Vec::new();
+        for name in names {
+            events.push(
+                originsrv::OriginWebhookEvent::from_str(&name)
+                    .map_err(SrvError::UnknownOriginWebhookEvent)?,
+            );
+        }
+        webhook.set_events(events);
+        Ok(webhook)
+    }
+
+    fn row_to_origin_webhook_delivery(
+        &self,
+        row: &postgres::rows::Row,
+    ) -> SrvResult<originsrv::OriginWebhookDelivery> {
+        let mut delivery = originsrv::OriginWebhookDelivery::new();
+        let id: i64 = row.get("id");
+        delivery.set_id(id as u64);
+        let webhook_id: i64 = row.get("webhook_id");
+        delivery.set_webhook_id(webhook_id as u64);
+        let event: String = row.get("event");
+        delivery.set_event(
+            originsrv::OriginWebhookEvent::from_str(&event)
+                .map_err(SrvError::UnknownOriginWebhookEvent)?,
+        );
+        delivery.set_body(row.get("body"));
+        let attempts: i32 = row.get("attempts");
+        delivery.set_attempts(attempts as u32);
+        let status_code: i32 = row.get("status_code");
+        delivery.set_status_code(status_code as u32);
+        delivery.set_error(row.get("error"));
+        delivery.set_delivered(row.get("delivered"));
+        let created_at = row.get::<&str, DateTime<Utc>>("created_at");
+        delivery.set_created_at(created_at.to_rfc3339());
+        let updated_at = row.get::<&str, DateTime<Utc>>("updated_at");
+        delivery.set_updated_at(updated_at.to_rfc3339());
+        Ok(delivery)
+    }
+
     pub fn delete_origin_member(&self, omr: &originsrv::OriginMemberRemove) -> SrvResult<()> {
         let conn = self.pool.get(omr)?;
 
@@ -1946,6 +2497,16 @@ impl DataStore {
     }
 }
 
+/// Parses an optional RFC 3339 bound for audit queries; empty or malformed values are unbounded.
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    if value.is_empty() {
+        return None;
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .ok()
+}
+
 fn sync_origins(pool: Pool, mut route_conn: RouteClient) -> DbResult<EventOutcome> {
     let mut result = EventOutcome::Finished;
     for shard in pool.shards.iter() {
@@ -1984,6 +2545,85 @@ fn sync_origins(pool: Pool, mut route_conn: RouteClient) -> DbResult<EventOutcom
     Ok(result)
 }
 
+fn prune_channels(pool: Pool, _route_conn: RouteClient) -> DbResult<EventOutcome> {
+    for shard in pool.shards.iter() {
+        let conn = pool.get_shard(*shard)?;
+        let rows = &conn.query("SELECT * FROM prune_origin_channels_v1()", &[])
+            .map_err(DbError::AsyncFunctionUpdate)?;
+        if rows.len() > 0 {
+            info!(
+                "Demoted {} releases exceeding channel retention policies on shard {}",
+                rows.len(),
+                shard
+            );
+        }
+    }
+    Ok(EventOutcome::Finished)
+}
+
+/// Returns the deploy token of a project, `None` for projects which don't have one.
+fn vcs_auth(project: &originsrv::OriginProject) -> (Option<&str>, Option<&str>) {
+    if project.has_vcs_auth_token() {
+        (
+            Some(project.get_vcs_auth_user()),
+            Some(project.get_vcs_auth_token()),
+        )
+    } else {
+        (None, None)
+    }
+}
+
+/// Returns the hook secret of a project, `None` for projects which don't have one.
+fn hook_secret(project: &originsrv::OriginProject) -> Option<&str> {
+    if project.has_hook_secret() {
+        Some(project.get_hook_secret())
+    } else {
+        None
+    }
+}
+
+fn deliver_webhooks(pool: Pool, _route_conn: RouteClient) -> DbResult<EventOutcome> {
+    for shard in pool.shards.iter() {
+        let conn = pool.get_shard(*shard)?;
+        let rows = &conn.query(
+            "SELECT * FROM pending_origin_webhook_deliveries_v1($1, $2, $3)",
+            &[
+                &webhook::MAX_ATTEMPTS,
+                &WEBHOOK_DELIVERY_BATCH,
+                &WEBHOOK_DELIVERY_CLAIM_SECS,
+            ],
+        ).map_err(DbError::AsyncFunctionCheck)?;
+
+        for row in rows.iter() {
+            let id: i64 = row.get("id");
+            let attempts: i32 = row.get("attempts");
+            let event: String = row.get("event");
+            let url: String = row.get("url");
+            let secret: String = row.get("secret");
+            let body: String = row.get("body");
+
+            let attempt = webhook::deliver(id as u64, &url, &secret, &event, &body);
+            if !attempt.delivered() {
+                warn!(
+                    "Webhook delivery {} of {} to {} failed, {}",
+                    id, event, url, attempt.error
+                );
+            }
+            conn.execute(
+                "SELECT update_origin_webhook_delivery_v1($1, $2, $3, $4, $5)",
+                &[
+                    &id,
+                    &(attempt.status_code as i32),
+                    &attempt.error,
+                    &attempt.delivered(),
+                    &webhook::retry_delay(attempts + 1),
+                ],
+            ).map_err(DbError::AsyncFunctionUpdate)?;
+        }
+    }
+    Ok(EventOutcome::Finished)
+}
+
 fn sync_packages(pool: Pool, mut route_conn: RouteClient) -> DbResult<EventOutcome> {
     let mut result = EventOutcome::Finished;
     for shard in pool.shards.iter() {
@@ -46,10 +46,14 @@ pub enum SrvError {
     OriginChannelGet(postgres::error::Error),
     OriginChannelList(postgres::error::Error),
     OriginChannelDelete(postgres::error::Error),
+    OriginChannelRetentionDelete(postgres::error::Error),
+    OriginChannelRetentionGet(postgres::error::Error),
+    OriginChannelRetentionSet(postgres::error::Error),
     OriginChannelPackageGet(postgres::error::Error),
     OriginChannelPackageLatestGet(postgres::error::Error),
     OriginChannelPackageList(postgres::error::Error),
     OriginCheckAccess(postgres::error::Error),
+    OriginDelete(postgres::error::Error),
     OriginGet(postgres::error::Error),
     OriginMemberList(postgres::error::Error),
     OriginIntegrationCreate(postgres::error::Error),
@@ -66,7 +70,13 @@ pub enum SrvError {
     OriginInvitationListForAccount(postgres::error::Error),
     OriginInvitationValidate(postgres::error::Error),
     OriginMemberDelete(postgres::error::Error),
+    OriginMemberRoleUpdate(postgres::error::Error),
+    OriginOwnerTransferAccept(postgres::error::Error),
+    OriginOwnerTransferCreate(postgres::error::Error),
+    OriginOwnerTransferGet(postgres::error::Error),
+    OriginOwnerTransferRescind(postgres::error::Error),
     OriginPackageCreate(postgres::error::Error),
+    OriginPackageDelete(postgres::error::Error),
     OriginPackageGet(postgres::error::Error),
     OriginPackageLatestGet(postgres::error::Error),
     OriginPackageChannelList(postgres::error::Error),
@@ -106,14 +116,24 @@ pub enum SrvError {
     OriginSecretGet(postgres::error::Error),
     OriginSecretList(postgres::error::Error),
     OriginUpdate(postgres::error::Error),
+    OriginWebhookCreate(postgres::error::Error),
+    OriginWebhookDelete(postgres::error::Error),
+    OriginWebhookDeliveryList(postgres::error::Error),
+    OriginWebhookList(postgres::error::Error),
+    OriginWebhookNotify(postgres::error::Error),
     OriginAccountList(postgres::error::Error),
     OriginAccountInOrigin(postgres::error::Error),
     PackageChannelAudit(postgres::error::Error),
+    PackageDeleteAudit(postgres::error::Error),
     PackageGroupChannelAudit(postgres::error::Error),
+    OriginAudit(postgres::error::Error),
+    OriginAuditList(postgres::error::Error),
     Protocol(protocol::ProtocolError),
     SyncInvitations(postgres::error::Error),
     SyncInvitationsUpdate(postgres::error::Error),
     Protobuf(protobuf::ProtobufError),
+    UnknownOriginMemberRole(protocol::originsrv::Error),
+    UnknownOriginWebhookEvent(protocol::originsrv::Error),
     UnknownOriginPackageVisibility(protocol::originsrv::Error),
     VisibilityCascade(postgres::error::Error),
 }
@@ -156,6 +176,15 @@ impl fmt::Display for SrvError {
             SrvError::OriginChannelDelete(ref e) => {
                 format!("Error deleting channel in database, {}", e)
             }
+            SrvError::OriginChannelRetentionDelete(ref e) => {
+                format!("Error deleting channel retention policy in database, {}", e)
+            }
+            SrvError::OriginChannelRetentionGet(ref e) => {
+                format!("Error getting channel retention policy from database, {}", e)
+            }
+            SrvError::OriginChannelRetentionSet(ref e) => {
+                format!("Error setting channel retention policy in database, {}", e)
+            }
             SrvError::OriginChannelPackageGet(ref e) => {
                 format!("Error getting package for a channel from database, {}", e)
             }
@@ -169,6 +198,7 @@ impl fmt::Display for SrvError {
             SrvError::OriginCheckAccess(ref e) => {
                 format!("Error checking access to origin in database, {}", e)
             }
+            SrvError::OriginDelete(ref e) => format!("Error deleting origin in database, {}", e),
             SrvError::OriginGet(ref e) => format!("Error getting origin from database, {}", e),
             SrvError::OriginMemberList(ref e) => {
                 format!("Error getting origin members from database, {}", e)
@@ -221,6 +251,39 @@ impl fmt::Display for SrvError {
             SrvError::OriginMemberDelete(ref e) => {
                 format!("Error deleting member of origin in database, {}", e)
             }
+            SrvError::OriginMemberRoleUpdate(ref e) => {
+                format!("Error updating role of origin member in database, {}", e)
+            }
+            SrvError::OriginOwnerTransferAccept(ref e) => {
+                format!("Error accepting origin ownership transfer in database, {}", e)
+            }
+            SrvError::OriginOwnerTransferCreate(ref e) => {
+                format!("Error creating origin ownership transfer in database, {}", e)
+            }
+            SrvError::OriginOwnerTransferGet(ref e) => {
+                format!("Error getting origin ownership transfer from database, {}", e)
+            }
+            SrvError::OriginOwnerTransferRescind(ref e) => {
+                format!("Error rescinding origin ownership transfer in database, {}", e)
+            }
+            SrvError::OriginPackageDelete(ref e) => {
+                format!("Error deleting package in database, {}", e)
+            }
+            SrvError::OriginWebhookCreate(ref e) => {
+                format!("Error creating origin webhook in database, {}", e)
+            }
+            SrvError::OriginWebhookDelete(ref e) => {
+                format!("Error deleting origin webhook in database, {}", e)
+            }
+            SrvError::OriginWebhookDeliveryList(ref e) => {
+                format!("Error listing origin webhook deliveries from database, {}", e)
+            }
+            SrvError::OriginWebhookList(ref e) => {
+                format!("Error listing origin webhooks from database, {}", e)
+            }
+            SrvError::OriginWebhookNotify(ref e) => {
+                format!("Error queueing origin webhook deliveries in database, {}", e)
+            }
             SrvError::OriginPackageGet(ref e) => {
                 format!("Error getting package in database, {}", e)
             }
@@ -355,9 +418,16 @@ impl fmt::Display for SrvError {
             SrvError::PackageChannelAudit(ref e) => {
                 format!("Error auditing package channel rank change, {}", e)
             }
+            SrvError::PackageDeleteAudit(ref e) => {
+                format!("Error auditing package deletion, {}", e)
+            }
             SrvError::PackageGroupChannelAudit(ref e) => {
                 format!("Error auditing package group channel rank change, {}", e)
             }
+            SrvError::OriginAudit(ref e) => format!("Error auditing origin change, {}", e),
+            SrvError::OriginAuditList(ref e) => {
+                format!("Error listing origin audit entries, {}", e)
+            }
             SrvError::Protocol(ref e) => format!("{}", e),
             SrvError::SyncInvitations(ref e) => {
                 format!("Error syncing invitations for account, {}", e)
@@ -367,6 +437,8 @@ impl fmt::Display for SrvError {
             }
             SrvError::OriginUpdate(ref e) => format!("Error updating origin, {}", e),
             SrvError::Protobuf(ref e) => format!("{}", e),
+            SrvError::UnknownOriginMemberRole(ref e) => format!("{}", e),
+            SrvError::UnknownOriginWebhookEvent(ref e) => format!("{}", e),
             SrvError::UnknownOriginPackageVisibility(ref e) => format!("{}", e),
             SrvError::VisibilityCascade(ref e) => format!("{}", e),
         };
@@ -399,7 +471,11 @@ impl error::Error for SrvError {
             SrvError::OriginChannelPackageLatestGet(ref err) => err.description(),
             SrvError::OriginChannelPackageList(ref err) => err.description(),
             SrvError::OriginCheckAccess(ref err) => err.description(),
+            SrvError::OriginDelete(ref err) => err.description(),
             SrvError::OriginChannelDelete(ref err) => err.description(),
+            SrvError::OriginChannelRetentionDelete(ref err) => err.description(),
+            SrvError::OriginChannelRetentionGet(ref err) => err.description(),
+            SrvError::OriginChannelRetentionSet(ref err) => err.description(),
             SrvError::OriginGet(ref err) => err.description(),
             SrvError::OriginMemberList(ref err) => err.description(),
             SrvError::OriginIntegrationCreate(ref err) => err.description(),
@@ -416,7 +492,18 @@ impl error::Error for SrvError {
             SrvError::OriginInvitationListForAccount(ref err) => err.description(),
             SrvError::OriginInvitationValidate(ref err) => err.description(),
             SrvError::OriginMemberDelete(ref err) => err.description(),
+            SrvError::OriginMemberRoleUpdate(ref err) => err.description(),
+            SrvError::OriginOwnerTransferAccept(ref err) => err.description(),
+            SrvError::OriginOwnerTransferCreate(ref err) => err.description(),
+            SrvError::OriginOwnerTransferGet(ref err) => err.description(),
+            SrvError::OriginOwnerTransferRescind(ref err) => err.description(),
+            SrvError::OriginWebhookCreate(ref err) => err.description(),
+            SrvError::OriginWebhookDelete(ref err) => err.description(),
+            SrvError::OriginWebhookDeliveryList(ref err) => err.description(),
+            SrvError::OriginWebhookList(ref err) => err.description(),
+            SrvError::OriginWebhookNotify(ref err) => err.description(),
             SrvError::OriginPackageCreate(ref err) => err.description(),
+            SrvError::OriginPackageDelete(ref err) => err.description(),
             SrvError::OriginPackageGet(ref err) => err.description(),
             SrvError::OriginPackageLatestGet(ref err) => err.description(),
             SrvError::OriginPackageChannelList(ref err) => err.description(),
@@ -459,11 +546,16 @@ impl error::Error for SrvError {
             SrvError::OriginAccountInOrigin(ref err) => err.description(),
             SrvError::OriginUpdate(ref err) => err.description(),
             SrvError::PackageChannelAudit(ref err) => err.description(),
+            SrvError::PackageDeleteAudit(ref err) => err.description(),
             SrvError::PackageGroupChannelAudit(ref err) => err.description(),
+            SrvError::OriginAudit(ref err) => err.description(),
+            SrvError::OriginAuditList(ref err) => err.description(),
             SrvError::Protocol(ref err) => err.description(),
             SrvError::SyncInvitations(ref err) => err.description(),
             SrvError::SyncInvitationsUpdate(ref err) => err.description(),
             SrvError::Protobuf(ref err) => err.description(),
+            SrvError::UnknownOriginMemberRole(ref err) => err.description(),
+            SrvError::UnknownOriginWebhookEvent(ref err) => err.description(),
             SrvError::UnknownOriginPackageVisibility(ref err) => err.description(),
             SrvError::VisibilityCascade(ref err) => err.description(),
         }
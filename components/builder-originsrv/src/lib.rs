@@ -16,6 +16,7 @@
 #![cfg_attr(feature = "clippy", plugin(clippy))]
 
 extern crate builder_core as bldr_core;
+extern crate chrono;
 extern crate diesel;
 #[macro_use]
 extern crate diesel_migrations;
@@ -23,10 +24,15 @@ extern crate habitat_builder_db as db;
 extern crate habitat_builder_protocol as protocol;
 extern crate habitat_core as hab_core;
 extern crate habitat_net as hab_net;
+extern crate hex;
+#[macro_use]
+extern crate hyper;
+extern crate hyper_openssl;
 #[macro_use]
 extern crate lazy_static;
 #[macro_use]
 extern crate log;
+extern crate openssl;
 extern crate postgres;
 extern crate protobuf;
 extern crate r2d2;
@@ -40,6 +46,7 @@ pub mod config;
 pub mod data_store;
 pub mod error;
 pub mod server;
+pub mod webhook;
 
 pub use self::config::Config;
 pub use self::error::{SrvError, SrvResult};
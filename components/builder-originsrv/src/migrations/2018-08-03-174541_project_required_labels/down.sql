@@ -0,0 +1,3 @@
+DROP FUNCTION IF EXISTS update_origin_project_v5(bigint, bigint, text, text, text, text, bigint, bigint, text, bool, text[]);
+DROP FUNCTION IF EXISTS insert_origin_project_v6(text, text, text, text, text, bigint, bigint, text, bool, text[]);
+ALTER TABLE IF EXISTS origin_projects DROP COLUMN IF EXISTS required_labels;
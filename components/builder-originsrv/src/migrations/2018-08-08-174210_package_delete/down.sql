@@ -0,0 +1,3 @@
+DROP FUNCTION IF EXISTS add_audit_package_delete_entry_v1(bigint, text, text, smallint, smallint, bigint, text);
+DROP FUNCTION IF EXISTS delete_origin_package_v1(bigint);
+DROP TABLE IF EXISTS audit_package_delete;
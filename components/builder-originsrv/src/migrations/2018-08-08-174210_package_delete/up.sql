@@ -0,0 +1,33 @@
+CREATE TABLE IF NOT EXISTS audit_package_delete (
+  origin_id bigint,
+  ident text,
+  target text,
+  operation smallint,
+  trigger smallint,
+  requester_id bigint,
+  requester_name text,
+  created_at timestamptz DEFAULT now()
+);
+
+CREATE OR REPLACE FUNCTION delete_origin_package_v1 (
+  p_id bigint
+) RETURNS void AS $$
+BEGIN
+  DELETE FROM origin_channel_packages WHERE package_id = p_id;
+  DELETE FROM origin_packages WHERE id = p_id;
+END
+$$ LANGUAGE plpgsql VOLATILE;
+
+CREATE OR REPLACE FUNCTION add_audit_package_delete_entry_v1 (
+  p_origin_id bigint,
+  p_ident text,
+  p_target text,
+  p_operation smallint,
+  p_trigger smallint,
+  p_requester_id bigint,
+  p_requester_name text
+) RETURNS SETOF audit_package_delete AS $$
+INSERT INTO audit_package_delete (origin_id, ident, target, operation, trigger, requester_id, requester_name)
+VALUES (p_origin_id, p_ident, p_target, p_operation, p_trigger, p_requester_id, p_requester_name)
+RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
@@ -0,0 +1,5 @@
+DROP FUNCTION IF EXISTS prune_origin_channels_v1();
+DROP FUNCTION IF EXISTS delete_origin_channel_retention_v1(bigint);
+DROP FUNCTION IF EXISTS get_origin_channel_retention_v1(bigint);
+DROP FUNCTION IF EXISTS upsert_origin_channel_retention_v1(bigint, bigint, integer, integer);
+DROP TABLE IF EXISTS origin_channel_retention;
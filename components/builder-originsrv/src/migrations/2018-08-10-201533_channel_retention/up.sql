@@ -0,0 +1,64 @@
+CREATE TABLE IF NOT EXISTS origin_channel_retention (
+  channel_id bigint PRIMARY KEY REFERENCES origin_channels(id) ON DELETE CASCADE,
+  origin_id bigint REFERENCES origins(id),
+  keep_latest integer DEFAULT 0,
+  max_age_days integer DEFAULT 0,
+  created_at timestamptz DEFAULT now(),
+  updated_at timestamptz
+);
+
+CREATE OR REPLACE FUNCTION upsert_origin_channel_retention_v1 (
+  ocr_channel_id bigint,
+  ocr_origin_id bigint,
+  ocr_keep_latest integer,
+  ocr_max_age_days integer
+) RETURNS SETOF origin_channel_retention AS $$
+  INSERT INTO origin_channel_retention (channel_id, origin_id, keep_latest, max_age_days)
+  VALUES (ocr_channel_id, ocr_origin_id, ocr_keep_latest, ocr_max_age_days)
+  ON CONFLICT (channel_id) DO UPDATE
+  SET keep_latest = excluded.keep_latest,
+      max_age_days = excluded.max_age_days,
+      updated_at = now()
+  RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION get_origin_channel_retention_v1 (
+  ocr_channel_id bigint
+) RETURNS SETOF origin_channel_retention AS $$
+  SELECT * FROM origin_channel_retention WHERE channel_id = ocr_channel_id;
+$$ LANGUAGE SQL STABLE;
+
+CREATE OR REPLACE FUNCTION delete_origin_channel_retention_v1 (
+  ocr_channel_id bigint
+) RETURNS void AS $$
+  DELETE FROM origin_channel_retention WHERE channel_id = ocr_channel_id;
+$$ LANGUAGE SQL VOLATILE;
+
+-- Demotes every release which falls outside the retention policy of its channel, recording each
+-- demotion in the package audit log. The latest release of each package is always kept.
+CREATE OR REPLACE FUNCTION prune_origin_channels_v1 (
+) RETURNS SETOF audit_package AS $$
+  WITH ranked AS (
+    SELECT ocp.channel_id, ocp.package_id, ocr.origin_id, ocr.keep_latest, ocr.max_age_days,
+      op.created_at,
+      row_number() OVER (
+        PARTITION BY ocp.channel_id, op.name, op.target ORDER BY op.created_at DESC
+      ) AS rank
+    FROM origin_channel_retention ocr
+    INNER JOIN origin_channel_packages ocp ON ocp.channel_id = ocr.channel_id
+    INNER JOIN origin_packages op ON op.id = ocp.package_id
+  ), pruned AS (
+    DELETE FROM origin_channel_packages ocp
+    USING ranked
+    WHERE ocp.channel_id = ranked.channel_id
+    AND ocp.package_id = ranked.package_id
+    AND ranked.rank > 1
+    AND ((ranked.keep_latest > 0 AND ranked.rank > ranked.keep_latest)
+      OR (ranked.max_age_days > 0
+        AND ranked.created_at < now() - make_interval(days => ranked.max_age_days)))
+    RETURNING ocp.channel_id, ocp.package_id, ranked.origin_id
+  )
+  INSERT INTO audit_package (origin_id, package_id, channel_id, operation, trigger, requester_id, requester_name)
+  SELECT origin_id, package_id, channel_id, 1, 0, 0, 'BUILDER' FROM pruned
+  RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
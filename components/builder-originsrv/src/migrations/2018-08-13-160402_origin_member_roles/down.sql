@@ -0,0 +1,5 @@
+DROP FUNCTION IF EXISTS update_origin_member_role_v1(bigint, text, text);
+DROP FUNCTION IF EXISTS get_origin_member_role_v1(text, bigint);
+DROP FUNCTION IF EXISTS list_origin_members_v2(bigint);
+DROP FUNCTION IF EXISTS insert_origin_v3(text, bigint, text, text);
+ALTER TABLE IF EXISTS origin_members DROP COLUMN IF EXISTS role;
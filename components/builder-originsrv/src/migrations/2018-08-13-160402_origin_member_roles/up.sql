@@ -0,0 +1,59 @@
+ALTER TABLE IF EXISTS origin_members ADD COLUMN IF NOT EXISTS role text NOT NULL DEFAULT 'member';
+
+UPDATE origin_members SET role = 'owner'
+  FROM origins
+  WHERE origins.id = origin_members.origin_id
+  AND origins.owner_id = origin_members.account_id;
+
+CREATE OR REPLACE FUNCTION insert_origin_v3 (
+  origin_name text,
+  origin_owner_id bigint,
+  origin_owner_name text,
+  origin_default_package_visibility text
+) RETURNS SETOF origins AS $$
+  DECLARE
+    inserted_origin origins;
+  BEGIN
+    INSERT INTO origins (name, owner_id, default_package_visibility)
+          VALUES (origin_name, origin_owner_id, origin_default_package_visibility) RETURNING * into inserted_origin;
+    INSERT INTO origin_members (origin_id, origin_name, account_id, account_name, role)
+          VALUES (inserted_origin.id, origin_name, origin_owner_id, origin_owner_name, 'owner');
+    PERFORM insert_origin_channel_v1(inserted_origin.id, origin_owner_id, 'unstable');
+    PERFORM insert_origin_channel_v1(inserted_origin.id, origin_owner_id, 'stable');
+    RETURN NEXT inserted_origin;
+    RETURN;
+  END
+$$ LANGUAGE plpgsql VOLATILE;
+
+CREATE OR REPLACE FUNCTION list_origin_members_v2 (
+  om_origin_id bigint
+) RETURNS TABLE(account_id bigint, account_name text, role text) AS $$
+  SELECT origin_members.account_id, origin_members.account_name, origin_members.role
+    FROM origin_members
+    WHERE origin_id = om_origin_id
+    ORDER BY account_name ASC;
+$$ LANGUAGE SQL STABLE;
+
+CREATE OR REPLACE FUNCTION get_origin_member_role_v1 (
+  om_origin_name text,
+  om_account_id bigint
+) RETURNS TABLE(role text) AS $$
+  SELECT origin_members.role FROM origin_members
+    WHERE origin_name = om_origin_name
+    AND account_id = om_account_id;
+$$ LANGUAGE SQL STABLE;
+
+-- The account which owns the origin always keeps the owner role
+CREATE OR REPLACE FUNCTION update_origin_member_role_v1 (
+  om_origin_id bigint,
+  om_account_name text,
+  om_role text
+) RETURNS SETOF origin_members AS $$
+  UPDATE origin_members SET
+    role = om_role,
+    updated_at = now()
+    WHERE origin_id = om_origin_id
+    AND account_name = om_account_name
+    AND account_id <> (SELECT owner_id FROM origins WHERE id = om_origin_id)
+    RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
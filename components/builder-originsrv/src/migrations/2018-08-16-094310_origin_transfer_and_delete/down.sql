@@ -0,0 +1,7 @@
+DROP FUNCTION IF EXISTS delete_origin_v1(bigint);
+DROP FUNCTION IF EXISTS count_origin_contents_v1(bigint);
+DROP FUNCTION IF EXISTS accept_origin_owner_transfer_v1(bigint, bigint);
+DROP FUNCTION IF EXISTS delete_origin_owner_transfer_v1(bigint);
+DROP FUNCTION IF EXISTS get_origin_owner_transfer_v1(bigint);
+DROP FUNCTION IF EXISTS upsert_origin_owner_transfer_v1(bigint, text, bigint, bigint, text);
+DROP TABLE IF EXISTS origin_owner_transfers;
@@ -0,0 +1,100 @@
+CREATE TABLE IF NOT EXISTS origin_owner_transfers (
+  origin_id bigint PRIMARY KEY REFERENCES origins(id),
+  origin_name text,
+  owner_id bigint,
+  account_id bigint,
+  account_name text,
+  created_at timestamptz DEFAULT now()
+);
+
+-- An origin has at most one pending transfer, and can only be transferred to one of its members
+CREATE OR REPLACE FUNCTION upsert_origin_owner_transfer_v1 (
+  p_origin_id bigint,
+  p_origin_name text,
+  p_owner_id bigint,
+  p_account_id bigint,
+  p_account_name text
+) RETURNS SETOF origin_owner_transfers AS $$
+  INSERT INTO origin_owner_transfers (origin_id, origin_name, owner_id, account_id, account_name)
+    SELECT p_origin_id, p_origin_name, p_owner_id, p_account_id, p_account_name
+    WHERE EXISTS (SELECT 1 FROM origin_members
+                  WHERE origin_id = p_origin_id AND account_id = p_account_id)
+  ON CONFLICT (origin_id) DO UPDATE SET
+    owner_id = EXCLUDED.owner_id,
+    account_id = EXCLUDED.account_id,
+    account_name = EXCLUDED.account_name,
+    created_at = now()
+  RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION get_origin_owner_transfer_v1 (
+  p_origin_id bigint
+) RETURNS SETOF origin_owner_transfers AS $$
+  SELECT * FROM origin_owner_transfers WHERE origin_id = p_origin_id;
+$$ LANGUAGE SQL STABLE;
+
+CREATE OR REPLACE FUNCTION delete_origin_owner_transfer_v1 (
+  p_origin_id bigint
+) RETURNS SETOF origin_owner_transfers AS $$
+  DELETE FROM origin_owner_transfers WHERE origin_id = p_origin_id RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
+
+-- The previous owner stays a member of the origin as a maintainer
+CREATE OR REPLACE FUNCTION accept_origin_owner_transfer_v1 (
+  p_origin_id bigint,
+  p_account_id bigint
+) RETURNS SETOF origins AS $$
+  DECLARE
+    transfer origin_owner_transfers;
+  BEGIN
+    DELETE FROM origin_owner_transfers
+      WHERE origin_id = p_origin_id AND account_id = p_account_id
+      RETURNING * INTO transfer;
+    IF NOT FOUND THEN
+      RETURN;
+    END IF;
+    UPDATE origin_members SET role = 'owner', updated_at = now()
+      WHERE origin_id = transfer.origin_id AND account_id = transfer.account_id;
+    IF NOT FOUND THEN
+      RETURN;
+    END IF;
+    UPDATE origin_members SET role = 'maintainer', updated_at = now()
+      WHERE origin_id = transfer.origin_id AND account_id = transfer.owner_id;
+    RETURN QUERY UPDATE origins SET owner_id = transfer.account_id, updated_at = now()
+      WHERE id = transfer.origin_id
+      RETURNING *;
+  END
+$$ LANGUAGE plpgsql VOLATILE;
+
+CREATE OR REPLACE FUNCTION count_origin_contents_v1 (
+  p_origin_id bigint
+) RETURNS TABLE(packages bigint, projects bigint) AS $$
+  SELECT (SELECT COUNT(*) FROM origin_packages WHERE origin_id = p_origin_id),
+         (SELECT COUNT(*) FROM origin_projects WHERE origin_id = p_origin_id);
+$$ LANGUAGE SQL STABLE;
+
+-- Removes the origin and everything it owns
+CREATE OR REPLACE FUNCTION delete_origin_v1 (
+  p_origin_id bigint
+) RETURNS void AS $$
+  DECLARE
+    p_origin_name text;
+  BEGIN
+    SELECT name INTO p_origin_name FROM origins WHERE id = p_origin_id;
+    DELETE FROM origin_channel_packages WHERE channel_id IN
+      (SELECT id FROM origin_channels WHERE origin_id = p_origin_id);
+    DELETE FROM origin_channels WHERE origin_id = p_origin_id;
+    DELETE FROM origin_packages WHERE origin_id = p_origin_id;
+    DELETE FROM origin_projects WHERE origin_id = p_origin_id;
+    DELETE FROM origin_integrations WHERE origin = p_origin_name;
+    DELETE FROM origin_secrets WHERE origin_id = p_origin_id;
+    DELETE FROM origin_public_keys WHERE origin_id = p_origin_id;
+    DELETE FROM origin_secret_keys WHERE origin_id = p_origin_id;
+    DELETE FROM origin_public_encryption_keys WHERE origin_id = p_origin_id;
+    DELETE FROM origin_private_encryption_keys WHERE origin_id = p_origin_id;
+    DELETE FROM origin_invitations WHERE origin_id = p_origin_id;
+    DELETE FROM origin_owner_transfers WHERE origin_id = p_origin_id;
+    DELETE FROM origin_members WHERE origin_id = p_origin_id;
+    DELETE FROM origins WHERE id = p_origin_id;
+  END
+$$ LANGUAGE plpgsql VOLATILE;
@@ -0,0 +1,11 @@
+DROP FUNCTION IF EXISTS list_origin_webhook_deliveries_v1(bigint, bigint, bigint);
+DROP FUNCTION IF EXISTS update_origin_webhook_delivery_v1(bigint, integer, text, boolean, bigint);
+DROP FUNCTION IF EXISTS pending_origin_webhook_deliveries_v1(integer, bigint, bigint);
+DROP FUNCTION IF EXISTS insert_origin_webhook_deliveries_v1(text, text, text);
+DROP FUNCTION IF EXISTS delete_origin_webhook_v1(bigint, bigint);
+DROP FUNCTION IF EXISTS list_origin_webhooks_v1(bigint);
+DROP FUNCTION IF EXISTS insert_origin_webhook_v1(bigint, text, text, text, text[]);
+DROP TABLE IF EXISTS origin_webhook_deliveries;
+DROP SEQUENCE IF EXISTS origin_webhook_delivery_id_seq;
+DROP TABLE IF EXISTS origin_webhooks;
+DROP SEQUENCE IF EXISTS origin_webhook_id_seq;
@@ -0,0 +1,125 @@
+CREATE SEQUENCE IF NOT EXISTS origin_webhook_id_seq;
+
+-- Webhooks go away with their origin, and deliveries with their webhook
+CREATE TABLE IF NOT EXISTS origin_webhooks (
+  id bigint PRIMARY KEY DEFAULT next_id_v1('origin_webhook_id_seq'),
+  origin_id bigint REFERENCES origins(id) ON DELETE CASCADE,
+  origin_name text,
+  url text,
+  secret text,
+  events text[],
+  created_at timestamptz DEFAULT now(),
+  updated_at timestamptz
+);
+
+CREATE INDEX IF NOT EXISTS origin_webhooks_origin_name ON origin_webhooks(origin_name);
+
+CREATE SEQUENCE IF NOT EXISTS origin_webhook_delivery_id_seq;
+
+CREATE TABLE IF NOT EXISTS origin_webhook_deliveries (
+  id bigint PRIMARY KEY DEFAULT next_id_v1('origin_webhook_delivery_id_seq'),
+  webhook_id bigint REFERENCES origin_webhooks(id) ON DELETE CASCADE,
+  event text,
+  body text,
+  attempts integer DEFAULT 0,
+  status_code integer DEFAULT 0,
+  error text DEFAULT '',
+  delivered boolean DEFAULT false,
+  next_attempt_at timestamptz DEFAULT now(),
+  created_at timestamptz DEFAULT now(),
+  updated_at timestamptz DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS origin_webhook_deliveries_pending
+  ON origin_webhook_deliveries(next_attempt_at) WHERE NOT delivered;
+
+CREATE OR REPLACE FUNCTION insert_origin_webhook_v1 (
+  p_origin_id bigint,
+  p_origin_name text,
+  p_url text,
+  p_secret text,
+  p_events text[]
+) RETURNS SETOF origin_webhooks AS $$
+  INSERT INTO origin_webhooks (origin_id, origin_name, url, secret, events)
+    VALUES (p_origin_id, p_origin_name, p_url, p_secret, p_events)
+    RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION list_origin_webhooks_v1 (
+  p_origin_id bigint
+) RETURNS SETOF origin_webhooks AS $$
+  SELECT * FROM origin_webhooks WHERE origin_id = p_origin_id ORDER BY id;
+$$ LANGUAGE SQL STABLE;
+
+CREATE OR REPLACE FUNCTION delete_origin_webhook_v1 (
+  p_id bigint,
+  p_origin_id bigint
+) RETURNS SETOF origin_webhooks AS $$
+  DELETE FROM origin_webhooks WHERE id = p_id AND origin_id = p_origin_id RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
+
+-- Queues one delivery of the event for every webhook of the origin subscribed to it
+CREATE OR REPLACE FUNCTION insert_origin_webhook_deliveries_v1 (
+  p_origin_name text,
+  p_event text,
+  p_body text
+) RETURNS SETOF origin_webhook_deliveries AS $$
+  INSERT INTO origin_webhook_deliveries (webhook_id, event, body)
+    SELECT id, p_event, p_body FROM origin_webhooks
+    WHERE origin_name = p_origin_name AND p_event = ANY(events)
+    RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
+
+-- Claims pending deliveries by pushing their next attempt back by p_claim_secs, so deliveries
+-- being attempted by one originsrv aren't picked up by another
+CREATE OR REPLACE FUNCTION pending_origin_webhook_deliveries_v1 (
+  p_max_attempts integer,
+  p_limit bigint,
+  p_claim_secs bigint
+) RETURNS TABLE(id bigint, event text, body text, attempts integer, url text, secret text) AS $$
+  WITH claimed AS (
+    UPDATE origin_webhook_deliveries SET
+      next_attempt_at = now() + p_claim_secs * interval '1 second'
+      WHERE origin_webhook_deliveries.id IN (
+        SELECT d.id FROM origin_webhook_deliveries d
+          WHERE NOT d.delivered AND d.attempts < p_max_attempts AND d.next_attempt_at <= now()
+          ORDER BY d.next_attempt_at
+          LIMIT p_limit
+          FOR UPDATE SKIP LOCKED
+      )
+      RETURNING origin_webhook_deliveries.*
+  )
+  SELECT c.id, c.event, c.body, c.attempts, w.url, w.secret
+    FROM claimed c
+    JOIN origin_webhooks w ON w.id = c.webhook_id
+    ORDER BY c.id;
+$$ LANGUAGE SQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION update_origin_webhook_delivery_v1 (
+  p_id bigint,
+  p_status_code integer,
+  p_error text,
+  p_delivered boolean,
+  p_retry_secs bigint
+) RETURNS void AS $$
+  UPDATE origin_webhook_deliveries SET
+    attempts = attempts + 1,
+    status_code = p_status_code,
+    error = p_error,
+    delivered = p_delivered,
+    next_attempt_at = now() + p_retry_secs * interval '1 second',
+    updated_at = now()
+    WHERE id = p_id;
+$$ LANGUAGE SQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION list_origin_webhook_deliveries_v1 (
+  p_webhook_id bigint,
+  p_origin_id bigint,
+  p_limit bigint
+) RETURNS SETOF origin_webhook_deliveries AS $$
+  SELECT d.* FROM origin_webhook_deliveries d
+    JOIN origin_webhooks w ON w.id = d.webhook_id
+    WHERE d.webhook_id = p_webhook_id AND w.origin_id = p_origin_id
+    ORDER BY d.created_at DESC, d.id DESC
+    LIMIT p_limit;
+$$ LANGUAGE SQL STABLE;
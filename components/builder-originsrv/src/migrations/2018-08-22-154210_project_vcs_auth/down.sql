@@ -0,0 +1,5 @@
+DROP FUNCTION IF EXISTS insert_origin_project_v7(text, text, text, text, text, bigint, bigint, text, bool, text[], text, text, text);
+DROP FUNCTION IF EXISTS update_origin_project_v6(bigint, bigint, text, text, text, text, bigint, bigint, text, bool, text[], text, text, text);
+ALTER TABLE IF EXISTS origin_projects DROP COLUMN IF EXISTS hook_secret;
+ALTER TABLE IF EXISTS origin_projects DROP COLUMN IF EXISTS vcs_auth_token;
+ALTER TABLE IF EXISTS origin_projects DROP COLUMN IF EXISTS vcs_auth_user;
@@ -0,0 +1,7 @@
+DROP FUNCTION IF EXISTS search_all_origin_packages_ranked_v1(text, text, text, text, text, boolean);
+DROP FUNCTION IF EXISTS package_search_query_v1(text);
+DROP TRIGGER IF EXISTS origin_packages_search_vector ON origin_packages;
+DROP FUNCTION IF EXISTS origin_packages_search_vector_trigger_v1();
+DROP FUNCTION IF EXISTS origin_package_search_vector_v1(text, text, text, text);
+DROP INDEX IF EXISTS origin_packages_search_vector_index_v1;
+ALTER TABLE IF EXISTS origin_packages DROP COLUMN IF EXISTS search_vector;
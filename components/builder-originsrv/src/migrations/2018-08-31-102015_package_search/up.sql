@@ -0,0 +1,78 @@
+ALTER TABLE IF EXISTS origin_packages ADD COLUMN IF NOT EXISTS search_vector tsvector;
+
+-- The package's name weighs the most, then what its manifest says about it, then the ports it
+-- exposes and the packages it depends on
+CREATE OR REPLACE FUNCTION origin_package_search_vector_v1 (
+  op_ident text,
+  op_manifest text,
+  op_exposes text,
+  op_deps text
+) RETURNS tsvector AS $$
+  SELECT setweight(to_tsvector('simple', translate(coalesce(op_ident, ''), '/', ' ')), 'A') ||
+         setweight(to_tsvector('simple', coalesce(op_manifest, '')), 'B') ||
+         setweight(to_tsvector('simple', translate(coalesce(op_exposes, ''), ':', ' ')), 'C') ||
+         setweight(to_tsvector('simple', translate(coalesce(op_deps, ''), '/:', '  ')), 'C');
+$$ LANGUAGE SQL IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION origin_packages_search_vector_trigger_v1 () RETURNS trigger AS $$
+  BEGIN
+    NEW.search_vector := origin_package_search_vector_v1(NEW.ident, NEW.manifest, NEW.exposes, NEW.deps);
+    RETURN NEW;
+  END
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS origin_packages_search_vector ON origin_packages;
+CREATE TRIGGER origin_packages_search_vector BEFORE INSERT OR UPDATE OF ident, manifest, exposes, deps ON origin_packages
+  FOR EACH ROW EXECUTE PROCEDURE origin_packages_search_vector_trigger_v1();
+
+UPDATE origin_packages SET search_vector = origin_package_search_vector_v1(ident, manifest, exposes, deps);
+
+CREATE INDEX IF NOT EXISTS origin_packages_search_vector_index_v1 ON origin_packages USING gin(search_vector);
+
+-- Every word of the query has to match, as a prefix, e.g. `post data` finds postgresql's
+-- "database" description
+CREATE OR REPLACE FUNCTION package_search_query_v1 (op_query text) RETURNS tsquery AS $$
+  SELECT to_tsquery('simple', string_agg(term || ':*', ' & '))
+  FROM regexp_split_to_table(lower(op_query), '[^[:alnum:]]+') AS term
+  WHERE term <> ''
+$$ LANGUAGE SQL IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION search_all_origin_packages_ranked_v1 (
+  op_query text,
+  op_origin text,
+  op_target text,
+  op_channel text,
+  op_my_origins text,
+  op_distinct boolean
+) RETURNS TABLE(ident text, rank real) AS $$
+  DECLARE
+    schema RECORD;
+  BEGIN
+    FOR schema IN EXECUTE
+      format(
+        'SELECT schema_name FROM information_schema.schemata WHERE left(schema_name, 6) = %L',
+        'shard_'
+      )
+    LOOP
+      -- Matches are ranked by relevance, boosted by how many releases the package has had.
+      -- Idents containing the query as is still match, ranked last.
+      RETURN QUERY EXECUTE
+      format('SELECT s.ident, max(s.rank)::real AS rank FROM (
+                SELECT CASE WHEN $7 THEN split_part(op.ident, ''/'', 1) || ''/'' || split_part(op.ident, ''/'', 2) ELSE op.ident END AS ident,
+                       coalesce(ts_rank(op.search_vector, $1), 0) * (1 + ln((SELECT COUNT(*) FROM %1$I.origin_packages r WHERE r.origin_id = op.origin_id AND r.name = op.name))) AS rank
+                FROM %1$I.origin_packages op
+                JOIN %1$I.origins o ON o.id = op.origin_id
+                WHERE (op.search_vector @@ $1 OR op.ident LIKE (''%%'' || $2 || ''%%''))
+                AND ($3 = '''' OR o.name = $3)
+                AND ($4 = '''' OR op.target = $4)
+                AND ($5 = '''' OR EXISTS (
+                  SELECT 1 FROM %1$I.origin_channel_packages ocp
+                  JOIN %1$I.origin_channels oc ON oc.id = ocp.channel_id
+                  WHERE ocp.package_id = op.id AND oc.name = $5))
+                AND (op.visibility = ''public'' OR (op.visibility IN (''hidden'', ''private'') AND o.name = ANY(STRING_TO_ARRAY($6, '',''))))
+              ) AS s GROUP BY s.ident', schema.schema_name)
+      USING package_search_query_v1(op_query), op_query, op_origin, op_target, op_channel, op_my_origins, op_distinct;
+    END LOOP;
+    RETURN;
+  END;
+$$ LANGUAGE plpgsql STABLE;
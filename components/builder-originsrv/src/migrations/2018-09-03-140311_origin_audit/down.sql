@@ -0,0 +1,9 @@
+DROP FUNCTION IF EXISTS get_origin_audit_v1(bigint, text, text, text, timestamptz, timestamptz);
+DROP FUNCTION IF EXISTS get_origin_audit_rows_v1(bigint);
+DROP FUNCTION IF EXISTS add_audit_origin_entry_v1(bigint, smallint, text, smallint, bigint, text);
+DROP INDEX IF EXISTS audit_package_delete_origin_id_index_v1;
+DROP INDEX IF EXISTS audit_package_group_origin_id_index_v1;
+DROP INDEX IF EXISTS audit_package_origin_id_index_v1;
+DROP INDEX IF EXISTS audit_origin_id_index_v1;
+DROP INDEX IF EXISTS audit_origin_origin_id_index_v1;
+DROP TABLE IF EXISTS audit_origin;
@@ -0,0 +1,84 @@
+CREATE TABLE IF NOT EXISTS audit_origin (
+  origin_id bigint,
+  operation smallint,
+  subject text,
+  trigger smallint,
+  requester_id bigint,
+  requester_name text,
+  created_at timestamptz DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS audit_origin_origin_id_index_v1 ON audit_origin(origin_id);
+CREATE INDEX IF NOT EXISTS audit_origin_id_index_v1 ON audit(origin_id);
+CREATE INDEX IF NOT EXISTS audit_package_origin_id_index_v1 ON audit_package(origin_id);
+CREATE INDEX IF NOT EXISTS audit_package_group_origin_id_index_v1 ON audit_package_group(origin_id);
+CREATE INDEX IF NOT EXISTS audit_package_delete_origin_id_index_v1 ON audit_package_delete(origin_id);
+
+CREATE OR REPLACE FUNCTION add_audit_origin_entry_v1 (
+  p_origin_id bigint,
+  p_operation smallint,
+  p_subject text,
+  p_trigger smallint,
+  p_requester_id bigint,
+  p_requester_name text
+) RETURNS SETOF audit_origin AS $$
+INSERT INTO audit_origin (origin_id, operation, subject, trigger, requester_id, requester_name)
+VALUES (p_origin_id, p_operation, p_subject, p_trigger, p_requester_id, p_requester_name)
+RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
+
+-- Audit entries have not always been written to the origin's own shard, so every shard is read.
+-- Operations are named after PackageChannelOperation, PackageDeleteOperation and
+-- OriginAuditOperation.
+CREATE OR REPLACE FUNCTION get_origin_audit_rows_v1 (
+  p_origin_id bigint
+) RETURNS TABLE(operation text, package_id bigint, ident text, channel_id bigint, subject text, trigger smallint, requester_id bigint, requester_name text, group_id bigint, created_at timestamptz) AS $$
+  DECLARE
+    schema RECORD;
+  BEGIN
+    FOR schema IN EXECUTE
+      format(
+        'SELECT schema_name FROM information_schema.schemata WHERE left(schema_name, 6) = %L',
+        'shard_'
+      )
+    LOOP
+      RETURN QUERY EXECUTE
+      format('SELECT (ARRAY[''Promote'', ''Demote''])[a.operation + 1], a.package_id, NULL::text, a.channel_id, NULL::text, a.trigger, a.requester_id, a.requester_name, NULL::bigint, a.created_at
+              FROM %1$I.audit a WHERE a.origin_id = $1
+              UNION ALL
+              SELECT (ARRAY[''Promote'', ''Demote''])[a.operation + 1], a.package_id, NULL::text, a.channel_id, NULL::text, a.trigger, a.requester_id, a.requester_name, NULL::bigint, a.created_at
+              FROM %1$I.audit_package a WHERE a.origin_id = $1
+              UNION ALL
+              SELECT (ARRAY[''Promote'', ''Demote''])[a.operation + 1], unnest(a.package_ids), NULL::text, a.channel_id, NULL::text, a.trigger, a.requester_id, a.requester_name, a.group_id, a.created_at
+              FROM %1$I.audit_package_group a WHERE a.origin_id = $1
+              UNION ALL
+              SELECT (ARRAY[''PackageDelete'', ''ArtifactCollect''])[a.operation + 1], NULL::bigint, a.ident, NULL::bigint, a.target, a.trigger, a.requester_id, a.requester_name, NULL::bigint, a.created_at
+              FROM %1$I.audit_package_delete a WHERE a.origin_id = $1
+              UNION ALL
+              SELECT (ARRAY[''MemberAdd'', ''MemberRemove'', ''MemberRoleUpdate'', ''KeysGenerate'', ''PublicKeyUpload'', ''SecretKeyUpload'', ''SecretCreate'', ''SecretDelete'', ''IntegrationCreate'', ''IntegrationDelete'', ''OwnerTransferCreate'', ''OwnerTransferAccept'', ''OwnerTransferRescind''])[a.operation + 1], NULL::bigint, NULL::text, NULL::bigint, a.subject, a.trigger, a.requester_id, a.requester_name, NULL::bigint, a.created_at
+              FROM %1$I.audit_origin a WHERE a.origin_id = $1', schema.schema_name)
+      USING p_origin_id;
+    END LOOP;
+    RETURN;
+  END;
+$$ LANGUAGE plpgsql STABLE;
+
+-- Packages and channels are looked up in the origin's own shard
+CREATE OR REPLACE FUNCTION get_origin_audit_v1 (
+  p_origin_id bigint,
+  p_requester_name text,
+  p_operation text,
+  p_package text,
+  p_from timestamptz,
+  p_to timestamptz
+) RETURNS TABLE(operation text, package text, channel text, subject text, trigger smallint, requester_id bigint, requester_name text, group_id bigint, created_at timestamptz) AS $$
+  SELECT a.operation, COALESCE(a.ident, p.ident), c.name, a.subject, a.trigger, a.requester_id, a.requester_name, a.group_id, a.created_at
+  FROM get_origin_audit_rows_v1(p_origin_id) a
+  LEFT JOIN origin_packages p ON p.id = a.package_id
+  LEFT JOIN origin_channels c ON c.id = a.channel_id
+  WHERE (p_requester_name = '' OR a.requester_name = p_requester_name)
+  AND (p_operation = '' OR lower(a.operation) = lower(p_operation))
+  AND (p_package = '' OR COALESCE(a.ident, p.ident) = p_package OR COALESCE(a.ident, p.ident) LIKE (p_package || '/%'))
+  AND a.created_at >= COALESCE(p_from, '-infinity')
+  AND a.created_at <= COALESCE(p_to, 'infinity')
+$$ LANGUAGE SQL STABLE;
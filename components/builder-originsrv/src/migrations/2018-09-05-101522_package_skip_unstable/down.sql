@@ -0,0 +1 @@
+DROP FUNCTION IF EXISTS insert_origin_package_v4(bigint, bigint, text, text, text, text, text, text, text, text, text, text, bool);
@@ -50,6 +50,23 @@ pub fn package_channel_audit(
     Ok(())
 }
 
+pub fn package_delete_audit(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::PackageDeleteAudit>()?;
+    match state.datastore.package_delete_audit(&msg) {
+        Ok(()) => conn.route_reply(req, &net::NetOk::new())?,
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:package-delete-audit:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn package_group_channel_audit(
     req: &mut Message,
     conn: &mut RouteConn,
@@ -67,6 +84,40 @@ pub fn package_group_channel_audit(
     Ok(())
 }
 
+pub fn origin_audit(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginAudit>()?;
+    match state.datastore.origin_audit(&msg) {
+        Ok(()) => conn.route_reply(req, &net::NetOk::new())?,
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-audit:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn origin_audit_list(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginAuditListRequest>()?;
+    match state.datastore.list_origin_audit(&msg) {
+        Ok(ref response) => conn.route_reply(req, response)?,
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-audit-list:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn origin_check_owner(
     req: &mut Message,
     conn: &mut RouteConn,
@@ -77,8 +128,27 @@ pub fn origin_check_owner(
     og.set_name(msg.get_origin_name().to_string());
     match state.datastore.get_origin(&og) {
         Ok(Some(ref origin)) => {
+            // Besides the account which created the origin, members given the owner role are
+            // owners too.
+            let is_owner = if origin.get_owner_id() == msg.get_account_id() {
+                true
+            } else {
+                let mut coar = proto::CheckOriginAccessRequest::new();
+                coar.set_account_id(msg.get_account_id());
+                coar.set_origin_name(msg.get_origin_name().to_string());
+                coar.set_role(proto::OriginMemberRole::Owner);
+                match state.datastore.check_account_in_origin(&coar) {
+                    Ok(is_owner) => is_owner,
+                    Err(e) => {
+                        let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-check-owner:2");
+                        error!("{}, {}", err, e);
+                        conn.route_reply(req, &*err)?;
+                        return Ok(());
+                    }
+                }
+            };
             let mut reply = proto::CheckOriginOwnerResponse::new();
-            reply.set_is_owner(origin.get_owner_id() == msg.get_account_id());
+            reply.set_is_owner(is_owner);
             conn.route_reply(req, &reply)?;
         }
         Ok(None) => {
@@ -921,6 +991,64 @@ pub fn origin_channel_delete(
     Ok(())
 }
 
+pub fn origin_channel_retention_delete(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginChannelRetentionDelete>()?;
+    match state.datastore.delete_origin_channel_retention(&msg) {
+        Ok(()) => conn.route_reply(req, &net::NetOk::new())?,
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-channel-retention-delete:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn origin_channel_retention_get(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginChannelRetentionGet>()?;
+    match state.datastore.get_origin_channel_retention(&msg) {
+        Ok(Some(ref retention)) => conn.route_reply(req, retention)?,
+        Ok(None) => {
+            let err = NetError::new(
+                ErrCode::ENTITY_NOT_FOUND,
+                "vt:origin-channel-retention-get:0",
+            );
+            conn.route_reply(req, &*err)?;
+        }
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-channel-retention-get:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn origin_channel_retention_set(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginChannelRetentionSet>()?;
+    match state.datastore.set_origin_channel_retention(&msg) {
+        Ok(ref retention) => conn.route_reply(req, retention)?,
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-channel-retention-set:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn origin_channel_get(
     req: &mut Message,
     conn: &mut RouteConn,
@@ -997,6 +1125,23 @@ pub fn origin_package_get(
     Ok(())
 }
 
+pub fn origin_package_delete(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginPackageDelete>()?;
+    match state.datastore.delete_origin_package(&msg) {
+        Ok(()) => conn.route_reply(req, &net::NetOk::new())?,
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-package-delete:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn origin_channel_package_get(
     req: &mut Message,
     conn: &mut RouteConn,
@@ -1290,6 +1435,236 @@ pub fn origin_member_delete(
     Ok(())
 }
 
+pub fn origin_delete(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginDelete>()?;
+    let origin = match state.datastore.get_origin_by_name(msg.get_name()) {
+        Ok(Some(origin)) => origin,
+        Ok(None) => {
+            let err = NetError::new(ErrCode::ENTITY_NOT_FOUND, "vt:origin-delete:0");
+            conn.route_reply(req, &*err)?;
+            return Ok(());
+        }
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-delete:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+            return Ok(());
+        }
+    };
+    match state.datastore.delete_origin(&msg, origin.get_id()) {
+        Ok(true) => conn.route_reply(req, &NetOk::new())?,
+        Ok(false) => {
+            let err = NetError::new(ErrCode::ENTITY_CONFLICT, "vt:origin-delete:2");
+            conn.route_reply(req, &*err)?;
+        }
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-delete:3");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn origin_owner_transfer_create(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginOwnerTransferCreate>()?;
+    match state.datastore.create_origin_owner_transfer(&msg) {
+        Ok(Some(ref transfer)) => conn.route_reply(req, transfer)?,
+        Ok(None) => {
+            let err = NetError::new(ErrCode::ENTITY_NOT_FOUND, "vt:origin-owner-transfer-create:0");
+            conn.route_reply(req, &*err)?;
+        }
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-owner-transfer-create:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn origin_owner_transfer_get(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginOwnerTransferGet>()?;
+    match state.datastore.get_origin_owner_transfer(&msg) {
+        Ok(Some(ref transfer)) => conn.route_reply(req, transfer)?,
+        Ok(None) => {
+            let err = NetError::new(ErrCode::ENTITY_NOT_FOUND, "vt:origin-owner-transfer-get:0");
+            conn.route_reply(req, &*err)?;
+        }
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-owner-transfer-get:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn origin_owner_transfer_accept(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginOwnerTransferAccept>()?;
+    match state.datastore.accept_origin_owner_transfer(&msg) {
+        Ok(Some(ref origin)) => conn.route_reply(req, origin)?,
+        Ok(None) => {
+            let err = NetError::new(ErrCode::ENTITY_NOT_FOUND, "vt:origin-owner-transfer-accept:0");
+            conn.route_reply(req, &*err)?;
+        }
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-owner-transfer-accept:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn origin_owner_transfer_rescind(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginOwnerTransferRescind>()?;
+    match state.datastore.rescind_origin_owner_transfer(&msg) {
+        Ok(true) => conn.route_reply(req, &NetOk::new())?,
+        Ok(false) => {
+            let err =
+                NetError::new(ErrCode::ENTITY_NOT_FOUND, "vt:origin-owner-transfer-rescind:0");
+            conn.route_reply(req, &*err)?;
+        }
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-owner-transfer-rescind:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn origin_webhook_create(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginWebhookCreate>()?;
+    match state.datastore.create_origin_webhook(&msg) {
+        Ok(ref webhook) => conn.route_reply(req, webhook)?,
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-webhook-create:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn origin_webhook_delete(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginWebhookDelete>()?;
+    match state.datastore.delete_origin_webhook(&msg) {
+        Ok(true) => conn.route_reply(req, &NetOk::new())?,
+        Ok(false) => {
+            let err = NetError::new(ErrCode::ENTITY_NOT_FOUND, "vt:origin-webhook-delete:0");
+            conn.route_reply(req, &*err)?;
+        }
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-webhook-delete:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn origin_webhook_list(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginWebhookListRequest>()?;
+    match state.datastore.list_origin_webhooks(&msg) {
+        Ok(ref webhooks) => conn.route_reply(req, webhooks)?,
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-webhook-list:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn origin_webhook_notify(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginWebhookNotify>()?;
+    match state.datastore.notify_origin_webhooks(&msg) {
+        Ok(_) => conn.route_reply(req, &NetOk::new())?,
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-webhook-notify:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn origin_webhook_delivery_list(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginWebhookDeliveryListRequest>()?;
+    match state.datastore.list_origin_webhook_deliveries(&msg) {
+        Ok(ref deliveries) => conn.route_reply(req, deliveries)?,
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-webhook-delivery-list:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn origin_member_role_update(
+    req: &mut Message,
+    conn: &mut RouteConn,
+    state: &mut ServerState,
+) -> SrvResult<()> {
+    let msg = req.parse::<proto::OriginMemberRoleUpdate>()?;
+    match state.datastore.update_origin_member_role(&msg) {
+        Ok(Some(ref member)) => conn.route_reply(req, member)?,
+        Ok(None) => {
+            let err = NetError::new(ErrCode::ENTITY_NOT_FOUND, "vt:origin-member-role-update:0");
+            conn.route_reply(req, &*err)?;
+        }
+        Err(e) => {
+            let err = NetError::new(ErrCode::DATA_STORE, "vt:origin-member-role-update:1");
+            error!("{}, {}", err, e);
+            conn.route_reply(req, &*err)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn origin_secret_create(
     req: &mut Message,
     conn: &mut RouteConn,
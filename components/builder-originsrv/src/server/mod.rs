@@ -14,6 +14,8 @@
 
 mod handlers;
 
+use std::time::Duration;
+
 use hab_net::app::prelude::*;
 use protocol::originsrv::*;
 
@@ -177,6 +179,10 @@ lazy_static! {
             OriginPackageCreate::descriptor_static(None),
             handlers::origin_package_create,
         );
+        map.register(
+            OriginPackageDelete::descriptor_static(None),
+            handlers::origin_package_delete,
+        );
         map.register(
             OriginPackageGet::descriptor_static(None),
             handlers::origin_package_get,
@@ -249,6 +255,18 @@ lazy_static! {
             OriginChannelGet::descriptor_static(None),
             handlers::origin_channel_get,
         );
+        map.register(
+            OriginChannelRetentionDelete::descriptor_static(None),
+            handlers::origin_channel_retention_delete,
+        );
+        map.register(
+            OriginChannelRetentionGet::descriptor_static(None),
+            handlers::origin_channel_retention_get,
+        );
+        map.register(
+            OriginChannelRetentionSet::descriptor_static(None),
+            handlers::origin_channel_retention_set,
+        );
         map.register(
             OriginChannelListRequest::descriptor_static(None),
             handlers::origin_channel_list,
@@ -269,6 +287,50 @@ lazy_static! {
             OriginMemberRemove::descriptor_static(None),
             handlers::origin_member_delete,
         );
+        map.register(
+            OriginMemberRoleUpdate::descriptor_static(None),
+            handlers::origin_member_role_update,
+        );
+        map.register(
+            OriginDelete::descriptor_static(None),
+            handlers::origin_delete,
+        );
+        map.register(
+            OriginOwnerTransferCreate::descriptor_static(None),
+            handlers::origin_owner_transfer_create,
+        );
+        map.register(
+            OriginOwnerTransferGet::descriptor_static(None),
+            handlers::origin_owner_transfer_get,
+        );
+        map.register(
+            OriginOwnerTransferAccept::descriptor_static(None),
+            handlers::origin_owner_transfer_accept,
+        );
+        map.register(
+            OriginOwnerTransferRescind::descriptor_static(None),
+            handlers::origin_owner_transfer_rescind,
+        );
+        map.register(
+            OriginWebhookCreate::descriptor_static(None),
+            handlers::origin_webhook_create,
+        );
+        map.register(
+            OriginWebhookDelete::descriptor_static(None),
+            handlers::origin_webhook_delete,
+        );
+        map.register(
+            OriginWebhookListRequest::descriptor_static(None),
+            handlers::origin_webhook_list,
+        );
+        map.register(
+            OriginWebhookNotify::descriptor_static(None),
+            handlers::origin_webhook_notify,
+        );
+        map.register(
+            OriginWebhookDeliveryListRequest::descriptor_static(None),
+            handlers::origin_webhook_delivery_list,
+        );
         map.register(
             MyOriginsRequest::descriptor_static(None),
             handlers::my_origins,
@@ -277,10 +339,19 @@ lazy_static! {
             PackageChannelAudit::descriptor_static(None),
             handlers::package_channel_audit,
         );
+        map.register(
+            PackageDeleteAudit::descriptor_static(None),
+            handlers::package_delete_audit,
+        );
         map.register(
             PackageGroupChannelAudit::descriptor_static(None),
             handlers::package_group_channel_audit,
         );
+        map.register(OriginAudit::descriptor_static(None), handlers::origin_audit);
+        map.register(
+            OriginAuditListRequest::descriptor_static(None),
+            handlers::origin_audit_list,
+        );
         map
     };
 }
@@ -321,9 +392,17 @@ impl Dispatcher for OriginSrv {
         router_pipe: Arc<String>,
     ) -> SrvResult<<Self::State as AppState>::InitState> {
         let jobsrv_enabled = config.jobsrv_enabled;
+        let retention_interval = Duration::from_secs(config.retention_interval_secs);
+        let webhook_interval = Duration::from_secs(config.webhook_interval_secs);
         let state = ServerState::new(config, router_pipe)?;
         state.datastore.register_async_events(jobsrv_enabled);
         state.datastore.start_async();
+        state
+            .datastore
+            .schedule_periodic("prune_channels", retention_interval);
+        state
+            .datastore
+            .schedule_periodic("deliver_webhooks", webhook_interval);
         Ok(state)
     }
 
@@ -0,0 +1,190 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Delivery of origin webhook events over HTTP
+
+use std::io::{self, Read};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use bldr_core::helpers::is_public_addr;
+use hex;
+use hyper;
+use hyper::client::Client;
+use hyper::header::{ContentType, Headers, UserAgent};
+use hyper::mime::{Mime, SubLevel, TopLevel};
+use hyper::net::{HttpStream, HttpsConnector, NetworkConnector};
+use hyper_openssl::OpensslClient;
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+
+const USER_AGENT: &'static str = "Habitat-Builder-Webhooks";
+
+/// Deliveries are abandoned after this many failed attempts
+pub const MAX_ATTEMPTS: i32 = 5;
+
+/// Seconds to wait for a webhook's server to accept a connection
+const CONNECT_TIMEOUT_SECS: u64 = 5;
+
+/// Seconds to wait on a webhook's server while sending the body or reading the response
+const IO_TIMEOUT_SECS: u64 = 10;
+
+/// Bytes of a webhook server's error response kept with the delivery
+const ERROR_BODY_LEN: u64 = 1024;
+
+header! { (XBuilderDelivery, "X-Builder-Delivery") => [u64] }
+header! { (XBuilderEvent, "X-Builder-Event") => [String] }
+header! { (XBuilderSignature, "X-Builder-Signature") => [String] }
+
+/// The outcome of one attempt at delivering an event.
+pub struct Attempt {
+    /// HTTP status of the response, 0 if none was received
+    pub status_code: u16,
+    pub error: String,
+}
+
+impl Attempt {
+    pub fn delivered(&self) -> bool {
+        self.status_code >= 200 && self.status_code < 300
+    }
+}
+
+/// Builds the JSON document sent to webhooks for an event. The payload is expected to already be
+/// a JSON document.
+pub fn body(event: &str, origin: &str, payload: &str) -> String {
+    let data = if payload.is_empty() { "{}" } else { payload };
+    format!(
+        r#"{{"event":"{}","origin":"{}","data":{}}}"#,
+        event, origin, data
+    )
+}
+
+/// Signs a body with the webhook's secret, in the form receivers find in the
+/// `X-Builder-Signature` header.
+pub fn sign(secret: &str, body: &str) -> String {
+    let key = PKey::hmac(secret.as_bytes()).unwrap();
+    let mut signer = Signer::new(MessageDigest::sha256(), &key).unwrap();
+    signer.update(body.as_bytes()).unwrap();
+    format!("sha256={}", hex::encode(signer.sign_to_vec().unwrap()))
+}
+
+/// Seconds to wait before the next attempt at a delivery which failed `attempts` times.
+pub fn retry_delay(attempts: i32) -> i64 {
+    60 * (1i64 << attempts.min(10))
+}
+
+/// Connects to webhook servers within a timeout. Addresses which aren't public are skipped, in
+/// case a webhook's host has come to resolve to one since the webhook was created.
+struct WebhookConnector;
+
+impl NetworkConnector for WebhookConnector {
+    type Stream = HttpStream;
+
+    fn connect(&self, host: &str, port: u16, _scheme: &str) -> hyper::Result<HttpStream> {
+        let host = host.trim_left_matches('[').trim_right_matches(']');
+        let timeout = Duration::from_secs(CONNECT_TIMEOUT_SECS);
+        let mut last_err = io::Error::new(
+            io::ErrorKind::AddrNotAvailable,
+            format!("{} has no public address", host),
+        );
+        for addr in (host, port).to_socket_addrs()? {
+            if !is_public_addr(&addr.ip()) {
+                continue;
+            }
+            match TcpStream::connect_timeout(&addr, timeout) {
+                Ok(stream) => return Ok(HttpStream(stream)),
+                Err(err) => last_err = err,
+            }
+        }
+        Err(hyper::Error::Io(last_err))
+    }
+}
+
+pub fn deliver(id: u64, url: &str, secret: &str, event: &str, body: &str) -> Attempt {
+    let ssl = match OpensslClient::new() {
+        Ok(ssl) => ssl,
+        Err(err) => {
+            return Attempt {
+                status_code: 0,
+                error: err.to_string(),
+            }
+        }
+    };
+    let mut client = Client::with_connector(HttpsConnector::with_connector(ssl, WebhookConnector));
+    client.set_read_timeout(Some(Duration::from_secs(IO_TIMEOUT_SECS)));
+    client.set_write_timeout(Some(Duration::from_secs(IO_TIMEOUT_SECS)));
+
+    let mut headers = Headers::new();
+    headers.set(ContentType(Mime(
+        TopLevel::Application,
+        SubLevel::Json,
+        vec![],
+    )));
+    headers.set(UserAgent(USER_AGENT.to_string()));
+    headers.set(XBuilderDelivery(id));
+    headers.set(XBuilderEvent(event.to_string()));
+    headers.set(XBuilderSignature(sign(secret, body)));
+
+    match client.post(url).body(body).headers(headers).send() {
+        Ok(mut response) => {
+            let status_code = response.status.to_u16();
+            let mut error = String::new();
+            if !response.status.is_success() {
+                let mut text = Vec::new();
+                response.by_ref().take(ERROR_BODY_LEN).read_to_end(&mut text).ok();
+                error = format!("{}: {}", response.status, String::from_utf8_lossy(&text));
+            }
+            Attempt {
+                status_code: status_code,
+                error: error,
+            }
+        }
+        Err(err) => Attempt {
+            status_code: 0,
+            error: err.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_body() {
+        assert_eq!(
+            sign("secret", r#"{"event":"job.started"}"#),
+            "sha256=5c7aed79646d643c1d784be7782c4663bbca950db47d4b3bfad21a5d8df53018"
+        );
+    }
+
+    #[test]
+    fn event_body() {
+        assert_eq!(
+            body("group.complete", "core", r#"{"id":"1"}"#),
+            r#"{"event":"group.complete","origin":"core","data":{"id":"1"}}"#
+        );
+        assert_eq!(
+            body("package.uploaded", "core", ""),
+            r#"{"event":"package.uploaded","origin":"core","data":{}}"#
+        );
+    }
+
+    #[test]
+    fn retry_delay_backs_off() {
+        assert_eq!(retry_delay(1), 120);
+        assert_eq!(retry_delay(4), 960);
+    }
+}
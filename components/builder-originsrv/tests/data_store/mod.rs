@@ -467,6 +467,257 @@ fn check_account_in_origin() {
     );
 }
 
+#[test]
+fn origin_member_roles() {
+    let ds = datastore_test!(DataStore);
+    let mut origin = originsrv::OriginCreate::new();
+    origin.set_name(String::from("neurosis"));
+    origin.set_owner_id(1);
+    origin.set_owner_name(String::from("scottkelly"));
+    let neurosis = ds.create_origin(&origin)
+        .expect("Should create origin")
+        .unwrap();
+
+    let mut coar = originsrv::CheckOriginAccessRequest::new();
+    coar.set_origin_name(String::from("neurosis"));
+    coar.set_account_id(2);
+    coar.set_role(originsrv::OriginMemberRole::Member);
+
+    let conn = ds.pool.get(&coar).expect("Cannot get connection from pool");
+    conn.execute(
+        "SELECT insert_origin_member_v1($1, $2, $3, $4)",
+        &[
+            &(neurosis.get_id() as i64),
+            &"neurosis",
+            &2i64,
+            &"noel_gallagher",
+        ],
+    ).expect("Failed to insert origin member");
+
+    // New members get the member role, the creator of the origin is its owner
+    let mut omlr = originsrv::OriginMemberListRequest::new();
+    omlr.set_origin_id(neurosis.get_id());
+    let members = ds.list_origin_members(&omlr)
+        .expect("Error getting origin members from database");
+    assert_eq!(members.get_roles().len(), 2);
+    assert_eq!(members.get_roles()[0].get_account_name(), "noel_gallagher");
+    assert_eq!(
+        members.get_roles()[0].get_role(),
+        originsrv::OriginMemberRole::Member
+    );
+    assert_eq!(
+        members.get_roles()[1].get_role(),
+        originsrv::OriginMemberRole::Owner
+    );
+
+    assert!(ds.check_account_in_origin(&coar).unwrap());
+    coar.set_role(originsrv::OriginMemberRole::Maintainer);
+    assert!(!ds.check_account_in_origin(&coar).unwrap());
+
+    let mut omru = originsrv::OriginMemberRoleUpdate::new();
+    omru.set_origin_id(neurosis.get_id());
+    omru.set_account_name(String::from("noel_gallagher"));
+    omru.set_role(originsrv::OriginMemberRole::Maintainer);
+    let member = ds.update_origin_member_role(&omru)
+        .expect("Failed to update member role")
+        .expect("Member does not exist");
+    assert_eq!(member.get_role(), originsrv::OriginMemberRole::Maintainer);
+    assert!(ds.check_account_in_origin(&coar).unwrap());
+    coar.set_role(originsrv::OriginMemberRole::Owner);
+    assert!(!ds.check_account_in_origin(&coar).unwrap());
+
+    // The owner of the origin can't be demoted
+    omru.set_account_name(String::from("scottkelly"));
+    omru.set_role(originsrv::OriginMemberRole::ReadOnly);
+    assert!(
+        ds.update_origin_member_role(&omru)
+            .expect("Failed to update member role")
+            .is_none()
+    );
+}
+
+#[test]
+fn transfer_origin_ownership() {
+    let ds = datastore_test!(DataStore);
+    let mut origin = originsrv::OriginCreate::new();
+    origin.set_name(String::from("neurosis"));
+    origin.set_owner_id(1);
+    origin.set_owner_name(String::from("scottkelly"));
+    let neurosis = ds.create_origin(&origin)
+        .expect("Should create origin")
+        .unwrap();
+
+    let mut ootc = originsrv::OriginOwnerTransferCreate::new();
+    ootc.set_origin_id(neurosis.get_id());
+    ootc.set_origin_name(String::from("neurosis"));
+    ootc.set_owner_id(1);
+    ootc.set_account_id(2);
+    ootc.set_account_name(String::from("noel_gallagher"));
+
+    // Origins can only be transferred to their members
+    assert!(
+        ds.create_origin_owner_transfer(&ootc)
+            .expect("Failed to create transfer")
+            .is_none()
+    );
+
+    let conn = ds.pool.get(&ootc).expect("Cannot get connection from pool");
+    conn.execute(
+        "SELECT insert_origin_member_v1($1, $2, $3, $4)",
+        &[
+            &(neurosis.get_id() as i64),
+            &"neurosis",
+            &2i64,
+            &"noel_gallagher",
+        ],
+    ).expect("Failed to insert origin member");
+    ds.create_origin_owner_transfer(&ootc)
+        .expect("Failed to create transfer")
+        .expect("Transfer should exist");
+
+    let mut oota = originsrv::OriginOwnerTransferAccept::new();
+    oota.set_origin_id(neurosis.get_id());
+    oota.set_account_id(3);
+    assert!(
+        ds.accept_origin_owner_transfer(&oota)
+            .expect("Failed to accept transfer")
+            .is_none()
+    );
+
+    oota.set_account_id(2);
+    let neurosis = ds.accept_origin_owner_transfer(&oota)
+        .expect("Failed to accept transfer")
+        .expect("Origin should be transferred");
+    assert_eq!(neurosis.get_owner_id(), 2);
+
+    let mut omlr = originsrv::OriginMemberListRequest::new();
+    omlr.set_origin_id(neurosis.get_id());
+    let members = ds.list_origin_members(&omlr)
+        .expect("Error getting origin members from database");
+    assert_eq!(
+        members.get_roles()[0].get_role(),
+        originsrv::OriginMemberRole::Owner
+    );
+    assert_eq!(
+        members.get_roles()[1].get_role(),
+        originsrv::OriginMemberRole::Maintainer
+    );
+
+    let mut ootg = originsrv::OriginOwnerTransferGet::new();
+    ootg.set_origin_id(neurosis.get_id());
+    assert!(ds.get_origin_owner_transfer(&ootg).unwrap().is_none());
+}
+
+#[test]
+fn delete_origin() {
+    let ds = datastore_test!(DataStore);
+    let mut origin = originsrv::OriginCreate::new();
+    origin.set_name(String::from("neurosis"));
+    origin.set_owner_id(1);
+    origin.set_owner_name(String::from("scottkelly"));
+    let neurosis = ds.create_origin(&origin)
+        .expect("Should create origin")
+        .unwrap();
+
+    let mut op = originsrv::OriginProject::new();
+    op.set_origin_name(String::from(neurosis.get_name()));
+    op.set_origin_id(neurosis.get_id());
+    op.set_package_name(String::from("zeal"));
+    op.set_plan_path(String::from("foo"));
+    op.set_vcs_type(String::from("git"));
+    op.set_vcs_data(String::from("git://github.com/habitat-sh/core-plans"));
+    op.set_vcs_installation_id(1);
+    op.set_owner_id(1);
+    let mut opc = originsrv::OriginProjectCreate::new();
+    opc.set_project(op);
+    ds.create_origin_project(&opc)
+        .expect("Failed to create origin project");
+
+    // Origins with projects are only deleted when purging
+    let mut od = originsrv::OriginDelete::new();
+    od.set_name(String::from("neurosis"));
+    assert!(!ds.delete_origin(&od, neurosis.get_id()).unwrap());
+    assert!(ds.get_origin_by_name("neurosis").unwrap().is_some());
+
+    od.set_purge(true);
+    assert!(ds.delete_origin(&od, neurosis.get_id()).unwrap());
+    assert!(ds.get_origin_by_name("neurosis").unwrap().is_none());
+}
+
+#[test]
+fn origin_webhooks() {
+    let ds = datastore_test!(DataStore);
+    let mut origin = originsrv::OriginCreate::new();
+    origin.set_name(String::from("neurosis"));
+    origin.set_owner_id(1);
+    origin.set_owner_name(String::from("scottkelly"));
+    let neurosis = ds.create_origin(&origin)
+        .expect("Should create origin")
+        .unwrap();
+
+    let mut webhook = originsrv::OriginWebhook::new();
+    webhook.set_origin_id(neurosis.get_id());
+    webhook.set_origin_name(String::from("neurosis"));
+    webhook.set_url(String::from("https://example.com/hooks"));
+    webhook.set_secret(String::from("s3cr3t"));
+    webhook.set_events(vec![
+        originsrv::OriginWebhookEvent::GroupComplete,
+        originsrv::OriginWebhookEvent::PackageUploaded,
+    ]);
+    let mut owc = originsrv::OriginWebhookCreate::new();
+    owc.set_webhook(webhook);
+    let created = ds.create_origin_webhook(&owc)
+        .expect("Should create webhook");
+    assert_eq!(created.get_url(), "https://example.com/hooks");
+    assert_eq!(created.get_events().len(), 2);
+
+    let mut owlr = originsrv::OriginWebhookListRequest::new();
+    owlr.set_origin_id(neurosis.get_id());
+    let webhooks = ds.list_origin_webhooks(&owlr).unwrap();
+    assert_eq!(webhooks.get_webhooks().len(), 1);
+    assert_eq!(webhooks.get_webhooks()[0].get_id(), created.get_id());
+
+    // Only events the webhook subscribes to are queued for delivery
+    let mut own = originsrv::OriginWebhookNotify::new();
+    own.set_origin_name(String::from("neurosis"));
+    own.set_event(originsrv::OriginWebhookEvent::JobFailed);
+    own.set_payload(String::from(r#"{"id":"1"}"#));
+    assert_eq!(ds.notify_origin_webhooks(&own).unwrap(), 0);
+    own.set_event(originsrv::OriginWebhookEvent::GroupComplete);
+    assert_eq!(ds.notify_origin_webhooks(&own).unwrap(), 1);
+
+    let mut owdlr = originsrv::OriginWebhookDeliveryListRequest::new();
+    owdlr.set_webhook_id(created.get_id());
+    owdlr.set_origin_id(neurosis.get_id());
+    owdlr.set_limit(10);
+    let deliveries = ds.list_origin_webhook_deliveries(&owdlr).unwrap();
+    assert_eq!(deliveries.get_deliveries().len(), 1);
+    let delivery = &deliveries.get_deliveries()[0];
+    assert_eq!(
+        delivery.get_event(),
+        originsrv::OriginWebhookEvent::GroupComplete
+    );
+    assert_eq!(
+        delivery.get_body(),
+        r#"{"event":"group.complete","origin":"neurosis","data":{"id":"1"}}"#
+    );
+    assert!(!delivery.get_delivered());
+    assert_eq!(delivery.get_attempts(), 0);
+
+    let mut owd = originsrv::OriginWebhookDelete::new();
+    owd.set_id(created.get_id());
+    owd.set_origin_id(neurosis.get_id());
+    assert!(ds.delete_origin_webhook(&owd).unwrap());
+    assert!(!ds.delete_origin_webhook(&owd).unwrap());
+    assert_eq!(
+        ds.list_origin_webhooks(&owlr)
+            .unwrap()
+            .get_webhooks()
+            .len(),
+        0
+    );
+}
+
 #[test]
 fn create_origin_project() {
     let ds = datastore_test!(DataStore);
@@ -495,6 +746,35 @@ fn create_origin_project() {
         .expect("Failed to create origin project");
 }
 
+#[test]
+fn create_origin_project_with_hook_secret() {
+    let ds = datastore_test!(DataStore);
+    let mut origin = originsrv::OriginCreate::new();
+    origin.set_name(String::from("neurosis"));
+    origin.set_owner_id(1);
+    origin.set_owner_name(String::from("scottkelly"));
+    let neurosis = ds.create_origin(&origin)
+        .expect("Should create origin")
+        .expect("Should return the origin");
+
+    let mut op = originsrv::OriginProject::new();
+    op.set_origin_name(String::from(neurosis.get_name()));
+    op.set_origin_id(neurosis.get_id());
+    op.set_package_name(String::from("zeal"));
+    op.set_plan_path(String::from("foo"));
+    op.set_vcs_type(String::from("gitlab"));
+    op.set_vcs_data(String::from("https://gitlab.com/neurosis/zeal.git"));
+    op.set_owner_id(1);
+    op.set_hook_secret(String::from("s3cr3t"));
+
+    let mut opc = originsrv::OriginProjectCreate::new();
+    opc.set_project(op);
+
+    let project = ds.create_origin_project(&opc)
+        .expect("Failed to create origin project");
+    assert_eq!(project.get_hook_secret(), "s3cr3t");
+}
+
 #[test]
 fn create_origin_project_handles_unique_constraint_violations_correctly() {
     let ds = datastore_test!(DataStore);
@@ -873,6 +1153,9 @@ fn update_origin_project() {
     project.set_vcs_data(String::from("svn://github.com/habitat-sh/core-plans"));
     op.set_vcs_installation_id(2);
     project.set_owner_id(2);
+    project.set_required_labels(protobuf::RepeatedField::from_vec(vec![String::from(
+        "docker",
+    )]));
 
     let mut opu = originsrv::OriginProjectUpdate::new();
     let updated_project = project.clone();
@@ -934,6 +1217,11 @@ fn update_origin_project() {
         sepultura.get_vcs_installation_id(),
         "Should have the same vcs installation id"
     );
+    assert_eq!(
+        sepultura.get_required_labels(),
+        &["docker".to_string()],
+        "Should have the updated required labels"
+    );
 }
 
 #[test]
@@ -1075,6 +1363,59 @@ fn get_origin_package() {
     assert_eq!(result.get_tdeps().to_vec(), tdep_idents.to_vec());
 }
 
+#[test]
+fn delete_origin_package() {
+    let ds = datastore_test!(DataStore);
+    let mut origin = originsrv::OriginCreate::new();
+    origin.set_name(String::from("core"));
+    origin.set_owner_id(1);
+    origin.set_owner_name(String::from("scottkelly"));
+    let origin = ds.create_origin(&origin)
+        .expect("Should create origin")
+        .unwrap();
+
+    let mut ident = originsrv::OriginPackageIdent::new();
+    ident.set_origin("core".to_string());
+    ident.set_name("cacerts".to_string());
+    ident.set_version("2017.01.17".to_string());
+    ident.set_release("20170209064044".to_string());
+
+    let mut package = originsrv::OriginPackageCreate::new();
+    package.set_owner_id(1);
+    package.set_origin_id(origin.get_id());
+    package.set_ident(ident.clone());
+    package.set_checksum("checksum".to_string());
+    package.set_manifest("manifest".to_string());
+    package.set_config("config".to_string());
+    package.set_target("x86_64-linux".to_string());
+    let package = ds.create_origin_package(&package)
+        .expect("Failed to create origin package");
+
+    let mut package_delete = originsrv::OriginPackageDelete::new();
+    package_delete.set_id(package.get_id());
+    package_delete.set_ident(ident.clone());
+    ds.delete_origin_package(&package_delete)
+        .expect("Failed to delete origin package");
+
+    let mut package_get = originsrv::OriginPackageGet::new();
+    package_get.set_ident(ident.clone());
+    package_get.set_visibilities(vec![originsrv::OriginPackageVisibility::Public]);
+    let result = ds.get_origin_package(&package_get)
+        .expect("Failed to get origin package");
+    assert!(result.is_none());
+
+    let mut audit = originsrv::PackageDeleteAudit::new();
+    audit.set_ident(ident);
+    audit.set_target("x86_64-linux".to_string());
+    audit.set_operation(originsrv::PackageDeleteOperation::PackageDelete);
+    audit.set_trigger(originsrv::PackageChannelTrigger::BuilderUI);
+    audit.set_requester_id(1);
+    audit.set_requester_name("scottkelly".to_string());
+    audit.set_origin_id(origin.get_id());
+    ds.package_delete_audit(&audit)
+        .expect("Failed to audit package deletion");
+}
+
 #[test]
 fn get_latest_package() {
     let ds = datastore_test!(DataStore);
@@ -1637,6 +1978,128 @@ fn search_origin_package_for_origin() {
     assert_eq!(pkg6.to_string(), "josh/red_dog/2017.01.19/20170209064045");
 }
 
+#[test]
+fn search_origin_package_full_text() {
+    let ds = datastore_test!(DataStore);
+
+    let mut origin = originsrv::OriginCreate::new();
+    origin.set_name(String::from("core"));
+    origin.set_owner_id(1);
+    origin.set_owner_name(String::from("scottkelly"));
+    let origin1 = ds.create_origin(&origin.clone())
+        .expect("Should create origin")
+        .unwrap();
+
+    let packages = vec![
+        (
+            "redis/3.2.4/20170514150022",
+            "Persistent key-value database, with built-in net interface",
+            "x86_64-linux",
+            vec![6379],
+        ),
+        (
+            "redis/4.0.10/20180608165107",
+            "Persistent key-value database, with built-in net interface",
+            "x86_64-linux",
+            vec![6379],
+        ),
+        (
+            "postgresql/9.6.9/20180608164543",
+            "PostgreSQL is a powerful, open source object-relational database system.",
+            "x86_64-linux",
+            vec![5432],
+        ),
+        (
+            "nginx/1.15.0/20180608102033",
+            "NGINX web server.",
+            "x86_64-windows",
+            vec![80, 443],
+        ),
+    ];
+
+    let mut package = originsrv::OriginPackageCreate::new();
+    package.set_owner_id(1);
+    package.set_origin_id(origin1.get_id());
+    package.set_checksum("checksum".to_string());
+    package.set_config("config".to_string());
+    let mut created = Vec::new();
+    for (ident, manifest, target, exposes) in packages {
+        let ident =
+            originsrv::OriginPackageIdent::from_str(&format!("core/{}", ident)).unwrap();
+        package.set_ident(ident);
+        package.set_manifest(manifest.to_string());
+        package.set_target(target.to_string());
+        package.set_exposes(exposes);
+        created.push(
+            ds.create_origin_package(&package.clone())
+                .expect("Failed to create origin package"),
+        );
+    }
+
+    let mut oscc = originsrv::OriginChannelCreate::new();
+    oscc.set_origin_id(origin1.get_id());
+    oscc.set_origin_name(origin1.get_name().to_string());
+    oscc.set_name(String::from("search"));
+    let channel = ds.create_origin_channel(&oscc)
+        .expect("Failed to create origin channel");
+
+    let mut opp = originsrv::OriginPackagePromote::new();
+    opp.set_channel_id(channel.get_id());
+    opp.set_package_id(created[2].get_id());
+    opp.set_ident(created[2].get_ident().clone());
+    ds.promote_origin_package(&opp)
+        .expect("Could not promote package");
+
+    // Words of the description match by prefix, and packages with more releases rank higher
+    let mut ops = originsrv::OriginPackageSearchRequest::new();
+    ops.set_query("key datab".to_string());
+    ops.set_full_text(true);
+    ops.set_distinct(true);
+    ops.set_start(0);
+    ops.set_stop(20);
+    let result = ds.search_origin_package_for_origin(&ops)
+        .expect("Could not get the packages from the database");
+    assert_eq!(result.get_count(), 1);
+    assert_eq!(result.get_idents()[0].to_string(), "core/redis");
+
+    ops.set_query("database".to_string());
+    let result = ds.search_origin_package_for_origin(&ops)
+        .expect("Could not get the packages from the database");
+    assert_eq!(result.get_count(), 2);
+    assert_eq!(result.get_idents()[0].to_string(), "core/redis");
+    assert_eq!(result.get_idents()[1].to_string(), "core/postgresql");
+
+    // Exposed ports are indexed
+    ops.set_query("443".to_string());
+    let result = ds.search_origin_package_for_origin(&ops)
+        .expect("Could not get the packages from the database");
+    assert_eq!(result.get_count(), 1);
+    assert_eq!(result.get_idents()[0].to_string(), "core/nginx");
+
+    // Results may be narrowed to a channel or target
+    ops.set_query("database".to_string());
+    ops.set_channel("search".to_string());
+    let result = ds.search_origin_package_for_origin(&ops)
+        .expect("Could not get the packages from the database");
+    assert_eq!(result.get_count(), 1);
+    assert_eq!(result.get_idents()[0].to_string(), "core/postgresql");
+
+    ops.set_query("server".to_string());
+    ops.set_channel("".to_string());
+    ops.set_target("x86_64-linux".to_string());
+    let result = ds.search_origin_package_for_origin(&ops)
+        .expect("Could not get the packages from the database");
+    assert_eq!(result.get_count(), 0);
+
+    // Without distinct, every release is returned
+    ops.set_query("redis".to_string());
+    ops.set_target("".to_string());
+    ops.set_distinct(false);
+    let result = ds.search_origin_package_for_origin(&ops)
+        .expect("Could not get the packages from the database");
+    assert_eq!(result.get_count(), 2);
+}
+
 #[test]
 fn create_origin_channel() {
     let ds = datastore_test!(DataStore);
@@ -1989,6 +2452,100 @@ fn get_demote_channel_package() {
         .expect("Could not promote package");
 }
 
+#[test]
+fn channel_retention_prunes_old_releases() {
+    let ds = datastore_test!(DataStore);
+    let mut origin = originsrv::OriginCreate::new();
+    origin.set_name(String::from("neurosis"));
+    origin.set_owner_id(1);
+    origin.set_owner_name(String::from("scottkelly"));
+    ds.create_origin(&origin).expect("Should create origin");
+
+    let neurosis = ds.get_origin_by_name("neurosis")
+        .expect("Could not retrieve origin")
+        .expect("Origin does not exist");
+
+    let mut oscc = originsrv::OriginChannelCreate::new();
+    oscc.set_origin_id(neurosis.get_id());
+    oscc.set_origin_name(neurosis.get_name().to_string());
+    oscc.set_name(String::from("eve"));
+    oscc.set_owner_id(1);
+    let channel1 = ds.create_origin_channel(&oscc)
+        .expect("Failed to create origin channel");
+
+    let releases = vec!["20170209064044", "20170209064045", "20170209064046"];
+    let mut idents = Vec::new();
+    for release in releases {
+        let mut ident = originsrv::OriginPackageIdent::new();
+        ident.set_origin("neurosis".to_string());
+        ident.set_name("cacerts".to_string());
+        ident.set_version("2017.01.17".to_string());
+        ident.set_release(release.to_string());
+
+        let mut package = originsrv::OriginPackageCreate::new();
+        package.set_owner_id(1);
+        package.set_origin_id(neurosis.get_id());
+        package.set_ident(ident.clone());
+        package.set_target("x86_64-linux".to_string());
+        let package = ds.create_origin_package(&package)
+            .expect("Failed to create origin package");
+
+        let mut opp = originsrv::OriginPackagePromote::new();
+        opp.set_channel_id(channel1.get_id());
+        opp.set_package_id(package.get_id());
+        opp.set_ident(ident.clone());
+        ds.promote_origin_package(&opp)
+            .expect("Could not promote package");
+        idents.push(ident);
+    }
+
+    let mut retention = originsrv::OriginChannelRetention::new();
+    retention.set_channel_id(channel1.get_id());
+    retention.set_origin_id(neurosis.get_id());
+    retention.set_keep_latest(2);
+    let mut ocrs = originsrv::OriginChannelRetentionSet::new();
+    ocrs.set_retention(retention);
+    ds.set_origin_channel_retention(&ocrs)
+        .expect("Failed to set channel retention");
+
+    let mut ocrg = originsrv::OriginChannelRetentionGet::new();
+    ocrg.set_channel_id(channel1.get_id());
+    ocrg.set_origin_id(neurosis.get_id());
+    let result = ds.get_origin_channel_retention(&ocrg)
+        .expect("Failed to get channel retention")
+        .unwrap();
+    assert_eq!(result.get_keep_latest(), 2);
+    assert_eq!(result.get_max_age_days(), 0);
+
+    let conn = ds.pool
+        .get(&ocrg)
+        .expect("Cannot get connection from pool");
+    let rows = conn.query("SELECT * FROM prune_origin_channels_v1()", &[])
+        .expect("Failed to prune channels");
+    assert_eq!(rows.len(), 1);
+
+    for (i, ident) in idents.iter().enumerate() {
+        let mut package_get = originsrv::OriginChannelPackageGet::new();
+        package_get.set_ident(ident.clone());
+        package_get.set_name(channel1.get_name().to_string());
+        package_get.set_visibilities(vec![originsrv::OriginPackageVisibility::Public]);
+        let result = ds.get_origin_channel_package(&package_get)
+            .expect("Failed to get origin channel package");
+        assert_eq!(result.is_some(), i > 0);
+    }
+
+    let mut ocrd = originsrv::OriginChannelRetentionDelete::new();
+    ocrd.set_channel_id(channel1.get_id());
+    ocrd.set_origin_id(neurosis.get_id());
+    ds.delete_origin_channel_retention(&ocrd)
+        .expect("Failed to delete channel retention");
+    assert!(
+        ds.get_origin_channel_retention(&ocrg)
+            .expect("Failed to get channel retention")
+            .is_none()
+    );
+}
+
 #[test]
 fn get_promote_channel_package() {
     let ds = datastore_test!(DataStore);
@@ -2514,3 +3071,53 @@ fn delete_origin_channel_by_name() {
         "Channel package should not exist"
     );
 }
+
+#[test]
+fn origin_audit() {
+    let ds = datastore_test!(DataStore);
+    let mut origin = originsrv::OriginCreate::new();
+    origin.set_name(String::from("neurosis"));
+    origin.set_owner_id(1);
+    origin.set_owner_name(String::from("scottkelly"));
+    let neurosis = ds.create_origin(&origin)
+        .expect("Should create origin")
+        .expect("Should return the origin");
+
+    let mut audit = originsrv::OriginAudit::new();
+    audit.set_origin_id(neurosis.get_id());
+    audit.set_operation(originsrv::OriginAuditOperation::MemberAdd);
+    audit.set_subject(String::from("noahlandis"));
+    audit.set_trigger(originsrv::PackageChannelTrigger::BuilderUI);
+    audit.set_requester_id(2);
+    audit.set_requester_name(String::from("noahlandis"));
+    ds.origin_audit(&audit).expect("Failed to audit member add");
+
+    audit.set_operation(originsrv::OriginAuditOperation::SecretCreate);
+    audit.set_subject(String::from("TOKEN"));
+    audit.set_requester_id(1);
+    audit.set_requester_name(String::from("scottkelly"));
+    ds.origin_audit(&audit).expect("Failed to audit secret create");
+
+    let mut request = originsrv::OriginAuditListRequest::new();
+    request.set_origin_id(neurosis.get_id());
+    request.set_start(0);
+    request.set_stop(49);
+    let response = ds.list_origin_audit(&request)
+        .expect("Failed to list origin audit entries");
+    assert_eq!(response.get_count(), 2);
+    assert_eq!(response.get_entries()[0].get_operation(), "SecretCreate");
+    assert_eq!(response.get_entries()[1].get_operation(), "MemberAdd");
+
+    request.set_requester_name(String::from("noahlandis"));
+    let response = ds.list_origin_audit(&request)
+        .expect("Failed to list origin audit entries");
+    assert_eq!(response.get_count(), 1);
+    assert_eq!(response.get_entries()[0].get_subject(), "noahlandis");
+    assert_eq!(response.get_entries()[0].get_trigger(), "BuilderUI");
+
+    request.clear_requester_name();
+    request.set_from(String::from("2100-01-01T00:00:00Z"));
+    let response = ds.list_origin_audit(&request)
+        .expect("Failed to list origin audit entries");
+    assert_eq!(response.get_count(), 0);
+}
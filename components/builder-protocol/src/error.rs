@@ -23,6 +23,7 @@ use protobuf;
 pub enum ProtocolError {
     BadJobGroupProjectState(String),
     BadJobGroupState(String),
+    BadJobSectionStatus(String),
     BadJobState(String),
     BadPackageChannelOperation(String),
     BadPackageChannelTrigger(String),
@@ -46,6 +47,7 @@ impl fmt::Display for ProtocolError {
                 format!("Bad Job Group Project State {}", e)
             }
             ProtocolError::BadJobGroupState(ref e) => format!("Bad Job Group State {}", e),
+            ProtocolError::BadJobSectionStatus(ref e) => format!("Bad Job Section Status {}", e),
             ProtocolError::BadJobState(ref e) => format!("Bad Job State {}", e),
             ProtocolError::BadPackageChannelOperation(ref e) => {
                 format!("Bad Package Channel Operation {}", e)
@@ -85,6 +87,7 @@ impl error::Error for ProtocolError {
         match *self {
             ProtocolError::BadJobGroupProjectState(_) => "Job Group Project state cannot be parsed",
             ProtocolError::BadJobGroupState(_) => "Job Group state cannot be parsed",
+            ProtocolError::BadJobSectionStatus(_) => "Job section status cannot be parsed",
             ProtocolError::BadJobState(_) => "Job state cannot be parsed",
             ProtocolError::BadPackageChannelOperation(_) => {
                 "Package Channel Operation cannot be parsed"
@@ -39,6 +39,14 @@ impl Into<Job> for JobSpec {
         if self.has_channel() {
             job.set_channel(self.take_channel());
         }
+        job.set_priority(self.get_priority());
+        if self.has_target() {
+            job.set_target(self.take_target());
+        }
+        if self.has_git_ref() {
+            job.set_git_ref(self.take_git_ref());
+        }
+        job.set_pull_request(self.get_pull_request());
         job
     }
 }
@@ -99,7 +107,7 @@ impl Serialize for Job {
     where
         S: Serializer,
     {
-        let mut strukt = serializer.serialize_struct("job", 10)?;
+        let mut strukt = serializer.serialize_struct("job", 17)?;
 
         // Technically, an ID is a 64-bit integer, but that can cause
         // issues when processing it in JavaScript on the front-end,
@@ -148,6 +156,102 @@ impl Serialize for Job {
             strukt.serialize_field("channel", self.get_channel())?;
         }
 
+        if self.has_target() {
+            strukt.serialize_field("target", self.get_target())?;
+        }
+
+        if self.has_git_ref() {
+            strukt.serialize_field("git_ref", self.get_git_ref())?;
+        }
+        if self.has_git_sha() {
+            strukt.serialize_field("git_sha", self.get_git_sha())?;
+        }
+        if self.get_pull_request() != 0 {
+            strukt.serialize_field("pull_request", &self.get_pull_request())?;
+        }
+
+        strukt.serialize_field("retry_count", &self.get_retry_count())?;
+        strukt.serialize_field("attempts", self.get_attempts())?;
+        strukt.serialize_field("sections", self.get_sections())?;
+
+        strukt.end()
+    }
+}
+
+impl Serialize for JobSection {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut strukt = serializer.serialize_struct("job_section", 5)?;
+        strukt.serialize_field("name", self.get_name())?;
+        strukt.serialize_field("status", &self.get_status())?;
+        strukt.serialize_field("started_at", self.get_started_at())?;
+
+        if self.has_finished_at() {
+            strukt.serialize_field("finished_at", self.get_finished_at())?;
+        }
+        if self.has_duration_ms() {
+            strukt.serialize_field("duration_ms", &self.get_duration_ms())?;
+        }
+
+        strukt.end()
+    }
+}
+
+impl Serialize for JobSectionStatus {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl FromStr for JobSectionStatus {
+    type Err = ProtocolError;
+
+    fn from_str(value: &str) -> result::Result<Self, Self::Err> {
+        match value.to_lowercase().as_ref() {
+            "running" => Ok(JobSectionStatus::SectionRunning),
+            "succeeded" => Ok(JobSectionStatus::SectionSucceeded),
+            "failed" => Ok(JobSectionStatus::SectionFailed),
+            _ => Err(ProtocolError::BadJobSectionStatus(value.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for JobSectionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match *self {
+            JobSectionStatus::SectionRunning => "Running",
+            JobSectionStatus::SectionSucceeded => "Succeeded",
+            JobSectionStatus::SectionFailed => "Failed",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl Serialize for JobAttempt {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut strukt = serializer.serialize_struct("job_attempt", 6)?;
+        strukt.serialize_field("attempt", &self.get_attempt())?;
+        strukt.serialize_field("worker", &self.get_worker())?;
+        strukt.serialize_field("created_at", &self.get_created_at())?;
+
+        if self.has_build_started_at() {
+            strukt.serialize_field("build_started_at", &self.get_build_started_at())?;
+        }
+        if self.has_build_finished_at() {
+            strukt.serialize_field("build_finished_at", &self.get_build_finished_at())?;
+        }
+        if self.has_error() {
+            strukt.serialize_field("error", self.get_error())?;
+        }
+
         strukt.end()
     }
 }
@@ -305,6 +409,8 @@ impl fmt::Display for JobGroupOperation {
         let value = match *self {
             JobGroupOperation::JobGroupOpCreate => "JobGroupCreate",
             JobGroupOperation::JobGroupOpCancel => "JobGroupCancel",
+            JobGroupOperation::JobGroupOpPause => "JobGroupPause",
+            JobGroupOperation::JobGroupOpResume => "JobGroupResume",
         };
         write!(f, "{}", value)
     }
@@ -317,6 +423,8 @@ impl FromStr for JobGroupOperation {
         match value.to_lowercase().as_ref() {
             "jobgroupcreate" => Ok(JobGroupOperation::JobGroupOpCreate),
             "jobgroupcancel" => Ok(JobGroupOperation::JobGroupOpCancel),
+            "jobgrouppause" => Ok(JobGroupOperation::JobGroupOpPause),
+            "jobgroupresume" => Ok(JobGroupOperation::JobGroupOpResume),
             _ => Err(ProtocolError::BadJobGroupState(value.to_string())),
         }
     }
@@ -401,6 +509,22 @@ impl Routable for JobGroupCancel {
     }
 }
 
+impl Routable for JobGroupPause {
+    type H = String;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(self.get_group_id().to_string())
+    }
+}
+
+impl Routable for JobGroupResume {
+    type H = String;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(self.get_group_id().to_string())
+    }
+}
+
 impl Routable for JobGraphPackageCreate {
     type H = String;
 
@@ -425,6 +549,28 @@ impl Routable for JobGroupOriginGet {
     }
 }
 
+impl Routable for JobGroupOriginActiveGet {
+    type H = String;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(self.get_origin().to_string())
+    }
+}
+
+impl Pageable for JobGroupAuditListRequest {
+    fn get_range(&self) -> [u64; 2] {
+        [self.get_start(), self.get_stop()]
+    }
+}
+
+impl Routable for JobGroupAuditListRequest {
+    type H = String;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(self.get_origin().to_string())
+    }
+}
+
 impl Routable for JobGraphPackageStatsGet {
     type H = String;
 
@@ -450,6 +596,8 @@ impl fmt::Display for JobGroupState {
             JobGroupState::GroupFailed => "Failed",
             JobGroupState::GroupQueued => "Queued",
             JobGroupState::GroupCanceled => "Canceled",
+            JobGroupState::GroupPaused => "Paused",
+            JobGroupState::GroupResuming => "Resuming",
         };
         write!(f, "{}", value)
     }
@@ -466,6 +614,8 @@ impl FromStr for JobGroupState {
             "failed" => Ok(JobGroupState::GroupFailed),
             "queued" => Ok(JobGroupState::GroupQueued),
             "canceled" => Ok(JobGroupState::GroupCanceled),
+            "paused" => Ok(JobGroupState::GroupPaused),
+            "resuming" => Ok(JobGroupState::GroupResuming),
             _ => Err(ProtocolError::BadJobGroupState(value.to_string())),
         }
     }
@@ -483,6 +633,8 @@ impl Serialize for JobGroupState {
             3 => serializer.serialize_str("Failed"),
             4 => serializer.serialize_str("Queued"),
             5 => serializer.serialize_str("Canceled"),
+            6 => serializer.serialize_str("Paused"),
+            7 => serializer.serialize_str("Resuming"),
             _ => panic!("Unexpected enum value"),
         }
     }
@@ -518,6 +670,22 @@ impl FromStr for JobGroupProjectState {
     }
 }
 
+impl Serialize for JobGroupSkipReason {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self as u64 {
+            0 => serializer.serialize_str("NotSkipped"),
+            1 => serializer.serialize_str("NoProject"),
+            2 => serializer.serialize_str("AutoBuildDisabled"),
+            3 => serializer.serialize_str("OtherOrigin"),
+            4 => serializer.serialize_str("DependencySkipped"),
+            _ => panic!("Unexpected enum value"),
+        }
+    }
+}
+
 impl Serialize for JobGroupProjectState {
     fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
     where
@@ -540,11 +708,15 @@ impl Serialize for JobGroupProject {
     where
         S: Serializer,
     {
-        let mut strukt = serializer.serialize_struct("job_group_project", 4)?;
+        let mut strukt = serializer.serialize_struct("job_group_project", 6)?;
         strukt.serialize_field("name", &self.get_name())?;
         strukt.serialize_field("ident", &self.get_ident())?;
         strukt.serialize_field("state", &self.get_state())?;
         strukt.serialize_field("job_id", &self.get_job_id().to_string())?;
+        strukt.serialize_field("target", &self.get_target())?;
+        if self.has_skip_reason() {
+            strukt.serialize_field("skip_reason", &self.get_skip_reason())?;
+        }
         strukt.end()
     }
 }
@@ -554,12 +726,43 @@ impl Serialize for JobGroup {
     where
         S: Serializer,
     {
-        let mut strukt = serializer.serialize_struct("job_group", 5)?;
+        let mut strukt = serializer.serialize_struct("job_group", 11)?;
         strukt.serialize_field("id", &self.get_id().to_string())?;
         strukt.serialize_field("state", &self.get_state())?;
         strukt.serialize_field("projects", &self.get_projects())?;
         strukt.serialize_field("created_at", &self.get_created_at())?;
         strukt.serialize_field("project_name", &self.get_project_name())?;
+        strukt.serialize_field("priority", &self.get_priority())?;
+        if self.has_git_ref() {
+            strukt.serialize_field("git_ref", self.get_git_ref())?;
+        }
+        if self.get_pull_request() != 0 {
+            strukt.serialize_field("pull_request", &self.get_pull_request())?;
+        }
+        if self.has_channel() {
+            strukt.serialize_field("channel", self.get_channel())?;
+        }
+        strukt.serialize_field("trigger", &self.get_trigger().to_string())?;
+        if self.has_job_count() {
+            strukt.serialize_field("job_count", &self.get_job_count())?;
+        }
+        strukt.end()
+    }
+}
+
+impl Serialize for JobGroupAuditEntry {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut strukt = serializer.serialize_struct("job_group_audit_entry", 7)?;
+        strukt.serialize_field("group_id", &self.get_group_id().to_string())?;
+        strukt.serialize_field("project_name", self.get_project_name())?;
+        strukt.serialize_field("operation", &self.get_operation().to_string())?;
+        strukt.serialize_field("trigger", &self.get_trigger().to_string())?;
+        strukt.serialize_field("requester_id", &self.get_requester_id().to_string())?;
+        strukt.serialize_field("requester_name", self.get_requester_name())?;
+        strukt.serialize_field("created_at", self.get_created_at())?;
         strukt.end()
     }
 }
@@ -629,4 +832,14 @@ mod tests {
         assert_eq!(stripped_lines, expected);
     }
 
+    #[test]
+    fn group_state_round_trip() {
+        for state in &[
+            JobGroupState::GroupPaused,
+            JobGroupState::GroupResuming,
+            JobGroupState::GroupCanceled,
+        ] {
+            assert_eq!(state.to_string().parse::<JobGroupState>().unwrap(), *state);
+        }
+    }
 }
@@ -110,6 +110,9 @@ pub struct Message {
     /// Ordered list of network identities of servers which have handled the message starting
     /// with the originator.
     pub identities: Vec<Vec<u8>>,
+    /// Z85 encoded public key the sender authenticated with, if the receiving socket requires
+    /// a CurveZMQ handshake.
+    pub user_id: Option<String>,
     /// Message buffer for `header` portion of a router message.
     header: Header,
     /// Message buffer for `route_info` portion of a router message.
@@ -170,6 +173,7 @@ impl Message {
     /// Useful if you want to re-use the Message struct without allocating a new one.
     pub fn reset(&mut self) {
         self.identities.clear();
+        self.user_id = None;
         self.header.clear();
         self.txn = None;
         self.route_info = None;
@@ -254,6 +258,7 @@ impl Default for Message {
         Message {
             body: Vec::with_capacity(MAX_BODY_LEN),
             identities: Vec::with_capacity(MAX_IDENTITIES),
+            user_id: None,
             header: Header::default(),
             route_info: None,
             txn: None,
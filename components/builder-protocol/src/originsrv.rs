@@ -25,13 +25,17 @@ use sharding::InstaId;
 
 #[derive(Debug)]
 pub enum Error {
+    BadOriginMemberRole,
     BadOriginPackageVisibility,
+    BadOriginWebhookEvent,
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let msg = match *self {
+            Error::BadOriginMemberRole => "Bad Origin Member Role",
             Error::BadOriginPackageVisibility => "Bad Origin Package Visibility",
+            Error::BadOriginWebhookEvent => "Bad Origin Webhook Event",
         };
         write!(f, "{}", msg)
     }
@@ -40,7 +44,9 @@ impl fmt::Display for Error {
 impl error::Error for Error {
     fn description(&self) -> &str {
         match *self {
+            Error::BadOriginMemberRole => "Origin member role cannot be parsed",
             Error::BadOriginPackageVisibility => "Origin package visibility cannot be parsed",
+            Error::BadOriginWebhookEvent => "Origin webhook event cannot be parsed",
         }
     }
 }
@@ -109,6 +115,14 @@ impl Routable for PackageChannelAudit {
     }
 }
 
+impl Routable for PackageDeleteAudit {
+    type H = String;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(String::from(self.get_ident().get_origin()))
+    }
+}
+
 impl Routable for PackageGroupChannelAudit {
     type H = u64;
 
@@ -117,6 +131,55 @@ impl Routable for PackageGroupChannelAudit {
     }
 }
 
+impl Routable for OriginAudit {
+    type H = InstaId;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(InstaId(self.get_origin_id()))
+    }
+}
+
+impl Pageable for OriginAuditListRequest {
+    fn get_range(&self) -> [u64; 2] {
+        [self.get_start(), self.get_stop()]
+    }
+}
+
+impl Routable for OriginAuditListRequest {
+    type H = InstaId;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(InstaId(self.get_origin_id()))
+    }
+}
+
+impl Serialize for OriginAuditEntry {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut strukt = serializer.serialize_struct("origin_audit_entry", 9)?;
+        strukt.serialize_field("operation", self.get_operation())?;
+        if self.has_package() {
+            strukt.serialize_field("package", self.get_package())?;
+        }
+        if self.has_channel() {
+            strukt.serialize_field("channel", self.get_channel())?;
+        }
+        if self.has_subject() {
+            strukt.serialize_field("subject", self.get_subject())?;
+        }
+        strukt.serialize_field("trigger", self.get_trigger())?;
+        strukt.serialize_field("requester_id", &self.get_requester_id().to_string())?;
+        strukt.serialize_field("requester_name", self.get_requester_name())?;
+        strukt.serialize_field("created_at", self.get_created_at())?;
+        if self.has_group_id() {
+            strukt.serialize_field("group_id", &self.get_group_id().to_string())?;
+        }
+        strukt.end()
+    }
+}
+
 pub trait Pageable {
     fn get_range(&self) -> [u64; 2];
 
@@ -252,6 +315,42 @@ impl Serialize for OriginChannel {
     }
 }
 
+impl Serialize for OriginChannelRetention {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut strukt = serializer.serialize_struct("origin_channel_retention", 2)?;
+        strukt.serialize_field("keep_latest", &self.get_keep_latest())?;
+        strukt.serialize_field("max_age_days", &self.get_max_age_days())?;
+        strukt.end()
+    }
+}
+
+impl Routable for OriginChannelRetentionGet {
+    type H = InstaId;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(InstaId(self.get_origin_id()))
+    }
+}
+
+impl Routable for OriginChannelRetentionSet {
+    type H = InstaId;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(InstaId(self.get_retention().get_origin_id()))
+    }
+}
+
+impl Routable for OriginChannelRetentionDelete {
+    type H = InstaId;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(InstaId(self.get_origin_id()))
+    }
+}
+
 impl Routable for OriginChannelCreate {
     type H = InstaId;
 
@@ -481,6 +580,14 @@ impl Routable for OriginUpdate {
     }
 }
 
+impl Routable for OriginDelete {
+    type H = String;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(self.get_name().to_string())
+    }
+}
+
 impl Routable for OriginGet {
     type H = String;
 
@@ -594,13 +701,240 @@ impl Serialize for OriginMemberListResponse {
     where
         S: Serializer,
     {
-        let mut strukt = serializer.serialize_struct("origin_member_list_response", 2)?;
+        let mut strukt = serializer.serialize_struct("origin_member_list_response", 3)?;
         strukt.serialize_field("origin_id", &self.get_origin_id().to_string())?;
         strukt.serialize_field("members", self.get_members())?;
+        strukt.serialize_field("roles", self.get_roles())?;
         strukt.end()
     }
 }
 
+impl Serialize for OriginMember {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut strukt = serializer.serialize_struct("origin_member", 3)?;
+        strukt.serialize_field("account_id", &self.get_account_id().to_string())?;
+        strukt.serialize_field("account_name", self.get_account_name())?;
+        strukt.serialize_field("role", &self.get_role().to_string())?;
+        strukt.end()
+    }
+}
+
+impl OriginMemberRole {
+    /// Returns true if this role holds every permission of the given role.
+    pub fn permits(&self, role: OriginMemberRole) -> bool {
+        *self as i32 >= role as i32
+    }
+}
+
+impl FromStr for OriginMemberRole {
+    type Err = Error;
+
+    fn from_str(value: &str) -> result::Result<Self, Self::Err> {
+        match value.to_lowercase().as_ref() {
+            "readonly" => Ok(OriginMemberRole::ReadOnly),
+            "member" => Ok(OriginMemberRole::Member),
+            "maintainer" => Ok(OriginMemberRole::Maintainer),
+            "owner" => Ok(OriginMemberRole::Owner),
+            _ => Err(Error::BadOriginMemberRole),
+        }
+    }
+}
+
+impl fmt::Display for OriginMemberRole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match *self {
+            OriginMemberRole::ReadOnly => "readonly",
+            OriginMemberRole::Member => "member",
+            OriginMemberRole::Maintainer => "maintainer",
+            OriginMemberRole::Owner => "owner",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl Routable for OriginMemberRoleUpdate {
+    type H = InstaId;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(InstaId(self.get_origin_id()))
+    }
+}
+
+impl Serialize for OriginOwnerTransfer {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut strukt = serializer.serialize_struct("origin_owner_transfer", 5)?;
+        strukt.serialize_field("origin_id", &self.get_origin_id().to_string())?;
+        strukt.serialize_field("origin_name", self.get_origin_name())?;
+        strukt.serialize_field("owner_id", &self.get_owner_id().to_string())?;
+        strukt.serialize_field("account_id", &self.get_account_id().to_string())?;
+        strukt.serialize_field("account_name", self.get_account_name())?;
+        strukt.end()
+    }
+}
+
+impl Routable for OriginOwnerTransferCreate {
+    type H = InstaId;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(InstaId(self.get_origin_id()))
+    }
+}
+
+impl Routable for OriginOwnerTransferGet {
+    type H = InstaId;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(InstaId(self.get_origin_id()))
+    }
+}
+
+impl Routable for OriginOwnerTransferAccept {
+    type H = InstaId;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(InstaId(self.get_origin_id()))
+    }
+}
+
+impl Routable for OriginOwnerTransferRescind {
+    type H = InstaId;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(InstaId(self.get_origin_id()))
+    }
+}
+
+impl Serialize for OriginWebhook {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let events: Vec<String> = self.get_events().iter().map(|e| e.to_string()).collect();
+        let mut strukt = serializer.serialize_struct("origin_webhook", 4)?;
+        strukt.serialize_field("id", &self.get_id().to_string())?;
+        strukt.serialize_field("origin", self.get_origin_name())?;
+        strukt.serialize_field("url", self.get_url())?;
+        strukt.serialize_field("events", &events)?;
+        strukt.end()
+    }
+}
+
+impl Routable for OriginWebhookCreate {
+    type H = InstaId;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(InstaId(self.get_webhook().get_origin_id()))
+    }
+}
+
+impl Routable for OriginWebhookDelete {
+    type H = InstaId;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(InstaId(self.get_origin_id()))
+    }
+}
+
+impl Routable for OriginWebhookListRequest {
+    type H = InstaId;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(InstaId(self.get_origin_id()))
+    }
+}
+
+impl Serialize for OriginWebhookListResponse {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut strukt = serializer.serialize_struct("origin_webhook_list_response", 1)?;
+        strukt.serialize_field("webhooks", self.get_webhooks())?;
+        strukt.end()
+    }
+}
+
+impl Routable for OriginWebhookNotify {
+    type H = String;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(self.get_origin_name().to_string())
+    }
+}
+
+impl Serialize for OriginWebhookDelivery {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut strukt = serializer.serialize_struct("origin_webhook_delivery", 10)?;
+        strukt.serialize_field("id", &self.get_id().to_string())?;
+        strukt.serialize_field("webhook_id", &self.get_webhook_id().to_string())?;
+        strukt.serialize_field("event", &self.get_event().to_string())?;
+        strukt.serialize_field("body", self.get_body())?;
+        strukt.serialize_field("attempts", &self.get_attempts())?;
+        strukt.serialize_field("status_code", &self.get_status_code())?;
+        strukt.serialize_field("error", self.get_error())?;
+        strukt.serialize_field("delivered", &self.get_delivered())?;
+        strukt.serialize_field("created_at", self.get_created_at())?;
+        strukt.serialize_field("updated_at", self.get_updated_at())?;
+        strukt.end()
+    }
+}
+
+impl Routable for OriginWebhookDeliveryListRequest {
+    type H = InstaId;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(InstaId(self.get_origin_id()))
+    }
+}
+
+impl Serialize for OriginWebhookDeliveryListResponse {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut strukt = serializer.serialize_struct("origin_webhook_delivery_list_response", 1)?;
+        strukt.serialize_field("deliveries", self.get_deliveries())?;
+        strukt.end()
+    }
+}
+
+impl FromStr for OriginWebhookEvent {
+    type Err = Error;
+
+    fn from_str(value: &str) -> result::Result<Self, Self::Err> {
+        match value.to_lowercase().as_ref() {
+            "job.started" => Ok(OriginWebhookEvent::JobStarted),
+            "job.failed" => Ok(OriginWebhookEvent::JobFailed),
+            "group.complete" => Ok(OriginWebhookEvent::GroupComplete),
+            "package.uploaded" => Ok(OriginWebhookEvent::PackageUploaded),
+            "package.promoted" => Ok(OriginWebhookEvent::PackagePromoted),
+            _ => Err(Error::BadOriginWebhookEvent),
+        }
+    }
+}
+
+impl fmt::Display for OriginWebhookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match *self {
+            OriginWebhookEvent::JobStarted => "job.started",
+            OriginWebhookEvent::JobFailed => "job.failed",
+            OriginWebhookEvent::GroupComplete => "group.complete",
+            OriginWebhookEvent::PackageUploaded => "package.uploaded",
+            OriginWebhookEvent::PackagePromoted => "package.promoted",
+        };
+        write!(f, "{}", value)
+    }
+}
+
 impl Routable for OriginMemberRemove {
     type H = InstaId;
 
@@ -724,6 +1058,14 @@ impl Routable for OriginPackageGet {
     }
 }
 
+impl Routable for OriginPackageDelete {
+    type H = String;
+
+    fn route_key(&self) -> Option<Self::H> {
+        Some(String::from(self.get_ident().get_origin()))
+    }
+}
+
 impl fmt::Display for OriginPackageIdent {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if !self.get_version().is_empty() && !self.get_release().is_empty() {
@@ -1107,6 +1449,9 @@ impl Serialize for OriginProject {
         )?;
         state.serialize_field("visibility", &self.get_visibility())?;
         state.serialize_field("auto_build", &self.get_auto_build())?;
+        state.serialize_field("required_labels", self.get_required_labels())?;
+        state.serialize_field("vcs_auth_user", self.get_vcs_auth_user())?;
+        state.serialize_field("hook_secret", self.get_hook_secret())?;
         state.end()
     }
 }
@@ -1565,4 +1910,13 @@ mod tests {
             (&origin_ident).into(),
         )
     }
+
+    #[test]
+    fn origin_member_role_permits() {
+        let maintainer = OriginMemberRole::from_str("Maintainer").unwrap();
+        assert!(maintainer.permits(OriginMemberRole::ReadOnly));
+        assert!(maintainer.permits(OriginMemberRole::Maintainer));
+        assert!(!maintainer.permits(OriginMemberRole::Owner));
+        assert!(OriginMemberRole::from_str("admin").is_err());
+    }
 }
@@ -12,8 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::result;
+
 pub use message::routesrv::*;
 use message::Routable;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
 
 pub const DEFAULT_ROUTER_PORT: u16 = 5562;
 pub const PING_INTERVAL_MS: i64 = 30_000;
@@ -41,3 +45,56 @@ impl Routable for Registration {
         None
     }
 }
+
+impl Routable for RouterStatusGet {
+    type H = u64;
+
+    fn route_key(&self) -> Option<Self::H> {
+        None
+    }
+}
+
+impl Serialize for RouterServer {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut strukt = serializer.serialize_struct("router_server", 5)?;
+        strukt.serialize_field("net_ident", self.get_net_ident())?;
+        strukt.serialize_field("protocol", &self.get_protocol().to_string())?;
+        strukt.serialize_field("shards", self.get_shards())?;
+        strukt.serialize_field("heartbeat_age_ms", &self.get_heartbeat_age_ms())?;
+        strukt.serialize_field("outstanding", &self.get_outstanding())?;
+        strukt.end()
+    }
+}
+
+impl Serialize for RouterProtocolStatus {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut strukt = serializer.serialize_struct("router_protocol_status", 7)?;
+        strukt.serialize_field("protocol", &self.get_protocol().to_string())?;
+        strukt.serialize_field("unserved_shards", self.get_unserved_shards())?;
+        strukt.serialize_field("routed", &self.get_routed())?;
+        strukt.serialize_field("replied", &self.get_replied())?;
+        strukt.serialize_field("failovers", &self.get_failovers())?;
+        strukt.serialize_field("errors", &self.get_errors())?;
+        strukt.serialize_field("in_flight", &self.get_in_flight())?;
+        strukt.end()
+    }
+}
+
+impl Serialize for RouterStatus {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut strukt = serializer.serialize_struct("router_status", 3)?;
+        strukt.serialize_field("net_ident", self.get_net_ident())?;
+        strukt.serialize_field("servers", self.get_servers())?;
+        strukt.serialize_field("protocols", self.get_protocols())?;
+        strukt.end()
+    }
+}
@@ -27,12 +27,14 @@ use sharding::InstaId;
 
 #[derive(Debug)]
 pub enum Error {
+    BadAccessTokenScope,
     BadOAuthProvider,
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let msg = match *self {
+            Error::BadAccessTokenScope => "Bad Access Token Scope",
             Error::BadOAuthProvider => "Bad OAuth Provider",
         };
         write!(f, "{}", msg)
@@ -42,6 +44,7 @@ impl fmt::Display for Error {
 impl error::Error for Error {
     fn description(&self) -> &str {
         match *self {
+            Error::BadAccessTokenScope => "Access token scope cannot be parsed",
             Error::BadOAuthProvider => "OAuth Provider cannot be parsed",
         }
     }
@@ -156,24 +159,56 @@ impl Routable for AccountUpdate {
 }
 
 impl Into<Session> for AccessToken {
-    fn into(self) -> Session {
+    fn into(mut self) -> Session {
         let mut session = Session::new();
         session.set_id(self.get_account_id());
         session.set_flags(self.get_flags());
+        session.set_token_origins(self.take_origins());
+        session.set_token_scopes(self.take_scopes());
         session
     }
 }
 
+impl fmt::Display for AccessTokenScope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match *self {
+            AccessTokenScope::Read => "read",
+            AccessTokenScope::Upload => "upload",
+            AccessTokenScope::Promote => "promote",
+            AccessTokenScope::Schedule => "schedule",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for AccessTokenScope {
+    type Err = Error;
+
+    fn from_str(value: &str) -> result::Result<Self, Self::Err> {
+        match value.to_lowercase().as_ref() {
+            "read" => Ok(AccessTokenScope::Read),
+            "upload" => Ok(AccessTokenScope::Upload),
+            "promote" => Ok(AccessTokenScope::Promote),
+            "schedule" => Ok(AccessTokenScope::Schedule),
+            _ => Err(Error::BadAccessTokenScope),
+        }
+    }
+}
+
 impl Serialize for AccountToken {
     fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut strukt = serializer.serialize_struct("account_token", 5)?;
+        let scopes: Vec<String> = self.get_scopes().iter().map(|s| s.to_string()).collect();
+        let mut strukt = serializer.serialize_struct("account_token", 7)?;
         strukt.serialize_field("id", &self.get_id().to_string())?;
         strukt.serialize_field("account_id", &self.get_account_id().to_string())?;
         strukt.serialize_field("token", self.get_token())?;
         strukt.serialize_field("created_at", &self.get_created_at())?;
+        strukt.serialize_field("origins", self.get_origins())?;
+        strukt.serialize_field("scopes", &scopes)?;
+        strukt.serialize_field("expires_at", self.get_expires_at())?;
         strukt.end()
     }
 }
@@ -337,6 +372,25 @@ impl Serialize for AccountOriginListResponse {
     }
 }
 
+impl Session {
+    /// Returns true if the session may act on the given origin. Sessions which were not created
+    /// from an access token limited to some origins may act on every origin.
+    pub fn permits_origin(&self, origin: &str) -> bool {
+        self.get_token_origins().is_empty() || self.get_token_origins().iter().any(|o| o == origin)
+    }
+
+    /// Returns true if the session may perform operations of the given scope. Sessions which were
+    /// not created from a scoped access token may perform every operation.
+    pub fn permits_scope(&self, scope: AccessTokenScope) -> bool {
+        self.get_token_scopes().is_empty() || self.get_token_scopes().contains(&scope)
+    }
+
+    /// Returns true if the session was created from an access token limited to some scopes.
+    pub fn is_scoped(&self) -> bool {
+        !self.get_token_scopes().is_empty()
+    }
+}
+
 impl Serialize for Session {
     fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
     where
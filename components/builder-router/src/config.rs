@@ -18,6 +18,8 @@ use std::net::{IpAddr, Ipv4Addr};
 use std::str::FromStr;
 
 use hab_core::config::ConfigFile;
+use hab_net::curve::CurveCfg;
+use protocol::message::Protocol;
 use protocol::routesrv::DEFAULT_ROUTER_PORT;
 use toml;
 
@@ -30,6 +32,12 @@ pub struct Config {
     pub listen: IpAddr,
     /// Port for receiving routable messages from services and gateways
     pub port: u16,
+    /// How to choose between several servers registered for the same shard
+    pub balance: BalanceStrategy,
+    /// CurveZMQ keys of the router and of the services and gateways allowed to connect
+    pub curve: CurveCfg,
+    /// CurveZMQ public keys of the services allowed to register for each protocol
+    pub registration_keys: RegistrationKeys,
 }
 
 impl Config {
@@ -43,10 +51,49 @@ impl Default for Config {
         Config {
             listen: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
             port: DEFAULT_ROUTER_PORT,
+            balance: BalanceStrategy::RoundRobin,
+            curve: CurveCfg::default(),
+            registration_keys: RegistrationKeys::default(),
         }
     }
 }
 
+/// Z85 encoded public keys allowed to register a server for each protocol. Only consulted when
+/// CurveZMQ is enabled, gateways only need to be present in `curve.allowed_keys`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct RegistrationKeys {
+    pub sessionsrv: Vec<String>,
+    pub originsrv: Vec<String>,
+    pub jobsrv: Vec<String>,
+}
+
+impl RegistrationKeys {
+    /// Returns true if a sender authenticated with `user_id` may register for `protocol`.
+    pub fn allows(&self, protocol: Protocol, user_id: Option<&str>) -> bool {
+        let keys = match protocol {
+            Protocol::SessionSrv => &self.sessionsrv,
+            Protocol::OriginSrv => &self.originsrv,
+            Protocol::JobSrv => &self.jobsrv,
+            Protocol::Net | Protocol::RouteSrv => return false,
+        };
+        match user_id {
+            Some(user_id) => keys.iter().any(|key| key == user_id),
+            None => false,
+        }
+    }
+}
+
+/// Strategy for choosing which of the servers hosting a shard receives the next message.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BalanceStrategy {
+    /// Take turns between the servers hosting the shard.
+    RoundRobin,
+    /// Prefer the server with the fewest transactions still awaiting a reply.
+    LeastOutstanding,
+}
+
 impl ConfigFile for Config {
     type Error = Error;
 }
@@ -70,11 +117,36 @@ mod tests {
         let content = r#"
         listen = "0:0:0:0:0:0:0:1"
         port = 9000
+        balance = "least_outstanding"
+
+        [curve]
+        public_key = "rq:rM>}U?@Lns47E1%kR.o@n%FcmmsL/@{H8]yf7"
+        secret_key = "JTKVSB%%)wK0E.X)V>+}o?pNmC{O&4W4b!Ni{Lh6"
+        allowed_keys = ["Yne@$w-vo<fVvi]a<NY6T1ed:M$fCG*[IaLV{hID"]
+
+        [registration_keys]
+        originsrv = ["Yne@$w-vo<fVvi]a<NY6T1ed:M$fCG*[IaLV{hID"]
         "#;
 
         let config = Config::from_str(&content).unwrap();
         assert_eq!(&format!("{}", config.listen), "::1");
         assert_eq!(config.port, 9000);
+        assert_eq!(config.balance, BalanceStrategy::LeastOutstanding);
+        assert!(config.curve.is_enabled());
+        assert_eq!(config.curve.allowed_keys.len(), 1);
+        assert_eq!(config.registration_keys.originsrv.len(), 1);
+        assert!(config.registration_keys.sessionsrv.is_empty());
+    }
+
+    #[test]
+    fn registration_keys_allow() {
+        let key = "Yne@$w-vo<fVvi]a<NY6T1ed:M$fCG*[IaLV{hID";
+        let mut keys = RegistrationKeys::default();
+        keys.originsrv.push(key.to_string());
+        assert!(keys.allows(Protocol::OriginSrv, Some(key)));
+        assert!(!keys.allows(Protocol::OriginSrv, None));
+        assert!(!keys.allows(Protocol::JobSrv, Some(key)));
+        assert!(!keys.allows(Protocol::RouteSrv, Some(key)));
     }
 
     #[test]
@@ -85,5 +157,7 @@ mod tests {
 
         let config = Config::from_str(&content).unwrap();
         assert_eq!(&format!("{}", config.listen), "172.18.0.1");
+        assert_eq!(config.balance, BalanceStrategy::RoundRobin);
+        assert!(!config.curve.is_enabled());
     }
 }
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub use hab_net::conn::{route, route_reply, wait_recv, ConnErr, ConnEvent};
+use hab_net::curve::CurveCfg;
 use hab_net::socket;
 use protobuf;
 use protocol::Message;
@@ -24,11 +25,12 @@ pub struct SrvConn {
 }
 
 impl SrvConn {
-    pub fn new(context: &mut zmq::Context) -> Result<Self, ConnErr> {
+    pub fn new(context: &mut zmq::Context, curve: &CurveCfg) -> Result<Self, ConnErr> {
         let socket = context.socket(zmq::ROUTER)?;
         socket.set_router_mandatory(true)?;
         socket.set_probe_router(true)?;
         socket.set_identity(socket::srv_ident().as_bytes())?;
+        curve.configure_server(&socket)?;
         Ok(SrvConn {
             socket: socket,
             recv_buf: zmq::Message::new()?,
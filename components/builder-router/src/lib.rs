@@ -32,5 +32,5 @@ mod conn;
 pub mod error;
 pub mod server;
 
-pub use self::config::Config;
+pub use self::config::{BalanceStrategy, Config};
 pub use self::error::{Error, Result};
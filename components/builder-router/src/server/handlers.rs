@@ -12,21 +12,33 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use hab_net::socket;
 use hab_net::{ErrCode, NetError};
 use protocol::message::Message;
 use protocol::routesrv;
 
 use super::ServerMap;
+use config::Config;
 use conn::SrvConn;
 use error::Result;
 
-pub fn on_disconnect(_: &SrvConn, message: &mut Message, servers: &mut ServerMap) -> Result<()> {
+pub fn on_disconnect(
+    _: &SrvConn,
+    message: &mut Message,
+    _: &Config,
+    servers: &mut ServerMap,
+) -> Result<()> {
     debug!("OnDisconnect, {:?}", message.sender_str().unwrap());
     servers.drop(message.sender().unwrap());
     Ok(())
 }
 
-pub fn on_heartbeat(conn: &SrvConn, message: &mut Message, servers: &mut ServerMap) -> Result<()> {
+pub fn on_heartbeat(
+    conn: &SrvConn,
+    message: &mut Message,
+    _: &Config,
+    servers: &mut ServerMap,
+) -> Result<()> {
     debug!("OnHeartbeat, {:?}", message.sender_str().unwrap());
     if !servers.renew(message.sender().unwrap()) {
         let err = NetError::new(ErrCode::REG_NOT_FOUND, "rt:heartbeat:1");
@@ -37,18 +49,41 @@ pub fn on_heartbeat(conn: &SrvConn, message: &mut Message, servers: &mut ServerM
 }
 
 pub fn on_registration(
-    conn: &SrvConn,
+    _: &SrvConn,
     message: &mut Message,
+    config: &Config,
     servers: &mut ServerMap,
 ) -> Result<()> {
     let mut body = message.parse::<routesrv::Registration>()?;
     debug!("OnRegistration, {:?}", body);
     let protocol = body.get_protocol();
-    let shards = body.take_shards();
-    if !servers.add(protocol, message.sender().unwrap().to_vec(), shards) {
-        let err = NetError::new(ErrCode::REG_CONFLICT, "rt:register:1");
-        warn!("{}", err);
-        conn.route_reply(message, &*err)?;
+    if config.curve.is_enabled()
+        && !config
+            .registration_keys
+            .allows(protocol, message.user_id.as_ref().map(String::as_str))
+    {
+        warn!(
+            "Rejected registration of {} for {:?}, key {:?} is not allowed",
+            message.sender_str().unwrap(),
+            protocol,
+            message.user_id
+        );
+        return Ok(());
     }
+    let shards = body.take_shards();
+    servers.add(protocol, message.sender().unwrap().to_vec(), shards);
+    Ok(())
+}
+
+pub fn on_status(
+    conn: &SrvConn,
+    message: &mut Message,
+    _: &Config,
+    servers: &mut ServerMap,
+) -> Result<()> {
+    debug!("OnStatus, {:?}", message.sender_str().unwrap());
+    let mut status = servers.status();
+    status.set_net_ident(socket::srv_ident());
+    conn.route_reply(message, &status)?;
     Ok(())
 }
@@ -18,13 +18,14 @@ use std::collections::HashMap;
 
 use hab_net::time;
 use hab_net::{ErrCode, NetError};
+use protobuf::RepeatedField;
 use protocol::message::{Message, Protocol};
-use protocol::routesrv::PING_INTERVAL_MS;
+use protocol::routesrv::{self, PING_INTERVAL_MS};
 use protocol::sharding::{ShardId, SHARD_COUNT};
 use rand::{self, Rng};
 use zmq;
 
-use config::Config;
+use config::{BalanceStrategy, Config};
 use conn::{ConnErr, ConnEvent, SrvConn};
 use error::{Error, Result};
 
@@ -53,8 +54,12 @@ impl Server {
     }
 
     /// Forward a protocol message containing a transaction reply to the originator.
-    fn forward_reply(&self, conn: &SrvConn, message: &mut Message) {
-        message.identities.remove(0);
+    fn forward_reply(&mut self, conn: &SrvConn, message: &mut Message) {
+        let sender = message.identities.remove(0);
+        self.servers.complete(&sender);
+        if let Some(protocol) = message.route_info().map(|r| r.protocol()) {
+            self.servers.counters_mut(protocol).replied += 1;
+        }
         trace!("route-message, transaction reply, {:?}", message);
         if let Err(err) = conn.forward_reply(message) {
             error!("{}", err);
@@ -68,12 +73,13 @@ impl Server {
             "Disconnect" => handlers::on_disconnect,
             "Heartbeat" => handlers::on_heartbeat,
             "Registration" => handlers::on_registration,
+            "RouterStatusGet" => handlers::on_status,
             message_id => {
                 warn!("handle-message, recv unknown message, {}", message_id);
                 return Ok(());
             }
         };
-        handler(conn, message, &mut self.servers)
+        handler(conn, message, &self.config, &mut self.servers)
     }
 
     /// Handle routing of a protocol message to a connected service or delegate to `handle_message`
@@ -86,13 +92,35 @@ impl Server {
                 }
             }
             Some(Protocol::Net) => warn!("route-message, unroutable message, {}", message),
-            Some(_) => {
-                if let Some(identity) = self.select_shard(message) {
-                    if let Err(err) = conn.forward(message, identity.to_vec()) {
-                        error!("{}", err);
+            Some(protocol) => {
+                // Fail over to another server hosting the shard if the selected one has gone away
+                // without deregistering.
+                while let Some(identity) = self.select_shard(message) {
+                    match conn.forward(message, identity.clone()) {
+                        Ok(()) => {
+                            if message.txn().is_some() {
+                                self.servers.begin(&identity);
+                            }
+                            self.servers.counters_mut(protocol).routed += 1;
+                            return;
+                        }
+                        Err(ConnErr::HostUnreachable) => {
+                            warn!(
+                                "route-message, server unreachable, {}",
+                                String::from_utf8_lossy(&identity)
+                            );
+                            message.identities.remove(0);
+                            self.servers.drop(&identity);
+                            self.servers.counters_mut(protocol).failovers += 1;
+                        }
+                        Err(err) => {
+                            error!("{}", err);
+                            self.servers.counters_mut(protocol).errors += 1;
+                            return;
+                        }
                     }
-                    return;
                 }
+                self.servers.counters_mut(protocol).errors += 1;
                 let err = NetError::new(ErrCode::NO_SHARD, "rt:route:2");
                 error!("{}", err);
                 message.populate_reply(&*err).unwrap();
@@ -106,7 +134,8 @@ impl Server {
 
     /// Run the server blocking the calling thread until the server shuts down.
     fn run(&mut self) -> Result<()> {
-        let mut conn = SrvConn::new(&mut self.context)?;
+        self.config.curve.start_authenticator(&mut self.context)?;
+        let mut conn = SrvConn::new(&mut self.context, &self.config.curve)?;
         let mut message = Message::default();
         conn.bind(&self.config.addr())?;
         println!("Listening on ({})", self.config.addr());
@@ -139,16 +168,20 @@ impl Server {
         Ok(())
     }
 
-    /// Returns `Some` with the net identity of the server registered for the given protocol
-    /// hosting the shard for which the given protocol message was intended for. Returns `None`
-    /// if there is no server hosting the shard for the given protocol.
-    fn select_shard(&mut self, message: &Message) -> Option<&[u8]> {
+    /// Returns `Some` with the net identity of a server registered for the given protocol
+    /// hosting the shard for which the given protocol message was intended for, chosen by the
+    /// configured balance strategy. Returns `None` if there is no server hosting the shard for
+    /// the given protocol.
+    fn select_shard(&mut self, message: &Message) -> Option<Vec<u8>> {
         let shard_id = match message.route_info().and_then(|m| m.hash()) {
             Some(hash) => (hash % SHARD_COUNT as u64) as u32,
             None => (self.rng.gen::<u64>() % SHARD_COUNT as u64) as u32,
         };
-        self.servers
-            .get(&message.route_info().unwrap().protocol(), &shard_id)
+        self.servers.select(
+            message.route_info().unwrap().protocol(),
+            shard_id,
+            self.config.balance,
+        )
     }
 
     /// A tickless timer for determining how long to wait between each server tick. This value is
@@ -159,37 +192,90 @@ impl Server {
     }
 }
 
+/// Running totals of the messages routed for a protocol.
+#[derive(Debug, Default)]
+pub struct RouteCounters {
+    /// Messages forwarded to a server.
+    pub routed: u64,
+    /// Transaction replies forwarded back to their originator.
+    pub replied: u64,
+    /// Messages redirected to another server after the selected one was unreachable.
+    pub failovers: u64,
+    /// Messages which could not be routed to any server.
+    pub errors: u64,
+}
+
 #[derive(Debug, Default)]
 pub struct ServerMap {
-    reg: HashMap<Protocol, HashMap<ShardId, Vec<u8>>>,
+    /// Servers hosting each shard of each protocol, in order of registration.
+    reg: HashMap<Protocol, HashMap<ShardId, Vec<Vec<u8>>>>,
+    /// Position of the next server to try for each shard of each protocol.
+    cursors: HashMap<(Protocol, ShardId), usize>,
+    /// Number of forwarded transactions each server has yet to reply to.
+    outstanding: HashMap<Vec<u8>, u64>,
+    /// Protocol each server registered for.
+    protocols: HashMap<Vec<u8>, Protocol>,
+    /// Routing totals for each protocol since the router started.
+    counters: HashMap<Protocol, RouteCounters>,
     timestamps: HashMap<Vec<u8>, i64>,
 }
 
 impl ServerMap {
-    pub fn add(&mut self, protocol: Protocol, net_ident: Vec<u8>, shards: Vec<ShardId>) -> bool {
-        if !self.reg.contains_key(&protocol) {
-            self.reg.insert(protocol, HashMap::default());
-        }
-        let registrations = self.reg.get_mut(&protocol).unwrap();
-        for shard in shards.iter() {
-            if let Some(reg) = registrations.get(&shard) {
-                if reg != &net_ident {
-                    return false;
-                }
+    /// Registers a server as hosting the given shards alongside any other servers already
+    /// hosting them. A server registering again replaces its previous registration.
+    pub fn add(&mut self, protocol: Protocol, net_ident: Vec<u8>, shards: Vec<ShardId>) {
+        for map in self.reg.values_mut() {
+            for net_idents in map.values_mut() {
+                net_idents.retain(|id| id != &net_ident);
             }
         }
+        let registrations = self.reg.entry(protocol).or_insert_with(HashMap::default);
         for shard in shards {
-            registrations.insert(shard, net_ident.clone());
+            registrations
+                .entry(shard)
+                .or_insert_with(Vec::new)
+                .push(net_ident.clone());
         }
+        self.protocols.insert(net_ident.clone(), protocol);
         self.timestamps
             .insert(net_ident, time::clock_time() + SERVER_TTL);
-        true
+    }
+
+    /// Records that a transaction was forwarded to the given server.
+    pub fn begin(&mut self, net_ident: &[u8]) {
+        if let Some(count) = self.outstanding.get_mut(net_ident) {
+            *count += 1;
+            return;
+        }
+        self.outstanding.insert(net_ident.to_vec(), 1);
+    }
+
+    pub fn counters_mut(&mut self, protocol: Protocol) -> &mut RouteCounters {
+        self.counters.entry(protocol).or_insert_with(RouteCounters::default)
+    }
+
+    /// Records that the given server replied to a transaction.
+    pub fn complete(&mut self, net_ident: &[u8]) {
+        if let Some(count) = self.outstanding.get_mut(net_ident) {
+            *count = count.saturating_sub(1);
+        }
     }
 
     pub fn drop(&mut self, target: &[u8]) {
-        for map in self.reg.values_mut() {
-            map.retain(|_, net_ident| net_ident.as_slice() != target);
+        for (protocol, map) in self.reg.iter_mut() {
+            map.retain(|shard, net_idents| {
+                let count = net_idents.len();
+                net_idents.retain(|net_ident| net_ident.as_slice() != target);
+                if count > 0 && net_idents.is_empty() {
+                    warn!("no servers remaining for shard {} of {}", shard, protocol);
+                }
+                !net_idents.is_empty()
+            });
         }
+        self.outstanding
+            .retain(|net_ident, _| net_ident.as_slice() != target);
+        self.protocols
+            .retain(|net_ident, _| net_ident.as_slice() != target);
         self.timestamps
             .retain(|net_ident, _| net_ident.as_slice() != target);
     }
@@ -214,11 +300,117 @@ impl ServerMap {
         }
     }
 
-    pub fn get(&self, protocol: &Protocol, shard: &ShardId) -> Option<&[u8]> {
+    /// Returns the net identities of all servers hosting the given shard.
+    pub fn get(&self, protocol: &Protocol, shard: &ShardId) -> &[Vec<u8>] {
         self.reg
             .get(protocol)
             .and_then(|shards| shards.get(shard))
-            .and_then(|s| Some(s.as_slice()))
+            .map_or(&[], |s| s.as_slice())
+    }
+
+    /// Returns the net identity of the server which should receive the next message for the
+    /// given shard. Servers are tried in turn, and with `LeastOutstanding` the server with the
+    /// fewest unanswered transactions wins, ties going to the next server in turn.
+    pub fn select(
+        &mut self,
+        protocol: Protocol,
+        shard: ShardId,
+        strategy: BalanceStrategy,
+    ) -> Option<Vec<u8>> {
+        let servers = match self.reg.get(&protocol).and_then(|shards| shards.get(&shard)) {
+            Some(servers) if !servers.is_empty() => servers,
+            _ => return None,
+        };
+        let start = {
+            let cursor = self.cursors.entry((protocol, shard)).or_insert(0);
+            let start = *cursor % servers.len();
+            *cursor = start + 1;
+            start
+        };
+        let index = match strategy {
+            BalanceStrategy::RoundRobin => start,
+            BalanceStrategy::LeastOutstanding => {
+                let outstanding = &self.outstanding;
+                (0..servers.len())
+                    .map(|offset| (start + offset) % servers.len())
+                    .min_by_key(|i| outstanding.get(&servers[*i]).cloned().unwrap_or(0))
+                    .unwrap()
+            }
+        };
+        Some(servers[index].clone())
+    }
+
+    /// Returns the current registrations, the shards of each protocol left without a server and
+    /// the routing totals of each protocol.
+    pub fn status(&self) -> routesrv::RouterStatus {
+        let now = time::clock_time();
+        let mut servers = self.timestamps
+            .iter()
+            .map(|(net_ident, expires)| {
+                let mut server = routesrv::RouterServer::new();
+                server.set_net_ident(String::from_utf8_lossy(net_ident).into_owned());
+                if let Some(protocol) = self.protocols.get(net_ident) {
+                    server.set_protocol(*protocol);
+                    let mut shards = self.reg
+                        .get(protocol)
+                        .map(|map| {
+                            map.iter()
+                                .filter(|&(_, net_idents)| net_idents.contains(net_ident))
+                                .map(|(shard, _)| *shard)
+                                .collect::<Vec<ShardId>>()
+                        })
+                        .unwrap_or_default();
+                    shards.sort();
+                    server.set_shards(shards);
+                }
+                server.set_heartbeat_age_ms(now - (expires - SERVER_TTL));
+                server.set_outstanding(self.outstanding.get(net_ident).cloned().unwrap_or(0));
+                server
+            })
+            .collect::<Vec<routesrv::RouterServer>>();
+        servers.sort_by(|a, b| {
+            (a.get_protocol() as i32, a.get_net_ident())
+                .cmp(&(b.get_protocol() as i32, b.get_net_ident()))
+        });
+
+        let mut protocols = self.reg
+            .keys()
+            .chain(self.counters.keys())
+            .cloned()
+            .collect::<Vec<Protocol>>();
+        protocols.sort_by_key(|protocol| *protocol as i32);
+        protocols.dedup();
+        let protocols = protocols
+            .into_iter()
+            .map(|protocol| {
+                let mut status = routesrv::RouterProtocolStatus::new();
+                status.set_protocol(protocol);
+                status.set_unserved_shards(
+                    (0..SHARD_COUNT)
+                        .filter(|shard| self.get(&protocol, shard).is_empty())
+                        .collect(),
+                );
+                if let Some(counters) = self.counters.get(&protocol) {
+                    status.set_routed(counters.routed);
+                    status.set_replied(counters.replied);
+                    status.set_failovers(counters.failovers);
+                    status.set_errors(counters.errors);
+                }
+                status.set_in_flight(
+                    self.protocols
+                        .iter()
+                        .filter(|&(_, p)| *p == protocol)
+                        .filter_map(|(net_ident, _)| self.outstanding.get(net_ident))
+                        .sum(),
+                );
+                status
+            })
+            .collect::<Vec<routesrv::RouterProtocolStatus>>();
+
+        let mut status = routesrv::RouterStatus::new();
+        status.set_servers(RepeatedField::from_vec(servers));
+        status.set_protocols(RepeatedField::from_vec(protocols));
+        status
     }
 
     pub fn next_expiration(&self) -> i64 {
@@ -247,3 +439,129 @@ impl ServerMap {
 pub fn run(config: Config) -> Result<()> {
     Server::new(config).run()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ident(name: &str) -> Vec<u8> {
+        name.as_bytes().to_vec()
+    }
+
+    #[test]
+    fn shards_hosted_by_several_servers() {
+        let mut servers = ServerMap::default();
+        servers.add(Protocol::OriginSrv, ident("a"), vec![0, 1]);
+        servers.add(Protocol::OriginSrv, ident("b"), vec![1, 2]);
+
+        assert_eq!(servers.get(&Protocol::OriginSrv, &0), &[ident("a")]);
+        assert_eq!(
+            servers.get(&Protocol::OriginSrv, &1),
+            &[ident("a"), ident("b")]
+        );
+
+        // Registering again replaces the previous registration
+        servers.add(Protocol::OriginSrv, ident("a"), vec![2]);
+        assert!(servers.get(&Protocol::OriginSrv, &0).is_empty());
+        assert_eq!(
+            servers.get(&Protocol::OriginSrv, &2),
+            &[ident("b"), ident("a")]
+        );
+    }
+
+    #[test]
+    fn round_robin_selection() {
+        let mut servers = ServerMap::default();
+        servers.add(Protocol::SessionSrv, ident("a"), vec![0]);
+        servers.add(Protocol::SessionSrv, ident("b"), vec![0]);
+
+        let picks = (0..4)
+            .map(|_| {
+                servers
+                    .select(Protocol::SessionSrv, 0, BalanceStrategy::RoundRobin)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(picks, vec![ident("a"), ident("b"), ident("a"), ident("b")]);
+        assert!(
+            servers
+                .select(Protocol::SessionSrv, 1, BalanceStrategy::RoundRobin)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn least_outstanding_selection() {
+        let mut servers = ServerMap::default();
+        servers.add(Protocol::OriginSrv, ident("a"), vec![0]);
+        servers.add(Protocol::OriginSrv, ident("b"), vec![0]);
+        servers.begin(&ident("a"));
+        servers.begin(&ident("a"));
+        servers.begin(&ident("b"));
+
+        for _ in 0..2 {
+            assert_eq!(
+                servers.select(Protocol::OriginSrv, 0, BalanceStrategy::LeastOutstanding),
+                Some(ident("b"))
+            );
+        }
+
+        servers.complete(&ident("a"));
+        servers.complete(&ident("a"));
+        assert_eq!(
+            servers.select(Protocol::OriginSrv, 0, BalanceStrategy::LeastOutstanding),
+            Some(ident("a"))
+        );
+    }
+
+    #[test]
+    fn dropped_server_fails_over() {
+        let mut servers = ServerMap::default();
+        servers.add(Protocol::OriginSrv, ident("a"), vec![0]);
+        servers.add(Protocol::OriginSrv, ident("b"), vec![0]);
+
+        servers.drop(&ident("a"));
+        for _ in 0..2 {
+            assert_eq!(
+                servers.select(Protocol::OriginSrv, 0, BalanceStrategy::RoundRobin),
+                Some(ident("b"))
+            );
+        }
+
+        servers.drop(&ident("b"));
+        assert!(
+            servers
+                .select(Protocol::OriginSrv, 0, BalanceStrategy::RoundRobin)
+                .is_none()
+        );
+        assert!(!servers.renew(&ident("b")));
+    }
+
+    #[test]
+    fn status_reports_registrations_gaps_and_counters() {
+        let mut servers = ServerMap::default();
+        servers.add(Protocol::OriginSrv, ident("a"), (1..SHARD_COUNT).collect());
+        servers.add(Protocol::SessionSrv, ident("b"), (0..SHARD_COUNT).collect());
+        servers.begin(&ident("a"));
+        servers.counters_mut(Protocol::OriginSrv).routed += 1;
+        servers.counters_mut(Protocol::OriginSrv).errors += 1;
+
+        let status = servers.status();
+        assert_eq!(status.get_servers().len(), 2);
+        let server = &status.get_servers()[1];
+        assert_eq!(server.get_net_ident(), "a");
+        assert_eq!(server.get_protocol(), Protocol::OriginSrv);
+        assert_eq!(server.get_shards().len(), SHARD_COUNT as usize - 1);
+        assert_eq!(server.get_outstanding(), 1);
+
+        let protocols = status.get_protocols();
+        assert_eq!(protocols.len(), 2);
+        assert_eq!(protocols[0].get_protocol(), Protocol::SessionSrv);
+        assert!(protocols[0].get_unserved_shards().is_empty());
+        assert_eq!(protocols[1].get_protocol(), Protocol::OriginSrv);
+        assert_eq!(protocols[1].get_unserved_shards(), &[0]);
+        assert_eq!(protocols[1].get_routed(), 1);
+        assert_eq!(protocols[1].get_errors(), 1);
+        assert_eq!(protocols[1].get_in_flight(), 1);
+    }
+}
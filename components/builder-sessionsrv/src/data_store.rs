@@ -17,9 +17,10 @@
 embed_migrations!("src/migrations");
 
 use std::io;
+use std::str::FromStr;
 use std::sync::Arc;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use db::config::{DataStoreCfg, ShardId};
 use db::diesel_pool::DieselPool;
 use db::migration::shard_setup;
@@ -154,16 +155,27 @@ impl DataStore {
         account_token_create: &sessionsrv::AccountTokenCreate,
     ) -> SrvResult<sessionsrv::AccountToken> {
         let conn = self.pool.get(account_token_create)?;
+        let scopes: Vec<String> = account_token_create
+            .get_scopes()
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let expires_at = match account_token_create.get_expires() {
+            0 => None,
+            expires => Some(Utc.timestamp(expires, 0)),
+        };
         let rows = conn.query(
-            "SELECT * FROM insert_account_token_v1($1, $2)",
+            "SELECT * FROM insert_account_token_v2($1, $2, $3, $4, $5)",
             &[
                 &(account_token_create.get_account_id() as i64),
                 &account_token_create.get_token(),
+                &account_token_create.get_origins().to_vec(),
+                &scopes,
+                &expires_at,
             ],
         ).map_err(SrvError::AccountTokenCreate)?;
         let row = rows.get(0);
-        let account = self.row_to_account_token(row);
-        Ok(account)
+        self.row_to_account_token(row)
     }
 
     pub fn get_account_tokens(
@@ -179,7 +191,7 @@ impl DataStore {
         let mut account_tokens = sessionsrv::AccountTokens::new();
         let mut tokens = protobuf::RepeatedField::new();
         for row in rows {
-            let account_token = self.row_to_account_token(row);
+            let account_token = self.row_to_account_token(row)?;
             tokens.push(account_token);
         }
         account_tokens.set_tokens(tokens);
@@ -198,8 +210,7 @@ impl DataStore {
 
         assert!(rows.len() == 1);
         let row = rows.get(0);
-        let account = self.row_to_account_token(row);
-        Ok(account)
+        self.row_to_account_token(row)
     }
 
     pub fn revoke_account_token(
@@ -372,7 +383,10 @@ impl DataStore {
         account
     }
 
-    fn row_to_account_token(&self, row: postgres::rows::Row) -> sessionsrv::AccountToken {
+    fn row_to_account_token(
+        &self,
+        row: postgres::rows::Row,
+    ) -> SrvResult<sessionsrv::AccountToken> {
         let mut account_token = sessionsrv::AccountToken::new();
         let id: i64 = row.get("id");
         account_token.set_id(id as u64);
@@ -383,6 +397,20 @@ impl DataStore {
         let created_at = row.get::<&str, DateTime<Utc>>("created_at");
         account_token.set_created_at(created_at.to_rfc3339());
 
-        account_token
+        let origins: Vec<String> = row.get("origins");
+        account_token.set_origins(origins.into());
+        let mut scopes = Vec::new();
+        for scope in row.get::<&str, Vec<String>>("scopes") {
+            scopes.push(
+                sessionsrv::AccessTokenScope::from_str(&scope)
+                    .map_err(SrvError::UnknownAccessTokenScope)?,
+            );
+        }
+        account_token.set_scopes(scopes);
+        if let Some(expires_at) = row.get::<&str, Option<DateTime<Utc>>>("expires_at") {
+            account_token.set_expires_at(expires_at.to_rfc3339());
+        }
+
+        Ok(account_token)
     }
 }
@@ -55,6 +55,7 @@ pub enum SrvError {
     Protocol(protocol::ProtocolError),
     SessionCreate(postgres::error::Error),
     SessionGet(postgres::error::Error),
+    UnknownAccessTokenScope(protocol::sessionsrv::Error),
 }
 
 impl fmt::Display for SrvError {
@@ -118,6 +119,7 @@ impl fmt::Display for SrvError {
             SrvError::Protocol(ref e) => format!("{}", e),
             SrvError::SessionCreate(ref e) => format!("Error creating session, {}", e),
             SrvError::SessionGet(ref e) => format!("Error getting session from database, {}", e),
+            SrvError::UnknownAccessTokenScope(ref e) => format!("{}", e),
         };
         write!(f, "{}", msg)
     }
@@ -156,6 +158,7 @@ impl error::Error for SrvError {
             SrvError::Protocol(ref err) => err.description(),
             SrvError::SessionCreate(ref err) => err.description(),
             SrvError::SessionGet(ref err) => err.description(),
+            SrvError::UnknownAccessTokenScope(ref err) => err.description(),
         }
     }
 }
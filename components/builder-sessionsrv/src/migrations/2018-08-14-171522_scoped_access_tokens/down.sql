@@ -0,0 +1,4 @@
+DROP FUNCTION IF EXISTS insert_account_token_v2(bigint, text, text[], text[], timestamptz);
+ALTER TABLE IF EXISTS account_tokens DROP COLUMN IF EXISTS expires_at;
+ALTER TABLE IF EXISTS account_tokens DROP COLUMN IF EXISTS scopes;
+ALTER TABLE IF EXISTS account_tokens DROP COLUMN IF EXISTS origins;
@@ -0,0 +1,20 @@
+ALTER TABLE IF EXISTS account_tokens ADD COLUMN IF NOT EXISTS origins text[] NOT NULL DEFAULT '{}';
+ALTER TABLE IF EXISTS account_tokens ADD COLUMN IF NOT EXISTS scopes text[] NOT NULL DEFAULT '{}';
+ALTER TABLE IF EXISTS account_tokens ADD COLUMN IF NOT EXISTS expires_at timestamptz;
+
+CREATE OR REPLACE FUNCTION insert_account_token_v2 (
+  p_account_id bigint,
+  p_token text,
+  p_origins text[],
+  p_scopes text[],
+  p_expires_at timestamptz
+) RETURNS SETOF account_tokens AS $$
+    -- A new unscoped token replaces the previous unscoped ones; scoped tokens are only added
+    DELETE FROM account_tokens
+    WHERE account_id = p_account_id
+    AND p_origins = '{}' AND p_scopes = '{}'
+    AND origins = '{}' AND scopes = '{}';
+    INSERT INTO account_tokens (account_id, token, origins, scopes, expires_at)
+    VALUES (p_account_id, p_token, p_origins, p_scopes, p_expires_at)
+    RETURNING *;
+$$ LANGUAGE SQL VOLATILE;
@@ -67,6 +67,76 @@ fn get_account_by_id() {
     assert_eq!(bobo.get_name(), bobo2.get_name());
 }
 
+#[test]
+fn create_scoped_account_token() {
+    let ds = datastore_test!(DataStore);
+    let bobo = create_bobo_account(&ds);
+
+    let mut atc = sessionsrv::AccountTokenCreate::new();
+    atc.set_account_id(bobo.get_id());
+    atc.set_token(String::from("_scoped"));
+    atc.set_origins(vec![String::from("core")].into());
+    atc.set_scopes(vec![
+        sessionsrv::AccessTokenScope::Read,
+        sessionsrv::AccessTokenScope::Upload,
+    ]);
+    atc.set_expires(1893456000);
+    ds.create_account_token(&atc)
+        .expect("Should create account token");
+
+    let mut atg = sessionsrv::AccountTokensGet::new();
+    atg.set_account_id(bobo.get_id());
+    let tokens = ds.get_account_tokens(&atg)
+        .expect("Should get account tokens");
+    assert_eq!(tokens.get_tokens().len(), 1);
+
+    let token = &tokens.get_tokens()[0];
+    assert_eq!(token.get_token(), "_scoped");
+    assert_eq!(token.get_origins(), &[String::from("core")]);
+    assert_eq!(
+        token.get_scopes(),
+        &[
+            sessionsrv::AccessTokenScope::Read,
+            sessionsrv::AccessTokenScope::Upload,
+        ]
+    );
+    assert!(token.get_expires_at().starts_with("2030-01-01T00:00:00"));
+}
+
+#[test]
+fn create_scoped_account_token_keeps_other_tokens() {
+    let ds = datastore_test!(DataStore);
+    let bobo = create_bobo_account(&ds);
+
+    let mut atc = sessionsrv::AccountTokenCreate::new();
+    atc.set_account_id(bobo.get_id());
+    atc.set_token(String::from("_unscoped"));
+    ds.create_account_token(&atc)
+        .expect("Should create account token");
+
+    atc.set_token(String::from("_scoped"));
+    atc.set_scopes(vec![sessionsrv::AccessTokenScope::Read]);
+    ds.create_account_token(&atc)
+        .expect("Should create account token");
+
+    let mut atg = sessionsrv::AccountTokensGet::new();
+    atg.set_account_id(bobo.get_id());
+    let tokens = ds.get_account_tokens(&atg)
+        .expect("Should get account tokens");
+    assert_eq!(tokens.get_tokens().len(), 2);
+
+    atc.set_token(String::from("_replacement"));
+    atc.set_scopes(vec![]);
+    ds.create_account_token(&atc)
+        .expect("Should create account token");
+
+    let tokens = ds.get_account_tokens(&atg)
+        .expect("Should get account tokens");
+    let mut names: Vec<&str> = tokens.get_tokens().iter().map(|t| t.get_token()).collect();
+    names.sort();
+    assert_eq!(names, vec!["_replacement", "_scoped"]);
+}
+
 #[test]
 fn delete_origin() {
     let ds = datastore_test!(DataStore);
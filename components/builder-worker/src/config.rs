@@ -20,6 +20,7 @@ use std::path::PathBuf;
 use github_api_client::config::GitHubCfg;
 use hab_core::config::ConfigFile;
 use hab_core::url;
+use hab_net::curve::CurveCfg;
 
 use error::Error;
 
@@ -44,6 +45,8 @@ pub struct Config {
     pub bldr_url: String,
     /// List of Job Servers to connect to
     pub jobsrv: JobSrvCfg,
+    /// CurveZMQ keys of this worker and the pinned public key of the Job Servers
+    pub curve: CurveCfg,
     pub features_enabled: String,
     /// Github application id to use for private repo access
     pub github: GitHubCfg,
@@ -52,6 +55,11 @@ pub struct Config {
     pub recreate_ns_dir: bool,
     pub network_interface: Option<String>,
     pub network_gateway: Option<IpAddr>,
+    /// Capability labels advertised to the Job Server (e.g. "docker", "large-memory"). Only
+    /// jobs whose project requires a subset of these labels will be dispatched to this worker
+    pub labels: Vec<String>,
+    /// Cache of dependency artifacts shared between jobs on this worker
+    pub artifact_cache: ArtifactCacheCfg,
 }
 
 impl Config {
@@ -69,6 +77,10 @@ impl Config {
     pub fn ns_dir_path(&self) -> PathBuf {
         self.data_path.join("network").join("airlock-ns")
     }
+
+    pub fn artifact_cache_path(&self) -> PathBuf {
+        self.data_path.join("artifact-cache")
+    }
 }
 
 impl Default for Config {
@@ -81,12 +93,15 @@ impl Default for Config {
             bldr_channel: String::from("unstable"),
             bldr_url: url::default_bldr_url(),
             jobsrv: vec![JobSrvAddr::default()],
+            curve: CurveCfg::default(),
             features_enabled: "".to_string(),
             github: GitHubCfg::default(),
             airlock_enabled: true,
             recreate_ns_dir: false,
             network_interface: None,
             network_gateway: None,
+            labels: vec![],
+            artifact_cache: ArtifactCacheCfg::default(),
         }
     }
 }
@@ -115,6 +130,29 @@ impl Default for JobSrvAddr {
     }
 }
 
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ArtifactCacheCfg {
+    pub enabled: bool,
+    /// Size the cache may grow to before the least recently used artifacts are evicted
+    pub max_size_mb: u64,
+}
+
+impl ArtifactCacheCfg {
+    pub fn max_size_bytes(&self) -> u64 {
+        self.max_size_mb * 1024 * 1024
+    }
+}
+
+impl Default for ArtifactCacheCfg {
+    fn default() -> Self {
+        ArtifactCacheCfg {
+            enabled: true,
+            max_size_mb: 10_240,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,6 +167,15 @@ mod tests {
         recreate_ns_dir = true
         network_interface = "eth1"
         network_gateway = "192.168.10.1"
+        labels = ["docker", "large-memory"]
+
+        [artifact_cache]
+        max_size_mb = 512
+
+        [curve]
+        public_key = "Yne@$w-vo<fVvi]a<NY6T1ed:M$fCG*[IaLV{hID"
+        secret_key = "D:)Q[IlAW!ahhC2ac:9*A}h:p?([4%wOTJ%JR%cs"
+        server_key = "rq:rM>}U?@Lns47E1%kR.o@n%FcmmsL/@{H8]yf7"
 
         [[jobsrv]]
         host = "1:1:1:1:1:1:1:1"
@@ -160,5 +207,13 @@ mod tests {
             config.network_gateway,
             Some(IpAddr::V4(Ipv4Addr::new(192, 168, 10, 1)))
         );
+        assert_eq!(config.labels, vec!["docker", "large-memory"]);
+        assert_eq!(config.artifact_cache.enabled, true);
+        assert_eq!(config.artifact_cache.max_size_bytes(), 512 * 1024 * 1024);
+        assert!(config.curve.is_enabled());
+        assert_eq!(
+            &config.curve.server_key,
+            "rq:rM>}U?@Lns47E1%kR.o@n%FcmmsL/@{H8]yf7"
+        );
     }
 }
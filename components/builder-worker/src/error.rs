@@ -24,6 +24,7 @@ use bldr_core;
 use git2;
 use github_api_client;
 use hab_core;
+use hab_net;
 use protobuf;
 use protocol;
 use retry;
@@ -38,10 +39,12 @@ pub type Result<T> = result::Result<T, Error>;
 pub enum Error {
     AirlockNetworking(PathBuf, io::Error),
     AirlockFailure(process::ExitStatus),
+    ArtifactCache(PathBuf, io::Error),
     BuildEnvFile(PathBuf, io::Error),
     BuildFailure(i32),
     BuilderCore(bldr_core::Error),
     CannotAddCreds,
+    Connection(hab_net::conn::ConnErr),
     Chown(PathBuf, u32, u32, io::Error),
     ChownWait(io::Error),
     CreateDirectory(PathBuf, io::Error),
@@ -82,6 +85,11 @@ impl fmt::Display for Error {
                 p.display(),
                 e
             ),
+            Error::ArtifactCache(ref p, ref e) => format!(
+                "Error while updating artifact cache at {}, err={}",
+                p.display(),
+                e
+            ),
             Error::BuildEnvFile(ref p, ref e) => format!(
                 "Unable to read workspace build env file, {}, {}",
                 p.display(),
@@ -92,6 +100,7 @@ impl fmt::Display for Error {
             }
             Error::BuilderCore(ref e) => format!("{}", e),
             Error::CannotAddCreds => format!("Cannot add credentials to url"),
+            Error::Connection(ref e) => format!("{}", e),
             Error::Chown(ref p, ref u, ref g, ref e) => format!(
                 "Unable to recursively chown path, {} with '{}:{}', {}",
                 p.display(),
@@ -165,10 +174,12 @@ impl error::Error for Error {
         match *self {
             Error::AirlockFailure(_) => "Airlock networking exited with a non-zero exit code",
             Error::AirlockNetworking(_, _) => "IO Error while running airlock networking command",
+            Error::ArtifactCache(_, _) => "IO Error while updating artifact cache",
             Error::BuildEnvFile(_, _) => "Unable to read workspace build env file",
             Error::BuildFailure(_) => "Build studio exited with a non-zero exit code",
             Error::BuilderCore(ref err) => err.description(),
             Error::CannotAddCreds => "Cannot add credentials to url",
+            Error::Connection(ref err) => err.description(),
             Error::Chown(_, _, _, _) => "Unable to recursively chown path",
             Error::ChownWait(_) => "Unable to complete chown process",
             Error::CreateDirectory(_, _) => "Unable to create directory",
@@ -206,6 +217,12 @@ impl From<bldr_core::Error> for Error {
     }
 }
 
+impl From<hab_net::conn::ConnErr> for Error {
+    fn from(err: hab_net::conn::ConnErr) -> Self {
+        Error::Connection(err)
+    }
+}
+
 impl From<hab_core::Error> for Error {
     fn from(err: hab_core::Error) -> Error {
         Error::HabitatCore(err)
@@ -16,7 +16,9 @@ use std::sync::mpsc;
 use std::thread::{self, JoinHandle};
 use std::time::Duration;
 
+use hab_net::curve::CurveCfg;
 use hab_net::socket::DEFAULT_CONTEXT;
+use protobuf::RepeatedField;
 use protocol::{jobsrv as proto, message};
 use zmq;
 
@@ -77,11 +79,12 @@ pub struct HeartbeatCli {
 
 impl HeartbeatCli {
     /// Create a new HeartbeatMgr client
-    pub fn new(net_ident: String) -> Self {
+    pub fn new(net_ident: String, labels: Vec<String>) -> Self {
         let sock = (**DEFAULT_CONTEXT).as_mut().socket(zmq::REQ).unwrap();
         let mut state = proto::Heartbeat::new();
         state.set_endpoint(net_ident);
         state.set_os(worker_os());
+        state.set_labels(RepeatedField::from_vec(labels));
         HeartbeatCli {
             msg: zmq::Message::new().unwrap(),
             sock: sock,
@@ -138,7 +141,7 @@ impl HeartbeatMgr {
     /// Start the HeartbeatMgr
     pub fn start(config: &Config, net_ident: String) -> Result<JoinHandle<()>> {
         let (tx, rx) = mpsc::sync_channel(0);
-        let mut heartbeat = Self::new(net_ident)?;
+        let mut heartbeat = Self::new(net_ident, config.labels.clone(), &config.curve)?;
         let jobsrv_addrs = config.jobsrv_addrs();
         let handle = thread::Builder::new()
             .name("heartbeat".to_string())
@@ -152,15 +155,17 @@ impl HeartbeatMgr {
         }
     }
 
-    fn new(net_ident: String) -> Result<Self> {
+    fn new(net_ident: String, labels: Vec<String>, curve: &CurveCfg) -> Result<Self> {
         let pub_sock = (**DEFAULT_CONTEXT).as_mut().socket(zmq::PUB)?;
         let cli_sock = (**DEFAULT_CONTEXT).as_mut().socket(zmq::REP)?;
         pub_sock.set_immediate(true)?;
         pub_sock.set_sndhwm(1)?;
         pub_sock.set_linger(0)?;
+        curve.configure_client(&pub_sock)?;
         let mut heartbeat = proto::Heartbeat::new();
         heartbeat.set_endpoint(net_ident);
         heartbeat.set_os(worker_os());
+        heartbeat.set_labels(RepeatedField::from_vec(labels));
         heartbeat.set_state(proto::WorkerState::Ready);
         Ok(HeartbeatMgr {
             state: PulseState::default(),
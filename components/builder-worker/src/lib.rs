@@ -27,6 +27,7 @@ extern crate habitat_builder_protocol;
 extern crate habitat_builder_protocol as protocol;
 extern crate habitat_core as hab_core;
 extern crate habitat_depot_client as depot_client;
+extern crate habitat_http_client as hab_http;
 extern crate habitat_net as hab_net;
 extern crate hyper;
 #[macro_use]
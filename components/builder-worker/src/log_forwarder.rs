@@ -41,6 +41,7 @@ impl LogForwarder {
         output_sock.set_sndhwm(5000)?;
         output_sock.set_linger(5000)?;
         output_sock.set_immediate(true)?;
+        config.curve.configure_client(&output_sock)?;
 
         let mut logger = Logger::init(&config.log_path, "log_forwarder.log");
         logger.log_ident("log_forwarder");
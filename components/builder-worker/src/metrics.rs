@@ -23,6 +23,9 @@ pub type InstallationId = u32;
 pub enum Counter {
     GitClone,
     GitAuthenticatedClone,
+    ArtifactCacheHit,
+    ArtifactCacheMiss,
+    ArtifactCacheEviction,
 }
 
 impl metrics::CounterMetric for Counter {}
@@ -32,6 +35,9 @@ impl metrics::Metric for Counter {
         match *self {
             Counter::GitAuthenticatedClone => format!("github.authenticated_clone").into(),
             Counter::GitClone => format!("github.clone").into(),
+            Counter::ArtifactCacheHit => "artifact_cache.hit".into(),
+            Counter::ArtifactCacheMiss => "artifact_cache.miss".into(),
+            Counter::ArtifactCacheEviction => "artifact_cache.eviction".into(),
         }
     }
 }
@@ -0,0 +1,357 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Content-addressed cache of package artifacts shared between jobs on a worker.
+//!
+//! Each artifact is stored once, named by its BLAKE2b checksum, and copied into a job's artifact
+//! directory under its original file name before the Studio starts. The Studio mounts that
+//! directory as its artifact cache, so dependencies already on the worker are not fetched again.
+//! Artifacts downloaded during the build are copied into the cache once the Studio exits and the
+//! least recently used artifacts are evicted when the cache outgrows its size limit.
+//!
+//! Artifacts are always copied rather than linked, so that files a build can write to never
+//! share an inode with the read-only blobs other jobs are populated from.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bldr_core::metrics::CounterMetric;
+use chrono::Utc;
+use hab_core::package::PackageArchive;
+use serde_json;
+
+use error::{Error, Result};
+use metrics::Counter;
+
+const BLOBS_DIR: &'static str = "blobs";
+const INDEX_FILE: &'static str = "index.json";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct CacheEntry {
+    /// Artifact file name the Studio looks for in its artifact cache
+    file_name: String,
+    /// Fully qualified identifier of the package contained in the artifact
+    ident: String,
+    size: u64,
+    /// Unix timestamp of the last build that installed the artifact
+    last_used: i64,
+}
+
+pub struct ArtifactCache {
+    root: PathBuf,
+    max_size: u64,
+    /// Cached artifacts keyed by their BLAKE2b checksum
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl ArtifactCache {
+    /// Opens the cache rooted at the given path, creating it if needed. The cache is only ever
+    /// used by the job runner, which builds one job at a time, so no locking is done.
+    pub fn new<T>(root: T, max_size: u64) -> Result<Self>
+    where
+        T: AsRef<Path>,
+    {
+        let root = root.as_ref().to_path_buf();
+        let blobs = root.join(BLOBS_DIR);
+        fs::create_dir_all(&blobs).map_err(|e| Error::CreateDirectory(blobs.clone(), e))?;
+
+        let mut entries: HashMap<String, CacheEntry> = match File::open(root.join(INDEX_FILE)) {
+            Ok(file) => serde_json::from_reader(file).unwrap_or_else(|err| {
+                warn!("Discarding unreadable artifact cache index, err={}", err);
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        entries.retain(|checksum, _| blobs.join(checksum).is_file());
+
+        Ok(ArtifactCache {
+            root: root,
+            max_size: max_size,
+            entries: entries,
+        })
+    }
+
+    /// Copies every cached artifact into the given artifact directory.
+    pub fn populate(&self, dst: &Path) -> Result<()> {
+        fs::create_dir_all(dst).map_err(|e| Error::CreateDirectory(dst.to_path_buf(), e))?;
+
+        for (checksum, entry) in self.entries.iter() {
+            let path = dst.join(&entry.file_name);
+            if path.exists() {
+                continue;
+            }
+            if let Err(err) = fs::copy(&self.blob_path(checksum), &path) {
+                warn!(
+                    "Failed to copy cached artifact {} into {}, err={}",
+                    entry.file_name,
+                    dst.display(),
+                    err
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the artifacts left in a job's artifact directory once its Studio has exited.
+    ///
+    /// A cached artifact whose package was installed into the Studio counts as a hit. An artifact
+    /// the cache has not seen was downloaded by the build, counts as a miss and is added to the
+    /// cache. Least recently used artifacts are then evicted until the cache fits its size limit.
+    pub fn ingest(&mut self, src: &Path, studio: &Path) -> Result<()> {
+        let now = Utc::now().timestamp();
+        let dir = fs::read_dir(src).map_err(|e| Error::ArtifactCache(src.to_path_buf(), e))?;
+
+        for path in dir.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if path.extension().map_or(true, |ext| ext != "hart") {
+                continue;
+            }
+
+            let mut archive = PackageArchive::new(path.clone());
+            let checksum = match archive.checksum() {
+                Ok(checksum) => checksum,
+                Err(err) => {
+                    warn!("Unable to checksum artifact {}, err={}", path.display(), err);
+                    continue;
+                }
+            };
+
+            if let Some(entry) = self.entries.get_mut(&checksum) {
+                if is_installed(studio, &entry.ident) {
+                    Counter::ArtifactCacheHit.increment();
+                    entry.last_used = now;
+                }
+                continue;
+            }
+
+            let ident = match archive.ident() {
+                Ok(ident) => ident.to_string(),
+                Err(err) => {
+                    warn!("Unable to read artifact {}, err={}", path.display(), err);
+                    continue;
+                }
+            };
+            let size = match fs::metadata(&path) {
+                Ok(metadata) => metadata.len(),
+                Err(err) => {
+                    warn!("Unable to stat artifact {}, err={}", path.display(), err);
+                    continue;
+                }
+            };
+
+            Counter::ArtifactCacheMiss.increment();
+            if let Err(err) = self.add_blob(&path, &checksum) {
+                warn!("Failed to cache artifact {}, err={}", path.display(), err);
+                continue;
+            }
+            let file_name = path.file_name().unwrap().to_string_lossy().into_owned(); // Unwrap OK
+            self.entries.insert(
+                checksum,
+                CacheEntry {
+                    file_name: file_name,
+                    ident: ident,
+                    size: size,
+                    last_used: now,
+                },
+            );
+        }
+
+        self.evict();
+        self.save()
+    }
+
+    /// Total size in bytes of all cached artifacts.
+    pub fn size(&self) -> u64 {
+        self.entries.values().map(|e| e.size).sum()
+    }
+
+    fn evict(&mut self) {
+        let mut size = self.size();
+        if size <= self.max_size {
+            return;
+        }
+
+        let mut lru: Vec<(String, i64, u64)> = self.entries
+            .iter()
+            .map(|(checksum, e)| (checksum.clone(), e.last_used, e.size))
+            .collect();
+        lru.sort_by_key(|&(_, last_used, _)| last_used);
+
+        for (checksum, _, entry_size) in lru {
+            if size <= self.max_size {
+                break;
+            }
+            if let Err(err) = fs::remove_file(self.blob_path(&checksum)) {
+                warn!("Failed to evict cached artifact {}, err={}", checksum, err);
+            }
+            self.entries.remove(&checksum);
+            size -= entry_size;
+            Counter::ArtifactCacheEviction.increment();
+        }
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = self.root.join(INDEX_FILE);
+        let tmp = self.root.join(format!("{}.tmp", INDEX_FILE));
+        let file = File::create(&tmp).map_err(|e| Error::ArtifactCache(tmp.clone(), e))?;
+        serde_json::to_writer(file, &self.entries)
+            .map_err(|e| Error::ArtifactCache(tmp.clone(), e.into()))?;
+        fs::rename(&tmp, &path).map_err(|e| Error::ArtifactCache(path.clone(), e))
+    }
+
+    fn blob_path(&self, checksum: &str) -> PathBuf {
+        self.root.join(BLOBS_DIR).join(checksum)
+    }
+
+    /// Copies an artifact into the cache as a read-only blob. The copy is checksummed again, as
+    /// the artifact came out of a job's Studio and may have changed since it was read.
+    fn add_blob(&self, src: &Path, checksum: &str) -> io::Result<()> {
+        let blob = self.blob_path(checksum);
+        let tmp = self.root.join(BLOBS_DIR).join(format!("{}.tmp", checksum));
+        fs::copy(src, &tmp)?;
+        match PackageArchive::new(tmp.clone()).checksum() {
+            Ok(ref copied) if copied == checksum => (),
+            _ => {
+                let _ = fs::remove_file(&tmp);
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "artifact changed while it was cached",
+                ));
+            }
+        }
+        let mut permissions = fs::metadata(&tmp)?.permissions();
+        permissions.set_readonly(true);
+        fs::set_permissions(&tmp, permissions)?;
+        fs::rename(&tmp, &blob)
+    }
+}
+
+/// Returns true if the package was installed into the Studio, either at its root or within one
+/// of the Studio roots created beneath it.
+fn is_installed(studio: &Path, ident: &str) -> bool {
+    if studio.join("hab").join("pkgs").join(ident).is_dir() {
+        return true;
+    }
+    match fs::read_dir(studio.join("hab").join("studios")) {
+        Ok(dirs) => dirs.filter_map(|d| d.ok())
+            .any(|d| d.path().join("hab").join("pkgs").join(ident).is_dir()),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::io::Write;
+    use std::process;
+
+    fn entry(file_name: &str, size: u64, last_used: i64) -> CacheEntry {
+        CacheEntry {
+            file_name: file_name.to_string(),
+            ident: String::from("core/zlib/1.2.11/20180608050617"),
+            size: size,
+            last_used: last_used,
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let root = env::temp_dir().join(format!("artifact-cache-{}", process::id()));
+        let mut cache = ArtifactCache::new(&root, 100).unwrap();
+        cache.entries.insert("a".to_string(), entry("a.hart", 40, 3));
+        cache.entries.insert("b".to_string(), entry("b.hart", 40, 1));
+        cache.entries.insert("c".to_string(), entry("c.hart", 40, 2));
+
+        cache.evict();
+        assert_eq!(cache.size(), 80);
+        assert!(!cache.entries.contains_key("b"));
+        assert!(cache.entries.contains_key("a"));
+        assert!(cache.entries.contains_key("c"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn ingest_records_hits_for_installed_artifacts() {
+        let root = env::temp_dir().join(format!("artifact-cache-hits-{}", process::id()));
+        let artifacts = root.join("artifacts");
+        let studio = root.join("studio");
+        fs::create_dir_all(&artifacts).unwrap();
+        let mut cache = ArtifactCache::new(root.join("cache"), 1024).unwrap();
+
+        // Both artifacts were linked into the job from the cache, but the build only resolved
+        // and installed one of them
+        for name in ["installed", "unused"].iter() {
+            let path = artifacts.join(format!("{}.hart", name));
+            File::create(&path)
+                .unwrap()
+                .write_all(name.as_bytes())
+                .unwrap();
+            let checksum = PackageArchive::new(path).checksum().unwrap();
+            let mut cached = entry(&format!("{}.hart", name), 10, 0);
+            cached.ident = format!("core/{}/1.0.0/20180608050617", name);
+            cache.entries.insert(checksum, cached);
+        }
+        let pkgs = studio.join("hab/studios/src/hab/pkgs");
+        fs::create_dir_all(pkgs.join("core/installed/1.0.0/20180608050617")).unwrap();
+
+        cache.ingest(&artifacts, &studio).unwrap();
+        let last_used = |file_name: &str| {
+            cache.entries
+                .values()
+                .find(|e| e.file_name == file_name)
+                .map(|e| e.last_used)
+                .unwrap()
+        };
+        assert!(last_used("installed.hart") > 0);
+        assert_eq!(last_used("unused.hart"), 0);
+        assert_eq!(cache.entries.len(), 2);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn artifacts_are_copied_in_and_out() {
+        let root = env::temp_dir().join(format!("artifact-cache-copies-{}", process::id()));
+        let built = root.join("built");
+        let populated = root.join("populated");
+        fs::create_dir_all(&built).unwrap();
+        let mut cache = ArtifactCache::new(root.join("cache"), 1024).unwrap();
+
+        let artifact = built.join("zlib.hart");
+        File::create(&artifact)
+            .unwrap()
+            .write_all(b"zlib")
+            .unwrap();
+        let checksum = PackageArchive::new(artifact.clone()).checksum().unwrap();
+        cache.add_blob(&artifact, &checksum).unwrap();
+        cache.entries.insert(checksum.clone(), entry("zlib.hart", 4, 0));
+        cache.populate(&populated).unwrap();
+
+        // Rewriting the files jobs see must leave the cached blob untouched
+        for path in [artifact, populated.join("zlib.hart")].iter() {
+            let mut permissions = fs::metadata(path).unwrap().permissions();
+            permissions.set_readonly(false);
+            fs::set_permissions(path, permissions).unwrap();
+            File::create(path).unwrap().write_all(b"evil").unwrap();
+        }
+        let blob = cache.blob_path(&checksum);
+        assert_eq!(fs::read(&blob).unwrap(), b"zlib");
+        assert!(fs::metadata(&blob).unwrap().permissions().readonly());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -18,6 +18,8 @@ const INPROC_ADDR: &'static str = "inproc://logger";
 const LOG_LINE: &'static str = "L";
 /// ZMQ protocol frame to indicate a log has finished
 const LOG_COMPLETE: &'static str = "C";
+/// ZMQ protocol frame to indicate a log section has started or ended
+const LOG_SECTION: &'static str = "S";
 /// End-of-line marker
 const EOL_MARKER: &'static str = "\n";
 
@@ -28,9 +30,10 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 
 use bldr_core::logger::Logger;
+use chrono::{DateTime, Utc};
 use hab_net::socket::DEFAULT_CONTEXT;
 use protobuf::Message;
-use protocol::jobsrv::{JobLogChunk, JobLogComplete};
+use protocol::jobsrv::{JobLogChunk, JobLogComplete, JobSection, JobSectionStatus};
 use zmq;
 
 use super::workspace::Workspace;
@@ -258,6 +261,27 @@ impl StreamTarget {
         Ok(())
     }
 
+    /// Sends the timing and status of a log section to the log stream on the socket. A section is
+    /// sent once when it starts and again when it ends.
+    ///
+    /// # Panics
+    ///
+    /// * If the protobuf struct cannot be serialized into bytes
+    ///
+    /// # Errors
+    ///
+    /// * If a message couldn't be sent successfully to the stream target socket
+    fn stream_section(&mut self, section: &JobSection) -> Result<()> {
+        self.sock
+            .send_str(LOG_SECTION, zmq::SNDMORE)
+            .map_err(|e| Error::StreamTargetSend(e))?;
+        self.sock
+            .send(section.write_to_bytes().unwrap().as_slice(), 0)
+            .map_err(|e| Error::StreamTargetSend(e))?;
+
+        Ok(())
+    }
+
     /// Marks the log stream as completed using the job identifier.
     ///
     /// # Panics
@@ -313,11 +337,17 @@ impl fmt::Display for Section {
 /// publishing and artifact, building a packages, etc. Note that this may also correspond to
 /// certain failure scenarios such as being unable to clone a repository, failing to build a
 /// package in the Studio, etc.
+///
+/// Besides the start and end markers written into the log, the section's timing and status are
+/// sent alongside the log lines. A section which is explicitly ended succeeded, whereas a section
+/// which is dropped before being ended failed.
 pub struct LogSection {
     /// The job identifer associated with this build log
     id: u64,
     /// The section name
     name: Section,
+    /// When the section was started
+    started_at: DateTime<Utc>,
     /// The underlying target for this log when streaming lines
     target: Arc<Mutex<StreamTarget>>,
     /// Whether or not the section has been marked as ended
@@ -330,6 +360,7 @@ impl LogSection {
         LogSection {
             id,
             name,
+            started_at: Utc::now(),
             target,
             ended: false,
         }
@@ -345,13 +376,16 @@ impl LogSection {
     ///
     /// * If the stream target could not be written to
     fn start(&mut self) -> Result<()> {
-        self.target
+        self.started_at = Utc::now();
+
+        let mut target = self.target
             .lock()
-            .expect("Stream target mutex is poisoned!")
-            .stream_line(
-                self.id,
-                format!("builder_log_section::start::{}", self.name),
-            )
+            .expect("Stream target mutex is poisoned!");
+        target.stream_line(
+            self.id,
+            format!("builder_log_section::start::{}", self.name),
+        )?;
+        target.stream_section(&self.to_job_section(JobSectionStatus::SectionRunning))
     }
 
     /// Ends a log section by writing to the log stream. This method can be called multiple times
@@ -365,6 +399,10 @@ impl LogSection {
     ///
     /// * If the stream target could not be written to
     pub fn end(&mut self) -> Result<()> {
+        self.finish(JobSectionStatus::SectionSucceeded)
+    }
+
+    fn finish(&mut self, status: JobSectionStatus) -> Result<()> {
         // Early return if the section has ended to make sure that the `Drop` implementation
         // doesn't double-close the section.
         if self.ended {
@@ -372,17 +410,32 @@ impl LogSection {
         }
 
         self.ended = true;
-        self.target
+        let mut target = self.target
             .lock()
-            .expect("Stream target mutex is poisoned!")
-            .stream_line(self.id, format!("builder_log_section::end::{}", self.name))
+            .expect("Stream target mutex is poisoned!");
+        target.stream_line(self.id, format!("builder_log_section::end::{}", self.name))?;
+
+        let mut section = self.to_job_section(status);
+        section.set_finished_at(Utc::now().to_rfc3339());
+        target.stream_section(&section)
+    }
+
+    fn to_job_section(&self, status: JobSectionStatus) -> JobSection {
+        let mut section = JobSection::new();
+        section.set_job_id(self.id);
+        section.set_name(self.name.to_string());
+        section.set_started_at(self.started_at.to_rfc3339());
+        section.set_status(status);
+        section
     }
 }
 
 impl Drop for LogSection {
     fn drop(&mut self) {
+        // A section is only dropped without being ended when its task failed.
+        //
         // This unwrap is intentional as more error handling isn't possible in a `Drop` trait
-        self.end().unwrap();
+        self.finish(JobSectionStatus::SectionFailed).unwrap();
     }
 }
 
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod artifact_cache;
 mod docker;
 mod job_streamer;
 mod postprocessor;
@@ -42,6 +43,7 @@ use protocol::originsrv::OriginPackageIdent;
 use protocol::{jobsrv, message};
 use zmq;
 
+use self::artifact_cache::ArtifactCache;
 use self::docker::DockerExporter;
 use self::job_streamer::{JobStreamer, Section};
 use self::postprocessor::post_process;
@@ -207,19 +209,22 @@ impl Runner {
         let mut section = streamer.start_section(Section::CloneRepository)?;
 
         let vcs = VCS::from_job(&self.job(), self.config.github.clone());
-        if let Some(err) = vcs.clone(&self.workspace.src()).err() {
-            let msg = format!(
-                "Failed to clone remote source repository for {}, err={:?}",
-                self.workspace.job.get_project().get_name(),
-                err
-            );
-            warn!("{}", msg);
-            self.logger.log(&msg);
+        match vcs.clone(&self.workspace.src()) {
+            Ok(sha) => self.workspace.job.set_git_sha(sha),
+            Err(err) => {
+                let msg = format!(
+                    "Failed to clone remote source repository for {}, err={:?}",
+                    self.workspace.job.get_project().get_name(),
+                    err
+                );
+                warn!("{}", msg);
+                self.logger.log(&msg);
 
-            streamer.println_stderr(msg)?;
-            self.fail(net::err(ErrCode::VCS_CLONE, "wk:run:clone:1"));
-            tx.send(self.job().clone()).map_err(Error::Mpsc)?;
-            return Err(err);
+                streamer.println_stderr(msg)?;
+                self.fail(net::err(ErrCode::VCS_CLONE, "wk:run:clone:1"));
+                tx.send(self.job().clone()).map_err(Error::Mpsc)?;
+                return Err(err);
+            }
         }
         if let Some(err) = util::chown_recursive(
             self.workspace.src(),
@@ -430,14 +435,22 @@ impl Runner {
             (None, Some(_)) => return Err(Error::NoNetworkInterfaceError),
             (Some(_), None) => return Err(Error::NoNetworkGatewayError),
         };
+        let mut artifact_cache = self.artifact_cache();
         let status = Studio::new(
             &self.workspace,
             &self.config.bldr_url,
             &self.bldr_token,
             self.config.airlock_enabled,
             network_namespace,
+            artifact_cache.as_ref().map(|_| self.workspace.artifacts()),
         ).build(streamer)?;
 
+        if let Some(ref mut cache) = artifact_cache {
+            if let Err(err) = cache.ingest(self.workspace.artifacts(), self.workspace.studio()) {
+                warn!("Failed to update artifact cache, err={}", err);
+            }
+        }
+
         if fs::rename(self.workspace.src().join("results"), self.workspace.out()).is_err() {
             return Err(Error::BuildFailure(status.code().unwrap_or(-2)));
         }
@@ -452,6 +465,45 @@ impl Runner {
         self.workspace.last_built()
     }
 
+    /// Opens the worker's artifact cache and seeds the workspace's artifact directory from it.
+    /// The cache is skipped, rather than failing the build, if it can't be used.
+    fn artifact_cache(&self) -> Option<ArtifactCache> {
+        if !self.config.artifact_cache.enabled {
+            return None;
+        }
+
+        let cache = match ArtifactCache::new(
+            self.config.artifact_cache_path(),
+            self.config.artifact_cache.max_size_bytes(),
+        ) {
+            Ok(cache) => cache,
+            Err(err) => {
+                warn!("Unable to open artifact cache, err={}", err);
+                return None;
+            }
+        };
+
+        let populated = cache.populate(self.workspace.artifacts()).and_then(|_| {
+            if self.config.airlock_enabled {
+                util::chown_recursive(
+                    self.workspace.artifacts(),
+                    studio::studio_uid(),
+                    studio::studio_gid(),
+                )
+            } else {
+                Ok(())
+            }
+        });
+
+        match populated {
+            Ok(()) => Some(cache),
+            Err(err) => {
+                warn!("Unable to populate artifact cache, err={}", err);
+                None
+            }
+        }
+    }
+
     fn export(&mut self, streamer: &mut JobStreamer) -> Result<()> {
         if self.has_docker_integration() {
             // TODO fn: This check should be updated in PackageArchive is check for run hooks.
@@ -562,6 +614,15 @@ impl Runner {
                 err
             );
         }
+        if self.workspace.artifacts().exists() {
+            if let Some(err) = fs::remove_dir_all(self.workspace.artifacts()).err() {
+                warn!(
+                    "Failed to remove artifacts dir {}, err: {:?}",
+                    self.workspace.artifacts().display(),
+                    err
+                );
+            }
+        }
         // TODO fn: purge the secret origin key from worker
     }
 
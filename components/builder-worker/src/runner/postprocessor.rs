@@ -39,6 +39,8 @@ pub fn post_process(
         enabled: config.auto_publish,
         url: url,
         channel_opt: channel_opt,
+        // Pull request builds are only published to their own channel
+        skip_unstable: workspace.job.get_pull_request() != 0,
     };
 
     debug!("Starting post processing");
@@ -12,15 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fs::File;
+use std::io::Read;
+
 use bldr_core::logger::Logger;
 use hab_core::channel::{STABLE_CHANNEL, UNSTABLE_CHANNEL};
 use hab_core::package::archive::PackageArchive;
+use hab_http::ApiClient;
+use hyper::client::Body;
+use hyper::header::{Authorization, Bearer};
 
 use super::{RETRIES, RETRY_WAIT};
 use depot_client;
 use error::{Error, Result};
 use hyper::status::StatusCode;
 use retry::retry;
+use url::Url;
 use {PRODUCT, VERSION};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
@@ -28,6 +35,9 @@ pub struct Publisher {
     pub enabled: bool,
     pub url: String,
     pub channel_opt: Option<String>,
+    /// Keep the package out of unstable, only publishing it to `channel_opt`
+    #[serde(default)]
+    pub skip_unstable: bool,
 }
 
 impl Publisher {
@@ -52,7 +62,13 @@ impl Publisher {
         match retry(
             RETRIES,
             RETRY_WAIT,
-            || client.x_put_package(archive, auth_token),
+            || {
+                if self.skip_unstable {
+                    self.put_package_skip_unstable(archive, auth_token)
+                } else {
+                    client.x_put_package(archive, auth_token)
+                }
+            },
             |res| {
                 match *res {
                 Ok(_) |  // Conflict means package got uploaded earlier
@@ -141,4 +157,47 @@ impl Publisher {
         }
         Ok(())
     }
+
+    // Uploads the archive like `x_put_package` does, but asks the depot to leave the package out
+    // of the unstable channel.
+    fn put_package_skip_unstable(
+        &self,
+        archive: &mut PackageArchive,
+        auth_token: &str,
+    ) -> depot_client::Result<()> {
+        let checksum = archive
+            .checksum()
+            .map_err(depot_client::Error::HabitatCore)?;
+        let ident = archive.ident().map_err(depot_client::Error::HabitatCore)?;
+        let mut file = File::open(&archive.path).map_err(depot_client::Error::IO)?;
+        let file_size = file.metadata().map_err(depot_client::Error::IO)?.len();
+
+        let endpoint = match Url::parse(&self.url) {
+            Ok(ref url) if url.path() == "/" => format!("{}v1", url),
+            _ => self.url.clone(),
+        };
+        let client = ApiClient::new(&endpoint, PRODUCT, VERSION, None)
+            .map_err(depot_client::Error::HabitatHttpClient)?;
+        let mut response = client
+            .post_with_custom_url(&format!("depot/pkgs/{}", ident), |url| {
+                url.query_pairs_mut()
+                    .append_pair("checksum", &checksum)
+                    .append_pair("builder", "")
+                    .append_pair("skip_unstable", "");
+            })
+            .header(Authorization(Bearer {
+                token: auth_token.to_string(),
+            }))
+            .body(Body::SizedBody(&mut file, file_size))
+            .send()
+            .map_err(depot_client::Error::HyperError)?;
+
+        if response.status.is_success() {
+            Ok(())
+        } else {
+            let mut body = String::new();
+            response.read_to_string(&mut body).ok();
+            Err(depot_client::Error::APIError(response.status, body))
+        }
+    }
 }
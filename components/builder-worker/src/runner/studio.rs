@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use std::os::unix::process::CommandExt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, ExitStatus, Stdio};
 use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
 use std::sync::Mutex;
@@ -54,6 +54,7 @@ pub struct Studio<'a> {
     auth_token: &'a str,
     airlock_enabled: bool,
     network_namespace: Option<NetworkNamespace>,
+    artifact_path: Option<&'a Path>,
 }
 
 impl<'a> Studio<'a> {
@@ -64,6 +65,7 @@ impl<'a> Studio<'a> {
         auth_token: &'a str,
         airlock_enabled: bool,
         network_namespace: Option<NetworkNamespace>,
+        artifact_path: Option<&'a Path>,
     ) -> Self {
         Studio {
             workspace,
@@ -71,6 +73,7 @@ impl<'a> Studio<'a> {
             auth_token,
             airlock_enabled,
             network_namespace,
+            artifact_path,
         }
     }
 
@@ -174,6 +177,17 @@ impl<'a> Studio<'a> {
                 cmd.arg("--use-netns");
                 cmd.arg(self.network_namespace.as_ref().unwrap().netns());
             }
+            if let Some(path) = self.artifact_path {
+                // Airlock bind mounts the directory as the artifact cache inside its rootfs,
+                // where the Studio mounts it from by default
+                debug!(
+                    "mounting artifact cache into airlock, path={}",
+                    path.display()
+                );
+                cmd.arg("--mount-artifact-cache");
+                cmd.arg("--artifact-cache-dir");
+                cmd.arg(path);
+            }
             cmd.arg(&*STUDIO_PROGRAM);
 
             Ok(cmd)
@@ -181,7 +195,18 @@ impl<'a> Studio<'a> {
             let mut cmd = Command::new(&*STUDIO_PROGRAM);
             cmd.env_clear();
             debug!("HAB_CACHE_KEY_PATH: {:?}", key_path());
-            cmd.env("NO_ARTIFACT_PATH", "true"); // Disables artifact cache mounting
+            match self.artifact_path {
+                Some(path) => {
+                    debug!(
+                        "mounting artifact cache into studio, path={}",
+                        path.display()
+                    );
+                    cmd.env("ARTIFACT_PATH", path); // Mounted as the studio's artifact cache
+                }
+                None => {
+                    cmd.env("NO_ARTIFACT_PATH", "true"); // Disables artifact cache mounting
+                }
+            }
             cmd.env("HAB_CACHE_KEY_PATH", key_path()); // Sets key cache to build user's home
 
             info!("Airlock is not enabled, running uncontained Studio");
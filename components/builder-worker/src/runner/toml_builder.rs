@@ -59,6 +59,7 @@ impl TomlPublishBuilder {
             enabled: self.publish.enabled.unwrap_or(config.auto_publish),
             url: self.publish.url.unwrap_or(config.bldr_url.clone()),
             channel_opt: self.publish.channel,
+            skip_unstable: false,
         }
     }
 }
@@ -23,6 +23,7 @@ use error::{Error, Result};
 
 pub struct Workspace {
     pub job: Job,
+    artifacts: PathBuf,
     out: PathBuf,
     src: PathBuf,
     studio: PathBuf,
@@ -38,6 +39,7 @@ impl Workspace {
         let root = data_path.as_ref().join(job.get_id().to_string());
         Workspace {
             job: job,
+            artifacts: root.join("artifacts"),
             out: root.join("out"),
             src: root.join("src"),
             studio: root.join("studio"),
@@ -71,6 +73,11 @@ impl Workspace {
         }
     }
 
+    /// Directory mounted as the studio's artifact cache, seeded from the worker's artifact cache
+    pub fn artifacts(&self) -> &Path {
+        &self.artifacts
+    }
+
     /// Directory to the output directory containing built artifacts from studio build
     pub fn out(&self) -> &Path {
         &self.out
@@ -58,9 +58,10 @@ impl Server {
     pub fn new(config: Config) -> Result<Self> {
         let net_ident = hab_net::socket::srv_ident();
         let fe_sock = (**DEFAULT_CONTEXT).as_mut().socket(zmq::DEALER)?;
-        let hb_cli = HeartbeatCli::new(net_ident.clone());
+        let hb_cli = HeartbeatCli::new(net_ident.clone(), config.labels.clone());
         let runner_cli = RunnerCli::new();
         fe_sock.set_identity(net_ident.as_bytes())?;
+        config.curve.configure_client(&fe_sock)?;
         Ok(Server {
             config: Arc::new(config),
             fe_sock: fe_sock,
@@ -28,10 +28,22 @@ pub struct VCS {
     pub data: String,
     pub github_client: GitHubClient,
     pub installation_id: Option<u32>,
+    /// Username and deploy token used to clone GitLab and Bitbucket repositories
+    pub credentials: Option<(String, String)>,
+    /// Branch, tag, commit or ref to check out instead of the default branch
+    pub git_ref: Option<String>,
 }
 
 impl VCS {
     pub fn from_job(job: &Job, config: GitHubCfg) -> Self {
+        let mut vcs = Self::from_project(job, config);
+        if job.has_git_ref() {
+            vcs.git_ref = Some(job.get_git_ref().to_string());
+        }
+        vcs
+    }
+
+    fn from_project(job: &Job, config: GitHubCfg) -> Self {
         match job.get_project().get_vcs_type() {
             "git" => {
                 let installation_id: Option<u32> = {
@@ -48,6 +60,22 @@ impl VCS {
                     installation_id,
                 )
             }
+            "gitlab" | "bitbucket" => {
+                let project = job.get_project();
+                let mut vcs = Self::new(
+                    String::from(project.get_vcs_type()),
+                    String::from(project.get_vcs_data()),
+                    config,
+                    None,
+                );
+                if project.has_vcs_auth_token() {
+                    vcs.credentials = Some((
+                        String::from(project.get_vcs_auth_user()),
+                        String::from(project.get_vcs_auth_token()),
+                    ));
+                }
+                vcs
+            }
             _ => panic!("unknown vcs associated with jobs project"),
         }
     }
@@ -63,10 +91,14 @@ impl VCS {
             data: data,
             github_client: GitHubClient::new(config),
             installation_id: installation_id,
+            credentials: None,
+            git_ref: None,
         }
     }
 
-    pub fn clone(&self, path: &Path) -> Result<()> {
+    /// Clones the repository into `path` and checks out the requested ref, returning the SHA of
+    /// the commit checked out.
+    pub fn clone(&self, path: &Path) -> Result<String> {
         match self.vcs_type.as_ref() {
             "git" => {
                 let token = match self.installation_id {
@@ -99,20 +131,76 @@ impl VCS {
                     self.url(token.clone())?,
                     path
                 );
-                git2::Repository::clone(&(self.url(token)?).as_str(), path)
+                let repo = git2::Repository::clone(&(self.url(token)?).as_str(), path)
                     .map_err(|e| Error::Git(e))?;
-                Ok(())
+                self.checkout(&repo)
+            }
+            "gitlab" | "bitbucket" => {
+                let url = match self.credentials {
+                    Some((ref user, ref token)) => {
+                        Counter::GitAuthenticatedClone.increment();
+                        self.url_with_credentials(user, Some(token))?
+                    }
+                    None => {
+                        Counter::GitClone.increment();
+                        self.url_with_credentials("", None)?
+                    }
+                };
+                debug!(
+                    "builder_worker::vcs::clone: cloning {} repository, url={}, path={:?}",
+                    self.vcs_type, self.data, path
+                );
+                let repo =
+                    git2::Repository::clone(url.as_str(), path).map_err(|e| Error::Git(e))?;
+                self.checkout(&repo)
             }
             _ => panic!("Unknown vcs type"),
         }
     }
 
+    fn checkout(&self, repo: &git2::Repository) -> Result<String> {
+        let git_ref = match self.git_ref {
+            Some(ref git_ref) => git_ref,
+            None => {
+                let head = repo.head().map_err(|e| Error::Git(e))?;
+                let commit = head.peel_to_commit().map_err(|e| Error::Git(e))?;
+                return Ok(commit.id().to_string());
+            }
+        };
+        debug!("builder_worker::vcs::checkout: checking out {}", git_ref);
+
+        // Clones only fetch branches and tags, other refs such as pull request heads are fetched
+        // on their own
+        if git_ref.starts_with("refs/") && !git_ref.starts_with("refs/tags/") {
+            let mut remote = repo.find_remote("origin").map_err(|e| Error::Git(e))?;
+            remote
+                .fetch(&[&format!("+{}:{}", git_ref, git_ref)], None, None)
+                .map_err(|e| Error::Git(e))?;
+        }
+
+        // Branches are only known as remote branches after a clone, so look those up first
+        let object = repo.revparse_single(&format!("origin/{}", git_ref))
+            .or_else(|_| repo.revparse_single(git_ref))
+            .map_err(|e| Error::Git(e))?;
+        let commit = object.peel_to_commit().map_err(|e| Error::Git(e))?;
+        repo.checkout_tree(
+            commit.as_object(),
+            Some(git2::build::CheckoutBuilder::new().force()),
+        ).map_err(|e| Error::Git(e))?;
+        repo.set_head_detached(commit.id())
+            .map_err(|e| Error::Git(e))?;
+        Ok(commit.id().to_string())
+    }
+
     pub fn url(&self, token: Option<String>) -> Result<Url> {
+        self.url_with_credentials("x-access-token", token.as_ref())
+    }
+
+    fn url_with_credentials(&self, user: &str, token: Option<&String>) -> Result<Url> {
         let mut url = Url::parse(self.data.as_str()).map_err(|e| Error::UrlParseError(e))?;
         if self.data.starts_with("https://") {
-            if let Some(ref tok) = token {
-                url.set_username("x-access-token")
-                    .map_err(|_| Error::CannotAddCreds)?;
+            if let Some(tok) = token {
+                url.set_username(user).map_err(|_| Error::CannotAddCreds)?;
                 url.set_password(Some(tok.as_str()))
                     .map_err(|_| Error::CannotAddCreds)?;
             }
@@ -19,7 +19,7 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use builder_core::metrics::CounterMetric;
 use hab_http::ApiClient;
 use hyper::client::IntoUrl;
-use hyper::header::{qitem, Accept, Authorization, Bearer, UserAgent};
+use hyper::header::{qitem, Accept, Authorization, Bearer, ContentType, UserAgent};
 use hyper::mime::{Mime, SubLevel, TopLevel};
 use hyper::status::StatusCode;
 use hyper::{self, Url};
@@ -110,7 +110,7 @@ impl GitHubClient {
         )).map_err(HubError::HttpClientParse)?;
 
         Counter::InstallationToken.increment();
-        let mut rep = http_post(url, Some(app_token))?;
+        let mut rep = http_post(url, Some(app_token), None)?;
         let mut body = String::new();
         rep.read_to_string(&mut body)?;
         debug!("GitHub response body, {}", body);
@@ -171,6 +171,35 @@ impl GitHubClient {
         Ok(Some(value))
     }
 
+    /// Sets the status of a commit in a repository, given by its full name (e.g.
+    /// `habitat-sh/core-plans`).
+    pub fn create_status(
+        &self,
+        token: &AppToken,
+        repo: &str,
+        sha: &str,
+        status: &CommitStatus,
+    ) -> HubResult<()> {
+        let url = Url::parse(&format!(
+            "{}/repos/{}/statuses/{}",
+            self.api_url, repo, sha
+        )).map_err(HubError::HttpClientParse)?;
+
+        Counter::Api("statuses").increment();
+        let body = serde_json::to_string(status)?;
+        let mut rep = http_post(url, Some(&token.inner_token), Some(body))?;
+        let mut body = String::new();
+        rep.read_to_string(&mut body)?;
+        debug!("GitHub response body, {}", body);
+        match rep.status {
+            StatusCode::Created | StatusCode::Ok => Ok(()),
+            status => {
+                let err: HashMap<String, String> = serde_json::from_str(&body)?;
+                Err(HubError::ApiError(status, err))
+            }
+        }
+    }
+
     // The main purpose of this is just to verify HTTP communication with GH.
     // There's nothing special about this endpoint, only that it doesn't require
     // auth and the response body seemed small. We don't even care what the
@@ -253,7 +282,11 @@ where
     req.send().map_err(HubError::HttpClient)
 }
 
-fn http_post<T, U>(url: T, token: Option<U>) -> HubResult<hyper::client::response::Response>
+fn http_post<T, U>(
+    url: T,
+    token: Option<U>,
+    body: Option<String>,
+) -> HubResult<hyper::client::response::Response>
 where
     T: IntoUrl,
     U: ToString,
@@ -288,7 +321,13 @@ where
         })),
         None => req,
     };
-    req.send().map_err(HubError::HttpClient)
+    match body {
+        Some(ref body) => req.header(ContentType::json())
+            .body(body.as_str())
+            .send()
+            .map_err(HubError::HttpClient),
+        None => req.send().map_err(HubError::HttpClient),
+    }
 }
 
 fn http_client<T>(url: T) -> HubResult<ApiClient>
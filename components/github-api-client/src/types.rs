@@ -142,6 +142,52 @@ impl GitHubWebhookPush {
     }
 }
 
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitHubWebhookPullRequest {
+    /// The action that was performed. Example: "opened", "synchronize" or "closed"
+    pub action: String,
+    /// The pull request number
+    pub number: u64,
+    pub pull_request: GitHubPullRequest,
+    pub repository: PushRepository,
+    pub sender: GitHubWebhookSender,
+    pub installation: GitHubAppInstallation,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitHubPullRequest {
+    pub number: u64,
+    pub state: String,
+    pub title: String,
+    pub merged: bool,
+    /// The branch the changes are pulled from
+    pub head: GitHubPullRequestRef,
+    /// The branch the changes are pulled into
+    pub base: GitHubPullRequestRef,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitHubPullRequestRef {
+    pub label: String,
+    /// The branch name. Example: "master"
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub sha: String,
+    /// The repository holding the branch, `None` if it has been deleted
+    pub repo: Option<GitHubPullRequestRepo>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitHubPullRequestRepo {
+    pub id: u32,
+    pub full_name: String,
+    pub clone_url: String,
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct GitHubWebhookCommit {
@@ -328,6 +374,28 @@ pub struct UserPlan {
     pub collaborators: u32,
 }
 
+/// State of a commit status. `Error` is used for builds that didn't run to completion.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitState {
+    Pending,
+    Success,
+    Failure,
+    Error,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CommitStatus {
+    pub state: CommitState,
+    /// Link shown next to the status, e.g. to the build's log
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Label telling this status apart from those set by other systems
+    pub context: String,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Search {
     pub total_count: u32,
@@ -350,6 +418,7 @@ pub struct SearchItem {
 #[cfg(test)]
 mod test {
     use super::*;
+    use serde_json;
 
     #[test]
     fn branch_from_hook() {
@@ -357,4 +426,41 @@ mod test {
         hook.git_ref = "refs/heads/master".to_string();
         assert_eq!(hook.branch(), "master");
     }
+
+    #[test]
+    fn pull_request_from_hook() {
+        let hook: GitHubWebhookPullRequest = serde_json::from_str(
+            r#"{
+                "action": "synchronize",
+                "number": 42,
+                "pull_request": {
+                    "number": 42,
+                    "state": "open",
+                    "head": { "ref": "feature", "sha": "2b3c4d" },
+                    "base": { "ref": "master", "sha": "1a2b3c" }
+                },
+                "repository": { "id": 7, "default_branch": "master" },
+                "installation": { "id": 9 }
+            }"#,
+        ).unwrap();
+        assert_eq!(hook.number, 42);
+        assert_eq!(hook.pull_request.head.git_ref, "feature");
+        assert_eq!(hook.pull_request.base.git_ref, "master");
+        assert_eq!(hook.repository.id, 7);
+        assert_eq!(hook.installation.id, 9);
+    }
+
+    #[test]
+    fn commit_status_to_json() {
+        let status = CommitStatus {
+            state: CommitState::Pending,
+            target_url: None,
+            description: Some("Build started".to_string()),
+            context: "bldr/core/nginx".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_string(&status).unwrap(),
+            r#"{"state":"pending","description":"Build started","context":"bldr/core/nginx"}"#
+        );
+    }
 }
@@ -22,6 +22,7 @@ use protocol::routesrv::DEFAULT_ROUTER_PORT;
 use protocol::sharding::{ShardId, SHARD_COUNT};
 use toml;
 
+use curve::CurveCfg;
 use socket::ToAddrString;
 
 /// Configuration structure for connecting to a Router
@@ -70,6 +71,8 @@ pub struct AppCfg {
     /// Count of Dispatch workers to start and supervise.
     #[serde(default = "AppCfg::default_worker_count")]
     pub worker_count: usize,
+    /// CurveZMQ keys used to connect to the routers.
+    pub curve: CurveCfg,
 }
 
 impl AppCfg {
@@ -93,6 +96,7 @@ impl Default for AppCfg {
             routers: Self::default_routers(),
             shards: Self::default_shards(),
             worker_count: Self::default_worker_count(),
+            curve: CurveCfg::default(),
         }
     }
 }
@@ -116,6 +120,11 @@ mod tests {
         shards = [0]
         worker_count = 1
 
+        [curve]
+        public_key = "Yne@$w-vo<fVvi]a<NY6T1ed:M$fCG*[IaLV{hID"
+        secret_key = "D:)Q[IlAW!ahhC2ac:9*A}h:p?([4%wOTJ%JR%cs"
+        server_key = "rq:rM>}U?@Lns47E1%kR.o@n%FcmmsL/@{H8]yf7"
+
         [[routers]]
         host = "1:1:1:1:1:1:1:1"
         port = 9000
@@ -125,6 +134,11 @@ mod tests {
         assert_eq!(config.shards, Some(vec![0]));
         assert_eq!(config.worker_count, 1);
         assert_eq!(&format!("{}", config.routers[0]), "1:1:1:1:1:1:1:1:9000");
+        assert!(config.curve.is_enabled());
+        assert_eq!(
+            &config.curve.server_key,
+            "rq:rM>}U?@Lns47E1%kR.o@n%FcmmsL/@{H8]yf7"
+        );
     }
 
     #[test]
@@ -135,5 +149,6 @@ mod tests {
 
         let config = AppCfg::from_str(&content).unwrap();
         assert_eq!(config.worker_count, 0);
+        assert!(!config.curve.is_enabled());
     }
 }
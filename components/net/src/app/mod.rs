@@ -253,6 +253,7 @@ where
         router_sock.set_probe_router(true)?;
         router_sock.set_immediate(true)?;
         router_sock.set_router_mandatory(true)?;
+        config.as_ref().curve.configure_client(&router_sock)?;
         let pipe_out = (**DEFAULT_CONTEXT).as_mut().socket(zmq::ROUTER).unwrap();
         pipe_out.set_immediate(true)?;
         let pipe_in = (**DEFAULT_CONTEXT).as_mut().socket(zmq::DEALER).unwrap();
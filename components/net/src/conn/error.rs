@@ -25,6 +25,7 @@ pub enum ConnErr {
     BadHeader(protocol::ProtocolError),
     BadRouteInfo(protocol::ProtocolError),
     BadTxn(protocol::ProtocolError),
+    BadCurveKey(String),
     HostUnreachable,
     MultipleSender,
     NoBody,
@@ -50,6 +51,7 @@ impl fmt::Display for ConnErr {
                 write!(f, "Unable to parse route-info message part, {}", e)
             }
             ConnErr::BadTxn(ref e) => write!(f, "Unable to parse transaction message part, {}", e),
+            ConnErr::BadCurveKey(ref e) => write!(f, "Invalid CurveZMQ configuration, {}", e),
             ConnErr::HostUnreachable => write!(f, "Unable to route message to destination"),
             ConnErr::MultipleSender => write!(f, "Message header contained multiple senders"),
             ConnErr::NoBody => write!(f, "Message missing body message part"),
@@ -79,6 +81,7 @@ impl error::Error for ConnErr {
             ConnErr::BadHeader(_) => "Unable to parse header message part",
             ConnErr::BadRouteInfo(_) => "Unable to parse route-info message part",
             ConnErr::BadTxn(_) => "Unable to parse transaction message part",
+            ConnErr::BadCurveKey(_) => "Invalid CurveZMQ configuration",
             ConnErr::HostUnreachable => "Unable to route message to destination",
             ConnErr::MultipleSender => "Message header contained multiple senders",
             ConnErr::NoBody => "Message missing body message part",
@@ -104,6 +107,7 @@ impl<'a> From<&'a ConnErr> for protocol::net::ErrCode {
             | ConnErr::BadHeader(_)
             | ConnErr::BadRouteInfo(_)
             | ConnErr::BadTxn(_)
+            | ConnErr::BadCurveKey(_)
             | ConnErr::HostUnreachable
             | ConnErr::MultipleSender
             | ConnErr::NoBody
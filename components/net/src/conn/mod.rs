@@ -383,6 +383,7 @@ fn read_body(
     socket.recv(buf, 0)?;
     trace!("recv: body, {:?}", buf);
     message.body = buf.to_vec();
+    message.user_id = buf.gets("User-Id").map(str::to_string);
     Ok(())
 }
 
@@ -0,0 +1,194 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! CurveZMQ encryption and authentication of the sockets connecting Builder services.
+//!
+//! A service which binds a socket (RouteSrv, JobSrv) acts as a Curve server and only admits
+//! clients whose public key is present in its `allowed_keys`. A service which connects a socket
+//! (net apps, gateways, workers) acts as a Curve client and pins the public key of the server it
+//! connects to with `server_key`. All keys are Z85 encoded, as produced by `curve_keygen`.
+
+use std::collections::HashSet;
+use std::thread;
+
+use zmq;
+
+use conn::ConnErr;
+
+/// Endpoint which libzmq sends authentication requests to, see ZMQ RFC 27.
+const ZAP_ENDPOINT: &'static str = "inproc://zeromq.zap.01";
+const ZAP_VERSION: &'static [u8] = b"1.0";
+const ZAP_DOMAIN: &'static str = "builder";
+/// Length of a Z85 encoded Curve key.
+const KEY_LEN: usize = 40;
+
+/// Configuration structure for securing a service's sockets with CurveZMQ
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct CurveCfg {
+    /// Z85 encoded public key of this service.
+    pub public_key: String,
+    /// Z85 encoded secret key of this service. Curve security is disabled when left empty.
+    pub secret_key: String,
+    /// Z85 encoded public key of the server this service connects to.
+    pub server_key: String,
+    /// Z85 encoded public keys of clients allowed to connect to the sockets this service binds.
+    pub allowed_keys: Vec<String>,
+}
+
+impl CurveCfg {
+    pub fn is_enabled(&self) -> bool {
+        !self.secret_key.is_empty()
+    }
+
+    /// Configure a socket this service binds to require a Curve handshake from every client.
+    ///
+    /// Must be called before the socket is bound and after an authenticator was started in the
+    /// socket's context with `start_authenticator()`.
+    pub fn configure_server(&self, socket: &zmq::Socket) -> Result<(), ConnErr> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        socket.set_curve_server(true)?;
+        socket.set_curve_secretkey(&decode_key("secret_key", &self.secret_key)?)?;
+        socket.set_zap_domain(ZAP_DOMAIN)?;
+        Ok(())
+    }
+
+    /// Configure a socket this service connects to encrypt its traffic and to only complete a
+    /// handshake with a server presenting `server_key`.
+    ///
+    /// Must be called before the socket is connected.
+    pub fn configure_client(&self, socket: &zmq::Socket) -> Result<(), ConnErr> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        socket.set_curve_serverkey(&decode_key("server_key", &self.server_key)?)?;
+        socket.set_curve_publickey(&decode_key("public_key", &self.public_key)?)?;
+        socket.set_curve_secretkey(&decode_key("secret_key", &self.secret_key)?)?;
+        Ok(())
+    }
+
+    /// Start a thread answering the authentication requests of every Curve server socket in the
+    /// given context. Handshakes from clients whose key isn't in `allowed_keys` are rejected.
+    ///
+    /// Only one authenticator may be started per context.
+    pub fn start_authenticator(&self, context: &mut zmq::Context) -> Result<(), ConnErr> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        let mut allowed = HashSet::default();
+        for key in self.allowed_keys.iter() {
+            allowed.insert(decode_key("allowed_keys", key)?);
+        }
+        if allowed.is_empty() {
+            warn!("CurveZMQ enabled without any `allowed_keys`, all clients will be rejected");
+        }
+        let socket = context.socket(zmq::REP)?;
+        socket.set_linger(0)?;
+        socket.bind(ZAP_ENDPOINT)?;
+        thread::Builder::new()
+            .name("zap-handler".to_string())
+            .spawn(move || authenticate(socket, allowed))
+            .unwrap();
+        Ok(())
+    }
+}
+
+fn authenticate(socket: zmq::Socket, allowed: HashSet<Vec<u8>>) {
+    loop {
+        let request = match socket.recv_multipart(0) {
+            Ok(request) => request,
+            Err(zmq::Error::ETERM) => break,
+            Err(err) => {
+                error!("Unable to receive ZAP request, {}", err);
+                continue;
+            }
+        };
+        // version, request id, domain, address, identity, mechanism, credentials
+        if request.len() < 6 || request[0] != ZAP_VERSION {
+            warn!("Received malformed ZAP request, {:?}", request);
+        }
+        let request_id = request.get(1).map_or(&[][..], |id| id.as_slice());
+        let address = String::from_utf8_lossy(request.get(3).map_or(&[][..], |a| a.as_slice()));
+        let key = match (request.get(5), request.get(6)) {
+            (Some(mechanism), Some(key)) if mechanism.as_slice() == b"CURVE" => Some(key),
+            _ => None,
+        };
+        let (status, text, user) = match key {
+            Some(key) if allowed.contains(key) => (
+                &b"200"[..],
+                &b"OK"[..],
+                zmq::z85_encode(key).unwrap_or_default(),
+            ),
+            Some(key) => {
+                warn!(
+                    "Rejected CurveZMQ handshake from {}, unknown key {}",
+                    address,
+                    zmq::z85_encode(key).unwrap_or_default()
+                );
+                (&b"400"[..], &b"Unknown key"[..], String::new())
+            }
+            None => {
+                warn!(
+                    "Rejected ZAP request from {}, Curve mechanism required",
+                    address
+                );
+                (&b"400"[..], &b"Curve required"[..], String::new())
+            }
+        };
+        let reply: [&[u8]; 6] = [ZAP_VERSION, request_id, status, text, user.as_bytes(), b""];
+        if let Err(err) = socket.send_multipart(&reply, 0) {
+            error!("Unable to send ZAP reply, {}", err);
+        }
+    }
+}
+
+fn decode_key(field: &str, key: &str) -> Result<Vec<u8>, ConnErr> {
+    if key.len() != KEY_LEN {
+        return Err(ConnErr::BadCurveKey(format!(
+            "`{}` must be a {} character Z85 encoded key",
+            field, KEY_LEN
+        )));
+    }
+    zmq::z85_decode(key)
+        .map_err(|e| ConnErr::BadCurveKey(format!("`{}` is not valid Z85, {}", field, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBLIC_KEY: &'static str = "Yne@$w-vo<fVvi]a<NY6T1ed:M$fCG*[IaLV{hID";
+
+    #[test]
+    fn decode_valid_key() {
+        assert_eq!(decode_key("public_key", PUBLIC_KEY).unwrap().len(), 32);
+    }
+
+    #[test]
+    fn decode_short_key() {
+        match decode_key("server_key", &PUBLIC_KEY[1..]) {
+            Err(ConnErr::BadCurveKey(_)) => (),
+            e => panic!("Unexpected result {:?}", e),
+        }
+    }
+
+    #[test]
+    fn disabled_without_secret_key() {
+        let mut config = CurveCfg::default();
+        config.public_key = PUBLIC_KEY.to_string();
+        assert!(!config.is_enabled());
+    }
+}
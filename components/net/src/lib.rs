@@ -35,6 +35,7 @@ extern crate zmq;
 
 pub mod app;
 pub mod conn;
+pub mod curve;
 pub mod error;
 pub mod privilege;
 pub mod socket;